@@ -70,6 +70,7 @@ fn bench_collect_stream_to_response(c: &mut Criterion) {
                         )),
                         request_body: None,
                         response_headers: None,
+                        raw_response: None,
                     },
                     StreamCollectorConfig {
                         allow_reasoning: true,
@@ -114,6 +115,7 @@ fn mapper_config() -> EventMapperConfig<()> {
         finish_reason_fallback: v2t::FinishReason::Stop,
         initial_extra: (),
         hooks: EventMapperHooks::default(),
+        stop_on_first_tool_call: false,
     }
 }
 
@@ -249,6 +249,7 @@ pub async fn run_anthropic_stream() {
         transport_cfg: TransportConfig::default(),
         supported_urls: HashMap::new(),
         default_options: None,
+        default_max_tokens: None,
     };
     let model = AnthropicMessagesLanguageModel::new("claude-3-5-sonnet-20241022".to_string(), cfg);
     let response = model
@@ -342,6 +343,7 @@ pub async fn run_gateway_generate() {
             token: "test-key".into(),
             method: GatewayAuthMethod::ApiKey,
         }),
+        http_method: None,
     };
     let model = GatewayLanguageModel::new(
         "openai/gpt-4.1-mini",
@@ -367,6 +369,9 @@ pub async fn run_openai_compatible_stream() {
         query_params: vec![],
         supports_structured_outputs: true,
         default_options: None,
+        field_aliases: HashMap::new(),
+        done_sentinel: None,
+        tool_limits: Default::default(),
     };
     let model = OpenAICompatibleChatLanguageModel::new("grok-beta", cfg);
     let response = model
@@ -640,6 +645,7 @@ async fn collect_anthropic_scale_parts() -> Vec<v2t::StreamPart> {
         transport_cfg: TransportConfig::default(),
         supported_urls: HashMap::new(),
         default_options: None,
+        default_max_tokens: None,
     };
     let model = AnthropicMessagesLanguageModel::new("claude-3-5-sonnet-20241022".to_string(), cfg);
     let response = model
@@ -674,6 +680,7 @@ async fn collect_gateway_scale_parts(backpressure: bool) -> Vec<v2t::StreamPart>
             token: "test-key".into(),
             method: GatewayAuthMethod::ApiKey,
         }),
+        http_method: None,
     };
     let model = GatewayLanguageModel::new(
         "openai/gpt-4.1-mini",
@@ -714,6 +721,9 @@ async fn collect_openai_compatible_adversarial_parts(backpressure: bool) -> Vec<
         query_params: vec![],
         supports_structured_outputs: true,
         default_options: None,
+        field_aliases: HashMap::new(),
+        done_sentinel: None,
+        tool_limits: Default::default(),
     };
     let model = OpenAICompatibleChatLanguageModel::new("grok-beta", cfg);
     let mut options = v2t::CallOptions::new(matrix_prompt());
@@ -963,6 +973,7 @@ fn small_stream_parts() -> Vec<v2t::StreamPart> {
                 total_tokens: Some(192),
                 reasoning_tokens: Some(16),
                 cached_input_tokens: Some(32),
+                ..Default::default()
             },
             finish_reason: v2t::FinishReason::Stop,
             provider_metadata: None,
@@ -1042,6 +1053,7 @@ fn scaled_stream_parts() -> Vec<v2t::StreamPart> {
             total_tokens: Some(12288),
             reasoning_tokens: Some(768),
             cached_input_tokens: Some(1024),
+            ..Default::default()
         },
         finish_reason: v2t::FinishReason::Stop,
         provider_metadata: None,
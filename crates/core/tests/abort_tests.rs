@@ -0,0 +1,78 @@
+use super::{abort_parts, call_transport_config, ABORTED_MESSAGE};
+use crate::ai_sdk_core::transport::{CancelToken, TransportConfig};
+use crate::ai_sdk_core::PartStream;
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, StreamExt};
+use std::borrow::Cow;
+use std::time::Duration;
+
+fn text_delta(delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: "t".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+fn stalled(parts: Vec<v2t::StreamPart>) -> PartStream {
+    Box::pin(stream::iter(parts.into_iter().map(Ok)).chain(stream::pending()))
+}
+
+#[tokio::test]
+async fn cancelling_ends_a_slow_stream_with_an_aborted_error() {
+    let token = CancelToken::new();
+    let mut parts = abort_parts(stalled(vec![text_delta("a")]), token.clone());
+    assert!(matches!(
+        parts.next().await,
+        Some(Ok(v2t::StreamPart::TextDelta { .. }))
+    ));
+
+    let canceller = tokio::spawn({
+        let token = token.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel();
+        }
+    });
+    let rest: Vec<_> = tokio::time::timeout(Duration::from_secs(1), parts.collect::<Vec<_>>())
+        .await
+        .expect("aborted stream terminates promptly");
+    canceller.await.unwrap();
+
+    assert_eq!(rest.len(), 1);
+    assert!(matches!(
+        &rest[0],
+        Ok(v2t::StreamPart::Error { error }) if error["message"] == ABORTED_MESSAGE
+    ));
+}
+
+#[tokio::test]
+async fn uncancelled_streams_pass_through() {
+    let parts: PartStream = Box::pin(stream::iter(
+        [text_delta("a"), text_delta("b")].into_iter().map(Ok),
+    ));
+    let collected: Vec<_> = abort_parts(parts, CancelToken::new()).collect().await;
+    assert_eq!(collected.len(), 2);
+    assert!(collected
+        .iter()
+        .all(|part| matches!(part, Ok(v2t::StreamPart::TextDelta { .. }))));
+}
+
+#[test]
+fn call_transport_config_carries_the_abort_token() {
+    let cfg = TransportConfig::default();
+    let options = v2t::CallOptions::default();
+    assert!(matches!(
+        call_transport_config(&cfg, &options),
+        Cow::Borrowed(_)
+    ));
+
+    let token = CancelToken::new();
+    let options = options.with_abort(token.clone());
+    let call_cfg = call_transport_config(&cfg, &options);
+    token.cancel();
+    assert!(call_cfg
+        .cancel
+        .as_ref()
+        .is_some_and(CancelToken::is_cancelled));
+}
@@ -0,0 +1,15 @@
+use super::index_max_output_tokens;
+use serde_json::json;
+
+#[test]
+fn index_output_limit_reads_both_limit_shapes() {
+    let models_dev = json!({"id": "m", "limit": {"context": 200000, "output": 64000}});
+    assert_eq!(index_max_output_tokens(&models_dev), Some(64_000));
+
+    let appendix = json!({"id": "m", "limits": {"context_output": 32768, "output": 8192}});
+    assert_eq!(index_max_output_tokens(&appendix), Some(32_768));
+    let old = json!({"id": "m", "limits": {"output": 8192}});
+    assert_eq!(index_max_output_tokens(&old), Some(8_192));
+
+    assert_eq!(index_max_output_tokens(&json!({"id": "m"})), None);
+}
@@ -0,0 +1,51 @@
+use super::{l2_normalize, DedupedValues};
+use crate::ai_sdk_core::SdkError;
+
+fn values(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+#[test]
+fn dedupe_keeps_first_occurrence_order() {
+    let deduped = DedupedValues::new(values(&["b", "a", "b", "c", "a"]));
+    assert_eq!(deduped.unique, values(&["b", "a", "c"]));
+    assert_eq!(deduped.positions, vec![0, 1, 0, 2, 1]);
+    assert!(deduped.has_duplicates());
+}
+
+#[test]
+fn dedupe_without_repeats_is_identity() {
+    let deduped = DedupedValues::new(values(&["x", "y"]));
+    assert_eq!(deduped.unique, values(&["x", "y"]));
+    assert_eq!(deduped.positions, vec![0, 1]);
+    assert!(!deduped.has_duplicates());
+}
+
+#[test]
+fn expand_restores_every_position() {
+    let deduped = DedupedValues::new(values(&["a", "b", "a"]));
+    let expanded = deduped
+        .expand(vec![vec![1.0], vec![2.0]])
+        .expect("expanded");
+    assert_eq!(expanded, vec![vec![1.0], vec![2.0], vec![1.0]]);
+}
+
+#[test]
+fn expand_rejects_mismatched_embedding_count() {
+    let deduped = DedupedValues::new(values(&["a", "b", "a"]));
+    let err = deduped.expand(vec![vec![1.0]]).unwrap_err();
+    assert!(matches!(err, SdkError::Upstream { message, .. } if message.contains("expected 2")));
+}
+
+#[test]
+fn l2_normalize_scales_to_unit_length_and_keeps_zero_vectors() {
+    let mut embedding = vec![1.0, 2.0, 2.0];
+    l2_normalize(&mut embedding);
+    let length: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    assert!((length - 1.0).abs() < 1e-6);
+    assert!((embedding[0] - 1.0 / 3.0).abs() < 1e-6);
+
+    let mut zero = vec![0.0; 4];
+    l2_normalize(&mut zero);
+    assert_eq!(zero, vec![0.0; 4]);
+}
@@ -0,0 +1,100 @@
+use crate::ai_sdk_core::headers::{HeaderConflictPolicy, HeaderMerger};
+use crate::ai_sdk_core::transport::TransportConfig;
+use crate::ai_sdk_core::SdkError;
+
+fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn override_policy_keeps_last_value() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Override, false);
+    merger.insert("X-Team", "a").unwrap();
+    merger.insert("x-team", "b").unwrap();
+    assert_eq!(merger.into_pairs(), pairs(&[("x-team", "b")]));
+}
+
+#[test]
+fn error_policy_rejects_conflicting_values() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Error, false);
+    merger.insert("X-Team", "a").unwrap();
+    merger.insert("x-team", "a").unwrap();
+    let err = merger.insert("X-TEAM", "b").unwrap_err();
+    match err {
+        SdkError::InvalidArgument { message } => assert!(message.contains("X-TEAM")),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn append_policy_joins_values() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Append, false);
+    merger.insert("Accept-Language", "en").unwrap();
+    merger.insert("accept-language", "fr").unwrap();
+    assert_eq!(merger.into_pairs(), pairs(&[("accept-language", "en, fr")]));
+}
+
+#[test]
+fn defaults_apply_only_when_unset() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Error, false);
+    merger.insert_default("user-agent", "sdk");
+    merger.insert_default("accept", "*/*");
+    merger.insert("User-Agent", "custom").unwrap();
+    assert_eq!(
+        merger.into_pairs(),
+        pairs(&[("accept", "*/*"), ("user-agent", "custom")])
+    );
+}
+
+#[test]
+fn force_bypasses_policy() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Error, false);
+    merger.insert("content-type", "text/plain").unwrap();
+    merger.force("Content-Type", "application/json");
+    assert_eq!(
+        merger.into_pairs(),
+        pairs(&[("content-type", "application/json")])
+    );
+}
+
+#[test]
+fn preserve_case_emits_inserted_names() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Override, true);
+    merger.insert("x-trace", "1").unwrap();
+    merger.insert("X-Trace-ID", "abc").unwrap();
+    merger.insert("X-Trace", "2").unwrap();
+    assert_eq!(
+        merger.into_pairs_with(|name| name.to_ascii_uppercase()),
+        pairs(&[("X-Trace", "2"), ("X-Trace-ID", "abc")])
+    );
+}
+
+#[test]
+fn normalized_names_use_callback() {
+    let mut merger = HeaderMerger::new(HeaderConflictPolicy::Override, false);
+    merger.insert("X-Trace", "1").unwrap();
+    assert_eq!(
+        merger.into_pairs_with(|name| name.to_ascii_uppercase()),
+        pairs(&[("X-TRACE", "1")])
+    );
+}
+
+#[test]
+fn for_transport_reads_header_settings() {
+    let cfg = TransportConfig {
+        header_conflict_policy: HeaderConflictPolicy::Append,
+        preserve_header_case: true,
+        ..TransportConfig::default()
+    };
+    let mut merger = HeaderMerger::for_transport(&cfg);
+    assert!(merger.preserves_case());
+    merger.insert("X-A", "1").unwrap();
+    merger.insert("x-a", "2").unwrap();
+    assert_eq!(merger.into_pairs(), pairs(&[("X-A", "1, 2")]));
+
+    let defaults = HeaderMerger::for_transport(&TransportConfig::default());
+    assert!(!defaults.preserves_case());
+}
@@ -0,0 +1,117 @@
+use super::MultiProviderModel;
+use crate::ai_sdk_core::{GenerateResponse, LanguageModel, SdkError, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::stream;
+use std::sync::Arc;
+
+struct StubModel {
+    model_id: String,
+}
+
+fn stub(model_id: &str) -> Arc<dyn LanguageModel> {
+    Arc::new(StubModel {
+        model_id: model_id.into(),
+    })
+}
+
+#[async_trait::async_trait]
+impl LanguageModel for StubModel {
+    fn provider_name(&self) -> &'static str {
+        "stub"
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    async fn do_generate(&self, _options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
+        Ok(GenerateResponse {
+            content: vec![v2t::Content::Text {
+                text: self.model_id.clone(),
+                provider_metadata: None,
+            }],
+            finish_reason: v2t::FinishReason::Stop,
+            usage: v2t::Usage::default(),
+            provider_metadata: None,
+            response_metadata: None,
+            request_body: None,
+            response_headers: None,
+            response_body: None,
+            warnings: vec![],
+        })
+    }
+
+    async fn do_stream(&self, _options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
+        Ok(StreamResponse {
+            stream: Box::pin(stream::empty()),
+            request_body: Some(serde_json::json!({ "model": self.model_id })),
+            response_headers: None,
+            raw_response: None,
+        })
+    }
+}
+
+fn facade() -> MultiProviderModel {
+    MultiProviderModel::new("openai/gpt-4o")
+        .with_model("openai", stub("gpt-4o"))
+        .with_factory("anthropic", |model_id| Ok(stub(model_id)))
+}
+
+async fn generated_by(model: &MultiProviderModel) -> String {
+    model
+        .do_generate(v2t::CallOptions::default())
+        .await
+        .expect("generate")
+        .text()
+}
+
+#[tokio::test]
+async fn routes_by_prefix_and_strips_it_for_factories() {
+    let model = facade();
+    assert_eq!(model.model_id(), "openai/gpt-4o");
+    assert_eq!(generated_by(&model).await, "gpt-4o");
+
+    let claude = model.for_model("anthropic/claude-sonnet-4-5");
+    assert_eq!(generated_by(&claude).await, "claude-sonnet-4-5");
+
+    let response = claude
+        .do_stream(v2t::CallOptions::default())
+        .await
+        .expect("stream");
+    assert_eq!(
+        response.request_body,
+        Some(serde_json::json!({ "model": "claude-sonnet-4-5" }))
+    );
+}
+
+#[tokio::test]
+async fn router_overrides_default_route_per_call() {
+    let model = facade().with_router(|options| options.headers.get("x-route-model").cloned());
+
+    assert_eq!(generated_by(&model).await, "gpt-4o");
+
+    let mut options = v2t::CallOptions::default();
+    options
+        .headers
+        .insert("x-route-model".into(), "anthropic/claude-haiku-4-5".into());
+    let response = model.do_generate(options).await.expect("generate");
+    assert_eq!(response.text(), "claude-haiku-4-5");
+}
+
+#[tokio::test]
+async fn unknown_or_missing_prefix_is_rejected() {
+    for (route, needle) in [
+        ("mistral/large", "unknown provider prefix 'mistral'"),
+        ("gpt-4o", "has no provider prefix"),
+    ] {
+        let err = facade()
+            .for_model(route)
+            .do_generate(v2t::CallOptions::default())
+            .await
+            .expect_err("routing error");
+        assert!(
+            matches!(&err, SdkError::InvalidArgument { message } if message.contains(needle)),
+            "unexpected error for {route}: {err:?}"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_types::v2 as v2t;
+
+fn system(text: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::System {
+        content: text.into(),
+        provider_options: None,
+    }
+}
+
+fn user(text: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: text.into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }
+}
+
+fn assistant_call(id: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::Assistant {
+        content: vec![v2t::AssistantPart::ToolCall(v2t::ToolCallPart {
+            tool_call_id: id.into(),
+            tool_name: "lookup".into(),
+            input: "{}".into(),
+            provider_executed: false,
+            provider_metadata: None,
+            dynamic: false,
+            provider_options: None,
+        })],
+        provider_options: None,
+    }
+}
+
+fn tool_result(id: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::Tool {
+        content: vec![v2t::ToolMessagePart::ToolResult(v2t::ToolResultPart {
+            r#type: v2t::ToolResultPartType::ToolResult,
+            tool_call_id: id.into(),
+            tool_name: "lookup".into(),
+            output: v2t::ToolResultOutput::Text { value: "ok".into() },
+            provider_options: None,
+        })],
+        provider_options: None,
+    }
+}
+
+fn user_texts(prompt: &[v2t::PromptMessage]) -> Vec<String> {
+    prompt
+        .iter()
+        .filter_map(|message| match message {
+            v2t::PromptMessage::User { content, .. } => match content.first() {
+                Some(v2t::UserPart::Text { text, .. }) => Some(text.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn limited(prompt: Vec<v2t::PromptMessage>, max: usize) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt,
+        max_prompt_messages: Some(max),
+        prompt_overflow: v2t::PromptOverflow::SlidingWindow,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn prompt_within_limit_is_borrowed() {
+    let options = limited(vec![system("s"), user("a")], 2);
+    let mut warnings = Vec::new();
+    let out = apply_prompt_message_limit(&options, &mut warnings).expect("within limit");
+    assert!(matches!(out, std::borrow::Cow::Borrowed(_)));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn sliding_window_drops_oldest_and_keeps_system() {
+    let options = limited(
+        vec![
+            system("s1"),
+            user("a"),
+            user("b"),
+            system("s2"),
+            user("c"),
+            user("d"),
+        ],
+        4,
+    );
+    let mut warnings = Vec::new();
+    let out = apply_prompt_message_limit(&options, &mut warnings).expect("sliding window");
+
+    assert_eq!(out.prompt.len(), 4);
+    assert!(
+        matches!(out.prompt[0], v2t::PromptMessage::System { ref content, .. } if content == "s1")
+    );
+    assert!(
+        matches!(out.prompt[1], v2t::PromptMessage::System { ref content, .. } if content == "s2")
+    );
+    assert_eq!(user_texts(&out.prompt), vec!["c", "d"]);
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::Other { message }] if message.contains("Dropped 2")
+    ));
+}
+
+#[test]
+fn sliding_window_drops_orphaned_tool_results() {
+    let options = limited(
+        vec![
+            system("s"),
+            assistant_call("call-1"),
+            tool_result("call-1"),
+            user("a"),
+        ],
+        3,
+    );
+    let mut warnings = Vec::new();
+    let out = apply_prompt_message_limit(&options, &mut warnings).expect("sliding window");
+
+    assert_eq!(out.prompt.len(), 2);
+    assert!(matches!(out.prompt[0], v2t::PromptMessage::System { .. }));
+    assert_eq!(user_texts(&out.prompt), vec!["a"]);
+}
+
+#[test]
+fn strict_mode_rejects_oversized_prompt() {
+    let options = v2t::CallOptions {
+        prompt: vec![system("s"), user("a"), user("b")],
+        max_prompt_messages: Some(2),
+        ..Default::default()
+    };
+    let mut warnings = Vec::new();
+    let err = apply_prompt_message_limit(&options, &mut warnings).expect_err("limit exceeded");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { ref message } if message.contains("max_prompt_messages (2)")
+    ));
+    assert!(warnings.is_empty());
+}
@@ -0,0 +1,119 @@
+use super::{RawCaptureConfig, RawResponseCapture};
+use crate::ai_sdk_core::{PartStream, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, StreamExt};
+use serde_json::json;
+
+fn raw(value: serde_json::Value) -> v2t::StreamPart {
+    v2t::StreamPart::Raw { raw_value: value }
+}
+
+fn response(parts: Vec<v2t::StreamPart>) -> StreamResponse {
+    let stream: PartStream = Box::pin(stream::iter(parts.into_iter().map(Ok)));
+    StreamResponse {
+        stream,
+        request_body: None,
+        response_headers: None,
+        raw_response: None,
+    }
+}
+
+fn text_delta(delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: "t".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+async fn drain(response: StreamResponse) -> (Vec<v2t::StreamPart>, RawResponseCapture) {
+    let capture = response.raw_response.expect("capture attached");
+    let parts = response
+        .stream
+        .map(|item| item.expect("part"))
+        .collect()
+        .await;
+    (parts, capture)
+}
+
+#[test]
+fn config_requires_capture_flag() {
+    let mut options = v2t::CallOptions {
+        include_raw_chunks: true,
+        ..Default::default()
+    };
+    assert_eq!(RawCaptureConfig::from_options(&options), None);
+    assert!(options.emit_raw_chunks());
+
+    options.capture_raw_response = true;
+    options.max_raw_response_bytes = Some(64);
+    assert_eq!(
+        RawCaptureConfig::from_options(&options),
+        Some(RawCaptureConfig {
+            forward_raw_chunks: true,
+            max_bytes: Some(64),
+        })
+    );
+}
+
+#[tokio::test]
+async fn capture_records_and_hides_raw_parts() {
+    let config = RawCaptureConfig {
+        forward_raw_chunks: false,
+        max_bytes: None,
+    };
+    let (parts, capture) = drain(
+        response(vec![
+            raw(json!({"n": 1})),
+            text_delta("a"),
+            raw(json!({"n": 2})),
+        ])
+        .with_raw_capture(Some(config)),
+    )
+    .await;
+
+    assert_eq!(parts.len(), 1);
+    assert!(matches!(parts[0], v2t::StreamPart::TextDelta { .. }));
+    assert_eq!(capture.to_json(), json!([{"n": 1}, {"n": 2}]));
+    assert!(!capture.is_truncated());
+}
+
+#[tokio::test]
+async fn capture_forwards_raw_parts_when_requested() {
+    let config = RawCaptureConfig {
+        forward_raw_chunks: true,
+        max_bytes: None,
+    };
+    let (parts, capture) =
+        drain(response(vec![raw(json!({"n": 1}))]).with_raw_capture(Some(config))).await;
+
+    assert_eq!(parts.len(), 1);
+    assert_eq!(capture.frames().len(), 1);
+}
+
+#[tokio::test]
+async fn capture_stops_at_byte_budget() {
+    // `{"n":1}` serializes to 7 bytes.
+    let config = RawCaptureConfig {
+        forward_raw_chunks: false,
+        max_bytes: Some(10),
+    };
+    let (_, capture) = drain(
+        response(vec![
+            raw(json!({"n": 1})),
+            raw(json!({"n": 2})),
+            raw(json!({"n": 3})),
+        ])
+        .with_raw_capture(Some(config)),
+    )
+    .await;
+
+    assert_eq!(capture.to_json(), json!([{"n": 1}]));
+    assert!(capture.is_truncated());
+}
+
+#[test]
+fn capture_is_not_attached_without_config() {
+    let response = response(vec![]).with_raw_capture(None);
+    assert!(response.raw_response.is_none());
+}
@@ -0,0 +1,55 @@
+use crate::ai_sdk_core::request_builder::raw_passthrough::{
+    apply_raw_passthrough, RAW_PASSTHROUGH_SCOPE,
+};
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn options_with_raw(raw: serde_json::Value) -> v2t::CallOptions {
+    let raw: HashMap<String, serde_json::Value> = serde_json::from_value(raw).unwrap();
+    v2t::CallOptions {
+        provider_options: HashMap::from([(RAW_PASSTHROUGH_SCOPE.to_string(), raw)]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn raw_fields_replace_top_level_keys() {
+    let options = options_with_raw(json!({
+        "temperature": 0.1,
+        "generationConfig": {"seed": 7},
+        "extra_flag": true,
+    }));
+    let mut body = json!({
+        "model": "m",
+        "temperature": 0.9,
+        "generationConfig": {"topK": 3, "seed": 1},
+    });
+    let mut warnings = Vec::new();
+    apply_raw_passthrough(&options, &mut body, &mut warnings);
+
+    assert_eq!(
+        body,
+        json!({
+            "model": "m",
+            "temperature": 0.1,
+            "generationConfig": {"seed": 7},
+            "extra_flag": true,
+        })
+    );
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::Other { message }]
+            if message == "raw provider options passthrough applied: extra_flag, generationConfig, temperature"
+    ));
+}
+
+#[test]
+fn missing_or_empty_scope_is_a_no_op() {
+    let mut body = json!({"model": "m"});
+    let mut warnings = Vec::new();
+    apply_raw_passthrough(&v2t::CallOptions::default(), &mut body, &mut warnings);
+    apply_raw_passthrough(&options_with_raw(json!({})), &mut body, &mut warnings);
+    assert_eq!(body, json!({"model": "m"}));
+    assert!(warnings.is_empty());
+}
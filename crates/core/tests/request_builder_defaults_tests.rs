@@ -1,5 +1,7 @@
+use crate::ai_sdk_core::options::merge_options_with_disallow;
 use crate::ai_sdk_core::request_builder::defaults::{
-    merge_provider_defaults, provider_defaults_from_json, request_overrides_from_json,
+    build_call_options, merge_provider_defaults, provider_defaults_from_json,
+    request_overrides_from_json,
 };
 use crate::ai_sdk_types::v2 as v2t;
 use serde_json::json;
@@ -54,3 +56,86 @@ fn merge_provider_defaults_preserves_existing_values() {
     assert_eq!(scope["top_p"], json!(0.2));
     assert_eq!(scope["nested"], json!({"a": 1, "b": 3}));
 }
+
+fn include_defaults(strategy: Option<&str>) -> v2t::ProviderOptions {
+    let mut scope = json!({"include": ["reasoning.encrypted_content", "file_search_call.results"]});
+    if let Some(strategy) = strategy {
+        scope["$merge"] = json!({ "include": strategy });
+    }
+    provider_defaults_from_json("openai", &json!({ "openai": scope })).expect("defaults")
+}
+
+fn call_with_include(include: serde_json::Value) -> v2t::CallOptions {
+    v2t::CallOptions {
+        provider_options: v2t::ProviderOptions::from([(
+            "openai".into(),
+            HashMap::from([("include".into(), include)]),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn array_defaults_merge_by_declared_strategy() {
+    let call_include = json!(["message.output_text.logprobs", "file_search_call.results"]);
+
+    let replaced = build_call_options(
+        call_with_include(call_include.clone()),
+        "openai",
+        Some(&include_defaults(None)),
+    );
+    assert_eq!(replaced.provider_options["openai"]["include"], call_include);
+
+    let unioned = build_call_options(
+        call_with_include(call_include.clone()),
+        "openai",
+        Some(&include_defaults(Some("union"))),
+    );
+    assert_eq!(
+        unioned.provider_options["openai"]["include"],
+        json!([
+            "reasoning.encrypted_content",
+            "file_search_call.results",
+            "message.output_text.logprobs"
+        ])
+    );
+    assert!(!unioned.provider_options["openai"].contains_key("$merge"));
+
+    let appended = build_call_options(
+        call_with_include(call_include),
+        "openai",
+        Some(&include_defaults(Some("append"))),
+    );
+    assert_eq!(
+        appended.provider_options["openai"]["include"]
+            .as_array()
+            .map(Vec::len),
+        Some(4)
+    );
+
+    let defaults_only = build_call_options(
+        v2t::CallOptions::default(),
+        "openai",
+        Some(&include_defaults(Some("union"))),
+    );
+    assert_eq!(
+        defaults_only.provider_options["openai"]["include"],
+        json!(["reasoning.encrypted_content", "file_search_call.results"])
+    );
+}
+
+#[test]
+fn request_overrides_union_arrays_into_body() {
+    let mut body = json!({"model": "m", "include": ["a", "b"], "modalities": ["text"]});
+    let overrides = json!({
+        "model": "ignored",
+        "include": ["b", "c"],
+        "modalities": ["audio"],
+        "$merge": {"include": "union", "modalities": "bogus"}
+    });
+    merge_options_with_disallow(&mut body, &overrides, &["model"]);
+    assert_eq!(
+        body,
+        json!({"model": "m", "include": ["a", "b", "c"], "modalities": ["audio"]})
+    );
+}
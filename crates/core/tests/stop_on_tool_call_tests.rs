@@ -0,0 +1,152 @@
+use super::{disable_stop_on_first_tool_call, stop_on_first_tool_call};
+use crate::ai_sdk_core::{PartStream, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, StreamExt};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+fn tool_call(id: &str, provider_executed: bool) -> v2t::StreamPart {
+    v2t::StreamPart::ToolCall(v2t::ToolCallPart {
+        tool_call_id: id.into(),
+        tool_name: "lookup".into(),
+        input: "{}".into(),
+        provider_executed,
+        provider_metadata: None,
+        dynamic: false,
+        provider_options: None,
+    })
+}
+
+fn text_start(id: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextStart {
+        id: id.into(),
+        provider_metadata: None,
+    }
+}
+
+fn text_delta(id: &str, delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: id.into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+/// Parts followed by a stream that never ends, flagging when it is dropped.
+fn pending_after(parts: Vec<v2t::StreamPart>) -> (PartStream, Arc<AtomicBool>) {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let guard = DropFlag(dropped.clone());
+    let stream: PartStream = Box::pin(
+        stream::iter(parts.into_iter().map(Ok))
+            .chain(stream::pending())
+            .map(move |item| {
+                let _ = &guard;
+                item
+            }),
+    );
+    (stream, dropped)
+}
+
+#[tokio::test]
+async fn ends_stream_after_first_tool_call() {
+    let (parts, dropped) = pending_after(vec![
+        v2t::StreamPart::StreamStart { warnings: vec![] },
+        text_start("t1"),
+        text_delta("t1", "Calling"),
+        tool_call("call-1", false),
+        text_delta("t1", " trailing"),
+    ]);
+
+    let parts: Vec<_> = stop_on_first_tool_call(parts)
+        .map(|item| item.expect("part"))
+        .collect()
+        .await;
+
+    assert!(dropped.load(Ordering::SeqCst));
+    assert_eq!(parts.len(), 6);
+    assert!(matches!(&parts[3], v2t::StreamPart::ToolCall(call) if call.tool_call_id == "call-1"));
+    assert!(matches!(&parts[4], v2t::StreamPart::TextEnd { id, .. } if id == "t1"));
+    match &parts[5] {
+        v2t::StreamPart::Finish {
+            usage,
+            finish_reason,
+            ..
+        } => {
+            assert!(matches!(finish_reason, v2t::FinishReason::ToolCalls));
+            assert!(usage.input_tokens.is_none() && usage.output_tokens.is_none());
+        }
+        other => panic!("expected finish, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn provider_executed_calls_do_not_stop() {
+    let parts: PartStream = Box::pin(stream::iter(
+        vec![
+            tool_call("search-1", true),
+            text_delta("t1", "after"),
+            tool_call("call-1", false),
+            text_delta("t1", "ignored"),
+        ]
+        .into_iter()
+        .map(Ok),
+    ));
+
+    let parts: Vec<_> = stop_on_first_tool_call(parts)
+        .map(|item| item.expect("part"))
+        .collect()
+        .await;
+
+    assert_eq!(parts.len(), 4);
+    assert!(matches!(&parts[1], v2t::StreamPart::TextDelta { delta, .. } if delta == "after"));
+    assert!(matches!(&parts[3], v2t::StreamPart::Finish { .. }));
+}
+
+#[tokio::test]
+async fn stream_response_wrapper_is_opt_in() {
+    let response = |parts: Vec<v2t::StreamPart>| StreamResponse {
+        stream: Box::pin(stream::iter(parts.into_iter().map(Ok))),
+        request_body: None,
+        response_headers: None,
+        raw_response: None,
+    };
+    let parts = vec![tool_call("call-1", false), text_delta("t1", "more")];
+
+    let untouched = response(parts.clone())
+        .with_stop_on_first_tool_call(false)
+        .stream
+        .count()
+        .await;
+    assert_eq!(untouched, 2);
+
+    let stopped: Vec<_> = response(parts)
+        .with_stop_on_first_tool_call(true)
+        .stream
+        .map(|item| item.expect("part"))
+        .collect()
+        .await;
+    assert_eq!(stopped.len(), 2);
+    assert!(matches!(&stopped[1], v2t::StreamPart::Finish { .. }));
+}
+
+#[test]
+fn disabling_for_generate_clears_flag_and_warns() {
+    let mut options = v2t::CallOptions::default();
+    assert!(disable_stop_on_first_tool_call(&mut options).is_none());
+
+    options.stop_on_first_tool_call = true;
+    let warning = disable_stop_on_first_tool_call(&mut options);
+    assert!(!options.stop_on_first_tool_call);
+    assert!(matches!(
+        warning,
+        Some(v2t::CallWarning::UnsupportedSetting { setting, .. }) if setting == "stopOnFirstToolCall"
+    ));
+}
@@ -0,0 +1,302 @@
+use super::{collect_generate, split_channels};
+use crate::ai_sdk_core::{PartStream, SdkError, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, StreamExt};
+use std::task::Poll;
+
+fn response(parts: Vec<v2t::StreamPart>) -> StreamResponse {
+    StreamResponse {
+        stream: Box::pin(stream::iter(parts.into_iter().map(Ok))),
+        request_body: Some(serde_json::json!({ "model": "m" })),
+        response_headers: None,
+        raw_response: None,
+    }
+}
+
+fn tool_call(id: &str, input: &str) -> v2t::StreamPart {
+    v2t::StreamPart::ToolCall(v2t::ToolCallPart {
+        tool_call_id: id.into(),
+        tool_name: "lookup".into(),
+        input: input.into(),
+        provider_executed: false,
+        provider_metadata: None,
+        dynamic: false,
+        provider_options: None,
+    })
+}
+
+#[tokio::test]
+async fn into_generate_collects_text_tool_calls_and_usage() {
+    let finish = v2t::StreamPart::Finish {
+        usage: v2t::Usage {
+            input_tokens: Some(12),
+            output_tokens: Some(4),
+            ..Default::default()
+        },
+        finish_reason: v2t::FinishReason::ToolCalls,
+        provider_metadata: None,
+    };
+    let generated = response(vec![
+        v2t::StreamPart::StreamStart { warnings: vec![] },
+        v2t::StreamPart::TextStart {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextDelta {
+            id: "t1".into(),
+            delta: "Looking ".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextDelta {
+            id: "t1".into(),
+            delta: "up".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextEnd {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        tool_call("call-1", r#"{"q":"a"}"#),
+        tool_call("call-2", r#"{"q":"b"}"#),
+        finish,
+    ])
+    .into_generate()
+    .await
+    .expect("collect");
+
+    assert_eq!(generated.text(), "Looking up");
+    let ids: Vec<_> = generated
+        .tool_calls()
+        .iter()
+        .map(|call| call.tool_call_id.as_str())
+        .collect();
+    assert_eq!(ids, ["call-1", "call-2"]);
+    assert_eq!(generated.tool_calls()[1].input, r#"{"q":"b"}"#);
+    assert!(matches!(
+        generated.finish_reason,
+        v2t::FinishReason::ToolCalls
+    ));
+    assert_eq!(generated.usage.input_tokens, Some(12));
+    assert_eq!(generated.usage.output_tokens, Some(4));
+    assert_eq!(
+        generated.request_body,
+        Some(serde_json::json!({ "model": "m" }))
+    );
+}
+
+#[tokio::test]
+async fn into_generate_fails_on_error_parts() {
+    let err = response(vec![
+        tool_call("call-1", "{}"),
+        v2t::StreamPart::Error {
+            error: serde_json::json!({ "message": "overloaded" }),
+        },
+    ])
+    .into_generate()
+    .await
+    .expect_err("error part");
+    assert!(matches!(err, SdkError::Upstream { .. }));
+}
+
+#[tokio::test]
+async fn collect_generate_rebuilds_content_from_a_part_stream() {
+    let parts = vec![
+        v2t::StreamPart::ReasoningStart {
+            id: "r1".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::ReasoningDelta {
+            id: "r1".into(),
+            delta: "think".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::ReasoningEnd {
+            id: "r1".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextStart {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextDelta {
+            id: "t1".into(),
+            delta: "Hel".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextDelta {
+            id: "t1".into(),
+            delta: "lo".into(),
+            provider_metadata: None,
+        },
+        v2t::StreamPart::TextEnd {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        tool_call("call-1", r#"{"q":"a"}"#),
+        v2t::StreamPart::Finish {
+            usage: v2t::Usage {
+                output_tokens: Some(7),
+                ..Default::default()
+            },
+            finish_reason: v2t::FinishReason::ToolCalls,
+            provider_metadata: None,
+        },
+    ];
+    let stream: PartStream = Box::pin(stream::iter(parts.into_iter().map(Ok)));
+    let generated = collect_generate(stream).await.expect("collect");
+
+    assert!(matches!(
+        &generated.content[..],
+        [
+            v2t::Content::Reasoning { text: reasoning, .. },
+            v2t::Content::Text { text, .. },
+            v2t::Content::ToolCall(call),
+        ] if reasoning == "think" && text == "Hello" && call.tool_call_id == "call-1"
+    ));
+    assert!(matches!(
+        generated.finish_reason,
+        v2t::FinishReason::ToolCalls
+    ));
+    assert_eq!(generated.usage.output_tokens, Some(7));
+}
+
+#[tokio::test]
+async fn into_generate_keeps_only_final_files() {
+    let partial = |index: u64| v2t::StreamPart::File {
+        media_type: "image/png".into(),
+        data: format!("preview-{index}"),
+        provider_metadata: Some(
+            [(
+                "openai".to_string(),
+                [
+                    ("partial".to_string(), serde_json::json!(true)),
+                    ("index".to_string(), serde_json::json!(index)),
+                ]
+                .into_iter()
+                .collect(),
+            )]
+            .into_iter()
+            .collect(),
+        ),
+    };
+    let generated = response(vec![
+        partial(0),
+        partial(1),
+        v2t::StreamPart::File {
+            media_type: "image/png".into(),
+            data: "final".into(),
+            provider_metadata: None,
+        },
+    ])
+    .into_generate()
+    .await
+    .expect("collect");
+
+    let files: Vec<_> = generated
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            v2t::Content::File { data, .. } => Some(data.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(files, ["final"]);
+}
+
+fn interleaved_parts() -> Vec<v2t::StreamPart> {
+    let text = |delta: &str| v2t::StreamPart::TextDelta {
+        id: "t1".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    };
+    let reasoning = |delta: &str| v2t::StreamPart::ReasoningDelta {
+        id: "r1".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    };
+    vec![
+        v2t::StreamPart::StreamStart { warnings: vec![] },
+        v2t::StreamPart::ReasoningStart {
+            id: "r1".into(),
+            provider_metadata: None,
+        },
+        reasoning("think"),
+        v2t::StreamPart::TextStart {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        text("Hel"),
+        reasoning("ing"),
+        text("lo"),
+        tool_call("call-1", "{}"),
+        v2t::StreamPart::Finish {
+            usage: v2t::Usage {
+                output_tokens: Some(7),
+                ..Default::default()
+            },
+            finish_reason: v2t::FinishReason::Stop,
+            provider_metadata: None,
+        },
+    ]
+}
+
+fn deltas(parts: &[v2t::StreamPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. }
+            | v2t::StreamPart::ReasoningDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+async fn drain(stream: PartStream) -> Vec<v2t::StreamPart> {
+    stream.map(|item| item.expect("part")).collect().await
+}
+
+#[tokio::test]
+async fn split_channels_demultiplexes_text_reasoning_and_tail() {
+    let source: PartStream = Box::pin(stream::iter(interleaved_parts().into_iter().map(Ok)));
+    let (text, reasoning, tail) = split_channels(source);
+    let (text, reasoning, tail) =
+        futures_util::future::join3(drain(text), drain(reasoning), drain(tail)).await;
+
+    assert_eq!(deltas(&text), "Hello");
+    assert!(text.iter().all(|part| matches!(
+        part,
+        v2t::StreamPart::TextStart { .. } | v2t::StreamPart::TextDelta { .. }
+    )));
+    assert_eq!(deltas(&reasoning), "thinking");
+    assert_eq!(reasoning.len(), 3);
+    assert_eq!(tail.len(), 3);
+    assert!(matches!(&tail[1], v2t::StreamPart::ToolCall(call) if call.tool_call_id == "call-1"));
+    assert!(matches!(
+        &tail[2],
+        v2t::StreamPart::Finish { usage, .. } if usage.output_tokens == Some(7)
+    ));
+}
+
+#[tokio::test]
+async fn split_channels_buffers_for_later_channels_and_skips_dropped_ones() {
+    let mut parts = interleaved_parts().into_iter();
+    let mut woke_once = false;
+    // Source that wakes itself while pending before every part.
+    let source: PartStream = Box::pin(stream::poll_fn(move |cx| {
+        if !woke_once {
+            woke_once = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        woke_once = false;
+        Poll::Ready(parts.next().map(Ok))
+    }));
+    let (text, reasoning, tail) = split_channels(source);
+    drop(reasoning);
+
+    let text = drain(text).await;
+    assert_eq!(deltas(&text), "Hello");
+    let tail = drain(tail).await;
+    assert_eq!(tail.len(), 3);
+    assert!(matches!(tail.last(), Some(v2t::StreamPart::Finish { .. })));
+}
@@ -0,0 +1,119 @@
+use super::{IntegrityReport, INTEGRITY_METADATA_SCOPE};
+use crate::ai_sdk_core::{GenerateResponse, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, TryStreamExt};
+use serde_json::json;
+
+fn usage(output_tokens: u64) -> v2t::Usage {
+    v2t::Usage {
+        output_tokens: Some(output_tokens),
+        ..Default::default()
+    }
+}
+
+fn stream_response(parts: Vec<v2t::StreamPart>) -> StreamResponse {
+    StreamResponse {
+        stream: Box::pin(stream::iter(parts.into_iter().map(Ok))),
+        request_body: None,
+        response_headers: None,
+        raw_response: None,
+    }
+}
+
+fn text_delta(delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: "t1".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+fn finish(usage: v2t::Usage) -> v2t::StreamPart {
+    v2t::StreamPart::Finish {
+        usage,
+        finish_reason: v2t::FinishReason::Stop,
+        provider_metadata: None,
+    }
+}
+
+#[test]
+fn report_tolerates_counter_drift() {
+    // 400 characters count as 100 tokens with the default counter.
+    let output = "x".repeat(400);
+    assert!(!IntegrityReport::new(&output, &usage(90), false).mismatch());
+    assert!(!IntegrityReport::new(&output, &usage(140), false).mismatch());
+    assert!(IntegrityReport::new(&output, &usage(400), false).mismatch());
+    assert!(IntegrityReport::new(&output, &usage(20), false).mismatch());
+    assert!(!IntegrityReport::new(&output, &v2t::Usage::default(), false).mismatch());
+}
+
+#[test]
+fn hidden_reasoning_tokens_are_not_expected_in_the_output() {
+    let output = "x".repeat(400);
+    let usage = v2t::Usage {
+        output_tokens: Some(1_100),
+        reasoning_tokens: Some(1_000),
+        ..Default::default()
+    };
+
+    let hidden = IntegrityReport::new(&output, &usage, false);
+    assert_eq!(hidden.expected_output_tokens, Some(100));
+    assert!(!hidden.mismatch());
+    assert!(IntegrityReport::new(&output, &usage, true).mismatch());
+}
+
+#[tokio::test]
+async fn stream_check_records_figures_and_collect_warns_on_mismatch() {
+    let parts: Vec<_> = stream_response(vec![text_delta("Hello"), finish(usage(2))])
+        .with_integrity_check()
+        .stream
+        .try_collect()
+        .await
+        .expect("parts");
+    let Some(v2t::StreamPart::Finish {
+        provider_metadata, ..
+    }) = parts.last()
+    else {
+        panic!("expected finish part");
+    };
+    let scope = &provider_metadata.as_ref().expect("metadata")[INTEGRITY_METADATA_SCOPE];
+    assert_eq!(scope["outputChars"], json!(5));
+    assert_eq!(scope["estimatedOutputTokens"], json!(2));
+    assert_eq!(scope["reportedOutputTokens"], json!(2));
+    assert_eq!(scope["mismatch"], json!(false));
+
+    let truncated = stream_response(vec![text_delta("Hello"), finish(usage(500))])
+        .with_integrity_check()
+        .into_generate()
+        .await
+        .expect("generate");
+    assert!(truncated.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message } if message.starts_with("stream integrity:")
+    )));
+}
+
+#[test]
+fn generate_check_pushes_warning_on_mismatch() {
+    let response = GenerateResponse {
+        content: vec![v2t::Content::Text {
+            text: "Hello".into(),
+            provider_metadata: None,
+        }],
+        finish_reason: v2t::FinishReason::Stop,
+        usage: usage(500),
+        provider_metadata: None,
+        response_metadata: None,
+        request_body: None,
+        response_headers: None,
+        response_body: None,
+        warnings: Vec::new(),
+    }
+    .with_integrity_check();
+
+    assert_eq!(response.warnings.len(), 1);
+    let report =
+        IntegrityReport::from_metadata(response.provider_metadata.as_ref()).expect("report");
+    assert_eq!(report.expected_output_tokens, Some(500));
+    assert!(report.mismatch());
+}
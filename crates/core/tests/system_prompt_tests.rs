@@ -0,0 +1,134 @@
+use crate::ai_sdk_core::request_builder::system_prompt::{
+    apply_system_prompt_mode, fold_system_into_user,
+};
+use crate::ai_sdk_types::v2 as v2t;
+
+fn system(text: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::System {
+        content: text.into(),
+        provider_options: None,
+    }
+}
+
+fn user(text: &str) -> v2t::PromptMessage {
+    v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: text.into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }
+}
+
+fn user_texts(message: &v2t::PromptMessage) -> Vec<String> {
+    match message {
+        v2t::PromptMessage::User { content, .. } => content
+            .iter()
+            .filter_map(|part| match part {
+                v2t::UserPart::Text { text, .. } => Some(text.clone()),
+                _ => None,
+            })
+            .collect(),
+        other => panic!("expected user message, got {other:?}"),
+    }
+}
+
+fn with_mode(prompt: Vec<v2t::PromptMessage>, mode: v2t::SystemPromptMode) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt,
+        system_prompt_mode: Some(mode),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fold_prepends_all_system_content_to_first_user_message() {
+    let folded = fold_system_into_user(&[system("s1"), user("a"), system("s2"), user("b")]);
+
+    assert_eq!(folded.len(), 2);
+    assert_eq!(user_texts(&folded[0]), vec!["s1\n\ns2", "a"]);
+    assert_eq!(user_texts(&folded[1]), vec!["b"]);
+}
+
+#[test]
+fn fold_without_user_message_inserts_one() {
+    let folded = fold_system_into_user(&[system("s")]);
+
+    assert_eq!(folded.len(), 1);
+    assert_eq!(user_texts(&folded[0]), vec!["s"]);
+}
+
+#[test]
+fn unset_mode_borrows_options() {
+    let options = v2t::CallOptions::new(vec![system("s"), user("a")]);
+    let mut warnings = Vec::new();
+    let (out, mode) = apply_system_prompt_mode(&options, &[], &mut warnings);
+
+    assert!(matches!(out, std::borrow::Cow::Borrowed(_)));
+    assert_eq!(mode, None);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn supported_mode_is_returned_without_rewriting() {
+    let options = with_mode(
+        vec![system("s"), user("a")],
+        v2t::SystemPromptMode::Developer,
+    );
+    let mut warnings = Vec::new();
+    let (out, mode) = apply_system_prompt_mode(
+        &options,
+        &[
+            v2t::SystemPromptMode::System,
+            v2t::SystemPromptMode::Developer,
+        ],
+        &mut warnings,
+    );
+
+    assert_eq!(out.prompt.len(), 2);
+    assert_eq!(mode, Some(v2t::SystemPromptMode::Developer));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn unsupported_mode_warns_and_falls_back() {
+    let options = with_mode(
+        vec![system("s"), user("a")],
+        v2t::SystemPromptMode::Developer,
+    );
+    let mut warnings = Vec::new();
+    let (out, mode) =
+        apply_system_prompt_mode(&options, &[v2t::SystemPromptMode::System], &mut warnings);
+
+    assert_eq!(out.prompt.len(), 2);
+    assert_eq!(mode, None);
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::UnsupportedSetting { setting, details: Some(details) }]
+            if setting == "systemPromptMode" && details.starts_with("developer")
+    ));
+}
+
+#[test]
+fn fold_mode_rewrites_prompt_for_any_provider() {
+    let options = with_mode(
+        vec![system("s"), user("a")],
+        v2t::SystemPromptMode::FoldIntoUser,
+    );
+    let mut warnings = Vec::new();
+    let (out, mode) = apply_system_prompt_mode(&options, &[], &mut warnings);
+
+    assert_eq!(mode, Some(v2t::SystemPromptMode::FoldIntoUser));
+    assert_eq!(out.prompt.len(), 1);
+    assert_eq!(user_texts(&out.prompt[0]), vec!["s", "a"]);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn fold_includes_developer_messages() {
+    let folded =
+        fold_system_into_user(&[system("s"), v2t::PromptMessage::developer("d"), user("a")]);
+
+    assert_eq!(folded.len(), 1);
+    assert_eq!(user_texts(&folded[0]), vec!["s\n\nd", "a"]);
+}
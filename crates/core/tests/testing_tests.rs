@@ -0,0 +1,146 @@
+use super::StreamAsserter;
+use crate::ai_sdk_core::{PartStream, SdkError};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::stream;
+
+fn text_delta(delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: "t1".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+fn finish(reason: v2t::FinishReason) -> v2t::StreamPart {
+    v2t::StreamPart::Finish {
+        usage: v2t::Usage {
+            input_tokens: Some(3),
+            output_tokens: Some(2),
+            ..Default::default()
+        },
+        finish_reason: reason,
+        provider_metadata: None,
+    }
+}
+
+fn text_parts() -> Vec<v2t::StreamPart> {
+    vec![
+        v2t::StreamPart::StreamStart { warnings: vec![] },
+        v2t::StreamPart::TextStart {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        text_delta("Hello"),
+        text_delta(" world"),
+        v2t::StreamPart::TextEnd {
+            id: "t1".into(),
+            provider_metadata: None,
+        },
+        finish(v2t::FinishReason::Stop),
+    ]
+}
+
+fn part_stream(parts: Vec<v2t::StreamPart>) -> PartStream {
+    Box::pin(stream::iter(parts.into_iter().map(Ok)))
+}
+
+#[tokio::test]
+async fn asserter_accepts_matching_sequence() {
+    let parts = StreamAsserter::new()
+        .text_start()
+        .text("Hello world")
+        .text_end()
+        .finish_with_usage(
+            v2t::FinishReason::Stop,
+            v2t::Usage {
+                input_tokens: Some(3),
+                output_tokens: Some(2),
+                ..Default::default()
+            },
+        )
+        .assert(part_stream(text_parts()))
+        .await;
+    assert_eq!(parts.len(), 6);
+}
+
+#[tokio::test]
+async fn asserter_reports_text_and_finish_diffs() {
+    let report = StreamAsserter::new()
+        .text_start()
+        .text("Hello there")
+        .check(part_stream(text_parts()))
+        .await
+        .expect_err("text mismatch");
+    assert!(report.contains("expectation #1 (text \"Hello there\")"));
+    assert!(report.contains("expected: \"Hello there\""));
+    assert!(report.contains("actual:   \"Hello world\""));
+    assert!(
+        report.contains(" >   1 {\"type\":\"text-delta\""),
+        "{report}"
+    );
+
+    let report = StreamAsserter::new()
+        .skip_unmatched()
+        .finish(v2t::FinishReason::Length)
+        .check(part_stream(text_parts()))
+        .await
+        .expect_err("finish mismatch");
+    assert!(report.contains("expected: \"length\""));
+    assert!(report.contains("actual:   \"stop\""));
+}
+
+#[tokio::test]
+async fn asserter_skips_and_rejects_trailing_parts() {
+    StreamAsserter::new()
+        .skip_unmatched()
+        .finish(v2t::FinishReason::Stop)
+        .assert(part_stream(text_parts()))
+        .await;
+
+    let report = StreamAsserter::new()
+        .text_start()
+        .check(part_stream(text_parts()))
+        .await
+        .expect_err("trailing parts");
+    assert!(report.contains("unexpected text-delta after the last expectation"));
+
+    StreamAsserter::new()
+        .text_start()
+        .allow_trailing()
+        .assert(part_stream(text_parts()))
+        .await;
+
+    let report = StreamAsserter::new()
+        .strict()
+        .text_start()
+        .allow_trailing()
+        .check(part_stream(text_parts()))
+        .await
+        .expect_err("stream start is not ignored");
+    assert!(report.contains("got stream-start"));
+}
+
+#[tokio::test]
+async fn asserter_reports_missing_parts_and_stream_errors() {
+    let report = StreamAsserter::new()
+        .skip_unmatched()
+        .tool_call("lookup")
+        .check(part_stream(text_parts()))
+        .await
+        .expect_err("no tool call");
+    assert!(report.contains("(tool-call \"lookup\"): stream ended"));
+
+    let failing: PartStream = Box::pin(stream::iter(vec![
+        Ok(text_delta("partial")),
+        Err(SdkError::Timeout),
+    ]));
+    let report = StreamAsserter::new()
+        .text("partial")
+        .check(failing)
+        .await
+        .expect_err("stream error");
+    assert!(
+        report.starts_with("stream failed after 1 parts"),
+        "{report}"
+    );
+}
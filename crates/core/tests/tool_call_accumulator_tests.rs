@@ -0,0 +1,141 @@
+use super::{completed_tool_calls, tool_call_updates, ToolCallAccumulator, ToolCallUpdate};
+use crate::ai_sdk_core::PartStream;
+use crate::ai_sdk_types::v2 as v2t;
+use crate::ai_sdk_types::Event;
+use futures_util::{stream, StreamExt};
+
+fn start(id: &str, name: &str) -> v2t::StreamPart {
+    v2t::StreamPart::ToolInputStart {
+        id: id.into(),
+        tool_name: name.into(),
+        provider_executed: false,
+        provider_metadata: None,
+    }
+}
+
+fn delta(id: &str, delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::ToolInputDelta {
+        id: id.into(),
+        delta: delta.into(),
+        provider_executed: false,
+        provider_metadata: None,
+    }
+}
+
+fn end(id: &str) -> v2t::StreamPart {
+    v2t::StreamPart::ToolInputEnd {
+        id: id.into(),
+        provider_executed: false,
+        provider_metadata: None,
+    }
+}
+
+fn call(id: &str, name: &str, input: &str) -> v2t::StreamPart {
+    v2t::StreamPart::ToolCall(v2t::ToolCallPart {
+        tool_call_id: id.into(),
+        tool_name: name.into(),
+        input: input.into(),
+        provider_executed: false,
+        provider_metadata: None,
+        dynamic: false,
+        provider_options: None,
+    })
+}
+
+fn part_stream(parts: Vec<v2t::StreamPart>) -> PartStream {
+    Box::pin(stream::iter(parts.into_iter().map(Ok)))
+}
+
+#[tokio::test]
+async fn interleaved_calls_complete_from_buffers() {
+    let parts = part_stream(vec![
+        start("a", "weather"),
+        start("b", "search"),
+        delta("a", "{\"city\":"),
+        delta("b", "{\"q\":\"rust\"}"),
+        delta("a", "\"Paris\"}"),
+        end("b"),
+        end("a"),
+    ]);
+
+    let calls: Vec<_> = completed_tool_calls(parts)
+        .map(|item| item.expect("call"))
+        .collect()
+        .await;
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].tool_call_id, "a");
+    assert_eq!(calls[0].tool_name, "weather");
+    assert_eq!(calls[0].input, "{\"city\":\"Paris\"}");
+    assert_eq!(calls[1].tool_call_id, "b");
+    assert_eq!(calls[1].input, "{\"q\":\"rust\"}");
+}
+
+#[tokio::test]
+async fn final_tool_call_part_wins_and_is_not_duplicated() {
+    let parts = part_stream(vec![
+        start("a", "weather"),
+        delta("a", "{\"city\":\"Par"),
+        end("a"),
+        call("a", "weather", "{\"city\":\"Paris\"}"),
+        call("a", "weather", "{\"city\":\"Paris\"}"),
+    ]);
+
+    let updates: Vec<_> = tool_call_updates(parts)
+        .map(|item| item.expect("update"))
+        .collect()
+        .await;
+
+    assert_eq!(updates.len(), 2);
+    match &updates[0] {
+        ToolCallUpdate::Progress(snapshot) => assert_eq!(snapshot.input, "{\"city\":\"Par"),
+        other => panic!("expected progress, got {other:?}"),
+    }
+    match &updates[1] {
+        ToolCallUpdate::Completed(call) => assert_eq!(call.input, "{\"city\":\"Paris\"}"),
+        other => panic!("expected completion, got {other:?}"),
+    }
+}
+
+#[test]
+fn snapshots_and_finish_skip_unfinished_calls() {
+    let mut acc = ToolCallAccumulator::new();
+    acc.push_part(&start("a", "weather"));
+    acc.push_part(&delta("a", "{}"));
+    acc.push_part(&start("b", "search"));
+    acc.push_part(&delta("b", "{\"q\""));
+    acc.push_part(&end("a"));
+
+    let snapshots = acc.snapshots();
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[1].tool_call_id, "b");
+    assert_eq!(snapshots[1].input, "{\"q\"");
+
+    let calls = acc.finish();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool_call_id, "a");
+    assert!(acc.snapshots().is_empty());
+}
+
+#[test]
+fn events_complete_on_tool_call_end() {
+    let mut acc = ToolCallAccumulator::new();
+    acc.push_event(&Event::ToolCallStart {
+        id: "a".into(),
+        name: "weather".into(),
+    });
+    let progress = acc.push_event(&Event::ToolCallDelta {
+        id: "a".into(),
+        args_json: "{}".into(),
+    });
+    assert!(matches!(progress, Some(ToolCallUpdate::Progress(_))));
+
+    match acc.push_event(&Event::ToolCallEnd { id: "a".into() }) {
+        Some(ToolCallUpdate::Completed(call)) => {
+            assert_eq!(call.tool_name, "weather");
+            assert_eq!(call.input, "{}");
+        }
+        other => panic!("expected completion, got {other:?}"),
+    }
+    assert!(acc.finish().is_empty());
+}
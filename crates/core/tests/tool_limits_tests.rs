@@ -0,0 +1,91 @@
+use crate::ai_sdk_core::request_builder::tool_limits::{check_tool_limits, OPENAI_TOOL_LIMITS};
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+
+fn tool(name: &str) -> v2t::Tool {
+    v2t::Tool::Function(v2t::FunctionTool {
+        r#type: Default::default(),
+        name: name.into(),
+        description: None,
+        input_schema: json!({"type": "object", "properties": {"query": {"type": "string"}}}),
+        strict: None,
+        provider_options: None,
+    })
+}
+
+fn options_with_tools(count: usize) -> v2t::CallOptions {
+    v2t::CallOptions {
+        tools: (0..count).map(|i| tool(&format!("tool_{i}"))).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn within_limits_adds_no_warnings() {
+    let options = options_with_tools(128);
+    let mut warnings = Vec::new();
+    check_tool_limits(&options, OPENAI_TOOL_LIMITS, &mut warnings).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn provider_tool_count_limit_warns() {
+    let options = options_with_tools(130);
+    let mut warnings = Vec::new();
+    check_tool_limits(&options, OPENAI_TOOL_LIMITS, &mut warnings).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(
+        &warnings[0],
+        v2t::CallWarning::Other { message }
+            if message == "request has 130 tools, exceeding max_tools (128)"
+    ));
+}
+
+#[test]
+fn configured_limits_override_provider_limits() {
+    let mut options = options_with_tools(3);
+    options.tool_limits = Some(v2t::ToolLimits {
+        max_tools: Some(2),
+        max_schema_bytes: Some(64),
+    });
+    let mut warnings = Vec::new();
+    check_tool_limits(&options, OPENAI_TOOL_LIMITS, &mut warnings).unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(
+        &warnings[1],
+        v2t::CallWarning::Other { message } if message.contains("max_schema_bytes (64)")
+    ));
+
+    options.tool_limits = Some(v2t::ToolLimits {
+        max_tools: Some(200),
+        max_schema_bytes: None,
+    });
+    let mut warnings = Vec::new();
+    check_tool_limits(&options_with_tools(150), OPENAI_TOOL_LIMITS, &mut warnings).unwrap();
+    assert_eq!(warnings.len(), 1);
+    let mut raised = options_with_tools(150);
+    raised.tool_limits = options.tool_limits;
+    let mut warnings = Vec::new();
+    check_tool_limits(&raised, OPENAI_TOOL_LIMITS, &mut warnings).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn strict_mode_rejects_exceeded_limit() {
+    let mut options = options_with_tools(2);
+    options.strict_tool_limits = true;
+    options.tool_limits = Some(v2t::ToolLimits {
+        max_tools: None,
+        max_schema_bytes: Some(10),
+    });
+    let mut warnings = Vec::new();
+    let err = check_tool_limits(&options, v2t::ToolLimits::default(), &mut warnings).unwrap_err();
+    assert!(warnings.is_empty());
+    match err {
+        SdkError::InvalidArgument { message } => {
+            assert!(message.contains("max_schema_bytes (10)"), "{message}")
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
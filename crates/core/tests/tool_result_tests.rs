@@ -0,0 +1,54 @@
+use super::{tool_result_output_from_bytes, tool_result_text_from_bytes};
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_types::v2 as v2t;
+
+#[test]
+fn invalid_utf8_text_output_is_rejected() {
+    let err = tool_result_output_from_bytes(vec![b'o', b'k', 0xff, b'!'], "text/plain")
+        .expect_err("invalid utf-8");
+    match err {
+        SdkError::InvalidArgument { message } => {
+            assert!(message.contains("invalid UTF-8 at byte 2"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn truncated_utf8_text_is_reported_as_truncated() {
+    // "é" is 0xC3 0xA9; drop the continuation byte.
+    let err = tool_result_text_from_bytes(vec![b'c', b'a', b'f', 0xc3]).expect_err("truncated");
+    match err {
+        SdkError::InvalidArgument { message } => {
+            assert!(
+                message.contains("truncated UTF-8 sequence at byte 3"),
+                "{message}"
+            );
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn valid_text_output_stays_text() {
+    let output = tool_result_output_from_bytes(
+        "café".as_bytes().to_vec(),
+        "application/json; charset=utf-8",
+    )
+    .expect("valid text");
+    assert!(matches!(output, v2t::ToolResultOutput::Text { value } if value == "café"));
+}
+
+#[test]
+fn binary_output_is_base64_media() {
+    let output = tool_result_output_from_bytes(vec![0x89, b'P', b'N', b'G', 0xff], "image/png")
+        .expect("binary output");
+    let v2t::ToolResultOutput::Content { value } = output else {
+        panic!("expected content output");
+    };
+    assert!(matches!(
+        value.as_slice(),
+        [v2t::ToolResultInlineContent::Media { data, media_type }]
+            if data == "iVBOR/8=" && media_type == "image/png"
+    ));
+}
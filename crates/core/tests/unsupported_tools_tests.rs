@@ -0,0 +1,111 @@
+use crate::ai_sdk_core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+
+fn function_tool(name: &str) -> v2t::Tool {
+    v2t::Tool::Function(v2t::FunctionTool {
+        r#type: Default::default(),
+        name: name.into(),
+        description: Some("Look up a record.".into()),
+        input_schema: json!({"type": "object", "properties": {"id": {"type": "string"}}}),
+        strict: None,
+        provider_options: None,
+    })
+}
+
+fn options(policy: v2t::UnsupportedToolsPolicy) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt: vec![
+            v2t::PromptMessage::System {
+                content: "Be brief.".into(),
+                provider_options: None,
+            },
+            v2t::PromptMessage::User {
+                content: vec![v2t::UserPart::Text {
+                    text: "Find record 7".into(),
+                    provider_options: None,
+                }],
+                provider_options: None,
+            },
+        ],
+        tools: vec![
+            function_tool("lookup"),
+            v2t::Tool::Provider(v2t::ProviderTool {
+                r#type: Default::default(),
+                id: "openai.web_search".into(),
+                name: "web_search".into(),
+                args: json!({}),
+            }),
+        ],
+        tool_choice: Some(v2t::ToolChoice::Required),
+        on_unsupported_tools: policy,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn tool_capable_models_are_untouched() {
+    let options = options(v2t::UnsupportedToolsPolicy::Error);
+    let mut warnings = Vec::new();
+    let out = apply_unsupported_tools_policy(&options, true, &mut warnings).unwrap();
+    assert_eq!(out.tools.len(), 2);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn error_policy_rejects_tools() {
+    let options = options(v2t::UnsupportedToolsPolicy::Error);
+    let mut warnings = Vec::new();
+    let err = apply_unsupported_tools_policy(&options, false, &mut warnings).unwrap_err();
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message }
+            if message == "model does not support tool calling; 2 tools were provided"
+    ));
+
+    let mut no_tools = options.clone();
+    no_tools.tools.clear();
+    assert!(apply_unsupported_tools_policy(&no_tools, false, &mut warnings).is_ok());
+}
+
+#[test]
+fn drop_policy_strips_tools_with_warning() {
+    let options = options(v2t::UnsupportedToolsPolicy::DropWithWarning);
+    let mut warnings = Vec::new();
+    let out = apply_unsupported_tools_policy(&options, false, &mut warnings).unwrap();
+    assert!(out.tools.is_empty());
+    assert!(out.tool_choice.is_none());
+    assert_eq!(out.prompt.len(), 2);
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::UnsupportedSetting { setting, .. }] if setting == "tools"
+    ));
+}
+
+#[test]
+fn inline_policy_describes_function_tools_in_system_prompt() {
+    let options = options(v2t::UnsupportedToolsPolicy::InlineAsText);
+    let mut warnings = Vec::new();
+    let out = apply_unsupported_tools_policy(&options, false, &mut warnings).unwrap();
+    assert!(out.tools.is_empty());
+    assert!(out.tool_choice.is_none());
+    assert_eq!(out.prompt.len(), 3);
+    let v2t::PromptMessage::System { content, .. } = &out.prompt[1] else {
+        panic!("expected inlined tools after the system prompt");
+    };
+    assert!(content.contains("{\"tool\": \"<tool name>\", \"arguments\": {...}}"));
+    assert!(content.contains("\n- lookup: Look up a record.\n  Parameters: {"));
+    assert!(content.ends_with("You must call one of these tools."));
+    assert!(!content.contains("web_search"));
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::UnsupportedTool { tool_name, .. }] if tool_name == "web_search"
+    ));
+
+    let mut none_choice = options.clone();
+    none_choice.tool_choice = Some(v2t::ToolChoice::None);
+    let out = apply_unsupported_tools_policy(&none_choice, false, &mut Vec::new()).unwrap();
+    assert_eq!(out.prompt.len(), 2);
+    assert!(out.tools.is_empty());
+}
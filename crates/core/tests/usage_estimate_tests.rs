@@ -0,0 +1,149 @@
+use super::{CharTokenCounter, TokenCounter, UsageEstimate, USAGE_METADATA_SCOPE};
+use crate::ai_sdk_core::{GenerateResponse, StreamResponse};
+use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, TryStreamExt};
+use serde_json::json;
+
+fn options(estimate: bool) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt: vec![
+            v2t::PromptMessage::System {
+                content: "Be brief.".into(),
+                provider_options: None,
+            },
+            v2t::PromptMessage::User {
+                content: vec![v2t::UserPart::Text {
+                    text: "Say hello".into(),
+                    provider_options: None,
+                }],
+                provider_options: None,
+            },
+        ],
+        estimate_usage_if_missing: estimate,
+        ..Default::default()
+    }
+}
+
+fn finish(usage: v2t::Usage) -> v2t::StreamPart {
+    v2t::StreamPart::Finish {
+        usage,
+        finish_reason: v2t::FinishReason::Stop,
+        provider_metadata: None,
+    }
+}
+
+fn stream_response(parts: Vec<v2t::StreamPart>) -> StreamResponse {
+    StreamResponse {
+        stream: Box::pin(stream::iter(parts.into_iter().map(Ok))),
+        request_body: None,
+        response_headers: None,
+        raw_response: None,
+    }
+}
+
+fn text_delta(delta: &str) -> v2t::StreamPart {
+    v2t::StreamPart::TextDelta {
+        id: "t1".into(),
+        delta: delta.into(),
+        provider_metadata: None,
+    }
+}
+
+#[test]
+fn char_counter_rounds_up() {
+    assert_eq!(CharTokenCounter.count_tokens(""), 0);
+    assert_eq!(CharTokenCounter.count_tokens("abcd"), 1);
+    assert_eq!(CharTokenCounter.count_tokens("abcde"), 2);
+}
+
+#[test]
+fn estimate_is_only_built_when_requested() {
+    assert!(UsageEstimate::from_options(&options(false)).is_none());
+    // "Be brief.\nSay hello" is 19 characters.
+    let estimate = UsageEstimate::from_options(&options(true)).expect("estimate");
+    assert_eq!(estimate.input_tokens, 5);
+}
+
+#[tokio::test]
+async fn fills_missing_stream_usage_and_flags_it() {
+    let estimate = UsageEstimate::from_options(&options(true));
+    let parts: Vec<_> = stream_response(vec![
+        text_delta("Hello"),
+        text_delta(" there!"),
+        finish(v2t::Usage::default()),
+    ])
+    .with_usage_estimate(estimate)
+    .stream
+    .try_collect()
+    .await
+    .expect("parts");
+
+    let Some(v2t::StreamPart::Finish {
+        usage,
+        provider_metadata,
+        ..
+    }) = parts.last()
+    else {
+        panic!("missing finish part");
+    };
+    assert_eq!(usage.input_tokens, Some(5));
+    assert_eq!(usage.output_tokens, Some(3));
+    assert_eq!(usage.total_tokens, Some(8));
+    let metadata = provider_metadata.as_ref().expect("metadata");
+    assert_eq!(metadata[USAGE_METADATA_SCOPE]["estimated"], json!(true));
+}
+
+#[tokio::test]
+async fn keeps_reported_stream_usage() {
+    let reported = v2t::Usage {
+        input_tokens: Some(40),
+        output_tokens: Some(2),
+        ..Default::default()
+    };
+    let parts: Vec<_> = stream_response(vec![text_delta("Hello"), finish(reported)])
+        .with_usage_estimate(UsageEstimate::from_options(&options(true)))
+        .stream
+        .try_collect()
+        .await
+        .expect("parts");
+
+    let Some(v2t::StreamPart::Finish {
+        usage,
+        provider_metadata,
+        ..
+    }) = parts.last()
+    else {
+        panic!("missing finish part");
+    };
+    assert_eq!(usage.input_tokens, Some(40));
+    assert_eq!(usage.total_tokens, None);
+    assert!(provider_metadata.is_none());
+}
+
+#[test]
+fn fills_missing_generate_usage() {
+    let response = GenerateResponse {
+        content: vec![v2t::Content::Text {
+            text: "Hello there!".into(),
+            provider_metadata: None,
+        }],
+        finish_reason: v2t::FinishReason::Stop,
+        usage: v2t::Usage::default(),
+        provider_metadata: None,
+        response_metadata: None,
+        request_body: None,
+        response_headers: None,
+        response_body: None,
+        warnings: vec![],
+    };
+
+    let unchanged = response.clone().with_usage_estimate(None);
+    assert!(unchanged.usage.input_tokens.is_none());
+    assert!(unchanged.provider_metadata.is_none());
+
+    let estimated = response.with_usage_estimate(UsageEstimate::from_options(&options(true)));
+    assert_eq!(estimated.usage.input_tokens, Some(5));
+    assert_eq!(estimated.usage.output_tokens, Some(3));
+    let metadata = estimated.provider_metadata.expect("metadata");
+    assert_eq!(metadata[USAGE_METADATA_SCOPE]["estimated"], json!(true));
+}
@@ -0,0 +1,304 @@
+use super::TracingTransport;
+use crate::ai_sdk_core::error::TransportError;
+use crate::ai_sdk_core::transport::{
+    HttpTransport, JsonStreamWebsocketConnection, TransportConfig, TransportStream,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{stream, StreamExt};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn records(&self) -> Vec<Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .expect("utf8 trace")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json record"))
+            .collect()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+struct StubTransport {
+    chunks: Vec<&'static [u8]>,
+    fail_status: Option<u16>,
+}
+
+fn stub_stream(chunks: &[&'static [u8]]) -> TransportStream {
+    let chunks: Vec<Result<Bytes, TransportError>> = chunks
+        .iter()
+        .map(|chunk| Ok(Bytes::from_static(chunk)))
+        .collect();
+    Box::pin(stream::iter(chunks))
+}
+
+struct StubWebsocket {
+    chunks: Vec<&'static [u8]>,
+}
+
+#[async_trait]
+impl JsonStreamWebsocketConnection for StubWebsocket {
+    async fn send_json_stream(
+        &self,
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<TransportStream, TransportError> {
+        Ok(stub_stream(&self.chunks))
+    }
+
+    fn response_headers(&self) -> Vec<(String, String)> {
+        vec![("x-request-id".into(), "ws1".into())]
+    }
+
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl HttpTransport for StubTransport {
+    type StreamResponse = TransportStream;
+
+    fn into_stream(resp: Self::StreamResponse) -> (TransportStream, Vec<(String, String)>) {
+        (
+            resp,
+            vec![("content-type".into(), "text/event-stream".into())],
+        )
+    }
+
+    async fn post_json_stream(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        Ok(stub_stream(&self.chunks))
+    }
+
+    async fn post_json(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        if let Some(status) = self.fail_status {
+            return Err(TransportError::HttpStatus {
+                status,
+                body: r#"{"error":{"message":"slow down"}}"#.into(),
+                retry_after_ms: None,
+                sanitized: "slow down".into(),
+                headers: vec![("retry-after".into(), "1".into())],
+            });
+        }
+        Ok((
+            json!({"id": "resp_1", "token": "echoed"}),
+            vec![("set-cookie".into(), "session=abc".into())],
+        ))
+    }
+
+    async fn connect_json_stream_websocket(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _cfg: &TransportConfig,
+    ) -> Result<Box<dyn JsonStreamWebsocketConnection>, TransportError> {
+        Ok(Box::new(StubWebsocket {
+            chunks: self.chunks.clone(),
+        }))
+    }
+}
+
+fn traced(fail_status: Option<u16>) -> (TracingTransport<StubTransport>, SharedBuffer) {
+    let sink = SharedBuffer::default();
+    let transport = TracingTransport::new(
+        StubTransport {
+            chunks: vec![b"data: {\"a\":1}\n\n", b"data: [DONE]\n\n", &[0xff, 0x00]],
+            fail_status,
+        },
+        sink.clone(),
+    );
+    (transport, sink)
+}
+
+fn headers() -> Vec<(String, String)> {
+    vec![
+        ("Authorization".into(), "Bearer sk-live".into()),
+        ("x-amz-security-token".into(), "session-token".into()),
+        ("x-request-id".into(), "r1".into()),
+    ]
+}
+
+#[tokio::test]
+async fn records_redacted_request_and_response() {
+    let (transport, sink) = traced(None);
+    let body = json!({"model": "m", "api_key": "sk-body"});
+    transport
+        .post_json(
+            "https://x/v1",
+            &headers(),
+            &body,
+            &TransportConfig::default(),
+        )
+        .await
+        .expect("post");
+
+    let records = sink.records();
+    assert_eq!(records.len(), 2);
+    let (request, response) = (&records[0], &records[1]);
+    assert_eq!(request["kind"], "request");
+    assert_eq!(request["method"], "POST");
+    assert_eq!(request["url"], "https://x/v1");
+    assert_eq!(
+        request["headers"],
+        json!([
+            ["Authorization", "<redacted>"],
+            ["x-amz-security-token", "<redacted>"],
+            ["x-request-id", "r1"]
+        ])
+    );
+    assert_eq!(
+        request["body"],
+        json!({"model": "m", "api_key": "[REDACTED]"})
+    );
+    assert_eq!(response["kind"], "response");
+    assert_eq!(response["id"], request["id"]);
+    assert_eq!(response["headers"], json!([["set-cookie", "<redacted>"]]));
+    assert_eq!(
+        response["body"],
+        json!({"id": "resp_1", "token": "[REDACTED]"})
+    );
+}
+
+#[tokio::test]
+async fn records_stream_frames_in_order() {
+    let (transport, sink) = traced(None);
+    let resp = transport
+        .post_json_stream("https://x/v1", &[], &json!({}), &TransportConfig::default())
+        .await
+        .expect("stream");
+    let (stream, _) = TracingTransport::<StubTransport>::into_stream(resp);
+    let chunks: Vec<_> = stream.collect().await;
+    assert_eq!(chunks.len(), 3);
+
+    let records = sink.records();
+    let kinds: Vec<&str> = records
+        .iter()
+        .map(|record| record["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        ["request", "response", "frame", "frame", "frame", "end"]
+    );
+    assert_eq!(records[1]["stream"], true);
+    assert_eq!(records[2]["data"], "data: {\"a\":1}\n\n");
+    assert_eq!(records[4]["data_base64"], "/wA=");
+    assert_eq!(records[5]["frames"], 3);
+    assert_eq!(records[5]["bytes"], 31);
+    assert!(records.iter().all(|record| record["ts_ms"].is_u64()));
+}
+
+#[tokio::test]
+async fn records_http_errors_and_honors_toggle() {
+    let (transport, sink) = traced(Some(429));
+    let err = transport
+        .post_json("https://x/v1", &[], &json!({}), &TransportConfig::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TransportError::HttpStatus { status: 429, .. }
+    ));
+
+    let records = sink.records();
+    assert_eq!(records[1]["kind"], "error");
+    assert_eq!(records[1]["status"], 429);
+    assert_eq!(records[1]["body"]["error"]["message"], "slow down");
+
+    transport.set_enabled(false);
+    let _ = transport
+        .post_json("https://x/v1", &[], &json!({}), &TransportConfig::default())
+        .await;
+    assert_eq!(sink.records().len(), 2);
+}
+
+#[tokio::test]
+async fn body_redaction_can_be_disabled() {
+    let (transport, sink) = traced(None);
+    let transport = transport.with_body_redaction(None);
+    transport
+        .post_json(
+            "https://x/v1",
+            &[],
+            &json!({"api_key": "sk-body"}),
+            &TransportConfig::default(),
+        )
+        .await
+        .expect("post");
+
+    assert_eq!(sink.records()[0]["body"], json!({"api_key": "sk-body"}));
+}
+
+#[tokio::test]
+async fn records_websocket_handshake_and_messages() {
+    let (transport, sink) = traced(None);
+    let connection = transport
+        .connect_json_stream_websocket("wss://x/v1", &headers(), &TransportConfig::default())
+        .await
+        .expect("connect");
+    let stream = connection
+        .send_json_stream(
+            &json!({"type": "response.create", "api_key": "sk-body"}),
+            &TransportConfig::default(),
+        )
+        .await
+        .expect("send");
+    assert_eq!(stream.collect::<Vec<_>>().await.len(), 3);
+
+    let records = sink.records();
+    let kinds: Vec<&str> = records
+        .iter()
+        .map(|record| record["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        ["request", "response", "request", "frame", "frame", "frame", "end"]
+    );
+    assert_eq!(records[0]["method"], "GET");
+    assert_eq!(
+        records[0]["headers"][0],
+        json!(["Authorization", "<redacted>"])
+    );
+    assert_eq!(records[1]["websocket"], true);
+    assert_eq!(records[1]["headers"], json!([["x-request-id", "ws1"]]));
+    assert_eq!(records[2]["method"], "WS");
+    assert_eq!(records[2]["url"], "wss://x/v1");
+    assert_eq!(records[2]["body"]["api_key"], "[REDACTED]");
+    assert_ne!(records[2]["id"], records[0]["id"]);
+    assert_eq!(records[6]["id"], records[2]["id"]);
+
+    // Disabling tracing also silences connections that are already open.
+    transport.set_enabled(false);
+    let stream = connection
+        .send_json_stream(&json!({}), &TransportConfig::default())
+        .await
+        .expect("send");
+    let _ = stream.collect::<Vec<_>>().await;
+    assert_eq!(sink.records().len(), 7);
+}
@@ -1,8 +1,8 @@
 use crate::provider::{
-    persisted_reasoning_options, reasoning_scope_aliases, reasoning_stream_options,
+    persisted_reasoning_options, persisted_reasoning_options_with_override,
+    reasoning_scope_aliases, reasoning_scope_aliases_with_override, reasoning_stream_options,
+    reasoning_stream_options_with_override,
 };
-use crate::providers::amazon_bedrock as _;
-use crate::providers::anthropic as _;
 use crate::types::catalog::SdkType;
 use serde_json::Value as JsonValue;
 
@@ -89,3 +89,71 @@ fn persisted_reasoning_options_include_text_and_signature() {
         );
     }
 }
+
+#[test]
+fn scope_override_resolves_for_unhandled_custom_gateways() {
+    assert!(reasoning_scope_aliases(
+        "my-gateway",
+        &SdkType::OpenAICompatible,
+        Some("gpt-5"),
+        Some("https://llm.internal.example"),
+    )
+    .is_none());
+
+    let aliases = reasoning_scope_aliases_with_override(
+        "my-gateway",
+        &SdkType::OpenAICompatible,
+        Some("gpt-5"),
+        Some("https://llm.internal.example"),
+        Some("openai"),
+    )
+    .expect("override aliases");
+    assert_eq!(aliases[0], "openai");
+    assert!(aliases.iter().any(|a| a == "my-gateway"));
+
+    let opts = reasoning_stream_options_with_override(
+        "my-gateway",
+        &SdkType::OpenAICompatible,
+        Some("gpt-5"),
+        None,
+        Some("openai"),
+        Some("sig"),
+        None,
+    )
+    .expect("stream options");
+    assert_eq!(
+        opts.get("openai").and_then(|scope| scope.get("signature")),
+        Some(&JsonValue::String("sig".into()))
+    );
+}
+
+#[test]
+fn scope_override_is_added_ahead_of_registry_aliases() {
+    let opts = persisted_reasoning_options_with_override(
+        "aws-bedrock",
+        &SdkType::AmazonBedrock,
+        Some("anthropic.claude-3-5-sonnet-20240620-v1:0"),
+        None,
+        Some("corp-anthropic"),
+        "thinking",
+        None,
+    )
+    .expect("persisted options");
+    for key in ["corp-anthropic", "anthropic", "bedrock"] {
+        assert_eq!(
+            opts.get(key)
+                .and_then(|scope| scope.get("persistedReasoningText")),
+            Some(&JsonValue::String("thinking".into())),
+            "scope {key}"
+        );
+    }
+
+    assert!(reasoning_scope_aliases_with_override(
+        "my-gateway",
+        &SdkType::OpenAICompatible,
+        None,
+        None,
+        Some("  "),
+    )
+    .is_none());
+}
@@ -0,0 +1,44 @@
+use super::resolve_inference_profile;
+
+#[test]
+fn us_profile_resolves_to_us_source_region() {
+    assert_eq!(
+        resolve_inference_profile("us.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+        (
+            Some("us-east-1".to_string()),
+            "us.anthropic.claude-3-5-sonnet-20241022-v2:0".to_string()
+        )
+    );
+}
+
+#[test]
+fn eu_profile_resolves_to_eu_source_region() {
+    assert_eq!(
+        resolve_inference_profile("eu.meta.llama3-2-3b-instruct-v1:0"),
+        (
+            Some("eu-central-1".to_string()),
+            "eu.meta.llama3-2-3b-instruct-v1:0".to_string()
+        )
+    );
+}
+
+#[test]
+fn bare_model_id_keeps_configured_region() {
+    assert_eq!(
+        resolve_inference_profile("anthropic.claude-3-haiku-20240307-v1:0"),
+        (None, "anthropic.claude-3-haiku-20240307-v1:0".to_string())
+    );
+    assert_eq!(
+        resolve_inference_profile("amazon.nova-pro-v1:0"),
+        (None, "amazon.nova-pro-v1:0".to_string())
+    );
+}
+
+#[test]
+fn profile_arn_resolves_to_arn_region() {
+    let arn = "arn:aws:bedrock:eu-west-3:123456789012:inference-profile/eu.anthropic.claude-3-7-sonnet-20250219-v1:0";
+    assert_eq!(
+        resolve_inference_profile(arn),
+        (Some("eu-west-3".to_string()), arn.to_string())
+    );
+}
@@ -1,13 +1,14 @@
 use crate::core::error::TransportError;
-use crate::core::transport::{HttpTransport, TransportConfig};
+use crate::core::transport::{CancelToken, HttpTransport, TransportConfig};
 use crate::core::LanguageModel;
 use crate::providers::amazon_bedrock::config::{BedrockAuth, BedrockConfig};
 use crate::providers::amazon_bedrock::language_model::BedrockLanguageModel;
+use crate::providers::amazon_bedrock::messages::convert_prompt;
 use crate::types::v2 as v2t;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures_core::Stream;
-use futures_util::stream;
+use futures_util::{stream, TryStreamExt};
 use serde_json::json;
 use std::collections::HashMap;
 use std::pin::Pin;
@@ -16,39 +17,54 @@ use std::sync::{Arc, Mutex};
 #[derive(Clone)]
 struct TestTransport {
     response_json: Arc<Mutex<serde_json::Value>>,
+    stream_chunks: Arc<Vec<Bytes>>,
+    last_url: Arc<Mutex<Option<String>>>,
+    saw_cancel_token: Arc<Mutex<bool>>,
 }
 
 impl TestTransport {
     fn new(response_json: serde_json::Value) -> Self {
         Self {
             response_json: Arc::new(Mutex::new(response_json)),
+            stream_chunks: Arc::new(Vec::new()),
+            last_url: Arc::new(Mutex::new(None)),
+            saw_cancel_token: Arc::new(Mutex::new(false)),
         }
     }
 }
 
-struct TestStreamResponse;
+struct TestStreamResponse {
+    chunks: Vec<Bytes>,
+}
 
 #[async_trait]
 impl HttpTransport for TestTransport {
     type StreamResponse = TestStreamResponse;
 
     fn into_stream(
-        _resp: Self::StreamResponse,
+        resp: Self::StreamResponse,
     ) -> (
         Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>,
         Vec<(String, String)>,
     ) {
-        (Box::pin(stream::iter(vec![])), vec![])
+        (
+            Box::pin(stream::iter(resp.chunks.into_iter().map(Ok))),
+            vec![],
+        )
     }
 
     async fn post_json_stream(
         &self,
-        _url: &str,
+        url: &str,
         _headers: &[(String, String)],
         _body: &serde_json::Value,
-        _cfg: &TransportConfig,
+        cfg: &TransportConfig,
     ) -> Result<Self::StreamResponse, TransportError> {
-        Err(TransportError::Other("post_json_stream unused".into()))
+        *self.last_url.lock().unwrap() = Some(url.to_string());
+        *self.saw_cancel_token.lock().unwrap() = cfg.cancel.is_some();
+        Ok(TestStreamResponse {
+            chunks: self.stream_chunks.to_vec(),
+        })
     }
 
     async fn post_json(
@@ -63,7 +79,10 @@ impl HttpTransport for TestTransport {
 }
 
 fn build_model(response_json: serde_json::Value) -> BedrockLanguageModel<TestTransport> {
-    let transport = TestTransport::new(response_json);
+    build_model_with_transport(TestTransport::new(response_json))
+}
+
+fn build_model_with_transport(transport: TestTransport) -> BedrockLanguageModel<TestTransport> {
     let cfg = BedrockConfig {
         provider_name: "amazon-bedrock.converse",
         provider_scope_name: "bedrock".into(),
@@ -309,3 +328,353 @@ async fn provider_metadata_includes_trace_usage_and_json_flag() {
 
     assert_eq!(bedrock.get("isJsonResponseFromTool"), Some(&json!(true)));
 }
+
+#[test]
+fn capabilities_follow_the_model_vendor() {
+    let claude = build_model(json!({}));
+    assert_eq!(
+        claude.capabilities(),
+        crate::providers::anthropic::messages::language_model::model_capabilities(
+            "claude-3-sonnet"
+        )
+    );
+
+    let mut nova = build_model(json!({}));
+    nova.model_id = "us.amazon.nova-pro-v1:0".into();
+    let caps = nova.capabilities();
+    assert!(caps.vision && caps.tools && !caps.reasoning);
+
+    let mut llama = build_model(json!({}));
+    llama.model_id = "meta.llama3-1-70b-instruct-v1:0".into();
+    assert!(!llama.capabilities().vision);
+}
+
+#[test]
+fn developer_messages_join_the_system_block() {
+    let mut prompt = vec![
+        v2t::PromptMessage::system("be kind"),
+        v2t::PromptMessage::developer("be brief"),
+    ];
+    prompt.extend(base_prompt());
+
+    let converted = convert_prompt(&prompt).expect("converted prompt");
+    assert_eq!(
+        converted.system,
+        vec![json!({"text": "be kind"}), json!({"text": "be brief"})]
+    );
+    assert_eq!(converted.messages.len(), 1);
+}
+
+/// Encode a recorded ConverseStream fixture, one `{"<eventType>": payload}`
+/// object per line, as AWS event stream frames split into small chunks.
+fn converse_stream_chunks(fixture: &str) -> Vec<Bytes> {
+    use aws_smithy_eventstream::frame::write_message_to;
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+
+    let mut encoded = Vec::new();
+    for line in fixture.lines().filter(|line| !line.trim().is_empty()) {
+        let event: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(line).expect("fixture line");
+        let (event_type, payload) = event.into_iter().next().expect("event");
+        let (type_header, message_type) = if event_type.ends_with("Exception") {
+            (":exception-type", "exception")
+        } else {
+            (":event-type", "event")
+        };
+        let message = Message::new(payload.to_string().into_bytes())
+            .add_header(Header::new(
+                ":message-type",
+                HeaderValue::String(message_type.into()),
+            ))
+            .add_header(Header::new(
+                type_header,
+                HeaderValue::String(event_type.into()),
+            ))
+            .add_header(Header::new(
+                ":content-type",
+                HeaderValue::String("application/json".into()),
+            ));
+        write_message_to(&message, &mut encoded).expect("encode frame");
+    }
+    encoded.chunks(37).map(Bytes::copy_from_slice).collect()
+}
+
+async fn stream_fixture(fixture: &str, options: v2t::CallOptions) -> Vec<v2t::StreamPart> {
+    let mut transport = TestTransport::new(json!({}));
+    transport.stream_chunks = Arc::new(converse_stream_chunks(fixture));
+    let model = build_model_with_transport(transport.clone());
+    let response = model.do_stream(options).await.expect("stream");
+    assert_eq!(
+        transport.last_url.lock().unwrap().as_deref(),
+        Some("https://bedrock.example/model/anthropic.claude-3-sonnet/converse-stream")
+    );
+    response.stream.try_collect().await.expect("stream parts")
+}
+
+fn finish(parts: &[v2t::StreamPart]) -> (&v2t::FinishReason, &v2t::Usage) {
+    parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish {
+                finish_reason,
+                usage,
+                ..
+            } => Some((finish_reason, usage)),
+            _ => None,
+        })
+        .expect("finish part")
+}
+
+#[tokio::test]
+async fn converse_stream_maps_reasoning_text_and_usage() {
+    let parts = stream_fixture(
+        include_str!("fixtures/bedrock-converse-text.1.chunks.txt"),
+        v2t::CallOptions::new(base_prompt()),
+    )
+    .await;
+
+    let reasoning: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::ReasoningDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(reasoning, "The user said hello.");
+    assert!(parts.iter().any(|part| matches!(
+        part,
+        v2t::StreamPart::ReasoningSignature { signature, .. } if signature == "EqoBCkgIARABGAIiQL3hTp"
+    )));
+    let reasoning_end = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::ReasoningEnd { .. }))
+        .expect("reasoning end");
+    let text_start = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::TextStart { .. }))
+        .expect("text start");
+    assert!(reasoning_end < text_start);
+
+    let text: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Hello! How can I help you today?");
+
+    let (finish_reason, usage) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::Stop));
+    assert_eq!(usage.input_tokens, Some(12));
+    assert_eq!(usage.output_tokens, Some(25));
+    assert_eq!(usage.total_tokens, Some(37));
+}
+
+#[tokio::test]
+async fn converse_stream_assembles_tool_use_and_cache_usage() {
+    let parts = stream_fixture(
+        include_str!("fixtures/bedrock-converse-tool-use.1.chunks.txt"),
+        v2t::CallOptions::new(base_prompt()),
+    )
+    .await;
+
+    let call = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::ToolCall(call) => Some(call),
+            _ => None,
+        })
+        .expect("tool call");
+    assert_eq!(call.tool_call_id, "tooluse_kZJMlvQmRJ6eAyJE5GIl7Q");
+    assert_eq!(call.tool_name, "weather");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&call.input).expect("tool input"),
+        json!({"city": "Berlin"})
+    );
+
+    let (finish_reason, usage) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::ToolCalls));
+    assert_eq!(usage.input_tokens, Some(402));
+    assert_eq!(usage.cached_input_tokens, Some(256));
+}
+
+#[tokio::test]
+async fn converse_stream_captures_raw_frames_when_requested() {
+    let fixture = include_str!("fixtures/bedrock-converse-text.1.chunks.txt");
+    let mut transport = TestTransport::new(json!({}));
+    transport.stream_chunks = Arc::new(converse_stream_chunks(fixture));
+    let model = build_model_with_transport(transport);
+    let options = v2t::CallOptions {
+        prompt: base_prompt(),
+        capture_raw_response: true,
+        ..Default::default()
+    };
+
+    let response = model.do_stream(options).await.expect("stream");
+    let capture = response.raw_response.clone().expect("raw capture");
+    let parts: Vec<v2t::StreamPart> = response.stream.try_collect().await.expect("stream parts");
+
+    assert!(!parts
+        .iter()
+        .any(|part| matches!(part, v2t::StreamPart::Raw { .. })));
+    let expected: Vec<serde_json::Value> = fixture
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).expect("fixture line"))
+        .collect();
+    assert_eq!(capture.frames(), expected);
+}
+
+#[tokio::test]
+async fn converse_stream_ends_with_aborted_error_once_cancelled() {
+    let mut transport = TestTransport::new(json!({}));
+    transport.stream_chunks = Arc::new(converse_stream_chunks(include_str!(
+        "fixtures/bedrock-converse-text.1.chunks.txt"
+    )));
+    let model = build_model_with_transport(transport.clone());
+    let abort = CancelToken::new();
+    abort.cancel();
+    let options = v2t::CallOptions {
+        prompt: base_prompt(),
+        ..Default::default()
+    }
+    .with_abort(abort);
+
+    let parts: Vec<v2t::StreamPart> = model
+        .do_stream(options)
+        .await
+        .expect("stream")
+        .stream
+        .try_collect()
+        .await
+        .expect("stream parts");
+
+    assert!(*transport.saw_cancel_token.lock().unwrap());
+    assert!(matches!(
+        &parts[..],
+        [v2t::StreamPart::Error { error }] if error["message"] == "aborted"
+    ));
+}
+
+#[tokio::test]
+async fn converse_stream_exception_ends_with_error() {
+    let fixture = concat!(
+        r#"{"contentBlockDelta":{"contentBlockIndex":0,"delta":{"text":"Hi"}}}"#,
+        "\n",
+        r#"{"throttlingException":{"message":"Too many requests, please wait before trying again."}}"#,
+    );
+    let parts = stream_fixture(fixture, v2t::CallOptions::new(base_prompt())).await;
+
+    assert!(parts.iter().any(|part| matches!(
+        part,
+        v2t::StreamPart::Error { error }
+            if error["message"] == "throttlingException: Too many requests, please wait before trying again."
+    )));
+    let (finish_reason, _) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::Error));
+}
+
+fn guardrail_options(config: serde_json::Value) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt: base_prompt(),
+        provider_options: HashMap::from([(
+            "bedrock".to_string(),
+            HashMap::from([("guardrailConfig".to_string(), config)]),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn guardrail_config_serializes_into_request() {
+    let model = build_model(json!({
+        "output": { "message": { "role": "assistant", "content": [{ "text": "Hi" }] } },
+        "stopReason": "end_turn"
+    }));
+
+    let result = model
+        .do_generate(guardrail_options(json!({
+            "guardrailIdentifier": "gr-compliance",
+            "guardrailVersion": "3",
+            "trace": "enabled_full"
+        })))
+        .await
+        .expect("generate");
+
+    let request = result.request_body.expect("request body");
+    assert_eq!(
+        request["guardrailConfig"],
+        json!({
+            "guardrailIdentifier": "gr-compliance",
+            "guardrailVersion": "3",
+            "trace": "enabled_full"
+        })
+    );
+    assert!(matches!(result.finish_reason, v2t::FinishReason::Stop));
+    assert_eq!(
+        result.provider_metadata.expect("metadata")["bedrock"]["guardrailAction"],
+        json!("NONE")
+    );
+
+    let err = model
+        .do_generate(guardrail_options(json!({ "guardrailVersion": "3" })))
+        .await
+        .expect_err("missing identifier");
+    assert!(err.to_string().contains("guardrailConfig"), "{err}");
+}
+
+#[tokio::test]
+async fn guardrail_block_maps_to_content_filter() {
+    let model = build_model(json!({
+        "output": {
+            "message": {
+                "role": "assistant",
+                "content": [{ "text": "Sorry, I can't help with that request." }]
+            }
+        },
+        "stopReason": "guardrail_intervened",
+        "trace": {
+            "guardrail": {
+                "outputAssessments": {
+                    "gr-compliance": [{ "topicPolicy": { "topics": [{ "action": "BLOCKED" }] } }]
+                }
+            }
+        }
+    }));
+
+    let result = model
+        .do_generate(guardrail_options(json!({
+            "guardrailIdentifier": "gr-compliance",
+            "guardrailVersion": "3",
+            "trace": "enabled"
+        })))
+        .await
+        .expect("generate");
+
+    assert!(matches!(
+        result.finish_reason,
+        v2t::FinishReason::ContentFilter
+    ));
+    let bedrock = &result.provider_metadata.expect("metadata")["bedrock"];
+    assert_eq!(bedrock["guardrailAction"], json!("INTERVENED"));
+    assert!(bedrock.contains_key("trace"));
+}
+
+#[test]
+fn inference_profile_ids_are_encoded_into_the_model_path() {
+    let model = build_model(json!({}));
+    assert_eq!(
+        model
+            .cfg
+            .endpoint_for_model("us.anthropic.claude-3-5-haiku-20241022-v1:0", "/converse"),
+        "https://bedrock.example/model/us.anthropic.claude-3-5-haiku-20241022-v1%3A0/converse"
+    );
+    assert_eq!(
+        model.cfg.endpoint_for_model(
+            "arn:aws:bedrock:us-west-2:123456789012:inference-profile/us.amazon.nova-pro-v1:0",
+            "/converse-stream"
+        ),
+        "https://bedrock.example/model/arn%3Aaws%3Abedrock%3Aus-west-2%3A123456789012%3Ainference-profile%2Fus.amazon.nova-pro-v1%3A0/converse-stream"
+    );
+}
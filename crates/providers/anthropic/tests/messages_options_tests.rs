@@ -1,5 +1,6 @@
 use crate::providers::anthropic::messages::options::{
-    parse_anthropic_provider_options, ThinkingOption,
+    parse_anthropic_cache_control, parse_anthropic_provider_options, AnthropicCacheControl,
+    CacheControlType, CacheTtl, ThinkingOption,
 };
 use crate::types::v2::ProviderOptions;
 use serde_json::json;
@@ -54,3 +55,34 @@ fn parses_thinking_from_anthropic_scope() {
         other => panic!("unexpected thinking option: {other:?}"),
     }
 }
+
+#[test]
+fn parses_cache_control_with_ttl() {
+    let cache_control = |value: serde_json::Value| {
+        let mut scope = HashMap::new();
+        scope.insert("cacheControl".to_string(), value);
+        let mut opts = ProviderOptions::new();
+        opts.insert("anthropic".to_string(), scope);
+        let mut warnings = Vec::new();
+        let parsed = parse_anthropic_cache_control(&Some(opts), "anthropic", &mut warnings);
+        assert_eq!(parsed.is_none(), warnings.len() == 1);
+        parsed
+    };
+
+    assert_eq!(
+        cache_control(json!({"type": "ephemeral", "ttl": "1h"})),
+        Some(AnthropicCacheControl {
+            r#type: CacheControlType::Ephemeral,
+            ttl: Some(CacheTtl::OneHour),
+        })
+    );
+    assert_eq!(
+        cache_control(json!({"type": "ephemeral"})).and_then(|cc| cc.ttl),
+        None
+    );
+    assert_eq!(
+        cache_control(json!({"type": "ephemeral", "ttl": "2h"})),
+        None
+    );
+    assert_eq!(cache_control(json!({"type": "persistent"})), None);
+}
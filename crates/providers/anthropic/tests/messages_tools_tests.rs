@@ -8,7 +8,9 @@ use crate::ai_sdk_core::transport::{
 };
 use crate::ai_sdk_core::{LanguageModel, SdkError};
 use crate::ai_sdk_provider::{registry, Credentials};
-use crate::ai_sdk_providers_anthropic::messages::language_model::AnthropicMessagesConfig;
+use crate::ai_sdk_providers_anthropic::messages::language_model::{
+    AnthropicMessagesConfig, DEFAULT_MAX_TOKENS,
+};
 use crate::ai_sdk_providers_anthropic::AnthropicMessagesLanguageModel;
 use crate::ai_sdk_types::catalog::{ModelInfo, ProviderDefinition, SdkType};
 use crate::ai_sdk_types::v2 as v2t;
@@ -90,18 +92,30 @@ fn transport_observer() -> Arc<RecordingObserver> {
 struct TestTransport {
     last_body: Arc<Mutex<Option<serde_json::Value>>>,
     last_headers: Arc<Mutex<Option<HeaderList>>>,
+    last_url: Arc<Mutex<Option<String>>>,
     stream_chunks: Arc<Vec<Bytes>>,
+    json_response: Option<serde_json::Value>,
 }
 
 impl TestTransport {
     fn with_stream_chunks(chunks: Vec<Bytes>) -> Self {
         Self {
-            last_body: Arc::new(Mutex::new(None)),
-            last_headers: Arc::new(Mutex::new(None)),
             stream_chunks: Arc::new(chunks),
+            ..Self::default()
         }
     }
 
+    fn with_json_response(response: serde_json::Value) -> Self {
+        Self {
+            json_response: Some(response),
+            ..Self::default()
+        }
+    }
+
+    fn last_url(&self) -> Option<String> {
+        self.last_url.lock().unwrap().clone()
+    }
+
     fn last_body(&self) -> Option<serde_json::Value> {
         self.last_body.lock().unwrap().clone()
     }
@@ -155,12 +169,24 @@ impl HttpTransport for TestTransport {
 
     async fn post_json(
         &self,
-        _url: &str,
-        _headers: &[(String, String)],
-        _body: &serde_json::Value,
-        _cfg: &TransportConfig,
+        url: &str,
+        headers: &[(String, String)],
+        body: &serde_json::Value,
+        cfg: &TransportConfig,
     ) -> Result<(serde_json::Value, Vec<(String, String)>), TransportError> {
-        Err(TransportError::Other("post_json unused".into()))
+        let response = self
+            .json_response
+            .clone()
+            .ok_or_else(|| TransportError::Other("post_json unused".into()))?;
+        let sent_body = if cfg.strip_null_fields {
+            without_null_fields(body)
+        } else {
+            body.clone()
+        };
+        *self.last_body.lock().unwrap() = Some(sent_body);
+        *self.last_headers.lock().unwrap() = Some(headers.to_vec().into());
+        *self.last_url.lock().unwrap() = Some(url.to_string());
+        Ok((response, Vec::new()))
     }
 
     async fn post_multipart(
@@ -211,6 +237,21 @@ fn anthropic_cache_control_options() -> Option<v2t::ProviderOptions> {
 }
 
 fn build_model(transport: TestTransport) -> AnthropicMessagesLanguageModel<TestTransport> {
+    build_model_with_max_tokens(transport, None)
+}
+
+fn build_model_with_max_tokens(
+    transport: TestTransport,
+    default_max_tokens: Option<u64>,
+) -> AnthropicMessagesLanguageModel<TestTransport> {
+    build_model_with_config(transport, default_max_tokens, HashMap::new())
+}
+
+fn build_model_with_config(
+    transport: TestTransport,
+    default_max_tokens: Option<u64>,
+    supported_urls: HashMap<String, Vec<String>>,
+) -> AnthropicMessagesLanguageModel<TestTransport> {
     let cfg = AnthropicMessagesConfig {
         provider_name: "anthropic",
         provider_scope_name: "anthropic".into(),
@@ -218,8 +259,9 @@ fn build_model(transport: TestTransport) -> AnthropicMessagesLanguageModel<TestT
         headers: vec![],
         http: transport,
         transport_cfg: TransportConfig::default(),
-        supported_urls: HashMap::new(),
+        supported_urls,
         default_options: None,
+        default_max_tokens,
     };
     AnthropicMessagesLanguageModel::new("claude-3-5-sonnet-20241022".into(), cfg)
 }
@@ -237,9 +279,15 @@ fn anthropic_provider_definition(base_url: String) -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: HashMap::<String, ModelInfo>::new(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
@@ -595,6 +643,98 @@ async fn provider_tool_maps_web_fetch() {
     assert!(betas.contains("web-fetch-2025-09-10"));
 }
 
+#[tokio::test]
+async fn forced_tool_choice_must_name_a_provided_tool() {
+    let transport = TestTransport::default();
+    let model = build_model(transport.clone());
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.tools = vec![provider_tool("anthropic.web_fetch_20250910", json!({}))];
+    options.tool_choice = Some(v2t::ToolChoice::Tool {
+        name: "anthropic.web_fetch_20250910".into(),
+    });
+    let response = model
+        .do_stream(options.clone())
+        .await
+        .expect("stream response");
+    let body = response.request_body.expect("request body");
+    assert_eq!(body["tool_choice"]["type"], json!("tool"));
+
+    options.tool_choice = Some(v2t::ToolChoice::Tool {
+        name: "lookup".into(),
+    });
+    let err = model
+        .do_stream(options)
+        .await
+        .err()
+        .expect("unknown tool is rejected");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message.contains("\"lookup\"")
+    ));
+}
+
+#[tokio::test]
+async fn count_tokens_posts_request_payload_without_sampling_settings() {
+    let response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/count_tokens.1.json"))
+            .expect("count tokens fixture json");
+    let transport = TestTransport::with_json_response(response);
+    let model = build_model(transport.clone());
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options
+        .prompt
+        .insert(0, v2t::PromptMessage::system("be brief"));
+    options.temperature = Some(0.3);
+    options.max_output_tokens = Some(512);
+
+    let tokens = model.count_tokens(&options).await.expect("count tokens");
+    assert_eq!(tokens, 2095);
+    assert_eq!(
+        transport.last_url().as_deref(),
+        Some("https://api.example.com/messages/count_tokens")
+    );
+    assert_eq!(
+        transport.last_body().expect("counted body"),
+        json!({
+            "model": "claude-3-5-sonnet-20241022",
+            "system": [{"type": "text", "text": "be brief"}],
+            "messages": [{"role": "user", "content": [{"type": "text", "text": "hi"}]}]
+        })
+    );
+    let headers = transport.last_headers().expect("headers captured");
+    assert!(headers
+        .iter()
+        .any(|(k, v)| k == "accept" && v == "application/json"));
+}
+
+#[tokio::test]
+async fn count_tokens_includes_tools_in_counted_payload() {
+    let transport = TestTransport::with_json_response(json!({ "input_tokens": 403 }));
+    let model = build_model(transport.clone());
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.tools = vec![v2t::Tool::Function(v2t::FunctionTool {
+        r#type: v2t::FunctionToolType::Function,
+        name: "get_weather".into(),
+        description: Some("Current weather".into()),
+        input_schema: json!({
+            "type": "object",
+            "properties": {"city": {"type": "string"}}
+        }),
+        strict: None,
+        provider_options: None,
+    })];
+    options.tool_choice = Some(v2t::ToolChoice::Required);
+
+    assert_eq!(model.count_tokens(&options).await.expect("count"), 403);
+    let body = transport.last_body().expect("counted body");
+    assert_eq!(body["tools"][0]["name"], json!("get_weather"));
+    assert_eq!(body["tool_choice"], json!({"type": "any"}));
+    assert!(body.get("max_tokens").is_none());
+}
+
 #[tokio::test]
 async fn adjacent_system_text_entries_with_matching_cache_policy_are_collapsed() {
     let transport = TestTransport::default();
@@ -780,7 +920,7 @@ async fn shared_event_mapper_streams_reasoning_text_tool_and_finish() {
     let model = build_model(transport);
 
     let response = model
-        .do_stream(v2t::CallOptions::new(basic_prompt()))
+        .do_stream(v2t::CallOptions::new(basic_prompt()).with_max_output_tokens(1024))
         .await
         .expect("stream response");
     let parts: Vec<v2t::StreamPart> = response
@@ -791,3 +931,917 @@ async fn shared_event_mapper_streams_reasoning_text_tool_and_finish() {
 
     assert_shared_event_mapper_sequence(&parts);
 }
+
+#[tokio::test]
+async fn final_frame_without_terminator_is_flushed() {
+    for include_raw_chunks in [false, true] {
+        let transport = TestTransport::with_stream_chunks(vec![
+            sse_chunk(
+                Some("content_block_delta"),
+                json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "text_delta", "text": "hello"}
+                }),
+            ),
+            sse_chunk(
+                Some("message_delta"),
+                json!({
+                    "type": "message_delta",
+                    "delta": {"stop_reason": "end_turn"},
+                    "usage": {"input_tokens": 2, "output_tokens": 3}
+                }),
+            ),
+            Bytes::from("event: message_stop\ndata: {\"type\":\"message_stop\"}"),
+        ]);
+        let model = build_model(transport);
+
+        let parts: Vec<v2t::StreamPart> = model
+            .do_stream(v2t::CallOptions {
+                include_raw_chunks,
+                ..v2t::CallOptions::new(basic_prompt())
+            })
+            .await
+            .expect("stream response")
+            .stream
+            .try_collect()
+            .await
+            .expect("stream without trailing blank line");
+
+        assert!(
+            matches!(
+                parts.last(),
+                Some(v2t::StreamPart::Finish { usage, .. }) if usage.output_tokens == Some(3)
+            ),
+            "raw={include_raw_chunks}: {parts:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn redacted_thinking_streams_empty_reasoning_block_with_redacted_data() {
+    let transport = TestTransport::with_stream_chunks(vec![
+        sse_chunk(
+            Some("content_block_start"),
+            json!({
+                "type": "content_block_start",
+                "index": 0,
+                "content_block": {"type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix"}
+            }),
+        ),
+        sse_chunk(
+            Some("content_block_stop"),
+            json!({"type": "content_block_stop", "index": 0}),
+        ),
+        sse_chunk(
+            Some("content_block_start"),
+            json!({
+                "type": "content_block_start",
+                "index": 1,
+                "content_block": {"type": "thinking"}
+            }),
+        ),
+        sse_chunk(
+            Some("content_block_delta"),
+            json!({
+                "type": "content_block_delta",
+                "index": 1,
+                "delta": {"type": "thinking_delta", "thinking": "visible"}
+            }),
+        ),
+        sse_chunk(Some("message_stop"), json!({"type": "message_stop"})),
+    ]);
+    let model = build_model(transport.clone());
+
+    let parts: Vec<v2t::StreamPart> = model
+        .do_stream(v2t::CallOptions::new(basic_prompt()))
+        .await
+        .expect("stream response")
+        .stream
+        .try_collect()
+        .await
+        .expect("collect stream parts");
+
+    let redacted: Vec<_> = parts
+        .iter()
+        .filter(|part| match part {
+            v2t::StreamPart::ReasoningStart { id, .. }
+            | v2t::StreamPart::ReasoningDelta { id, .. }
+            | v2t::StreamPart::ReasoningEnd { id, .. } => id == "0",
+            _ => false,
+        })
+        .collect();
+    assert_eq!(redacted.len(), 2, "{parts:?}");
+    let metadata = match (redacted[0], redacted[1]) {
+        (
+            v2t::StreamPart::ReasoningStart {
+                provider_metadata: start,
+                ..
+            },
+            v2t::StreamPart::ReasoningEnd {
+                provider_metadata: end,
+                ..
+            },
+        ) => {
+            assert_eq!(start, end);
+            end.clone().expect("redacted metadata")
+        }
+        other => panic!("expected reasoning start/end, got {other:?}"),
+    };
+    assert_eq!(
+        metadata["anthropic"]["redactedData"],
+        json!("EmwKAhgBEgy3va3pzix")
+    );
+    assert!(parts.iter().any(|part| matches!(
+        part,
+        v2t::StreamPart::ReasoningDelta { id, delta, .. } if id == "1" && delta == "visible"
+    )));
+
+    // The metadata replays as the original redacted block.
+    let mut prompt = basic_prompt();
+    prompt.push(v2t::PromptMessage::Assistant {
+        content: vec![v2t::AssistantPart::Reasoning {
+            text: String::new(),
+            provider_options: Some(metadata),
+        }],
+        provider_options: None,
+    });
+    prompt.extend(basic_prompt());
+    let _ = model
+        .do_stream(v2t::CallOptions::new(prompt))
+        .await
+        .expect("replay stream");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(
+        body["messages"][1]["content"][0],
+        json!({"type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix"})
+    );
+}
+
+#[tokio::test]
+async fn generate_reports_resolved_model_from_message_start() {
+    let transport = TestTransport::with_stream_chunks(vec![
+        sse_chunk(
+            Some("message_start"),
+            json!({
+                "type": "message_start",
+                "message": {
+                    "id": "msg_123",
+                    "model": "claude-sonnet-4-5-20250929",
+                    "usage": {"input_tokens": 2, "output_tokens": 0}
+                }
+            }),
+        ),
+        sse_chunk(
+            Some("content_block_delta"),
+            json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": {"type": "text_delta", "text": "hi"}
+            }),
+        ),
+        sse_chunk(Some("message_stop"), json!({"type": "message_stop"})),
+    ]);
+    let model = build_model(transport);
+
+    let response = model
+        .do_generate(v2t::CallOptions::new(basic_prompt()))
+        .await
+        .expect("generate response");
+
+    assert_eq!(
+        response.resolved_model_id(),
+        Some("claude-sonnet-4-5-20250929")
+    );
+    assert_eq!(
+        response
+            .response_metadata
+            .as_ref()
+            .and_then(|meta| meta.id.as_deref()),
+        Some("msg_123")
+    );
+}
+
+#[tokio::test]
+async fn fine_grained_tool_streaming_sets_beta_and_streams_partial_input() {
+    let delta = |partial: &str| {
+        sse_chunk(
+            Some("content_block_delta"),
+            json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": {"type": "input_json_delta", "partial_json": partial}
+            }),
+        )
+    };
+    let transport = TestTransport::with_stream_chunks(vec![
+        sse_chunk(
+            Some("content_block_start"),
+            json!({
+                "type": "content_block_start",
+                "index": 0,
+                "content_block": {"type": "tool_use", "id": "tool-1", "name": "weather", "input": {}}
+            }),
+        ),
+        delta("{\"ci"),
+        delta("ty\": \"San "),
+        delta("Francisco\"}"),
+        sse_chunk(
+            Some("content_block_stop"),
+            json!({"type": "content_block_stop", "index": 0}),
+        ),
+        sse_chunk(
+            Some("message_delta"),
+            json!({
+                "type": "message_delta",
+                "delta": {"stop_reason": "tool_use"},
+                "usage": {"output_tokens": 3}
+            }),
+        ),
+        sse_chunk(Some("message_stop"), json!({"type": "message_stop"})),
+    ]);
+    let model = build_model(transport.clone());
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.tools = vec![v2t::Tool::Function(v2t::FunctionTool {
+        r#type: v2t::FunctionToolType::Function,
+        name: "weather".into(),
+        description: None,
+        input_schema: json!({"type": "object"}),
+        strict: None,
+        provider_options: None,
+    })];
+    options.provider_options = v2t::ProviderOptions::from([(
+        "anthropic".into(),
+        HashMap::from([("toolStreaming".into(), json!(true))]),
+    )]);
+
+    let response = model.do_stream(options).await.expect("stream response");
+    let parts: Vec<v2t::StreamPart> = response
+        .stream
+        .try_collect()
+        .await
+        .expect("collect stream parts");
+
+    let headers = transport.last_headers().expect("headers captured");
+    let beta_header = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("anthropic-beta"))
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default();
+    assert!(beta_header
+        .split(',')
+        .any(|v| v.trim() == "fine-grained-tool-streaming-2025-05-14"));
+
+    let deltas: Vec<&str> = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::ToolInputDelta { id, delta, .. } if id == "tool-1" => {
+                Some(delta.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(deltas, vec!["{\"ci", "ty\": \"San ", "Francisco\"}"]);
+
+    let tool_end = stream_part_index(&parts, "tool input end", is_tool_input_end);
+    let last_delta = parts
+        .iter()
+        .rposition(|part| matches!(part, v2t::StreamPart::ToolInputDelta { .. }))
+        .expect("tool input delta");
+    assert!(last_delta < tool_end);
+    let input = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::ToolCall(call) => Some(call.input.clone()),
+            _ => None,
+        })
+        .expect("tool call");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&input).unwrap(),
+        json!({"city": "San Francisco"})
+    );
+}
+
+#[tokio::test]
+async fn tool_streaming_beta_is_opt_in() {
+    let transport = TestTransport::default();
+    let model = build_model(transport.clone());
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.tools = vec![provider_tool("anthropic.web_fetch_20250910", json!({}))];
+    let _ = model.do_stream(options).await.expect("stream response");
+
+    let headers = transport.last_headers().expect("headers captured");
+    assert!(!headers
+        .iter()
+        .any(|(k, v)| k.eq_ignore_ascii_case("anthropic-beta")
+            && v.contains("fine-grained-tool-streaming")));
+}
+
+#[tokio::test]
+async fn binary_tool_result_content_maps_to_image_block() {
+    let transport = TestTransport::default();
+    let model = build_model(transport);
+
+    let output = crate::ai_sdk_core::tool_result::tool_result_output_from_bytes(
+        vec![0x89, b'P', b'N', b'G', 0xff],
+        "image/png",
+    )
+    .expect("binary tool output");
+    let mut prompt = basic_prompt();
+    prompt.push(v2t::PromptMessage::Tool {
+        content: vec![v2t::ToolMessagePart::ToolResult(v2t::ToolResultPart {
+            r#type: Default::default(),
+            tool_call_id: "call_1".into(),
+            tool_name: "screenshot".into(),
+            output,
+            provider_options: None,
+        })],
+        provider_options: None,
+    });
+
+    let response = model
+        .do_stream(v2t::CallOptions::new(prompt))
+        .await
+        .expect("stream response");
+    let body = response.request_body.expect("request body");
+    let tool_result = body["messages"]
+        .as_array()
+        .expect("messages")
+        .iter()
+        .flat_map(|message| message["content"].as_array().cloned().unwrap_or_default())
+        .find(|entry| entry["type"] == "tool_result")
+        .expect("tool_result entry");
+    assert_eq!(
+        tool_result["content"],
+        json!([{
+            "type": "image",
+            "source": {"type": "base64", "media_type": "image/png", "data": "iVBOR/8="}
+        }])
+    );
+}
+
+#[tokio::test]
+async fn system_prompt_mode_folds_or_falls_back_to_system_field() {
+    let prompt = || {
+        let mut prompt = vec![v2t::PromptMessage::System {
+            content: "be brief".into(),
+            provider_options: None,
+        }];
+        prompt.extend(basic_prompt());
+        prompt
+    };
+
+    let model = build_model(TestTransport::default());
+    let mut options = v2t::CallOptions::new(prompt());
+    options.system_prompt_mode = Some(v2t::SystemPromptMode::FoldIntoUser);
+    let response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.expect("request body");
+    assert!(body.get("system").is_none());
+    assert_eq!(
+        without_null_fields(&body["messages"]),
+        json!([{
+            "role": "user",
+            "content": [
+                {"type": "text", "text": "be brief"},
+                {"type": "text", "text": "hi"}
+            ]
+        }])
+    );
+
+    let model = build_model(TestTransport::default());
+    let mut options = v2t::CallOptions::new(prompt());
+    options.system_prompt_mode = Some(v2t::SystemPromptMode::Developer);
+    let mut response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.take().expect("request body");
+    assert_eq!(
+        body["system"],
+        json!([{"type": "text", "text": "be brief"}])
+    );
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::UnsupportedSetting { setting, .. } if setting == "systemPromptMode"
+    )));
+}
+
+#[tokio::test]
+async fn raw_provider_options_merge_over_built_request_body() {
+    let model = build_model(TestTransport::default());
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.max_output_tokens = Some(512);
+    options.provider_options.insert(
+        "raw".into(),
+        HashMap::from([
+            ("max_tokens".into(), json!(64)),
+            ("metadata".into(), json!({"user_id": "u-1"})),
+        ]),
+    );
+    let mut response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.take().expect("request body");
+    assert_eq!(body["max_tokens"], json!(64));
+    assert_eq!(body["metadata"], json!({"user_id": "u-1"}));
+    assert!(body["messages"].is_array());
+
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message }
+            if message == "raw provider options passthrough applied: max_tokens, metadata"
+    )));
+}
+
+#[tokio::test]
+async fn generate_captures_raw_stream_frames_as_response_body() {
+    let message_start = json!({
+        "type": "message_start",
+        "message": {"id": "msg_1", "usage": {"input_tokens": 2, "output_tokens": 0}}
+    });
+    let text_delta = json!({
+        "type": "content_block_delta",
+        "index": 0,
+        "delta": {"type": "text_delta", "text": "hi"}
+    });
+    let message_stop = json!({"type": "message_stop"});
+    let transport = TestTransport::with_stream_chunks(vec![
+        sse_chunk(Some("message_start"), message_start.clone()),
+        sse_chunk(Some("content_block_delta"), text_delta.clone()),
+        sse_chunk(Some("message_stop"), message_stop.clone()),
+    ]);
+    let model = build_model(transport);
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.capture_raw_response = true;
+    let response = model.do_generate(options).await.expect("generate response");
+
+    assert_eq!(
+        response.response_body,
+        Some(json!([message_start, text_delta, message_stop]))
+    );
+    assert!(matches!(
+        response.content.as_slice(),
+        [v2t::Content::Text { text, .. }] if text == "hi"
+    ));
+}
+
+#[tokio::test]
+async fn missing_max_output_tokens_defaults_max_tokens_with_warning() {
+    async fn start(
+        model: &AnthropicMessagesLanguageModel<TestTransport>,
+        options: v2t::CallOptions,
+    ) -> (serde_json::Value, Vec<v2t::CallWarning>) {
+        let mut response = model.do_stream(options).await.expect("stream response");
+        let body = response.request_body.take().expect("request body");
+        let first = response.stream.try_next().await.expect("first part");
+        let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+            panic!("expected stream start, got {first:?}");
+        };
+        (body, warnings)
+    }
+    let defaulted = |warnings: &[v2t::CallWarning]| {
+        warnings.iter().any(|warning| {
+            matches!(
+                warning,
+                v2t::CallWarning::Other { message } if message.contains("maxOutputTokens")
+            )
+        })
+    };
+
+    let model = build_model(TestTransport::default());
+    let (body, warnings) = start(&model, v2t::CallOptions::new(basic_prompt())).await;
+    assert_eq!(body["max_tokens"], json!(DEFAULT_MAX_TOKENS));
+    assert!(defaulted(&warnings));
+
+    let (body, warnings) = start(
+        &model,
+        v2t::CallOptions::new(basic_prompt()).with_max_output_tokens(300),
+    )
+    .await;
+    assert_eq!(body["max_tokens"], json!(300));
+    assert!(!defaulted(&warnings));
+
+    let model = build_model_with_max_tokens(TestTransport::default(), Some(8192));
+    let (body, warnings) = start(&model, v2t::CallOptions::new(basic_prompt())).await;
+    assert_eq!(body["max_tokens"], json!(8192));
+    assert!(defaulted(&warnings));
+}
+
+#[test]
+fn claude_model_capabilities_follow_model_family() {
+    use crate::ai_sdk_providers_anthropic::messages::language_model::model_capabilities;
+
+    let haiku_3_5 = model_capabilities("claude-3-5-haiku-20241022");
+    assert!(haiku_3_5.vision && haiku_3_5.tools && haiku_3_5.citations);
+    assert!(!haiku_3_5.reasoning && !haiku_3_5.audio_in);
+    assert!(haiku_3_5.parallel_tool_calls);
+
+    assert!(model_capabilities("claude-3-7-sonnet-20250219").reasoning);
+    assert!(model_capabilities("claude-sonnet-4-5-20250929").reasoning);
+    assert!(model_capabilities("claude-opus-4-1").reasoning);
+
+    let claude_2 = model_capabilities("claude-2.1");
+    assert!(!claude_2.vision && !claude_2.reasoning);
+    assert!(!claude_2.parallel_tool_calls);
+}
+
+#[tokio::test]
+async fn developer_messages_are_sent_as_system_entries() {
+    let transport = TestTransport::default();
+    let model = build_model(transport);
+
+    let prompt = vec![
+        v2t::PromptMessage::system("be kind"),
+        v2t::PromptMessage::developer("be brief"),
+        v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hi".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        },
+    ];
+    let response = model
+        .do_stream(v2t::CallOptions::new(prompt))
+        .await
+        .expect("stream response");
+    let body = response.request_body.expect("request body");
+    assert_eq!(
+        body["system"],
+        json!([{"type": "text", "text": "be kind\n\nbe brief"}])
+    );
+    assert_eq!(body["messages"].as_array().map(Vec::len), Some(1));
+}
+
+fn cache_control_options(cache_control: serde_json::Value) -> Option<v2t::ProviderOptions> {
+    let mut scope = HashMap::new();
+    scope.insert("cacheControl".to_string(), cache_control);
+    let mut opts = v2t::ProviderOptions::new();
+    opts.insert("anthropic".to_string(), scope);
+    Some(opts)
+}
+
+fn cached_function_tool(name: &str) -> v2t::Tool {
+    v2t::Tool::Function(v2t::FunctionTool {
+        r#type: v2t::FunctionToolType::Function,
+        name: name.into(),
+        description: None,
+        input_schema: json!({"type": "object"}),
+        strict: None,
+        provider_options: cache_control_options(json!({"type": "ephemeral", "ttl": "1h"})),
+    })
+}
+
+fn cached_text(text: &str) -> v2t::UserPart {
+    v2t::UserPart::Text {
+        text: text.into(),
+        provider_options: anthropic_cache_control_options(),
+    }
+}
+
+#[tokio::test]
+async fn cache_control_is_placed_on_tools_system_and_parts() {
+    let model = build_model(TestTransport::default());
+    let prompt = vec![
+        v2t::PromptMessage::System {
+            content: "long system".into(),
+            provider_options: cache_control_options(json!({"type": "ephemeral", "ttl": "5m"})),
+        },
+        v2t::PromptMessage::User {
+            content: vec![
+                cached_text("document"),
+                v2t::UserPart::Text {
+                    text: "question".into(),
+                    provider_options: cache_control_options(json!({"type": "persistent"})),
+                },
+            ],
+            provider_options: None,
+        },
+    ];
+    let mut options = v2t::CallOptions::new(prompt);
+    options.tools = vec![cached_function_tool("lookup")];
+
+    let mut response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.take().expect("request body");
+    assert_eq!(
+        body["tools"][0]["cache_control"],
+        json!({"type": "ephemeral", "ttl": "1h"})
+    );
+    assert_eq!(
+        body["system"][0]["cache_control"],
+        json!({"type": "ephemeral", "ttl": "5m"})
+    );
+    let content = &body["messages"][0]["content"];
+    assert_eq!(content[0]["cache_control"], json!({"type": "ephemeral"}));
+    assert!(content[1]["cache_control"].is_null());
+
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::UnsupportedSetting { setting, details: Some(details) }
+            if setting == "cacheControl" && details.contains("persistent")
+    )));
+}
+
+#[tokio::test]
+async fn cache_breakpoints_beyond_the_limit_are_dropped_from_the_end() {
+    let model = build_model(TestTransport::default());
+    let prompt = vec![
+        v2t::PromptMessage::System {
+            content: "long system".into(),
+            provider_options: anthropic_cache_control_options(),
+        },
+        v2t::PromptMessage::User {
+            content: vec![cached_text("one"), cached_text("two")],
+            provider_options: None,
+        },
+    ];
+    let mut options = v2t::CallOptions::new(prompt);
+    options.tools = vec![cached_function_tool("a"), cached_function_tool("b")];
+
+    let mut response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.take().expect("request body");
+    assert!(body["tools"][0].get("cache_control").is_some());
+    assert!(body["tools"][1].get("cache_control").is_some());
+    assert!(body["system"][0].get("cache_control").is_some());
+    let content = &body["messages"][0]["content"];
+    assert_eq!(content[0]["cache_control"], json!({"type": "ephemeral"}));
+    assert!(content[1].get("cache_control").is_none());
+
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message } if message.contains("cache breakpoints")
+    )));
+}
+
+#[tokio::test]
+async fn cache_breakpoint_overflow_can_keep_the_latest_breakpoints() {
+    let model = build_model(TestTransport::default());
+    let prompt = vec![
+        v2t::PromptMessage::System {
+            content: "long system".into(),
+            provider_options: anthropic_cache_control_options(),
+        },
+        v2t::PromptMessage::User {
+            content: vec![cached_text("one"), cached_text("two")],
+            provider_options: None,
+        },
+    ];
+    let mut options = v2t::CallOptions::new(prompt);
+    options.tools = vec![cached_function_tool("a"), cached_function_tool("b")];
+    options.provider_options = v2t::ProviderOptions::from([(
+        "anthropic".into(),
+        HashMap::from([("cacheBreakpointOverflow".into(), json!("keepLast"))]),
+    )]);
+
+    let mut response = model.do_stream(options).await.expect("stream response");
+    let body = response.request_body.take().expect("request body");
+    assert!(body["tools"][0].get("cache_control").is_none());
+    assert!(body["tools"][1].get("cache_control").is_some());
+    assert!(body["system"][0].get("cache_control").is_some());
+    let content = &body["messages"][0]["content"];
+    assert_eq!(content[0]["cache_control"], json!({"type": "ephemeral"}));
+    assert_eq!(content[1]["cache_control"], json!({"type": "ephemeral"}));
+
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message } if message.contains("from the start")
+    )));
+}
+
+#[tokio::test]
+async fn cache_usage_from_message_start_survives_message_delta() {
+    let transport = TestTransport::with_stream_chunks(vec![
+        sse_chunk(
+            Some("message_start"),
+            json!({
+                "type": "message_start",
+                "message": {
+                    "usage": {
+                        "input_tokens": 4,
+                        "output_tokens": 1,
+                        "cache_creation_input_tokens": 1200,
+                        "cache_read_input_tokens": 3400
+                    }
+                }
+            }),
+        ),
+        sse_chunk(
+            Some("message_delta"),
+            json!({
+                "type": "message_delta",
+                "delta": {"stop_reason": "end_turn"},
+                "usage": {"output_tokens": 9}
+            }),
+        ),
+        sse_chunk(Some("message_stop"), json!({"type": "message_stop"})),
+    ]);
+    let model = build_model(transport);
+
+    let response = model
+        .do_generate(v2t::CallOptions::new(basic_prompt()))
+        .await
+        .expect("generate response");
+    assert_eq!(response.usage.input_tokens, Some(4));
+    assert_eq!(response.usage.output_tokens, Some(9));
+    assert_eq!(response.usage.cached_input_tokens, Some(3400));
+
+    let usage = crate::ai_sdk_types::usage::from_anthropic(&json!({
+        "input_tokens": 4,
+        "output_tokens": 9,
+        "cache_creation_input_tokens": 1200,
+        "cache_read_input_tokens": 3400
+    }));
+    assert_eq!(usage.cache_write_tokens, Some(1200));
+    assert_eq!(usage.cache_read_tokens, Some(3400));
+}
+
+fn pdf_prompt(data: v2t::DataContent) -> v2t::Prompt {
+    vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::File {
+            filename: None,
+            data,
+            media_type: "application/pdf".into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }]
+}
+
+#[tokio::test]
+async fn pdf_file_parts_become_document_blocks() {
+    let pdf_urls = HashMap::from([(
+        "application/pdf".to_string(),
+        vec![r"^https?://.*$".to_string()],
+    )]);
+    let model = build_model_with_config(TestTransport::default(), None, pdf_urls);
+
+    let response = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(v2t::DataContent::Bytes {
+            bytes: b"%PDF-1.7".to_vec(),
+        })))
+        .await
+        .expect("stream response");
+    let body = response.request_body.expect("request body");
+    assert_eq!(
+        body["messages"][0]["content"][0]["source"],
+        json!({"type": "base64", "media_type": "application/pdf", "data": "JVBERi0xLjc="})
+    );
+    assert_eq!(body["messages"][0]["content"][0]["type"], "document");
+
+    let url = "https://example.com/report.pdf";
+    let response = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(v2t::DataContent::Url {
+            url: url.into(),
+        })))
+        .await
+        .expect("stream response");
+    let body = response.request_body.expect("request body");
+    assert_eq!(
+        body["messages"][0]["content"][0]["source"],
+        json!({"type": "url", "url": url})
+    );
+}
+
+#[tokio::test]
+async fn pdf_file_parts_are_validated() {
+    let model = build_model(TestTransport::default());
+
+    let invalid = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(
+            v2t::DataContent::Base64 {
+                base64: "not base64!".into(),
+            },
+        )))
+        .await;
+    assert!(matches!(invalid, Err(SdkError::InvalidArgument { .. })));
+
+    let unlisted_url = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(v2t::DataContent::Url {
+            url: "https://example.com/report.pdf".into(),
+        })))
+        .await;
+    assert!(unlisted_url.is_ok());
+
+    let docs_only = HashMap::from([(
+        "application/pdf".to_string(),
+        vec![r"^https://docs\.example\.com/".to_string()],
+    )]);
+    let model = build_model_with_config(TestTransport::default(), None, docs_only);
+    let listed_url = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(v2t::DataContent::Url {
+            url: "https://docs.example.com/report.pdf".into(),
+        })))
+        .await;
+    assert!(listed_url.is_ok());
+    let unsupported_url = model
+        .do_stream(v2t::CallOptions::new(pdf_prompt(v2t::DataContent::Url {
+            url: "https://example.com/report.pdf".into(),
+        })))
+        .await;
+    assert!(matches!(
+        unsupported_url,
+        Err(SdkError::InvalidArgument { message }) if message.contains("PDF URLs")
+    ));
+}
+
+#[tokio::test]
+async fn request_headers_follow_transport_header_policy() {
+    let transport = TestTransport::with_json_response(json!({ "input_tokens": 1 }));
+    let cfg = AnthropicMessagesConfig {
+        provider_name: "anthropic",
+        provider_scope_name: "anthropic".into(),
+        base_url: "https://api.example.com".into(),
+        headers: vec![
+            ("X-Team".into(), "core".into()),
+            ("anthropic-beta".into(), "beta-a".into()),
+        ],
+        http: transport.clone(),
+        transport_cfg: TransportConfig {
+            header_conflict_policy: crate::ai_sdk_core::headers::HeaderConflictPolicy::Append,
+            preserve_header_case: true,
+            ..TransportConfig::default()
+        },
+        supported_urls: HashMap::new(),
+        default_options: None,
+        default_max_tokens: None,
+    };
+    let model = AnthropicMessagesLanguageModel::new("claude-3-5-sonnet-20241022".into(), cfg);
+
+    let mut options = v2t::CallOptions::new(basic_prompt());
+    options.headers = HashMap::from([
+        ("x-team".to_string(), "evals".to_string()),
+        ("anthropic-beta".to_string(), "beta-b".to_string()),
+    ]);
+    model.count_tokens(&options).await.expect("count tokens");
+
+    let headers = transport.last_headers().expect("headers captured");
+    assert!(headers
+        .iter()
+        .any(|(k, v)| k == "X-Team" && v == "core, evals"));
+    assert!(headers
+        .iter()
+        .any(|(k, v)| k == "anthropic-beta" && v == "beta-a,beta-b"));
+}
+
+#[tokio::test]
+async fn audio_file_parts_are_dropped_with_a_warning() {
+    let transport = TestTransport::default();
+    let model = build_model(transport.clone());
+
+    let mut response = model
+        .do_stream(
+            v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+                content: vec![
+                    v2t::UserPart::Text {
+                        text: "transcribe".into(),
+                        provider_options: None,
+                    },
+                    v2t::UserPart::File {
+                        filename: None,
+                        data: v2t::DataContent::Bytes {
+                            bytes: vec![0, 1, 2, 3],
+                        },
+                        media_type: "audio/wav".into(),
+                        provider_options: None,
+                    },
+                ],
+                provider_options: None,
+            }])
+            .with_max_output_tokens(64),
+        )
+        .await
+        .expect("stream response");
+
+    let body = response.request_body.take().expect("request body");
+    let content = body["messages"][0]["content"]
+        .as_array()
+        .expect("user content");
+    assert_eq!(content.len(), 1);
+    assert_eq!(content[0]["text"], "transcribe");
+    let first = response.stream.try_next().await.expect("first part");
+    let Some(v2t::StreamPart::StreamStart { warnings }) = first else {
+        panic!("expected stream start, got {first:?}");
+    };
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message } if message.contains("Anthropic") && message.contains("audio/wav")
+    )));
+}
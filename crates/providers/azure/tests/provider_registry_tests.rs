@@ -57,9 +57,15 @@ fn azure_definition() -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: HashMap::<String, ModelInfo>::new(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
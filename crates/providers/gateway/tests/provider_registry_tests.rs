@@ -1,6 +1,9 @@
 use crate::provider::{registry, Credentials};
 use crate::types::catalog::{ModelInfo, ProviderDefinition, SdkType};
+use crate::types::v2 as v2t;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 #[test]
 fn gateway_registry_maps_to_language_model_builder() {
@@ -10,12 +13,25 @@ fn gateway_registry_maps_to_language_model_builder() {
         .expect("gateway registration");
     assert!(matches!(reg.sdk_type, SdkType::Gateway));
 
+    let def = gateway_definition("https://ai-gateway.vercel.sh/v1/ai", Vec::new());
+
+    let model = (reg.build)(
+        &def,
+        "openai/gpt-4.1-mini",
+        &Credentials::ApiKey("test-key".into()),
+    )
+    .expect("build model");
+    assert_eq!(model.provider_name(), "gateway");
+    assert_eq!(model.model_id(), "openai/gpt-4.1-mini");
+}
+
+fn gateway_definition(base_url: &str, fallback_models: Vec<String>) -> ProviderDefinition {
     let models: HashMap<String, ModelInfo> = HashMap::new();
-    let def = ProviderDefinition {
+    ProviderDefinition {
         name: "gateway".into(),
         display_name: "Gateway".into(),
         sdk_type: SdkType::Gateway,
-        base_url: "https://ai-gateway.vercel.sh/v1/ai".into(),
+        base_url: base_url.into(),
         env: None,
         npm: None,
         doc: None,
@@ -23,10 +39,71 @@ fn gateway_registry_maps_to_language_model_builder() {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models,
         preserve_model_prefix: true,
-    };
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models,
+    }
+}
+
+/// Answer every request with a 503 and record its model id header.
+async fn unavailable_gateway() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind ephemeral port");
+    let base_url = format!("http://{}", listener.local_addr().expect("listener addr"));
+    let models = Arc::new(Mutex::new(Vec::new()));
+    let seen = models.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while let Ok(read @ 1..) = socket.read(&mut buf).await {
+                request.extend_from_slice(&buf[..read]);
+                let text = String::from_utf8_lossy(&request);
+                let Some(head_end) = text.find("\r\n\r\n") else {
+                    continue;
+                };
+                let header = |name: &str| {
+                    text[..head_end].lines().find_map(|line| {
+                        let (key, value) = line.split_once(':')?;
+                        key.eq_ignore_ascii_case(name)
+                            .then(|| value.trim().to_string())
+                    })
+                };
+                let body_len: usize =
+                    header("content-length").map_or(0, |len| len.parse().unwrap());
+                if request.len() < head_end + 4 + body_len {
+                    continue;
+                }
+                if let Some(model) = header("ai-language-model-id") {
+                    seen.lock().unwrap().push(model);
+                }
+                break;
+            }
+            let _ = socket
+                .write_all(
+                    b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await;
+        }
+    });
+    (base_url, models)
+}
+
+#[tokio::test]
+async fn gateway_registry_applies_definition_fallback_models() {
+    let reg = registry::iter()
+        .into_iter()
+        .find(|entry| entry.id.eq_ignore_ascii_case("gateway"))
+        .expect("gateway registration");
+    let (base_url, models) = unavailable_gateway().await;
+    let def = gateway_definition(&base_url, vec!["anthropic/claude-sonnet-4".into()]);
 
     let model = (reg.build)(
         &def,
@@ -34,6 +111,18 @@ fn gateway_registry_maps_to_language_model_builder() {
         &Credentials::ApiKey("test-key".into()),
     )
     .expect("build model");
-    assert_eq!(model.provider_name(), "gateway");
-    assert_eq!(model.model_id(), "openai/gpt-4.1-mini");
+    let result = model
+        .do_generate(v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }]))
+        .await;
+    assert!(result.is_err());
+
+    let mut tried = models.lock().unwrap().clone();
+    tried.dedup();
+    assert_eq!(tried, ["openai/gpt-4.1-mini", "anthropic/claude-sonnet-4"]);
 }
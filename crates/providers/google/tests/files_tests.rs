@@ -0,0 +1,335 @@
+use super::{upload_start_url, FileRef};
+use crate::core::error::TransportError;
+use crate::core::transport::{HttpTransport, TransportConfig, TransportStream};
+use crate::core::SdkError;
+use crate::providers::google::gen_ai::language_model::{
+    GoogleGenAiConfig, GoogleGenAiLanguageModel,
+};
+use crate::providers::google::shared::prompt::convert_to_google_prompt_with_scopes;
+use crate::types::v2 as v2t;
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const UPLOAD_URL: &str =
+    "https://generativelanguage.googleapis.com/upload/v1beta/files?upload_id=abc&upload_protocol=resumable";
+
+#[derive(Debug, Clone)]
+struct StartCall {
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+#[derive(Debug, Clone)]
+struct UploadCall {
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+}
+
+#[derive(Clone, Default)]
+struct UploadTransport {
+    start_headers: Vec<(String, String)>,
+    upload_error: Option<String>,
+    starts: Arc<Mutex<Vec<StartCall>>>,
+    uploads: Arc<Mutex<Vec<UploadCall>>>,
+}
+
+impl UploadTransport {
+    fn new() -> Self {
+        Self {
+            start_headers: vec![
+                ("x-goog-upload-status".into(), "active".into()),
+                ("X-Goog-Upload-URL".into(), UPLOAD_URL.into()),
+            ],
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for UploadTransport {
+    type StreamResponse = TransportStream;
+
+    fn into_stream(resp: Self::StreamResponse) -> (TransportStream, Vec<(String, String)>) {
+        (resp, Vec::new())
+    }
+
+    async fn post_json_stream(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        Err(TransportError::Other("post_json_stream unused".into()))
+    }
+
+    async fn post_json(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        self.starts.lock().unwrap().push(StartCall {
+            url: url.to_string(),
+            headers: headers.to_vec(),
+            body: body.clone(),
+        });
+        Ok((Value::Null, self.start_headers.clone()))
+    }
+
+    async fn post_bytes(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Bytes,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        self.uploads.lock().unwrap().push(UploadCall {
+            url: url.to_string(),
+            headers: headers.to_vec(),
+            body: body.clone(),
+        });
+        if let Some(body) = &self.upload_error {
+            return Err(TransportError::HttpStatus {
+                status: 400,
+                body: body.clone(),
+                retry_after_ms: None,
+                sanitized: "redacted".into(),
+                headers: Vec::new(),
+            });
+        }
+        Ok((
+            json!({
+                "file": {
+                    "name": "files/abc-123",
+                    "displayName": "notes",
+                    "mimeType": "text/plain",
+                    "sizeBytes": body.len().to_string(),
+                    "createTime": "2026-01-01T00:00:00.000000Z",
+                    "uri": "https://generativelanguage.googleapis.com/v1beta/files/abc-123",
+                    "state": "ACTIVE"
+                }
+            }),
+            Vec::new(),
+        ))
+    }
+}
+
+fn model(transport: UploadTransport) -> GoogleGenAiLanguageModel<UploadTransport> {
+    GoogleGenAiLanguageModel::new(
+        "gemini-2.5-flash",
+        GoogleGenAiConfig {
+            provider_name: "google.gen-ai",
+            provider_scope_name: "google".into(),
+            base_url: "https://generativelanguage.googleapis.com/v1beta".into(),
+            headers: vec![
+                ("content-type".into(), "application/json".into()),
+                ("x-goog-api-key".into(), "test-key".into()),
+            ],
+            http: transport,
+            transport_cfg: TransportConfig::default(),
+            supported_urls: HashMap::new(),
+            query_params: Vec::new(),
+            default_options: None,
+            warn_on_include_thoughts: true,
+        },
+    )
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+#[tokio::test]
+async fn upload_file_starts_a_resumable_upload_and_finalizes_it() {
+    let transport = UploadTransport::new();
+    let file = model(transport.clone())
+        .upload_file(b"hello world".to_vec(), "text/plain", Some("notes"))
+        .await
+        .expect("upload");
+
+    assert_eq!(
+        file,
+        FileRef {
+            name: "files/abc-123".into(),
+            uri: "https://generativelanguage.googleapis.com/v1beta/files/abc-123".into(),
+            mime_type: "text/plain".into(),
+            display_name: Some("notes".into()),
+            state: Some("ACTIVE".into()),
+        }
+    );
+
+    let starts = transport.starts.lock().unwrap().clone();
+    assert_eq!(starts.len(), 1);
+    let start = &starts[0];
+    assert_eq!(
+        start.url,
+        "https://generativelanguage.googleapis.com/upload/v1beta/files"
+    );
+    assert_eq!(start.body, json!({ "file": { "displayName": "notes" } }));
+    assert_eq!(
+        header(&start.headers, "x-goog-upload-protocol"),
+        Some("resumable")
+    );
+    assert_eq!(
+        header(&start.headers, "x-goog-upload-command"),
+        Some("start")
+    );
+    assert_eq!(
+        header(&start.headers, "x-goog-upload-header-content-length"),
+        Some("11")
+    );
+    assert_eq!(
+        header(&start.headers, "x-goog-upload-header-content-type"),
+        Some("text/plain")
+    );
+    assert_eq!(header(&start.headers, "x-goog-api-key"), Some("test-key"));
+
+    let uploads = transport.uploads.lock().unwrap().clone();
+    assert_eq!(uploads.len(), 1);
+    let upload = &uploads[0];
+    assert_eq!(upload.url, UPLOAD_URL);
+    assert_eq!(upload.body, Bytes::from_static(b"hello world"));
+    assert_eq!(header(&upload.headers, "content-type"), Some("text/plain"));
+    assert_eq!(header(&upload.headers, "x-goog-upload-offset"), Some("0"));
+    assert_eq!(
+        header(&upload.headers, "x-goog-upload-command"),
+        Some("upload, finalize")
+    );
+}
+
+#[tokio::test]
+async fn upload_file_fails_when_start_returns_no_upload_url() {
+    let transport = UploadTransport::default();
+    let err = model(transport.clone())
+        .upload_file(b"hello".to_vec(), "text/plain", None)
+        .await
+        .expect_err("missing upload url");
+
+    match err {
+        SdkError::Upstream { message, .. } => {
+            assert!(message.contains("x-goog-upload-url"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(
+        transport.starts.lock().unwrap()[0].body,
+        json!({ "file": {} })
+    );
+    assert!(transport.uploads.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn upload_file_surfaces_google_error_from_upload_step() {
+    let transport = UploadTransport {
+        upload_error: Some(
+            r#"{"error":{"code":400,"message":"Unsupported MIME type: foo/bar","status":"INVALID_ARGUMENT"}}"#
+                .into(),
+        ),
+        ..UploadTransport::new()
+    };
+    let err = model(transport)
+        .upload_file(b"hello".to_vec(), "foo/bar", None)
+        .await
+        .expect_err("upload error");
+
+    match err {
+        SdkError::Upstream {
+            status, message, ..
+        } => {
+            assert_eq!(status, 400);
+            assert_eq!(message, "Unsupported MIME type: foo/bar");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn upload_file_sends_configured_query_params_on_both_requests() {
+    let transport = UploadTransport::new();
+    let mut model = model(transport.clone());
+    model.cfg.query_params = vec![("key".into(), "test key".into())];
+    model
+        .upload_file(b"hello".to_vec(), "text/plain", None)
+        .await
+        .expect("upload");
+
+    let starts = transport.starts.lock().unwrap().clone();
+    assert_eq!(
+        starts[0].url,
+        "https://generativelanguage.googleapis.com/upload/v1beta/files?key=test%20key"
+    );
+    let uploads = transport.uploads.lock().unwrap().clone();
+    assert_eq!(uploads[0].url, format!("{UPLOAD_URL}&key=test%20key"));
+}
+
+#[tokio::test]
+async fn upload_file_requires_a_mime_type() {
+    let transport = UploadTransport::new();
+    let err = model(transport.clone())
+        .upload_file(b"hello".to_vec(), " ", None)
+        .await
+        .expect_err("mime type");
+
+    assert!(matches!(err, SdkError::InvalidArgument { .. }));
+    assert!(transport.starts.lock().unwrap().is_empty());
+}
+
+#[test]
+fn upload_start_url_inserts_upload_before_the_api_path() {
+    assert_eq!(
+        upload_start_url("https://generativelanguage.googleapis.com/v1beta/"),
+        "https://generativelanguage.googleapis.com/upload/v1beta/files"
+    );
+    assert_eq!(
+        upload_start_url("http://localhost:8080"),
+        "http://localhost:8080/upload/files"
+    );
+}
+
+#[test]
+fn file_ref_user_part_is_sent_as_file_data() {
+    let file = FileRef {
+        name: "files/abc-123".into(),
+        uri: "https://generativelanguage.googleapis.com/v1beta/files/abc-123".into(),
+        mime_type: "application/pdf".into(),
+        display_name: None,
+        state: Some("ACTIVE".into()),
+    };
+    let prompt = vec![v2t::PromptMessage::User {
+        content: vec![
+            file.user_part(),
+            v2t::UserPart::Text {
+                text: "Summarize this.".into(),
+                provider_options: None,
+            },
+        ],
+        provider_options: None,
+    }];
+
+    let converted =
+        convert_to_google_prompt_with_scopes(&prompt, false, &["google"]).expect("prompt");
+
+    assert_eq!(
+        serde_json::to_value(&converted.contents).expect("contents"),
+        json!([{
+            "role": "user",
+            "parts": [
+                {"fileData": {
+                    "mimeType": "application/pdf",
+                    "fileUri": "https://generativelanguage.googleapis.com/v1beta/files/abc-123"
+                }},
+                {"text": "Summarize this."}
+            ]
+        }])
+    );
+}
@@ -18,7 +18,12 @@ use crate::provider_google::shared::prepare_tools::prepare_tools as prepare_goog
 use crate::provider_google::shared::prompt::{
     convert_to_google_prompt_with_scopes, GoogleContent, GoogleContentPart, GooglePrompt,
 };
-use crate::provider_google::shared::stream_core::build_google_stream_part_stream;
+use crate::provider_google::shared::request_body::{
+    build_google_request_body, thoughts_disabled, GoogleRequestBodyBuildConfig,
+};
+use crate::provider_google::shared::stream_core::{
+    build_google_stream_part_stream, strip_thought_parts,
+};
 
 const GOOGLE_SCOPES: &[&str] = &["google"];
 const GOOGLE_VERTEX_SCOPES: &[&str] = &["google-vertex", "google"];
@@ -136,6 +141,107 @@ fn provider_option_scope_resolution_matches_expected_behavior() {
     );
 }
 
+#[test]
+fn raw_provider_options_merge_over_request_body_for_google_and_vertex() {
+    let mut options = v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: "hi".into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }]);
+    options.temperature = Some(0.5);
+    options.provider_options.insert(
+        "raw".into(),
+        HashMap::from([
+            ("generationConfig".into(), json!({"candidateCount": 2})),
+            ("cachedContent".into(), json!("cachedContents/abc")),
+        ]),
+    );
+
+    for scope_names in [GOOGLE_SCOPES, GOOGLE_VERTEX_SCOPES] {
+        let (body, warnings) = build_google_request_body(
+            GoogleRequestBodyBuildConfig {
+                scope_names,
+                raw_provider_option_keys: scope_names,
+                model_id: "gemini-2.5-flash",
+                is_gemma: false,
+                trace_prefix: "test",
+                include_thoughts_warning: None,
+            },
+            &options,
+        )
+        .expect("request body");
+        assert_eq!(body["generationConfig"], json!({"candidateCount": 2}));
+        assert_eq!(body["cachedContent"], json!("cachedContents/abc"));
+        assert!(body["contents"].is_array());
+        assert!(warnings.iter().any(|warning| matches!(
+            warning,
+            v2t::CallWarning::Other { message }
+                if message == "raw provider options passthrough applied: cachedContent, generationConfig"
+        )));
+    }
+}
+
+#[test]
+fn gemma_applies_unsupported_tools_policy() {
+    let mut options = v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: "hi".into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }]);
+    options.tools = vec![function_tool("lookup")];
+    let build = |options: &v2t::CallOptions| {
+        build_google_request_body(
+            GoogleRequestBodyBuildConfig {
+                scope_names: GOOGLE_SCOPES,
+                raw_provider_option_keys: GOOGLE_SCOPES,
+                model_id: "gemma-3-27b-it",
+                is_gemma: true,
+                trace_prefix: "test",
+                include_thoughts_warning: None,
+            },
+            options,
+        )
+    };
+
+    assert!(matches!(
+        build(&options),
+        Err(SdkError::InvalidArgument { message }) if message.contains("tool calling")
+    ));
+
+    options.on_unsupported_tools = v2t::UnsupportedToolsPolicy::DropWithWarning;
+    let (body, warnings) = build(&options).expect("request body");
+    assert!(body.get("tools").is_none());
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::UnsupportedSetting { setting, .. }] if setting == "tools"
+    ));
+
+    options.on_unsupported_tools = v2t::UnsupportedToolsPolicy::InlineAsText;
+    let (body, _) = build(&options).expect("request body");
+    assert!(body.get("tools").is_none());
+    let prompt = body.to_string();
+    assert!(prompt.contains("- lookup: test tool"), "{prompt}");
+
+    options.on_unsupported_tools = v2t::UnsupportedToolsPolicy::Error;
+    let (body, _) = build_google_request_body(
+        GoogleRequestBodyBuildConfig {
+            scope_names: GOOGLE_SCOPES,
+            raw_provider_option_keys: GOOGLE_SCOPES,
+            model_id: "tunedModels/support-bot-7",
+            is_gemma: false,
+            trace_prefix: "test",
+            include_thoughts_warning: None,
+        },
+        &options,
+    )
+    .expect("unrecognized ids keep their tools");
+    assert!(body.get("tools").is_some());
+}
+
 fn first_model_text_signature(prompt: &GooglePrompt) -> Option<String> {
     prompt.contents.iter().find_map(|content| match content {
         GoogleContent::Model { parts } => parts.iter().find_map(|part| match part {
@@ -246,10 +352,20 @@ async fn shared_stream_core_preserves_provider_scope_namespace() {
         }
     });
 
-    let mut google_stream =
-        build_google_stream_part_stream(stream_input(payload.clone()), vec![], false, "google");
-    let mut vertex_stream =
-        build_google_stream_part_stream(stream_input(payload), vec![], false, "google-vertex");
+    let mut google_stream = build_google_stream_part_stream(
+        stream_input(payload.clone()),
+        vec![],
+        false,
+        "google",
+        false,
+    );
+    let mut vertex_stream = build_google_stream_part_stream(
+        stream_input(payload),
+        vec![],
+        false,
+        "google-vertex",
+        false,
+    );
 
     let google_parts = collect_parts(&mut google_stream, "google").await;
     let vertex_parts = collect_parts(&mut vertex_stream, "google-vertex").await;
@@ -295,6 +411,212 @@ async fn shared_stream_core_preserves_provider_scope_namespace() {
     assert_eq!(vertex_scope.as_deref(), Some("google-vertex"));
 }
 
+#[tokio::test]
+async fn shared_stream_core_maps_all_usage_metadata_counts() {
+    let payloads = vec![
+        json!({
+            "candidates": [{
+                "content": {"parts": [{"text": "partial"}]}
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 12,
+                "totalTokenCount": 12
+            }
+        }),
+        json!({
+            "candidates": [{
+                "content": {"parts": [{"text": " answer"}]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 12,
+                "candidatesTokenCount": 7,
+                "totalTokenCount": 31,
+                "thoughtsTokenCount": 12,
+                "cachedContentTokenCount": 8
+            }
+        }),
+    ];
+
+    let mut google_stream =
+        build_google_stream_part_stream(stream_inputs(payloads), vec![], false, "google", false);
+    let parts = collect_parts(&mut google_stream, "google").await;
+    let usage = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish { usage, .. } => Some(usage),
+            _ => None,
+        })
+        .expect("finish usage");
+
+    assert_eq!(usage.input_tokens, Some(12));
+    assert_eq!(usage.output_tokens, Some(7));
+    assert_eq!(usage.total_tokens, Some(31));
+    assert_eq!(usage.reasoning_tokens, Some(12));
+    assert_eq!(usage.cached_input_tokens, Some(8));
+}
+
+#[tokio::test]
+async fn shared_stream_core_maps_audio_and_image_modality_counts() {
+    let payloads = vec![json!({
+        "candidates": [{
+            "content": {"parts": [{"text": "heard it"}]},
+            "finishReason": "STOP"
+        }],
+        "usageMetadata": {
+            "promptTokenCount": 900,
+            "candidatesTokenCount": 150,
+            "totalTokenCount": 1050,
+            "promptTokensDetails": [
+                {"modality": "TEXT", "tokenCount": 40},
+                {"modality": "AUDIO", "tokenCount": 600},
+                {"modality": "IMAGE", "tokenCount": 260}
+            ],
+            "candidatesTokensDetails": [
+                {"modality": "TEXT", "tokenCount": 30},
+                {"modality": "AUDIO", "tokenCount": 120}
+            ]
+        }
+    })];
+
+    let mut google_stream =
+        build_google_stream_part_stream(stream_inputs(payloads), vec![], false, "google", false);
+    let parts = collect_parts(&mut google_stream, "google").await;
+    let usage = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish { usage, .. } => Some(usage),
+            _ => None,
+        })
+        .expect("finish usage");
+
+    assert_eq!(usage.input_tokens, Some(900));
+    assert_eq!(usage.audio_input_tokens, Some(600));
+    assert_eq!(usage.image_input_tokens, Some(260));
+    assert_eq!(usage.audio_output_tokens, Some(120));
+}
+
+#[tokio::test]
+async fn shared_stream_core_accepts_float_and_string_token_counts() {
+    let payload = json!({
+        "candidates": [{
+            "content": {"parts": [{"text": "answer"}]},
+            "finishReason": "STOP"
+        }],
+        "usageMetadata": {
+            "promptTokenCount": "12",
+            "candidatesTokenCount": 7.0,
+            "totalTokenCount": "19.0",
+            "thoughtsTokenCount": 1.4
+        }
+    });
+
+    let mut google_stream = build_google_stream_part_stream(
+        stream_inputs(vec![payload]),
+        vec![],
+        false,
+        "google",
+        false,
+    );
+    let parts = collect_parts(&mut google_stream, "google").await;
+    let usage = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish { usage, .. } => Some(usage),
+            _ => None,
+        })
+        .expect("finish usage");
+
+    assert_eq!(usage.input_tokens, Some(12));
+    assert_eq!(usage.output_tokens, Some(7));
+    assert_eq!(usage.total_tokens, Some(19));
+    assert_eq!(usage.reasoning_tokens, Some(1));
+}
+
+#[tokio::test]
+async fn shared_stream_core_flushes_final_frame_without_terminator() {
+    let first = json!({
+        "candidates": [{"content": {"parts": [{"text": "hel"}]}}]
+    });
+    let last = json!({
+        "candidates": [{
+            "content": {"parts": [{"text": "lo"}]},
+            "finishReason": "STOP"
+        }],
+        "usageMetadata": {"promptTokenCount": 2, "candidatesTokenCount": 5}
+    });
+    let input = futures_util::stream::iter(vec![
+        Ok(Bytes::from(format!("data: {first}\n\n"))),
+        Ok(Bytes::from(format!("data: {last}"))),
+    ]);
+
+    let mut google_stream =
+        build_google_stream_part_stream(Box::pin(input), vec![], false, "google", false);
+    let parts = collect_parts(&mut google_stream, "google").await;
+
+    let text: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "hello");
+    assert!(matches!(
+        parts.last(),
+        Some(v2t::StreamPart::Finish { usage, finish_reason, .. })
+            if usage.output_tokens == Some(5) && matches!(finish_reason, v2t::FinishReason::Stop)
+    ));
+}
+
+#[tokio::test]
+async fn shared_stream_core_stops_on_first_tool_call_with_partial_usage() {
+    let payloads = vec![
+        json!({
+            "candidates": [{
+                "content": {"parts": [{
+                    "functionCall": {"name": "weather", "args": {"city": "SF"}}
+                }]}
+            }],
+            "usageMetadata": {"promptTokenCount": 9, "totalTokenCount": 9}
+        }),
+        json!({
+            "candidates": [{
+                "content": {"parts": [{"text": "trailing commentary"}]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 9,
+                "candidatesTokenCount": 20,
+                "totalTokenCount": 29
+            }
+        }),
+    ];
+
+    let mut google_stream =
+        build_google_stream_part_stream(stream_inputs(payloads), vec![], false, "google", true);
+    let parts = collect_parts(&mut google_stream, "google").await;
+
+    assert!(!parts
+        .iter()
+        .any(|part| matches!(part, v2t::StreamPart::TextDelta { .. })));
+    assert!(parts.iter().any(
+        |part| matches!(part, v2t::StreamPart::ToolCall(call) if call.tool_name == "weather")
+    ));
+    match parts.last() {
+        Some(v2t::StreamPart::Finish {
+            usage,
+            finish_reason,
+            ..
+        }) => {
+            assert!(matches!(finish_reason, v2t::FinishReason::ToolCalls));
+            assert_eq!(usage.input_tokens, Some(9));
+            assert_eq!(usage.total_tokens, Some(9));
+        }
+        other => panic!("expected finish, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn shared_stream_core_normalizes_reasoning_tool_raw_and_finish() {
     let payload = json!({
@@ -327,8 +649,13 @@ async fn shared_stream_core_normalizes_reasoning_tool_raw_and_finish() {
         }
     });
 
-    let mut google_stream =
-        build_google_stream_part_stream(stream_inputs(vec![payload]), vec![], true, "google");
+    let mut google_stream = build_google_stream_part_stream(
+        stream_inputs(vec![payload]),
+        vec![],
+        true,
+        "google",
+        false,
+    );
     let parts = collect_parts(&mut google_stream, "google").await;
 
     let [v2t::StreamPart::StreamStart { warnings }, v2t::StreamPart::Raw { .. }, v2t::StreamPart::ReasoningStart {
@@ -577,3 +904,249 @@ fn shared_generate_response_parser_preserves_google_vertex_fields() {
     assert_eq!(parsed.usage.cached_input_tokens, None);
     assert!(parsed.provider_metadata.is_none());
 }
+
+#[test]
+fn gemini_model_capabilities_follow_model_family() {
+    use crate::provider_google::shared::request_body::model_capabilities;
+
+    let flash_2_0 = model_capabilities("gemini-2.0-flash");
+    assert!(flash_2_0.vision && flash_2_0.audio_in && flash_2_0.tools && flash_2_0.citations);
+    assert!(!flash_2_0.reasoning && !flash_2_0.audio_out);
+    assert!(flash_2_0.parallel_tool_calls);
+
+    assert!(model_capabilities("models/gemini-2.5-pro").reasoning);
+    assert!(model_capabilities("gemini-2.0-flash-thinking-exp").reasoning);
+    assert!(model_capabilities("gemini-2.5-flash-preview-tts").audio_out);
+
+    let gemma = model_capabilities("gemma-3-27b-it");
+    assert!(gemma.vision && !gemma.tools && !gemma.structured_output);
+}
+
+#[test]
+fn developer_messages_join_system_instruction() {
+    let prompt = vec![
+        v2t::PromptMessage::system("be kind"),
+        v2t::PromptMessage::developer("be brief"),
+        v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hello".to_string(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        },
+    ];
+
+    let google_prompt = convert_google_prompt(&prompt, false).expect("google prompt");
+    let texts: Vec<String> = google_prompt
+        .system_instruction
+        .expect("system instruction")
+        .parts
+        .into_iter()
+        .map(|part| part.text)
+        .collect();
+    assert_eq!(texts, vec!["be kind", "be brief"]);
+    assert_eq!(google_prompt.contents.len(), 1);
+}
+
+#[test]
+fn safety_settings_are_validated_and_serialized() {
+    let build = |settings: serde_json::Value| {
+        let mut options = v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hi".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }]);
+        options.provider_options = HashMap::from([(
+            "google".to_string(),
+            HashMap::from([("safetySettings".to_string(), settings)]),
+        )]);
+        build_google_request_body(
+            GoogleRequestBodyBuildConfig {
+                scope_names: GOOGLE_SCOPES,
+                raw_provider_option_keys: GOOGLE_SCOPES,
+                model_id: "gemini-2.5-flash",
+                is_gemma: false,
+                trace_prefix: "test",
+                include_thoughts_warning: None,
+            },
+            &options,
+        )
+    };
+
+    let settings = json!([
+        {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_ONLY_HIGH"},
+        {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "threshold": "BLOCK_NONE"},
+        {"category": "HARM_CATEGORY_IMAGE_SEXUALLY_EXPLICIT", "threshold": "BLOCK_LOW_AND_ABOVE"}
+    ]);
+    let (body, _) = build(settings.clone()).expect("request body");
+    assert_eq!(body["safetySettings"], settings);
+
+    let invalid = build(json!([
+        {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_SOMETIMES"}
+    ]));
+    assert!(matches!(
+        invalid,
+        Err(SdkError::InvalidArgument { message }) if message.contains("BLOCK_SOMETIMES")
+    ));
+}
+
+fn thinking_payloads() -> Vec<serde_json::Value> {
+    vec![
+        json!({
+            "candidates": [{
+                "content": {"parts": [{"text": "Weighing options.", "thought": true}]}
+            }]
+        }),
+        json!({
+            "candidates": [{
+                "content": {"parts": [{"text": "Answer.", "thoughtSignature": "sig-1"}]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 5,
+                "candidatesTokenCount": 2,
+                "thoughtsTokenCount": 40,
+                "totalTokenCount": 47
+            }
+        }),
+    ]
+}
+
+#[tokio::test]
+async fn thinking_stream_maps_thoughts_to_reasoning_parts() {
+    let mut stream = build_google_stream_part_stream(
+        stream_inputs(thinking_payloads()),
+        vec![],
+        false,
+        "google",
+        false,
+    );
+    let parts = collect_parts(&mut stream, "google").await;
+    let kinds: Vec<&str> = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::ReasoningStart { .. } => Some("reasoning-start"),
+            v2t::StreamPart::ReasoningDelta { .. } => Some("reasoning-delta"),
+            v2t::StreamPart::ReasoningEnd { .. } => Some("reasoning-end"),
+            v2t::StreamPart::TextDelta { .. } => Some("text"),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "reasoning-start",
+            "reasoning-delta",
+            "reasoning-end",
+            "text"
+        ]
+    );
+    let usage = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish { usage, .. } => Some(usage),
+            _ => None,
+        })
+        .expect("finish usage");
+    assert_eq!(usage.reasoning_tokens, Some(40));
+
+    let mut stripped = strip_thought_parts(build_google_stream_part_stream(
+        stream_inputs(thinking_payloads()),
+        vec![],
+        false,
+        "google",
+        false,
+    ));
+    let parts = collect_parts(&mut stripped, "google").await;
+    assert!(!parts.iter().any(|part| matches!(
+        part,
+        v2t::StreamPart::ReasoningStart { .. }
+            | v2t::StreamPart::ReasoningDelta { .. }
+            | v2t::StreamPart::ReasoningEnd { .. }
+    )));
+    assert!(parts.iter().any(
+        |part| matches!(part, v2t::StreamPart::TextDelta { delta, .. } if delta == "Answer.")
+    ));
+}
+
+#[test]
+fn thinking_config_serializes_into_generation_config() {
+    let build = |thinking_config: serde_json::Value| {
+        let mut options = v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hi".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }]);
+        options.provider_options = HashMap::from([(
+            "google".to_string(),
+            HashMap::from([("thinkingConfig".to_string(), thinking_config)]),
+        )]);
+        build_google_request_body(
+            GoogleRequestBodyBuildConfig {
+                scope_names: GOOGLE_SCOPES,
+                raw_provider_option_keys: GOOGLE_SCOPES,
+                model_id: "gemini-2.5-flash",
+                is_gemma: false,
+                trace_prefix: "test",
+                include_thoughts_warning: None,
+            },
+            &options,
+        )
+        .expect("request body")
+        .0
+    };
+
+    let body = build(json!({"thinkingBudget": 1024, "includeThoughts": true}));
+    assert_eq!(
+        body["generationConfig"]["thinkingConfig"],
+        json!({"thinkingBudget": 1024, "includeThoughts": true})
+    );
+    assert!(!thoughts_disabled(&body));
+
+    let body = build(json!({"includeThoughts": false}));
+    assert!(thoughts_disabled(&body));
+
+    let parsed = parse_google_gen_ai_generate_response(&json!({
+        "candidates": [{
+            "content": {"parts": [
+                {"text": "Weighing options.", "thought": true},
+                {"text": "Answer."}
+            ]},
+            "finishReason": "STOP"
+        }]
+    }))
+    .strip_thoughts();
+    assert!(matches!(
+        parsed.content.as_slice(),
+        [v2t::Content::Text { text, .. }] if text == "Answer."
+    ));
+}
+
+#[test]
+fn audio_file_parts_become_inline_data() {
+    let prompt = vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::File {
+            filename: None,
+            data: v2t::DataContent::Bytes {
+                bytes: vec![0, 1, 2, 3],
+            },
+            media_type: "audio/wav".into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }];
+
+    let converted = convert_google_prompt(&prompt, false).expect("prompt");
+
+    assert_eq!(
+        serde_json::to_value(&converted.contents).expect("contents"),
+        json!([{
+            "role": "user",
+            "parts": [{"inlineData": {"mimeType": "audio/wav", "data": "AAECAw=="}}]
+        }])
+    );
+}
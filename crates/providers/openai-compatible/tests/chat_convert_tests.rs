@@ -1,4 +1,7 @@
-use crate::providers::openai_compatible::chat::convert::convert_to_openai_compatible_chat_messages;
+use crate::providers::openai_compatible::chat::convert::{
+    convert_to_openai_chat_messages, convert_to_openai_compatible_chat_messages,
+    unsupported_file_warnings,
+};
 use crate::types::v2 as v2t;
 use serde_json::json;
 use std::collections::HashMap;
@@ -160,3 +163,90 @@ fn collapsed_user_text_merges_message_and_part_metadata() {
         })]
     );
 }
+
+#[test]
+fn developer_messages_use_system_role_for_compatible_endpoints() {
+    let prompt = vec![
+        v2t::PromptMessage::system("be kind"),
+        v2t::PromptMessage::developer("be brief"),
+    ];
+
+    assert_eq!(
+        convert_to_openai_compatible_chat_messages("test-provider", &prompt),
+        vec![
+            json!({"role":"system","content":"be kind"}),
+            json!({"role":"system","content":"be brief"}),
+        ]
+    );
+    assert_eq!(
+        convert_to_openai_chat_messages("openai", &prompt),
+        vec![
+            json!({"role":"system","content":"be kind"}),
+            json!({"role":"developer","content":"be brief"}),
+        ]
+    );
+}
+
+fn audio_part(media_type: &str, data: v2t::DataContent) -> v2t::UserPart {
+    v2t::UserPart::File {
+        filename: None,
+        data,
+        media_type: media_type.into(),
+        provider_options: None,
+    }
+}
+
+#[test]
+fn user_audio_becomes_input_audio_and_unsupported_files_warn() {
+    let prompt = vec![v2t::PromptMessage::User {
+        content: vec![
+            audio_part(
+                "audio/wav",
+                v2t::DataContent::Bytes {
+                    bytes: vec![0, 1, 2, 3],
+                },
+            ),
+            audio_part(
+                "audio/mpeg",
+                v2t::DataContent::Base64 {
+                    base64: "SUQz".into(),
+                },
+            ),
+            audio_part(
+                "audio/wav",
+                v2t::DataContent::Url {
+                    url: "https://example.com/clip.wav".into(),
+                },
+            ),
+            audio_part(
+                "audio/flac",
+                v2t::DataContent::Base64 {
+                    base64: "ZkxhQw==".into(),
+                },
+            ),
+        ],
+        provider_options: None,
+    }];
+
+    assert_eq!(
+        convert_to_openai_chat_messages("openai", &prompt),
+        vec![json!({
+            "role":"user",
+            "content": [
+                {"type":"input_audio","input_audio":{"data":"AAECAw==","format":"wav"}},
+                {"type":"input_audio","input_audio":{"data":"SUQz","format":"mp3"}}
+            ]
+        })]
+    );
+
+    let warnings = unsupported_file_warnings(&prompt);
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(
+        &warnings[0],
+        v2t::CallWarning::Other { message } if message.contains("audio/wav audio given by URL")
+    ));
+    assert!(matches!(
+        &warnings[1],
+        v2t::CallWarning::Other { message } if message.contains("audio/flac")
+    ));
+}
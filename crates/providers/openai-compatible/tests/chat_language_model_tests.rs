@@ -167,6 +167,9 @@ fn build_model_for_scope(
         query_params: vec![],
         supports_structured_outputs,
         default_options: None,
+        field_aliases: HashMap::new(),
+        done_sentinel: None,
+        tool_limits: Default::default(),
     };
     (
         OpenAICompatibleChatLanguageModel::new("grok-beta", cfg),
@@ -213,6 +216,59 @@ async fn builds_request_body_with_user_and_messages() {
     );
 }
 
+#[tokio::test]
+async fn field_aliases_rename_top_level_body_keys() {
+    let transport = TestTransport::new(vec![]);
+    let cfg = OpenAICompatibleChatConfig {
+        provider_scope_name: "test-provider".into(),
+        base_url: "https://my.api.com/v1".into(),
+        headers: vec![],
+        http: transport.clone(),
+        transport_cfg: TransportConfig::default(),
+        include_usage: false,
+        supported_urls: HashMap::new(),
+        query_params: vec![],
+        supports_structured_outputs: false,
+        default_options: None,
+        field_aliases: HashMap::from([
+            ("max_tokens".into(), "max_new_tokens".into()),
+            ("frequency_penalty".into(), "repetition_penalty".into()),
+            ("content".into(), "text".into()),
+        ]),
+        done_sentinel: None,
+        tool_limits: Default::default(),
+    };
+    let model = OpenAICompatibleChatLanguageModel::new("grok-beta", cfg);
+
+    let _ = model
+        .do_stream(v2t::CallOptions {
+            prompt: vec![v2t::PromptMessage::User {
+                content: vec![v2t::UserPart::Text {
+                    text: "Hello".into(),
+                    provider_options: None,
+                }],
+                provider_options: None,
+            }],
+            max_output_tokens: Some(64),
+            frequency_penalty: Some(1.5),
+            ..Default::default()
+        })
+        .await
+        .expect("stream response");
+
+    let body = transport.last_body().expect("sent body");
+    let map = body.as_object().expect("object body");
+    assert!(!map.contains_key("max_tokens"));
+    assert!(!map.contains_key("frequency_penalty"));
+    assert_eq!(body.get("max_new_tokens"), Some(&json!(64)));
+    assert_eq!(body.get("repetition_penalty"), Some(&json!(1.5)));
+    // Nested keys keep their names.
+    assert_eq!(
+        body.get("messages"),
+        Some(&json!([{ "role": "user", "content": "Hello"}]))
+    );
+}
+
 #[tokio::test]
 async fn response_format_json_schema_when_structured_outputs_enabled() {
     let chunks = vec![
@@ -356,6 +412,76 @@ async fn includes_tools_and_tool_choice_in_request_body() {
     );
 }
 
+#[tokio::test]
+async fn tool_limits_come_from_the_config_and_default_to_none() {
+    let tools: Vec<v2t::Tool> = (0..129)
+        .map(|i| {
+            v2t::Tool::Function(v2t::FunctionTool {
+                r#type: v2t::FunctionToolType::Function,
+                name: format!("tool_{i}"),
+                description: None,
+                input_schema: json!({"type":"object"}),
+                strict: None,
+                provider_options: None,
+            })
+        })
+        .collect();
+    let options = v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        tools,
+        strict_tool_limits: true,
+        ..Default::default()
+    };
+
+    let (model, transport) = build_model(vec![], false);
+    model
+        .do_stream(options.clone())
+        .await
+        .expect("no limit without configuration");
+    assert_eq!(
+        transport
+            .last_body()
+            .and_then(|body| body["tools"].as_array().map(Vec::len)),
+        Some(129)
+    );
+
+    let transport = TestTransport::new(vec![]);
+    let cfg = OpenAICompatibleChatConfig {
+        provider_scope_name: "test-provider".into(),
+        base_url: "https://my.api.com/v1".into(),
+        headers: vec![],
+        http: transport.clone(),
+        transport_cfg: TransportConfig::default(),
+        include_usage: false,
+        supported_urls: HashMap::new(),
+        query_params: vec![],
+        supports_structured_outputs: false,
+        default_options: None,
+        field_aliases: HashMap::new(),
+        done_sentinel: None,
+        tool_limits: v2t::ToolLimits {
+            max_tools: Some(128),
+            max_schema_bytes: None,
+        },
+    };
+    let model = OpenAICompatibleChatLanguageModel::new("grok-beta", cfg);
+    let err = match model.do_stream(options).await {
+        Ok(_) => panic!("configured limit should reject the call"),
+        Err(err) => err,
+    };
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message.contains("max_tools (128)")
+    ));
+    assert!(transport.last_body().is_none());
+}
+
 #[tokio::test]
 async fn merges_openai_compatible_and_provider_specific_options() {
     let (model, transport) = build_model(vec![], false);
@@ -477,6 +603,9 @@ async fn newly_routed_scopes_map_transport_errors_identically() {
             query_params: vec![],
             supports_structured_outputs: false,
             default_options: None,
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
+            tool_limits: Default::default(),
         };
         let model = OpenAICompatibleChatLanguageModel::new("grok-beta", cfg);
         let result = model
@@ -508,3 +637,92 @@ async fn newly_routed_scopes_map_transport_errors_identically() {
         }
     }
 }
+
+#[tokio::test]
+async fn estimates_usage_when_stream_reports_none() {
+    let chunks = vec![
+        json_chunk(json!({
+            "choices":[{"delta":{"content":"Hello there!"},"finish_reason":null}]
+        })),
+        json_chunk(json!({
+            "choices":[{"delta":{},"finish_reason":"stop"}]
+        })),
+        Bytes::from_static(b"data: [DONE]\n\n"),
+    ];
+    let (model, _transport) = build_model(chunks, false);
+    let options = |estimate_usage_if_missing| v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        estimate_usage_if_missing,
+        ..Default::default()
+    };
+
+    let reported = model.do_generate(options(false)).await.expect("generate");
+    assert!(reported.usage.input_tokens.is_none());
+    assert!(reported.provider_metadata.is_none());
+
+    let estimated = model.do_generate(options(true)).await.expect("generate");
+    assert_eq!(estimated.text(), "Hello there!");
+    assert_eq!(estimated.usage.input_tokens, Some(2));
+    assert_eq!(estimated.usage.output_tokens, Some(3));
+    assert_eq!(estimated.usage.total_tokens, Some(5));
+    let metadata = estimated.provider_metadata.expect("provider metadata");
+    assert_eq!(metadata["usage"]["estimated"], json!(true));
+}
+
+#[tokio::test]
+async fn include_usage_option_maps_terminal_usage_chunk() {
+    let chunks = vec![
+        json_chunk(json!({
+            "choices":[{"delta":{"content":"Hi"},"finish_reason":null}],
+            "usage": null
+        })),
+        json_chunk(json!({
+            "choices":[{"delta":{},"finish_reason":"stop"}],
+            "usage": null
+        })),
+        json_chunk(json!({
+            "choices":[],
+            "usage":{"prompt_tokens":7,"completion_tokens":2,"total_tokens":9}
+        })),
+        Bytes::from_static(b"data: [DONE]\n\n"),
+    ];
+    let (model, transport) = build_model(chunks, false);
+    let options = |include_usage: Option<bool>| v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        provider_options: include_usage
+            .map(|value| {
+                v2t::ProviderOptions::from([(
+                    "test-provider".into(),
+                    HashMap::from([("includeUsage".into(), json!(value))]),
+                )])
+            })
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let reported = model.do_generate(options(None)).await.expect("generate");
+    assert_eq!(reported.usage.input_tokens, Some(7));
+    assert_eq!(reported.usage.output_tokens, Some(2));
+    assert_eq!(reported.usage.total_tokens, Some(9));
+
+    let skipped = model
+        .do_generate(options(Some(false)))
+        .await
+        .expect("generate");
+    let body = transport.last_body().expect("sent body");
+    assert!(body.get("stream_options").is_none());
+    assert!(body.get("includeUsage").is_none());
+    assert!(skipped.usage.input_tokens.is_none());
+}
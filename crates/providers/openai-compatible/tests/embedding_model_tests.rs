@@ -16,7 +16,9 @@ use futures_util::stream;
 use serde_json::json;
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 const DEFAULT_MAX_EMBEDDINGS_PER_CALL: usize = 2048;
 
@@ -202,6 +204,49 @@ async fn extracts_embeddings_usage_and_headers() {
     );
 }
 
+#[tokio::test]
+async fn deduplicates_values_and_restores_positions() {
+    let response = json!({
+        "object": "list",
+        "data": [
+            {"object":"embedding","index":0,"embedding":[0.1,0.2]},
+            {"object":"embedding","index":1,"embedding":[0.3,0.4]}
+        ],
+        "model":"text-embedding-3-large",
+        "usage": {"prompt_tokens": 6, "total_tokens": 6}
+    });
+    let transport = TestTransport::new(response);
+    let model = build_model(transport.clone());
+
+    let result = model
+        .do_embed(EmbedOptions {
+            deduplicate: true,
+            ..EmbedOptions::new(vec![
+                "footer".into(),
+                "body".into(),
+                "footer".into(),
+                "footer".into(),
+            ])
+        })
+        .await
+        .expect("embed response");
+
+    assert_eq!(
+        transport.last_body().unwrap()["input"],
+        json!(["footer", "body"])
+    );
+    assert_eq!(
+        result.embeddings,
+        vec![
+            vec![0.1, 0.2],
+            vec![0.3, 0.4],
+            vec![0.1, 0.2],
+            vec![0.1, 0.2]
+        ]
+    );
+    assert_eq!(result.usage.expect("usage").tokens, Some(6));
+}
+
 #[tokio::test]
 async fn passes_model_and_values_in_request_body() {
     let response = json!({
@@ -271,6 +316,7 @@ async fn includes_dimensions_from_provider_options() {
             values: vec!["sunny day at the beach".into()],
             headers: HashMap::new(),
             provider_options,
+            ..EmbedOptions::default()
         })
         .await
         .expect("embed response");
@@ -287,6 +333,42 @@ async fn includes_dimensions_from_provider_options() {
     );
 }
 
+#[tokio::test]
+async fn typed_dimensions_and_normalization_apply() {
+    let response = json!({
+        "object": "list",
+        "data": [
+            {"object":"embedding","index":0,"embedding":[3.0,4.0]},
+            {"object":"embedding","index":1,"embedding":[0.0,0.0]}
+        ],
+        "model":"text-embedding-3-large"
+    });
+    let transport = TestTransport::new(response);
+    let model = build_model(transport.clone());
+
+    let mut provider_options = v2t::ProviderOptions::new();
+    provider_options.insert(
+        "test-provider".into(),
+        HashMap::from([("dimensions".into(), json!(64))]),
+    );
+    let result = model
+        .do_embed(EmbedOptions {
+            dimensions: Some(2),
+            normalize: true,
+            provider_options,
+            ..EmbedOptions::new(vec!["a".into(), "b".into()])
+        })
+        .await
+        .expect("embed response");
+
+    assert_eq!(transport.last_body().unwrap()["dimensions"], json!(2));
+    let unit = &result.embeddings[0];
+    assert!((unit[0] - 0.6).abs() < 1e-6 && (unit[1] - 0.8).abs() < 1e-6);
+    let length: f32 = unit.iter().map(|x| x * x).sum::<f32>().sqrt();
+    assert!((length - 1.0).abs() < 1e-6);
+    assert_eq!(result.embeddings[1], vec![0.0, 0.0]);
+}
+
 #[tokio::test]
 async fn merges_provider_and_request_headers() {
     let response = json!({
@@ -324,6 +406,7 @@ async fn merges_provider_and_request_headers() {
                 "request-header-value".into(),
             )]),
             provider_options: HashMap::new(),
+            ..EmbedOptions::default()
         })
         .await
         .expect("embed response");
@@ -350,3 +433,128 @@ async fn merges_provider_and_request_headers() {
         ])
     );
 }
+
+/// Answers each request with one single-value embedding per input (the
+/// input's number) and tracks how many requests overlap.
+#[derive(Clone, Default)]
+struct BatchTransport {
+    bodies: Arc<Mutex<Vec<serde_json::Value>>>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl HttpTransport for BatchTransport {
+    type StreamResponse = TestStreamResponse;
+
+    fn into_stream(
+        resp: Self::StreamResponse,
+    ) -> (
+        Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>,
+        Vec<(String, String)>,
+    ) {
+        (Box::pin(stream::iter(resp.chunks)), resp.headers.into())
+    }
+
+    async fn post_json_stream(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &serde_json::Value,
+        _cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        Err(TransportError::Other("post_json_stream unused".into()))
+    }
+
+    async fn post_json(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        body: &serde_json::Value,
+        _cfg: &TransportConfig,
+    ) -> Result<(serde_json::Value, Vec<(String, String)>), TransportError> {
+        self.bodies.lock().unwrap().push(body.clone());
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let inputs = body["input"].as_array().cloned().unwrap_or_default();
+        let data: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let value: f32 = input.as_str().unwrap().parse().unwrap();
+                json!({"object": "embedding", "index": index, "embedding": [value]})
+            })
+            .collect();
+        Ok((
+            json!({
+                "object": "list",
+                "data": data,
+                "usage": {"prompt_tokens": inputs.len() * 10}
+            }),
+            vec![],
+        ))
+    }
+}
+
+async fn embed_in_batches(parallel: bool) -> (BatchTransport, crate::core::EmbedResponse) {
+    let transport = BatchTransport::default();
+    let model = OpenAICompatibleEmbeddingModel::new(
+        "text-embedding-3-large",
+        OpenAICompatibleEmbeddingConfig {
+            provider_scope_name: "test-provider".into(),
+            base_url: "https://my.api.com/v1".into(),
+            headers: vec![],
+            http: transport.clone(),
+            transport_cfg: TransportConfig::default(),
+            query_params: vec![],
+            max_embeddings_per_call: Some(2),
+            supports_parallel_calls: parallel,
+            default_options: None,
+        },
+    );
+    let values = (0..5).map(|i| i.to_string()).collect();
+    let response = model
+        .do_embed(EmbedOptions::new(values))
+        .await
+        .expect("embed response");
+    (transport, response)
+}
+
+#[tokio::test]
+async fn splits_inputs_past_max_embeddings_per_call() {
+    for parallel in [true, false] {
+        let (transport, response) = embed_in_batches(parallel).await;
+
+        let inputs: Vec<_> = transport
+            .bodies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|body| body["input"].clone())
+            .collect();
+        assert_eq!(inputs.len(), 3);
+        assert!(inputs.contains(&json!(["0", "1"])));
+        assert!(inputs.contains(&json!(["2", "3"])));
+        assert!(inputs.contains(&json!(["4"])));
+        assert_eq!(
+            response.embeddings,
+            vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0], vec![4.0]]
+        );
+        assert_eq!(response.usage.and_then(|usage| usage.tokens), Some(50));
+        assert_eq!(
+            response
+                .request_body
+                .and_then(|body| body.as_array().map(Vec::len)),
+            Some(3)
+        );
+        let max_in_flight = transport.max_in_flight.load(Ordering::SeqCst);
+        if parallel {
+            assert_eq!(max_in_flight, 3);
+        } else {
+            assert_eq!(max_in_flight, 1);
+        }
+    }
+}
@@ -3,11 +3,11 @@
 use crate::core::error::TransportError;
 use crate::core::json::without_null_fields;
 use crate::core::transport::{HttpTransport, MultipartForm, MultipartValue, TransportConfig};
-use crate::core::ImageModel;
+use crate::core::{ImageModel, SdkError};
 use crate::providers::openai_compatible::image::image_model::{
     OpenAICompatibleImageConfig, OpenAICompatibleImageModel,
 };
-use crate::types::image::{ImageData, ImageFile, ImageOptions, ImageWarning};
+use crate::types::image::{ImageData, ImageFile, ImageOptions, ImageResponseFormat, ImageWarning};
 use crate::types::v2 as v2t;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -406,6 +406,228 @@ async fn uses_edit_endpoint_with_files_and_mask() {
     assert_eq!(mask_fields.len(), 1);
 }
 
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend(width.to_be_bytes());
+    bytes.extend(height.to_be_bytes());
+    bytes.extend([8, 6, 0, 0, 0]);
+    bytes
+}
+
+fn png_file(width: u32, height: u32) -> ImageFile {
+    ImageFile::File {
+        media_type: "image/png".into(),
+        data: ImageData::Bytes {
+            bytes: png_bytes(width, height),
+        },
+    }
+}
+
+fn form_text(form: &MultipartForm, name: &str) -> Option<String> {
+    form.fields
+        .iter()
+        .find_map(|field| match (&field.value, field.name == name) {
+            (MultipartValue::Text(value), true) => Some(value.clone()),
+            _ => None,
+        })
+}
+
+fn form_bytes(form: &MultipartForm, name: &str) -> Option<Vec<u8>> {
+    form.fields
+        .iter()
+        .find_map(|field| match (&field.value, field.name == name) {
+            (MultipartValue::Bytes { data, .. }, true) => Some(data.clone()),
+            _ => None,
+        })
+}
+
+#[tokio::test]
+async fn image_edit_posts_image_mask_and_prompt() {
+    let transport = TestTransport::new(json!({ "data": [] }))
+        .with_multipart_response(json!({ "data": [{"b64_json": "ZWRpdGVk"}] }));
+    let model = build_model(transport.clone());
+
+    let result = model
+        .do_image_edit(
+            png_file(256, 256),
+            Some(png_file(256, 256)),
+            ImageOptions::new(Some("Add a hat".into())),
+        )
+        .await
+        .expect("edit response");
+    assert_eq!(result.images, vec![ImageData::Base64("ZWRpdGVk".into())]);
+    assert_eq!(
+        transport.last_url().unwrap(),
+        "https://api.example.com/v1/images/edits"
+    );
+    let form = transport.last_form().expect("multipart form");
+    assert_eq!(form_text(&form, "prompt").as_deref(), Some("Add a hat"));
+    assert_eq!(form_bytes(&form, "image"), Some(png_bytes(256, 256)));
+    assert_eq!(form_bytes(&form, "mask"), Some(png_bytes(256, 256)));
+}
+
+#[tokio::test]
+async fn image_edit_rejects_non_png_and_mismatched_mask() {
+    let transport = TestTransport::new(json!({ "data": [] }));
+    let model = build_model(transport.clone());
+
+    let jpeg = ImageFile::File {
+        media_type: "image/jpeg".into(),
+        data: ImageData::Bytes {
+            bytes: vec![0xff, 0xd8, 0xff],
+        },
+    };
+    let err = model
+        .do_image_edit(jpeg, None, ImageOptions::new(Some("Add a hat".into())))
+        .await
+        .expect_err("jpeg source");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message == "image must be a PNG image, got image/jpeg"
+    ));
+
+    let err = model
+        .do_image_edit(
+            png_file(256, 256),
+            Some(png_file(512, 512)),
+            ImageOptions::new(Some("Add a hat".into())),
+        )
+        .await
+        .expect_err("mask size mismatch");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message.contains("mask is 512x512")
+    ));
+    assert!(transport.last_form().is_none());
+}
+
+#[tokio::test]
+async fn image_variation_posts_image_without_prompt() {
+    let transport = TestTransport::new(json!({ "data": [] }))
+        .with_multipart_response(json!({ "data": [{"b64_json": "djE="}, {"b64_json": "djI="}] }));
+    let model = build_model(transport.clone());
+
+    let result = model
+        .do_image_variation(
+            png_file(64, 64),
+            ImageOptions {
+                prompt: Some("ignored".into()),
+                n: 2,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("variation response");
+    assert_eq!(result.images.len(), 2);
+    assert!(matches!(
+        &result.warnings[..],
+        [ImageWarning::Unsupported { feature, .. }] if feature == "prompt"
+    ));
+    assert_eq!(
+        transport.last_url().unwrap(),
+        "https://api.example.com/v1/images/variations"
+    );
+    let form = transport.last_form().expect("multipart form");
+    assert_eq!(form_text(&form, "prompt"), None);
+    assert_eq!(form_text(&form, "n").as_deref(), Some("2"));
+    assert_eq!(form_bytes(&form, "image"), Some(png_bytes(64, 64)));
+    assert_eq!(form_bytes(&form, "mask"), None);
+}
+
+#[tokio::test]
+async fn returns_two_images_in_either_response_format() {
+    let transport = TestTransport::new(json!({
+        "data": [{"b64_json": "aW1nMQ=="}, {"b64_json": "aW1nMg=="}]
+    }));
+    let model = build_model(transport.clone());
+    let result = model
+        .do_generate(ImageOptions {
+            prompt: Some("Two cats".into()),
+            n: 2,
+            response_format: Some(ImageResponseFormat::B64Json),
+            ..Default::default()
+        })
+        .await
+        .expect("generate response");
+    let body = transport.last_body().expect("sent body");
+    assert_eq!(body["n"], json!(2));
+    assert_eq!(body["response_format"], json!("b64_json"));
+    assert_eq!(
+        result.images,
+        vec![
+            ImageData::Base64("aW1nMQ==".into()),
+            ImageData::Base64("aW1nMg==".into()),
+        ]
+    );
+
+    let transport = TestTransport::new(json!({
+        "data": [
+            {"url": "https://images.example.com/1.png"},
+            {"url": "https://images.example.com/2.png"}
+        ]
+    }))
+    .with_download_payload(
+        vec![0x89, 0x50],
+        vec![("content-type".into(), "image/png".into())],
+    );
+    let model = build_model(transport.clone());
+    let result = model
+        .do_generate(ImageOptions {
+            prompt: Some("Two cats".into()),
+            n: 2,
+            response_format: Some(ImageResponseFormat::Url),
+            ..Default::default()
+        })
+        .await
+        .expect("generate response");
+    assert_eq!(
+        transport.last_body().expect("sent body")["response_format"],
+        json!("url")
+    );
+    assert_eq!(
+        result.images,
+        vec![
+            ImageData::Bytes {
+                bytes: vec![0x89, 0x50]
+            };
+            2
+        ]
+    );
+    assert!(result.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn clamps_image_count_to_per_call_limit() {
+    let transport = TestTransport::new(json!({ "data": [{"b64_json": "aW1n"}] }));
+    let model = build_model(transport.clone());
+
+    let result = model
+        .do_generate(ImageOptions {
+            prompt: Some("Many cats".into()),
+            n: 12,
+            ..Default::default()
+        })
+        .await
+        .expect("generate response");
+    assert_eq!(transport.last_body().expect("sent body")["n"], json!(10));
+    assert_eq!(
+        result.warnings,
+        vec![ImageWarning::Compatibility {
+            feature: "n".into(),
+            details: Some("12 exceeds the limit of 10 images per call; clamped to 10".into()),
+        }]
+    );
+
+    let err = model
+        .do_generate(ImageOptions {
+            n: 0,
+            ..Default::default()
+        })
+        .await
+        .expect_err("zero images");
+    assert!(matches!(err, SdkError::InvalidArgument { .. }));
+}
+
 #[tokio::test]
 async fn response_metadata_has_timestamp_and_model_id() {
     let response = json!({
@@ -435,3 +657,161 @@ async fn response_metadata_has_timestamp_and_model_id() {
         )]))
     );
 }
+
+/// Answers each generation call with `n` images labelled by call order;
+/// earlier calls respond last.
+#[derive(Clone, Default)]
+struct BatchTransport {
+    calls: Arc<Mutex<Vec<u64>>>,
+    in_flight: Arc<Mutex<(usize, usize)>>,
+    fail_calls: Vec<usize>,
+}
+
+#[async_trait]
+impl HttpTransport for BatchTransport {
+    type StreamResponse = TestStreamResponse;
+
+    fn into_stream(
+        resp: Self::StreamResponse,
+    ) -> (
+        Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>,
+        Vec<(String, String)>,
+    ) {
+        (Box::pin(stream::iter(resp.chunks)), resp.headers.into())
+    }
+
+    async fn post_json_stream(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &serde_json::Value,
+        _cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        Err(TransportError::Other("post_json_stream unused".into()))
+    }
+
+    async fn post_json(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        body: &serde_json::Value,
+        _cfg: &TransportConfig,
+    ) -> Result<(serde_json::Value, Vec<(String, String)>), TransportError> {
+        let n = body["n"].as_u64().expect("n");
+        let call = {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(n);
+            calls.len() - 1
+        };
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.0 += 1;
+            in_flight.1 = in_flight.1.max(in_flight.0);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(30 - 10 * call as u64)).await;
+        self.in_flight.lock().unwrap().0 -= 1;
+
+        if self.fail_calls.contains(&call) {
+            return Err(TransportError::Network("connection reset".into()));
+        }
+        let data: Vec<_> = (0..n)
+            .map(|i| json!({ "b64_json": format!("call{call}-{i}") }))
+            .collect();
+        Ok((
+            json!({ "data": data, "usage": { "inputTokens": 3, "outputTokens": n * 10 } }),
+            vec![],
+        ))
+    }
+}
+
+fn build_batch_model(transport: BatchTransport) -> OpenAICompatibleImageModel<BatchTransport> {
+    let cfg = OpenAICompatibleImageConfig {
+        provider_scope_name: "test-provider".into(),
+        base_url: "https://api.example.com/v1".into(),
+        headers: vec![],
+        http: transport,
+        transport_cfg: TransportConfig::default(),
+        query_params: vec![],
+        default_options: None,
+    };
+    OpenAICompatibleImageModel::new("dall-e-3", cfg)
+}
+
+fn split_options() -> ImageOptions {
+    ImageOptions {
+        prompt: Some("A lighthouse".into()),
+        n: 5,
+        max_images_per_call: Some(2),
+        max_parallel_calls: Some(2),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn splits_calls_over_max_images_per_call_and_keeps_order() {
+    let transport = BatchTransport::default();
+    let model = build_batch_model(transport.clone());
+
+    let result = model
+        .do_generate(split_options())
+        .await
+        .expect("generate response");
+
+    assert_eq!(*transport.calls.lock().unwrap(), vec![2, 2, 1]);
+    assert_eq!(transport.in_flight.lock().unwrap().1, 2);
+    assert_eq!(
+        result.images,
+        ["call0-0", "call0-1", "call1-0", "call1-1", "call2-0"]
+            .map(|image| ImageData::Base64(image.into()))
+    );
+    let usage = result.usage.expect("usage");
+    assert_eq!(usage.input_tokens, Some(9));
+    assert_eq!(usage.output_tokens, Some(50));
+    assert!(result.warnings.is_empty());
+    assert_eq!(
+        result.request_body.expect("request bodies")[2]["n"],
+        json!(1)
+    );
+}
+
+#[tokio::test]
+async fn reports_failed_image_calls_as_warnings() {
+    let transport = BatchTransport {
+        fail_calls: vec![1],
+        ..Default::default()
+    };
+    let model = build_batch_model(transport);
+
+    let result = model
+        .do_generate(split_options())
+        .await
+        .expect("partial response");
+    assert_eq!(
+        result.images,
+        ["call0-0", "call0-1", "call2-0"].map(|image| ImageData::Base64(image.into()))
+    );
+    assert_eq!(
+        result.warnings,
+        vec![ImageWarning::Other {
+            message: "images 3-4 of 5 failed: transport error: network: connection reset".into(),
+        }]
+    );
+
+    let transport = BatchTransport {
+        fail_calls: vec![0, 1],
+        ..Default::default()
+    };
+    let model = build_batch_model(transport);
+    let err = model
+        .do_generate(ImageOptions {
+            n: 2,
+            max_images_per_call: Some(1),
+            ..split_options()
+        })
+        .await
+        .expect_err("every call fails");
+    assert!(matches!(
+        err,
+        SdkError::Transport(TransportError::Network(_))
+    ));
+}
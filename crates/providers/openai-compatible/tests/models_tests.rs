@@ -0,0 +1,159 @@
+use crate::core::error::TransportError;
+use crate::core::transport::{HttpMethod, HttpTransport, TransportConfig, TransportStream};
+use crate::core::SdkError;
+use crate::providers::openai_compatible::list_models;
+use crate::types::catalog::ModelLimits;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+type Request = (HttpMethod, String, Vec<(String, String)>, Option<Value>);
+
+#[derive(Clone)]
+struct ModelsTransport {
+    response: Value,
+    last_request: Arc<Mutex<Option<Request>>>,
+}
+
+impl ModelsTransport {
+    fn new(response: Value) -> Self {
+        Self {
+            response,
+            last_request: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ModelsTransport {
+    type StreamResponse = TransportStream;
+
+    fn into_stream(resp: Self::StreamResponse) -> (TransportStream, Vec<(String, String)>) {
+        (resp, Vec::new())
+    }
+
+    async fn post_json_stream(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        Err(TransportError::Other("post_json_stream unused".into()))
+    }
+
+    async fn post_json(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: &Value,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        Err(TransportError::Other("post_json unused".into()))
+    }
+
+    async fn request_json(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&Value>,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        *self.last_request.lock().unwrap() =
+            Some((method, url.to_string(), headers.to_vec(), body.cloned()));
+        Ok((self.response.clone(), Vec::new()))
+    }
+}
+
+#[tokio::test]
+async fn lists_models_from_recorded_openai_response() {
+    let response: Value =
+        serde_json::from_str(include_str!("fixtures/models.1.json")).expect("models fixture json");
+    let transport = ModelsTransport::new(response);
+    let headers = vec![("authorization".to_string(), "Bearer test".to_string())];
+
+    let models = list_models(
+        &transport,
+        "https://api.example.com/v1/",
+        &headers,
+        &TransportConfig::default(),
+    )
+    .await
+    .expect("models");
+
+    let (method, url, sent_headers, body) = transport
+        .last_request
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("request");
+    assert_eq!(method, HttpMethod::Get);
+    assert_eq!(url, "https://api.example.com/v1/models");
+    assert_eq!(sent_headers, headers);
+    assert!(body.is_none());
+
+    let ids: Vec<&str> = models.iter().map(|model| model.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        [
+            "gpt-4o-mini",
+            "text-embedding-3-small",
+            "ft:gpt-4o-mini-2024-07-18:acme::9xYz1Abc"
+        ]
+    );
+    assert_eq!(models[0].display_name, "gpt-4o-mini");
+    assert_eq!(models[0].provider.as_deref(), Some("system"));
+    assert_eq!(models[0].release_date.as_deref(), Some("2024-07-16"));
+    assert!(models[0].limits.is_none());
+}
+
+#[tokio::test]
+async fn tolerates_wrapped_and_sparse_model_entries() {
+    let transport = ModelsTransport::new(json!({
+        "models": [
+            {
+                "id": "meta-llama/llama-3.3-70b-instruct",
+                "name": "Llama 3.3 70B Instruct",
+                "context_length": 131072,
+                "top_provider": { "max_completion_tokens": 16384 }
+            },
+            { "name": "local-model" },
+            "bare-id",
+            { "object": "model" }
+        ]
+    }));
+
+    let models = list_models(
+        &transport,
+        "http://localhost:8080/v1",
+        &[],
+        &TransportConfig::default(),
+    )
+    .await
+    .expect("models");
+
+    assert_eq!(models.len(), 3);
+    assert_eq!(models[0].display_name, "Llama 3.3 70B Instruct");
+    assert_eq!(
+        models[0].limits,
+        Some(ModelLimits {
+            context: Some(131_072),
+            output: Some(16_384),
+            ..ModelLimits::default()
+        })
+    );
+    assert_eq!(models[1].id, "local-model");
+    assert_eq!(models[2].id, "bare-id");
+
+    let transport = ModelsTransport::new(json!({ "error": "nope" }));
+    let err = list_models(
+        &transport,
+        "http://localhost:8080/v1",
+        &[],
+        &TransportConfig::default(),
+    )
+    .await
+    .expect_err("no model list");
+    assert!(matches!(err, SdkError::Upstream { .. }));
+}
@@ -27,9 +27,15 @@ fn openai_compatible_def(name: &str) -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models,
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
@@ -54,9 +60,15 @@ fn openai_compatible_registry_maps_to_chat_builder() {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models,
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     };
 
     let model = (reg.build)(&def, "gpt-4o", &Credentials::None).expect("build model");
@@ -85,9 +97,15 @@ fn groq_registry_maps_to_openai_compatible_chat_builder() {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models,
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     };
 
     let model =
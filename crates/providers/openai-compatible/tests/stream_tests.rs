@@ -5,6 +5,7 @@ use bytes::Bytes;
 use futures_util::stream;
 use futures_util::TryStreamExt;
 use serde_json::json;
+use std::collections::HashMap;
 
 const NEW_PROVIDER_SCOPES: &[&str] = &[
     "groq",
@@ -51,6 +52,8 @@ async fn streams_text_and_usage() {
             include_raw: false,
             include_usage: true,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -127,6 +130,8 @@ async fn streams_reasoning_content_before_text() {
             include_raw: false,
             include_usage: false,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -227,6 +232,8 @@ async fn streams_tool_calls_and_finish_reason() {
             include_raw: false,
             include_usage: true,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -302,6 +309,8 @@ async fn streams_tool_input_deltas_as_fragments() {
             include_raw: false,
             include_usage: false,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -343,6 +352,8 @@ async fn errors_on_missing_tool_call_id() {
             include_raw: false,
             include_usage: false,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -375,6 +386,8 @@ async fn errors_on_missing_tool_call_name() {
             include_raw: false,
             include_usage: false,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -420,6 +433,8 @@ async fn streams_usage_with_cache_reasoning_and_prediction_tokens() {
             include_raw: false,
             include_usage: true,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -450,6 +465,51 @@ async fn streams_usage_with_cache_reasoning_and_prediction_tokens() {
     assert_eq!(openai_meta.get("rejectedPredictionTokens"), Some(&json!(1)));
 }
 
+#[tokio::test]
+async fn streams_usage_reported_as_floats_and_strings() {
+    let parts: Vec<v2t::StreamPart> = build_stream(
+        stream::iter(vec![
+            json_chunk(json!({
+                "id":"chat-1",
+                "choices":[{"index":0,"delta":{},"finish_reason":"stop"}],
+                "usage":{
+                    "prompt_tokens":10.0,
+                    "completion_tokens":"5",
+                    "total_tokens":"15",
+                    "prompt_tokens_details":{"cached_tokens":"4"},
+                    "completion_tokens_details":{"reasoning_tokens":2.0}
+                }
+            })),
+            chunk("data: [DONE]\n\n"),
+        ]),
+        StreamSettings {
+            warnings: vec![],
+            include_raw: false,
+            include_usage: true,
+            provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
+        },
+        StreamMode::Chat,
+    )
+    .try_collect()
+    .await
+    .expect("stream parts");
+
+    let usage = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish { usage, .. } => Some(usage.clone()),
+            _ => None,
+        })
+        .expect("finish part");
+    assert_eq!(usage.input_tokens, Some(10));
+    assert_eq!(usage.output_tokens, Some(5));
+    assert_eq!(usage.total_tokens, Some(15));
+    assert_eq!(usage.cached_input_tokens, Some(4));
+    assert_eq!(usage.reasoning_tokens, Some(2));
+}
+
 #[tokio::test]
 async fn newly_routed_scopes_preserve_stream_usage_and_provider_metadata_shape() {
     for scope in NEW_PROVIDER_SCOPES {
@@ -479,6 +539,8 @@ async fn newly_routed_scopes_preserve_stream_usage_and_provider_metadata_shape()
                 include_raw: false,
                 include_usage: true,
                 provider_scope_name: (*scope).into(),
+                field_aliases: HashMap::new(),
+                done_sentinel: None,
             },
             StreamMode::Chat,
         )
@@ -534,6 +596,8 @@ async fn streams_raw_chunks_alongside_normalized_parts() {
             include_raw: true,
             include_usage: false,
             provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         },
         StreamMode::Chat,
     )
@@ -556,3 +620,139 @@ async fn streams_raw_chunks_alongside_normalized_parts() {
         .iter()
         .any(|part| matches!(part, v2t::StreamPart::Finish { .. })));
 }
+
+#[tokio::test]
+async fn flushes_final_frame_without_terminator() {
+    let parts: Vec<v2t::StreamPart> = build_stream(
+        stream::iter(vec![
+            json_chunk(json!({
+                "choices":[{"index":0,"delta":{"content":"Hello"}}]
+            })),
+            chunk(format!(
+                "data: {}",
+                json!({
+                    "choices":[{"index":0,"delta":{"content":" world"},"finish_reason":"stop"}],
+                    "usage":{"prompt_tokens":3,"completion_tokens":4,"total_tokens":7}
+                })
+            )),
+        ]),
+        StreamSettings {
+            warnings: vec![],
+            include_raw: false,
+            include_usage: true,
+            provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
+        },
+        StreamMode::Chat,
+    )
+    .try_collect()
+    .await
+    .expect("stream parts");
+
+    assert!(parts.iter().any(|part| matches!(
+        part,
+        v2t::StreamPart::TextDelta { delta, .. } if delta == " world"
+    )));
+    assert!(matches!(
+        parts.last(),
+        Some(v2t::StreamPart::Finish { usage, finish_reason, .. })
+            if usage.output_tokens == Some(4) && matches!(finish_reason, v2t::FinishReason::Stop)
+    ));
+}
+
+/// Collect a stream whose source never closes after `chunks`, so only a
+/// recognized terminator can end it.
+async fn collect_until_terminator(
+    chunks: Vec<Result<Bytes, SdkError>>,
+    done_sentinel: Option<serde_json::Value>,
+) -> Vec<v2t::StreamPart> {
+    use futures_util::StreamExt;
+
+    let parts = build_stream(
+        stream::iter(chunks).chain(stream::pending()),
+        StreamSettings {
+            warnings: vec![],
+            include_raw: false,
+            include_usage: true,
+            provider_scope_name: "openai-compatible".into(),
+            field_aliases: HashMap::new(),
+            done_sentinel,
+        },
+        StreamMode::Chat,
+    )
+    .try_collect();
+    tokio::time::timeout(std::time::Duration::from_secs(1), parts)
+        .await
+        .expect("stream ended at the terminator")
+        .expect("stream parts")
+}
+
+fn text_chunk(text: &str) -> Result<Bytes, SdkError> {
+    json_chunk(json!({"choices":[{"index":0,"delta":{"content":text}}]}))
+}
+
+#[tokio::test]
+async fn done_marker_variants_end_the_stream() {
+    for marker in [
+        "data: [done]\n\n",
+        "data:   [DONE]  \n\n",
+        "data: [Done]\r\n\r\n",
+    ] {
+        let parts = collect_until_terminator(vec![text_chunk("Hi"), chunk(marker)], None).await;
+        assert!(
+            matches!(parts.last(), Some(v2t::StreamPart::Finish { .. })),
+            "{marker:?}: {parts:?}"
+        );
+        assert!(parts
+            .iter()
+            .any(|part| matches!(part, v2t::StreamPart::TextEnd { .. })));
+    }
+}
+
+#[tokio::test]
+async fn configured_json_sentinel_ends_the_stream() {
+    let parts = collect_until_terminator(
+        vec![
+            text_chunk("Hi"),
+            json_chunk(json!({
+                "done": true,
+                "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 2, "completion_tokens": 1, "total_tokens": 3}
+            })),
+        ],
+        Some(json!({"done": true})),
+    )
+    .await;
+
+    match parts.last() {
+        Some(v2t::StreamPart::Finish {
+            usage,
+            finish_reason,
+            ..
+        }) => {
+            assert!(matches!(finish_reason, v2t::FinishReason::Stop));
+            assert_eq!(usage.input_tokens, Some(2));
+            assert_eq!(usage.output_tokens, Some(1));
+        }
+        other => panic!("expected finish, got {other:?}"),
+    }
+
+    let parts = collect_until_terminator(
+        vec![
+            json_chunk(json!({"done": false})),
+            text_chunk("still going"),
+            json_chunk(json!({"done": true})),
+        ],
+        Some(json!({"done": true})),
+    )
+    .await;
+    let text: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "still going");
+}
@@ -0,0 +1,304 @@
+use crate::ai_sdk_core::transport::TransportConfig;
+use crate::ai_sdk_core::LanguageModel;
+use crate::ai_sdk_types::v2 as v2t;
+use crate::provider_openai::config::OpenAIConfig;
+use crate::provider_openai::OpenAIChatLanguageModel;
+use futures_util::TryStreamExt;
+use serde_json::{json, Value};
+
+use super::stream_fixture_tests::benchmark_support::{self, FixtureTransport};
+
+fn chat_config() -> OpenAIConfig {
+    OpenAIConfig {
+        endpoint_path: "/chat/completions".into(),
+        provider_name: "openai.chat".into(),
+        file_id_prefixes: None,
+        ..benchmark_support::openai_responses_config()
+    }
+}
+
+fn chat_model(response: Value, fixture: Option<&str>) -> OpenAIChatLanguageModel<FixtureTransport> {
+    let chunks = fixture
+        .map(benchmark_support::stream_fixture_chunks)
+        .unwrap_or_default();
+    OpenAIChatLanguageModel::new(
+        "gpt-4.1-mini",
+        chat_config(),
+        FixtureTransport::new(response, chunks),
+        TransportConfig::default(),
+    )
+}
+
+fn weather_tool() -> v2t::Tool {
+    v2t::Tool::Function(v2t::FunctionTool {
+        r#type: v2t::FunctionToolType::Function,
+        name: "weather".into(),
+        description: Some("Get the weather".into()),
+        input_schema: json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"]
+        }),
+        strict: None,
+        provider_options: None,
+    })
+}
+
+fn call_options(tools: Vec<v2t::Tool>) -> v2t::CallOptions {
+    v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        tools,
+        ..Default::default()
+    }
+}
+
+async fn collect_parts(fixture: &str, tools: Vec<v2t::Tool>) -> Vec<v2t::StreamPart> {
+    let model = chat_model(Value::Null, Some(fixture));
+    let resp = model.do_stream(call_options(tools)).await.expect("stream");
+    resp.stream
+        .try_collect()
+        .await
+        .expect("collect stream parts")
+}
+
+fn text(parts: &[v2t::StreamPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn finish(parts: &[v2t::StreamPart]) -> (&v2t::FinishReason, &v2t::Usage) {
+    parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish {
+                finish_reason,
+                usage,
+                ..
+            } => Some((finish_reason, usage)),
+            _ => None,
+        })
+        .expect("finish part")
+}
+
+#[tokio::test]
+async fn chat_text_fixture_streams_text_metadata_and_usage() {
+    let parts = collect_parts("openai-chat-text.1", Vec::new()).await;
+
+    assert!(!parts
+        .iter()
+        .any(|part| matches!(part, v2t::StreamPart::Error { .. })));
+    assert_eq!(text(&parts), "Hello! How can I help you today?");
+    let meta = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::ResponseMetadata { meta } => Some(meta),
+            _ => None,
+        })
+        .expect("response metadata");
+    assert_eq!(
+        meta.id.as_deref(),
+        Some("chatcmpl-C9Xr1kqY2mBq4tT0d8vZgJ3WlH7aN")
+    );
+    assert_eq!(meta.model_id.as_deref(), Some("gpt-4.1-mini-2025-04-14"));
+    assert_eq!(meta.timestamp_ms, Some(1_763_474_589_000));
+
+    let (finish_reason, usage) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::Stop));
+    assert_eq!(usage.input_tokens, Some(9));
+    assert_eq!(usage.output_tokens, Some(10));
+    assert_eq!(usage.total_tokens, Some(19));
+}
+
+#[tokio::test]
+async fn chat_tool_call_fixture_assembles_parallel_calls() {
+    let parts = collect_parts("openai-chat-tool-call.1", vec![weather_tool()]).await;
+
+    let calls: Vec<&v2t::ToolCallPart> = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::ToolCall(call) => Some(call),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].tool_call_id, "call_3kVw8sYp1QmZr4Tn6uBx2LcD");
+    assert_eq!(calls[0].tool_name, "weather");
+    assert_eq!(
+        serde_json::from_str::<Value>(&calls[0].input).expect("weather args"),
+        json!({"city": "San Francisco"})
+    );
+    assert_eq!(calls[1].tool_name, "time");
+    assert_eq!(calls[1].input, r#"{"zone":"PST"}"#);
+
+    let (finish_reason, usage) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::ToolCalls));
+    assert_eq!(usage.cached_input_tokens, Some(64));
+}
+
+#[tokio::test]
+async fn chat_reasoning_fixture_closes_reasoning_before_text() {
+    let parts = collect_parts("openai-chat-reasoning.1", Vec::new()).await;
+
+    let reasoning: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::ReasoningDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(reasoning, "The user greets me. Reply briefly.");
+    let reasoning_end = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::ReasoningEnd { .. }))
+        .expect("reasoning end");
+    let text_start = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::TextStart { .. }))
+        .expect("text start");
+    assert!(reasoning_end < text_start);
+    assert_eq!(text(&parts), "Hi there!");
+
+    let (finish_reason, usage) = finish(&parts);
+    assert!(matches!(finish_reason, v2t::FinishReason::Length));
+    assert_eq!(usage.reasoning_tokens, Some(11));
+}
+
+#[tokio::test]
+async fn chat_stream_request_targets_chat_completions_shape() {
+    let model = chat_model(Value::Null, Some("openai-chat-text.1"));
+    let mut options = call_options(vec![weather_tool()]);
+    options.tool_choice = Some(v2t::ToolChoice::Tool {
+        name: "weather".into(),
+    });
+    options.max_output_tokens = Some(64);
+    let resp = model.do_stream(options).await.expect("stream");
+    let body = resp.request_body.expect("request body");
+
+    assert_eq!(body["model"], json!("gpt-4.1-mini"));
+    assert_eq!(body["stream"], json!(true));
+    assert_eq!(body["stream_options"], json!({"include_usage": true}));
+    assert_eq!(body["max_tokens"], json!(64));
+    assert_eq!(body["messages"][0]["role"], json!("user"));
+    assert_eq!(body["tools"][0]["type"], json!("function"));
+    assert_eq!(body["tools"][0]["function"]["name"], json!("weather"));
+    assert_eq!(
+        body["tool_choice"],
+        json!({"type": "function", "function": {"name": "weather"}})
+    );
+}
+
+#[tokio::test]
+async fn chat_include_usage_option_controls_stream_options() {
+    let model = chat_model(Value::Null, Some("openai-chat-text.1"));
+    let mut options = call_options(Vec::new());
+    options.provider_options = v2t::ProviderOptions::from([(
+        "openai".into(),
+        std::collections::HashMap::from([("includeUsage".into(), json!(false))]),
+    )]);
+    let resp = model.do_stream(options).await.expect("stream");
+    let body = resp.request_body.expect("request body");
+
+    assert!(body.get("stream_options").is_none());
+    assert!(body.get("includeUsage").is_none());
+}
+
+#[tokio::test]
+async fn chat_generate_parses_text_and_tool_calls() {
+    let response: Value = serde_json::from_str(include_str!("fixtures/openai-chat-text.1.json"))
+        .expect("chat completion fixture json");
+    let model = chat_model(response, None);
+    let resp = model
+        .do_generate(call_options(vec![weather_tool()]))
+        .await
+        .expect("generate");
+
+    assert!(matches!(resp.finish_reason, v2t::FinishReason::ToolCalls));
+    assert!(matches!(
+        &resp.content[0],
+        v2t::Content::Text { text, .. } if text == "Let me check the weather."
+    ));
+    assert!(matches!(
+        &resp.content[1],
+        v2t::Content::ToolCall(call)
+            if call.tool_call_id == "call_Wq4nB8sLx2Hv6Tk0Pj3Rz9Mc"
+                && call.input == r#"{"city":"Berlin"}"#
+    ));
+    assert_eq!(resp.usage.input_tokens, Some(57));
+    assert_eq!(resp.usage.output_tokens, Some(22));
+    assert_eq!(
+        resp.response_metadata.and_then(|meta| meta.model_id),
+        Some("gpt-4.1-mini-2025-04-14".into())
+    );
+}
+
+#[tokio::test]
+async fn chat_request_sends_developer_role() {
+    let model = chat_model(Value::Null, Some("openai-chat-text.1"));
+    let mut options = call_options(Vec::new());
+    options
+        .prompt
+        .insert(0, v2t::PromptMessage::developer("be brief"));
+    let resp = model.do_stream(options).await.expect("stream");
+    let body = resp.request_body.expect("request body");
+
+    assert_eq!(
+        body["messages"][0],
+        json!({"role": "developer", "content": "be brief"})
+    );
+}
+
+#[test]
+fn chat_request_applies_unsupported_tools_policy() {
+    use crate::provider_openai::chat::language_model::build_chat_request_body;
+
+    let mut options = call_options(vec![weather_tool()]);
+    assert!(build_chat_request_body(&options, "o1-mini", &chat_config()).is_err());
+
+    options.on_unsupported_tools = v2t::UnsupportedToolsPolicy::DropWithWarning;
+    let (body, warnings) =
+        build_chat_request_body(&options, "o1-mini", &chat_config()).expect("request body");
+    assert!(body.get("tools").is_none());
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::UnsupportedSetting { setting, .. } if setting == "tools"
+    )));
+
+    let (body, _) =
+        build_chat_request_body(&options, "gpt-4.1-mini", &chat_config()).expect("request body");
+    assert!(body.get("tools").is_some());
+}
+
+#[test]
+fn chat_request_carries_seed_and_clamped_logit_bias() {
+    use crate::provider_openai::chat::language_model::build_chat_request_body;
+
+    let mut options = call_options(Vec::new());
+    options.provider_options.insert(
+        "openai".into(),
+        std::collections::HashMap::from([
+            ("seed".into(), json!(42)),
+            ("logitBias".into(), json!({"50256": -100, "1234": 250})),
+        ]),
+    );
+
+    let (body, warnings) =
+        build_chat_request_body(&options, "gpt-4.1-mini", &chat_config()).expect("request body");
+    assert_eq!(body["seed"], json!(42));
+    assert_eq!(body["logit_bias"], json!({"1234": 100.0, "50256": -100.0}));
+    assert!(matches!(
+        warnings.as_slice(),
+        [v2t::CallWarning::UnsupportedSetting { setting, details: Some(details) }]
+            if setting == "logitBias" && details.contains("1234")
+    ));
+}
@@ -2,7 +2,7 @@ use crate::core::error::{
     is_codex_websocket_reconnect_replay_retry_error, SdkError, TransportError,
 };
 use crate::core::transport::{
-    HttpTransport, JsonStreamWebsocketConnection, TransportConfig, TransportStream,
+    CancelToken, HttpTransport, JsonStreamWebsocketConnection, TransportConfig, TransportStream,
 };
 use crate::core::LanguageModel;
 use crate::providers::openai::config::OpenAIConfig;
@@ -27,6 +27,8 @@ use tokio_tungstenite::tungstenite::Message as WsMessage;
 
 enum StreamBehavior {
     Chunks(Vec<Result<Bytes, TransportError>>),
+    /// Yields the chunks, then never ends.
+    Stalled(Vec<Result<Bytes, TransportError>>),
     Error(TransportError),
 }
 
@@ -42,6 +44,7 @@ struct TestTransport {
     close_websocket_after_send: Arc<AtomicBool>,
     json_response: Arc<Mutex<Option<Value>>>,
     stream_behaviors: Arc<Mutex<VecDeque<StreamBehavior>>>,
+    saw_cancel_token: Arc<AtomicBool>,
 }
 
 impl TestTransport {
@@ -57,6 +60,7 @@ impl TestTransport {
             close_websocket_after_send: Arc::new(AtomicBool::new(false)),
             json_response: Arc::new(Mutex::new(None)),
             stream_behaviors: Arc::new(Mutex::new(VecDeque::new())),
+            saw_cancel_token: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -148,6 +152,9 @@ impl JsonStreamWebsocketConnection for TestWebsocketConnection {
         }
         match behavior {
             StreamBehavior::Chunks(chunks) => Ok(Box::pin(stream::iter(chunks))),
+            StreamBehavior::Stalled(chunks) => {
+                Ok(Box::pin(stream::iter(chunks).chain(stream::pending())))
+            }
             StreamBehavior::Error(err) => {
                 self.closed.store(true, Ordering::SeqCst);
                 Err(err)
@@ -181,8 +188,10 @@ impl HttpTransport for TestTransport {
         url: &str,
         headers: &[(String, String)],
         body: &Value,
-        _cfg: &TransportConfig,
+        cfg: &TransportConfig,
     ) -> Result<Self::StreamResponse, TransportError> {
+        self.saw_cancel_token
+            .store(cfg.cancel.is_some(), Ordering::SeqCst);
         *self.last_body.lock().unwrap() = Some(body.clone());
         *self.last_url.lock().unwrap() = Some(url.to_string());
         *self.last_headers.lock().unwrap() = headers.to_vec();
@@ -201,6 +210,9 @@ impl HttpTransport for TestTransport {
             StreamBehavior::Chunks(chunks) => Ok(TestStreamResponse {
                 stream: Box::pin(stream::iter(chunks)),
             }),
+            StreamBehavior::Stalled(chunks) => Ok(TestStreamResponse {
+                stream: Box::pin(stream::iter(chunks).chain(stream::pending())),
+            }),
             StreamBehavior::Error(err) => Err(err),
         }
     }
@@ -464,6 +476,15 @@ fn function_tool_for_strict_passthrough(strict: Option<bool>) -> v2t::FunctionTo
 }
 
 async fn request_body_for_function_tool(function_tool: v2t::FunctionTool) -> Value {
+    request_body_for_options(v2t::CallOptions {
+        tools: vec![v2t::Tool::Function(function_tool)],
+        ..Default::default()
+    })
+    .await
+}
+
+/// Request body sent to `/responses` for `opts` with a "Hello" prompt.
+async fn request_body_for_options(opts: v2t::CallOptions) -> Value {
     let opts = v2t::CallOptions {
         prompt: vec![v2t::PromptMessage::User {
             content: vec![v2t::UserPart::Text {
@@ -472,8 +493,7 @@ async fn request_body_for_function_tool(function_tool: v2t::FunctionTool) -> Val
             }],
             provider_options: None,
         }],
-        tools: vec![v2t::Tool::Function(function_tool)],
-        ..Default::default()
+        ..opts
     };
     let cfg = OpenAIConfig {
         provider_name: "openai.responses".into(),
@@ -645,6 +665,33 @@ async fn codex_websocket_request_body_keeps_explicit_tool_settings() {
     assert_eq!(body.get("parallel_tool_calls"), Some(&json!(false)));
 }
 
+#[tokio::test]
+async fn parallel_tool_calls_is_sent_only_with_tools() {
+    let parallel_off = || {
+        v2t::ProviderOptions::from([(
+            "openai".into(),
+            HashMap::from([("parallelToolCalls".into(), json!(false))]),
+        )])
+    };
+
+    let with_tools = request_body_for_options(v2t::CallOptions {
+        tools: vec![v2t::Tool::Function(function_tool_for_strict_passthrough(
+            None,
+        ))],
+        provider_options: parallel_off(),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(with_tools.get("parallel_tool_calls"), Some(&json!(false)));
+
+    let without_tools = request_body_for_options(v2t::CallOptions {
+        provider_options: parallel_off(),
+        ..Default::default()
+    })
+    .await;
+    assert!(without_tools.get("parallel_tool_calls").is_none());
+}
+
 #[tokio::test]
 async fn codex_websocket_forwards_call_headers_and_client_metadata() {
     let cfg = OpenAIConfig {
@@ -777,6 +824,51 @@ async fn stream_uses_websocket_when_provider_transport_requests_it() {
         .any(|(name, value)| name == "OpenAI-Beta" && value == "responses_websockets=2026-02-06"));
 }
 
+#[tokio::test]
+async fn websocket_stream_keeps_configured_openai_beta_with_case_preserved() {
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://example.invalid/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![("OpenAI-Beta".into(), "custom=1".into())],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new();
+    let model = OpenAIResponsesLanguageModel::new(
+        "gpt-4o",
+        cfg,
+        transport.clone(),
+        TransportConfig {
+            preserve_header_case: true,
+            ..TransportConfig::default()
+        },
+    );
+    let opts = v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        provider_options: websocket_transport_options(),
+        ..Default::default()
+    };
+
+    let _ = model.do_stream(opts).await.expect("stream response");
+    let beta: Vec<(String, String)> = transport
+        .last_headers()
+        .into_iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("openai-beta"))
+        .collect();
+    assert_eq!(beta, vec![("OpenAI-Beta".into(), "custom=1".into())]);
+}
+
 #[tokio::test]
 async fn stream_falls_back_to_http_when_websocket_stream_closes_before_first_event() {
     let cfg = OpenAIConfig {
@@ -2013,6 +2105,234 @@ async fn request_body_includes_priority_service_tier_when_requested() {
     );
 }
 
+#[tokio::test]
+async fn raw_provider_options_merge_over_built_request_body() {
+    let mut provider_options = v2t::ProviderOptions::new();
+    provider_options.insert(
+        "openai".into(),
+        HashMap::from([("serviceTier".into(), json!("priority"))]),
+    );
+    provider_options.insert(
+        "raw".into(),
+        HashMap::from([
+            ("service_tier".into(), json!("flex")),
+            ("prompt_cache_retention".into(), json!("24h")),
+        ]),
+    );
+    let opts = v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        provider_options,
+        ..Default::default()
+    };
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = OpenAIResponsesLanguageModel::new(
+        "gpt-5",
+        cfg,
+        transport.clone(),
+        TransportConfig::default(),
+    );
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(body["service_tier"], json!("flex"));
+    assert_eq!(body["prompt_cache_retention"], json!("24h"));
+    assert_eq!(body["model"], json!("gpt-5"));
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message }
+            if message.starts_with("raw provider options passthrough applied")
+    )));
+}
+
+fn call_options_with_openai_options(openai: HashMap<String, Value>) -> v2t::CallOptions {
+    let mut provider_options = v2t::ProviderOptions::new();
+    provider_options.insert("openai".into(), openai);
+    v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        provider_options,
+        ..Default::default()
+    }
+}
+
+fn responses_test_model(transport: TestTransport) -> OpenAIResponsesLanguageModel<TestTransport> {
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    OpenAIResponsesLanguageModel::new("gpt-4o", cfg, transport, TransportConfig::default())
+}
+
+#[tokio::test]
+async fn response_format_provider_option_sets_text_format() {
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = responses_test_model(transport.clone());
+    let schema = json!({
+        "type": "object",
+        "properties": { "ok": { "type": "boolean" } },
+        "required": ["ok"],
+        "additionalProperties": false
+    });
+    let opts = call_options_with_openai_options(HashMap::from([(
+        "responseFormat".into(),
+        json!({
+            "type": "json_schema",
+            "json_schema": { "name": "status", "schema": schema, "strict": true }
+        }),
+    )]));
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(
+        body["text"]["format"],
+        json!({
+            "type": "json_schema",
+            "name": "status",
+            "schema": schema,
+            "strict": true
+        })
+    );
+    assert!(!result.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::UnsupportedSetting { setting, .. } if setting == "responseFormat"
+    )));
+}
+
+#[tokio::test]
+async fn response_format_strict_schema_without_closed_object_warns() {
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = responses_test_model(transport.clone());
+    let opts = call_options_with_openai_options(HashMap::from([(
+        "responseFormat".into(),
+        json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "status",
+                "schema": { "type": "object", "properties": {} },
+                "strict": true
+            }
+        }),
+    )]));
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(body["text"]["format"]["strict"], json!(true));
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::UnsupportedSetting { setting, .. } if setting == "responseFormat"
+    )));
+}
+
+#[tokio::test]
+async fn response_format_rejects_non_object_schema() {
+    let transport = TestTransport::new();
+    let model = responses_test_model(transport.clone());
+    let opts = call_options_with_openai_options(HashMap::from([(
+        "responseFormat".into(),
+        json!({
+            "type": "json_schema",
+            "json_schema": { "name": "status", "schema": ["not", "an", "object"] }
+        }),
+    )]));
+
+    let err = model
+        .do_stream(opts)
+        .await
+        .err()
+        .expect("non-object schema is rejected");
+    assert!(matches!(err, SdkError::InvalidArgument { .. }));
+    assert!(transport.last_body().is_none());
+}
+
+#[tokio::test]
+async fn forced_tool_choice_must_name_a_provided_tool() {
+    let tools = vec![v2t::Tool::Function(function_tool_for_strict_passthrough(
+        None,
+    ))];
+    let body = request_body_for_options(v2t::CallOptions {
+        tools: tools.clone(),
+        tool_choice: Some(v2t::ToolChoice::Tool {
+            name: "strict-tool".into(),
+        }),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(
+        body["tool_choice"],
+        json!({"type": "function", "name": "strict-tool"})
+    );
+
+    let transport = TestTransport::new();
+    let model = responses_test_model(transport.clone());
+    let err = model
+        .do_stream(v2t::CallOptions {
+            tools,
+            tool_choice: Some(v2t::ToolChoice::Tool {
+                name: "missing-tool".into(),
+            }),
+            ..call_options_with_openai_options(HashMap::new())
+        })
+        .await
+        .err()
+        .expect("unknown tool is rejected");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message.contains("\"missing-tool\"")
+    ));
+    assert!(transport.last_body().is_none());
+}
+
+#[tokio::test]
+async fn seed_and_logit_bias_provider_options_are_dropped_with_warnings() {
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = responses_test_model(transport.clone());
+    let opts = call_options_with_openai_options(HashMap::from([
+        ("seed".into(), json!(42)),
+        ("logitBias".into(), json!({"50256": -100})),
+    ]));
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert!(body.get("seed").is_none() && body.get("logit_bias").is_none());
+    for option in ["seed", "logitBias"] {
+        assert!(result.warnings.iter().any(|warning| matches!(
+            warning,
+            v2t::CallWarning::UnsupportedSetting { setting, .. } if setting == option
+        )));
+    }
+}
+
 #[tokio::test]
 async fn request_body_function_tool_strict_true_passthrough() {
     let body =
@@ -2315,6 +2635,47 @@ async fn non_stream_usage_maps_nested_details() {
     assert_eq!(result.usage.total_tokens, Some(431));
 }
 
+#[tokio::test]
+async fn non_stream_reports_resolved_model_id() {
+    let opts = v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "Hello".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        ..Default::default()
+    };
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = OpenAIResponsesLanguageModel::new(
+        "gpt-5",
+        OpenAIConfig {
+            provider_name: "openai.responses".into(),
+            provider_scope_name: "openai".into(),
+            base_url: "https://api.openai.com/v1".into(),
+            endpoint_path: "/responses".into(),
+            headers: vec![],
+            query_params: vec![],
+            supported_urls: HashMap::new(),
+            file_id_prefixes: Some(vec!["file-".into()]),
+            default_options: None,
+            request_defaults: None,
+        },
+        transport,
+        TransportConfig::default(),
+    );
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    assert_eq!(result.resolved_model_id(), Some("gpt-5-codex"));
+    let meta = result.response_metadata.expect("response metadata");
+    assert_eq!(
+        meta.id.as_deref(),
+        Some("resp_68da74aaae58819ca776fbd20244e8df0fdbc19a07110799")
+    );
+    assert_eq!(meta.timestamp_ms, Some(1_759_147_178_000));
+}
+
 #[tokio::test]
 async fn non_stream_mcp_approval_requests_emit_content() {
     let prompt = vec![v2t::PromptMessage::User {
@@ -2524,6 +2885,359 @@ async fn request_body_includes_reasoning_summary_when_store_false() {
     assert_eq!(body, reasoning_summary_fixture());
 }
 
+#[tokio::test]
+async fn encrypted_reasoning_round_trips_without_store() {
+    let response = json!({
+        "id": "resp_1",
+        "created_at": 1,
+        "model": "o4-mini",
+        "output": [
+            {
+                "type": "reasoning",
+                "id": "rs_1",
+                "encrypted_content": "enc_abc",
+                "summary": [{"type": "summary_text", "text": "thinking"}]
+            },
+            {
+                "type": "message",
+                "id": "msg_1",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "Hi", "annotations": []}]
+            }
+        ],
+        "usage": {"input_tokens": 1, "output_tokens": 1}
+    });
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new().with_json_response(response);
+    let model = OpenAIResponsesLanguageModel::new(
+        "o4-mini",
+        cfg,
+        transport.clone(),
+        TransportConfig::default(),
+    );
+    let provider_options = v2t::ProviderOptions::from([(
+        "openai".into(),
+        HashMap::from([("store".into(), json!(false))]),
+    )]);
+    let user = |text: &str| v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: text.into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    };
+
+    let first = model
+        .do_generate(v2t::CallOptions {
+            prompt: vec![user("Hello")],
+            provider_options: provider_options.clone(),
+            ..Default::default()
+        })
+        .await
+        .expect("first turn");
+    let body = transport.last_body().expect("first request body");
+    assert_eq!(body["include"], json!(["reasoning.encrypted_content"]));
+
+    let _ = model
+        .do_generate(v2t::CallOptions {
+            prompt: vec![user("Hello"), first.to_assistant_message(), user("Again")],
+            provider_options,
+            ..Default::default()
+        })
+        .await
+        .expect("second turn");
+    let body = transport.last_body().expect("second request body");
+    let input = body["input"].as_array().expect("input array");
+    assert_eq!(
+        input[1],
+        json!({
+            "type": "reasoning",
+            "id": "rs_1",
+            "summary": [{"type": "summary_text", "text": "thinking"}],
+            "encrypted_content": "enc_abc"
+        })
+    );
+    assert_eq!(input[2]["role"], json!("assistant"));
+    assert_eq!(input[3]["role"], json!("user"));
+}
+
+#[tokio::test]
+async fn max_prompt_messages_sliding_window_trims_request_input() {
+    let user = |text: &str| v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: text.into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    };
+    let opts = v2t::CallOptions {
+        prompt: vec![
+            v2t::PromptMessage::System {
+                content: "be brief".into(),
+                provider_options: None,
+            },
+            user("first"),
+            user("second"),
+            user("third"),
+        ],
+        max_prompt_messages: Some(2),
+        prompt_overflow: v2t::PromptOverflow::SlidingWindow,
+        ..Default::default()
+    };
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new().with_json_response(json!({
+        "id": "resp_1",
+        "output": [],
+        "usage": {"input_tokens": 1, "output_tokens": 1}
+    }));
+    let model = OpenAIResponsesLanguageModel::new(
+        "gpt-4o",
+        cfg,
+        transport.clone(),
+        TransportConfig::default(),
+    );
+
+    let result = model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(
+        body["input"],
+        json!([
+            {"role": "system", "content": "be brief"},
+            {"role": "user", "content": [{"type": "input_text", "text": "third"}]}
+        ])
+    );
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message } if message.contains("max_prompt_messages")
+    )));
+}
+
+#[tokio::test]
+async fn stream_flushes_final_frame_without_terminator() {
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new().with_stream_behavior(StreamBehavior::Chunks(vec![Ok(
+        Bytes::from(format!(
+            "data: {}",
+            json!({
+                "type": "response.completed",
+                "response": {"usage": {"input_tokens": 4, "output_tokens": 7}}
+            })
+        )),
+    )]));
+    let model =
+        OpenAIResponsesLanguageModel::new("gpt-4o", cfg, transport, TransportConfig::default());
+
+    let parts: Vec<v2t::StreamPart> = model
+        .do_stream(v2t::CallOptions {
+            prompt: vec![v2t::PromptMessage::User {
+                content: vec![v2t::UserPart::Text {
+                    text: "hello".into(),
+                    provider_options: None,
+                }],
+                provider_options: None,
+            }],
+            ..Default::default()
+        })
+        .await
+        .expect("stream response")
+        .stream
+        .map(|part| part.expect("stream part"))
+        .collect()
+        .await;
+
+    assert!(
+        matches!(
+            parts.last(),
+            Some(v2t::StreamPart::Finish { usage, .. }) if usage.output_tokens == Some(7)
+        ),
+        "{parts:?}"
+    );
+}
+
+#[tokio::test]
+async fn tool_limits_warn_over_openai_tool_count_and_reject_when_strict() {
+    let tools: Vec<v2t::Tool> = (0..129)
+        .map(|i| {
+            v2t::Tool::Function(v2t::FunctionTool {
+                r#type: v2t::FunctionToolType::Function,
+                name: format!("tool_{i}"),
+                description: None,
+                input_schema: json!({"type": "object"}),
+                strict: None,
+                provider_options: None,
+            })
+        })
+        .collect();
+    let opts = v2t::CallOptions {
+        prompt: vec![v2t::PromptMessage::User {
+            content: vec![v2t::UserPart::Text {
+                text: "hi".into(),
+                provider_options: None,
+            }],
+            provider_options: None,
+        }],
+        tools,
+        ..Default::default()
+    };
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: "https://api.openai.com/v1".into(),
+        endpoint_path: "/responses".into(),
+        headers: vec![],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport = TestTransport::new().with_json_response(json!({
+        "id": "resp_1",
+        "output": [],
+        "usage": {"input_tokens": 1, "output_tokens": 1}
+    }));
+    let model = OpenAIResponsesLanguageModel::new(
+        "gpt-4o",
+        cfg,
+        transport.clone(),
+        TransportConfig::default(),
+    );
+
+    let result = model
+        .do_generate(opts.clone())
+        .await
+        .expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(body["tools"].as_array().map(Vec::len), Some(129));
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        v2t::CallWarning::Other { message }
+            if message == "request has 129 tools, exceeding max_tools (128)"
+    )));
+
+    let err = model
+        .do_generate(v2t::CallOptions {
+            strict_tool_limits: true,
+            ..opts
+        })
+        .await
+        .expect_err("strict tool limits");
+    assert!(matches!(
+        err,
+        SdkError::InvalidArgument { message } if message.contains("max_tools (128)")
+    ));
+}
+
+#[tokio::test]
+async fn system_prompt_mode_controls_system_placement() {
+    let cases = [
+        (
+            v2t::SystemPromptMode::System,
+            json!([
+                {"role": "system", "content": "be brief"},
+                {"role": "user", "content": [{"type": "input_text", "text": "hi"}]}
+            ]),
+        ),
+        (
+            v2t::SystemPromptMode::Developer,
+            json!([
+                {"role": "developer", "content": "be brief"},
+                {"role": "user", "content": [{"type": "input_text", "text": "hi"}]}
+            ]),
+        ),
+        (
+            v2t::SystemPromptMode::FoldIntoUser,
+            json!([
+                {"role": "user", "content": [
+                    {"type": "input_text", "text": "be brief"},
+                    {"type": "input_text", "text": "hi"}
+                ]}
+            ]),
+        ),
+    ];
+
+    for (mode, expected_input) in cases {
+        let opts = v2t::CallOptions {
+            prompt: vec![
+                v2t::PromptMessage::System {
+                    content: "be brief".into(),
+                    provider_options: None,
+                },
+                v2t::PromptMessage::User {
+                    content: vec![v2t::UserPart::Text {
+                        text: "hi".into(),
+                        provider_options: None,
+                    }],
+                    provider_options: None,
+                },
+            ],
+            system_prompt_mode: Some(mode),
+            ..Default::default()
+        };
+        let cfg = OpenAIConfig {
+            provider_name: "openai.responses".into(),
+            provider_scope_name: "openai".into(),
+            base_url: "https://api.openai.com/v1".into(),
+            endpoint_path: "/responses".into(),
+            headers: vec![],
+            query_params: vec![],
+            supported_urls: HashMap::new(),
+            file_id_prefixes: Some(vec!["file-".into()]),
+            default_options: None,
+            request_defaults: None,
+        };
+        let transport = TestTransport::new().with_json_response(json!({
+            "id": "resp_1",
+            "output": [],
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        }));
+        let model = OpenAIResponsesLanguageModel::new(
+            "gpt-4o",
+            cfg,
+            transport.clone(),
+            TransportConfig::default(),
+        );
+
+        let result = model.do_generate(opts).await.expect("generate response");
+        let body = transport.last_body().expect("request body");
+        assert_eq!(body["input"], expected_input, "mode {mode:?}");
+        assert!(result.warnings.is_empty(), "mode {mode:?}");
+    }
+}
+
 #[tokio::test]
 async fn request_body_includes_provider_tool_outputs() {
     let prompt = vec![v2t::PromptMessage::Tool {
@@ -2946,3 +3660,161 @@ async fn request_defaults_apply_reasoning_when_effort_not_explicit() {
         Some(&json!("auto"))
     );
 }
+
+#[tokio::test]
+async fn check_websocket_handshakes_without_sending_a_request() {
+    let server = TestWebsocketServer::start().await;
+    let cfg = OpenAIConfig {
+        provider_name: "openai.responses".into(),
+        provider_scope_name: "openai".into(),
+        base_url: server.base_url.clone(),
+        endpoint_path: "/backend-api/codex/responses".into(),
+        headers: vec![("authorization".into(), "Bearer oauth-token".into())],
+        query_params: vec![],
+        supported_urls: HashMap::new(),
+        file_id_prefixes: Some(vec!["file-".into()]),
+        default_options: None,
+        request_defaults: None,
+    };
+    let transport_cfg = TransportConfig::default();
+    let transport = crate::transport_reqwest::ReqwestTransport::new(&transport_cfg);
+    let model = OpenAIResponsesLanguageModel::new("gpt-5.3-codex", cfg, transport, transport_cfg);
+
+    let handshake = model.check_websocket().await.expect("websocket handshake");
+    assert_eq!(handshake.status, 101);
+    assert_eq!(handshake.subprotocol, None);
+
+    server.wait_for_connection_count(1).await;
+    let connect_headers = server.connect_headers();
+    assert_eq!(
+        connect_headers[0].get("authorization").map(String::as_str),
+        Some("Bearer oauth-token")
+    );
+    assert!(connect_headers[0].contains_key("openai-beta"));
+    assert!(server.request_records().is_empty());
+}
+
+#[test]
+fn model_capabilities_follow_openai_model_families() {
+    use crate::providers::openai::responses::request_translation::model_capabilities;
+
+    let gpt_4o = model_capabilities("gpt-4o");
+    assert!(gpt_4o.vision && gpt_4o.tools && gpt_4o.structured_output);
+    assert!(!gpt_4o.reasoning && !gpt_4o.audio_in);
+
+    assert!(gpt_4o.parallel_tool_calls);
+
+    let o3_mini = model_capabilities("o3-mini");
+    assert!(o3_mini.reasoning && o3_mini.tools && o3_mini.structured_output);
+    let o3 = model_capabilities("o3");
+    assert!(o3.reasoning && o3.parallel_tool_calls);
+
+    let o1_mini = model_capabilities("o1-mini");
+    assert!(o1_mini.reasoning);
+    assert!(!o1_mini.vision && !o1_mini.tools && !o1_mini.structured_output);
+    assert!(!o1_mini.parallel_tool_calls);
+
+    let audio = model_capabilities("gpt-4o-audio-preview");
+    assert!(audio.audio_in && audio.audio_out && !audio.vision);
+
+    let gpt_5_chat = model_capabilities("gpt-5-chat-latest");
+    assert!(gpt_5_chat.vision && !gpt_5_chat.reasoning);
+
+    assert!(!model_capabilities("gpt-4-0613").structured_output);
+}
+
+#[tokio::test]
+async fn developer_messages_keep_developer_role() {
+    let transport = TestTransport::new().with_json_response(local_shell_response_fixture());
+    let model = responses_test_model(transport.clone());
+    let mut opts = call_options_with_openai_options(HashMap::new());
+    opts.prompt
+        .insert(0, v2t::PromptMessage::developer("be brief"));
+    opts.prompt.insert(0, v2t::PromptMessage::system("be kind"));
+
+    model.do_generate(opts).await.expect("generate response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(
+        body["input"][0],
+        json!({"role": "system", "content": "be kind"})
+    );
+    assert_eq!(
+        body["input"][1],
+        json!({"role": "developer", "content": "be brief"})
+    );
+}
+
+#[tokio::test]
+async fn abort_ends_a_stalled_stream_with_an_aborted_error() {
+    let transport = TestTransport::new().with_stream_behavior(StreamBehavior::Stalled(vec![Ok(
+        Bytes::from_static(
+            b"data: {\"type\":\"response.output_text.delta\",\"item_id\":\"msg_1\",\"delta\":\"Hi\"}\n\n",
+        ),
+    )]));
+    let model = responses_test_model(transport.clone());
+    let abort = CancelToken::new();
+    let options = call_options_with_openai_options(HashMap::new()).with_abort(abort.clone());
+
+    let mut stream = model.do_stream(options).await.expect("stream").stream;
+    assert!(transport.saw_cancel_token.load(Ordering::SeqCst));
+    let mut saw_text = false;
+    while !saw_text {
+        let part = stream.next().await.expect("part").expect("ok part");
+        saw_text = matches!(part, v2t::StreamPart::TextDelta { ref delta, .. } if delta == "Hi");
+    }
+
+    abort.cancel();
+    let rest: Vec<_> = tokio::time::timeout(Duration::from_secs(1), stream.collect::<Vec<_>>())
+        .await
+        .expect("aborted stream terminates promptly");
+    assert!(matches!(
+        &rest[..],
+        [Ok(v2t::StreamPart::Error { error })] if error["message"] == "aborted"
+    ));
+}
+
+fn audio_prompt(media_type: &str) -> v2t::Prompt {
+    vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::File {
+            filename: None,
+            data: v2t::DataContent::Bytes {
+                bytes: vec![0, 1, 2, 3],
+            },
+            media_type: media_type.into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }]
+}
+
+#[tokio::test]
+async fn audio_file_parts_become_input_audio_or_are_dropped_with_a_warning() {
+    let transport = TestTransport::new();
+    let model = responses_test_model(transport.clone());
+
+    let _ = model
+        .do_stream(v2t::CallOptions::new(audio_prompt("audio/wav")))
+        .await
+        .expect("stream response");
+    let body = transport.last_body().expect("request body");
+    assert_eq!(
+        body["input"][0]["content"][0],
+        json!({"type": "input_audio", "input_audio": {"data": "AAECAw==", "format": "wav"}})
+    );
+
+    let mut response = model
+        .do_stream(v2t::CallOptions::new(audio_prompt("audio/flac")))
+        .await
+        .expect("stream response");
+    let body = transport.last_body().expect("request body");
+    assert!(!body.to_string().contains("input_audio"));
+    let first = response.stream.next().await.expect("first part");
+    assert!(matches!(
+        first,
+        Ok(v2t::StreamPart::StreamStart { ref warnings })
+            if warnings.iter().any(|warning| matches!(
+                warning,
+                v2t::CallWarning::Other { message } if message.contains("audio/flac")
+            ))
+    ));
+}
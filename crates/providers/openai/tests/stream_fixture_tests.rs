@@ -12,7 +12,7 @@ pub mod ai_sdk_rs {
 }
 
 #[path = "../../../../benches/support/mod.rs"]
-mod benchmark_support;
+pub(super) mod benchmark_support;
 
 fn openai_error_fixture_message() -> String {
     serde_json::from_str::<Value>(include_str!("fixtures/openai-error.1.json"))
@@ -285,6 +285,53 @@ async fn stream_image_generation_fixture() {
     assert!(results.iter().any(|(_, preliminary)| *preliminary));
 }
 
+#[tokio::test]
+async fn stream_image_generation_partials_fixture() {
+    let tools = vec![provider_tool(
+        "openai.image_generation",
+        "generateImage",
+        json!({}),
+    )];
+    let parts = collect_parts(
+        "openai-image-generation-partials.1",
+        "gpt-5-nano",
+        tools,
+        None,
+    )
+    .await;
+
+    assert_ok_stream(&parts);
+
+    let partials: Vec<_> = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::File {
+                media_type,
+                data,
+                provider_metadata: Some(meta),
+            } => Some((media_type.as_str(), data.as_str(), &meta["openai"])),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(partials.len(), 3);
+    for (index, (media_type, data, meta)) in partials.iter().enumerate() {
+        assert_eq!(*media_type, "image/png");
+        assert!(data.starts_with("iVBOR"));
+        assert_eq!(meta.get("partial"), Some(&json!(true)));
+        assert_eq!(meta.get("index"), Some(&json!(index)));
+    }
+
+    let results = tool_results(&parts, "generateImage");
+    assert_eq!(
+        results
+            .iter()
+            .filter(|(_, preliminary)| *preliminary)
+            .count(),
+        3
+    );
+    assert!(results.iter().any(|(_, preliminary)| !*preliminary));
+}
+
 #[tokio::test]
 async fn stream_local_shell_fixture() {
     let tools = vec![provider_tool("openai.local_shell", "shell", json!({}))];
@@ -312,6 +359,20 @@ async fn stream_usage_maps_nested_details() {
     assert_eq!(usage.total_tokens, Some(558));
 }
 
+#[tokio::test]
+async fn stream_usage_maps_audio_and_image_details() {
+    let parts = collect_parts("openai-multimodal-usage.1", "gpt-realtime", vec![], None).await;
+
+    assert_ok_stream(&parts);
+    let usage = finish_usage(&parts);
+    assert_eq!(usage.input_tokens, Some(1420));
+    assert_eq!(usage.cached_input_tokens, Some(200));
+    assert_eq!(usage.audio_input_tokens, Some(640));
+    assert_eq!(usage.image_input_tokens, Some(512));
+    assert_eq!(usage.output_tokens, Some(380));
+    assert_eq!(usage.audio_output_tokens, Some(300));
+}
+
 #[tokio::test]
 async fn stream_shell_fixture() {
     let tools = vec![provider_tool("openai.shell", "shell", json!({}))];
@@ -403,6 +464,50 @@ async fn stream_mcp_approval_turn1_fixture() {
     assert_eq!(tool_call_id, call.tool_call_id);
 }
 
+#[tokio::test]
+async fn stream_mcp_list_tools_fixture() {
+    let tools = vec![mcp_tool(
+        "zip1",
+        "https://zip1.io/mcp",
+        "Link shortener",
+        true,
+    )];
+    let parts = collect_parts("openai-mcp-tool-approval.1", "gpt-5-mini", tools, None).await;
+
+    assert_ok_stream(&parts);
+    let metadata = parts
+        .iter()
+        .find_map(|part| match part {
+            v2t::StreamPart::Finish {
+                provider_metadata, ..
+            } => provider_metadata.as_ref(),
+            _ => None,
+        })
+        .expect("finish provider metadata");
+    let mcp_tools = metadata["openai"]["mcpTools"]
+        .as_array()
+        .expect("mcpTools array");
+    let names: Vec<_> = mcp_tools
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "create_short_url",
+            "get_url_stats",
+            "validate_url",
+            "generate_short_code"
+        ]
+    );
+    let first = &mcp_tools[0];
+    assert_eq!(first["serverLabel"], "zip1");
+    assert!(first["description"]
+        .as_str()
+        .is_some_and(|d| d.starts_with("Create a shortened URL")));
+    assert_eq!(first["inputSchema"]["type"], "object");
+}
+
 #[tokio::test]
 async fn stream_mcp_approval_turn2_fixture() {
     let tools = vec![mcp_tool(
@@ -452,6 +557,45 @@ async fn stream_mcp_approval_turn4_fixture() {
     assert!(has_result);
 }
 
+#[tokio::test]
+async fn stream_mid_stream_error_fixture_finishes_after_partial_text() {
+    let parts = collect_parts("openai-error-mid-stream.1", "gpt-4o-mini", vec![], None).await;
+
+    let errors = parts
+        .iter()
+        .filter(|part| matches!(part, v2t::StreamPart::Error { .. }))
+        .count();
+    assert_eq!(errors, 1, "mid-stream error must not add an EOF error");
+    let text: String = parts
+        .iter()
+        .filter_map(|part| match part {
+            v2t::StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Here is the beginning");
+
+    let error_idx = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::Error { .. }))
+        .expect("error part index");
+    let text_end_idx = parts
+        .iter()
+        .position(|part| matches!(part, v2t::StreamPart::TextEnd { .. }))
+        .expect("open text must be closed");
+    assert!(text_end_idx > error_idx);
+    assert!(
+        matches!(
+            parts.last(),
+            Some(v2t::StreamPart::Finish {
+                finish_reason: v2t::FinishReason::Error,
+                ..
+            })
+        ),
+        "stream must end with a finish part carrying the error reason"
+    );
+}
+
 #[tokio::test]
 async fn stream_error_fixture() {
     let parts = collect_parts("openai-error.1", "gpt-4o-mini", vec![], None).await;
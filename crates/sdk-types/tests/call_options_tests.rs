@@ -0,0 +1,96 @@
+use crate::ai_sdk_types::cancel::CancelToken;
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn function_tool(name: &str) -> v2t::Tool {
+    v2t::Tool::Function(v2t::FunctionTool {
+        r#type: Default::default(),
+        name: name.into(),
+        description: None,
+        input_schema: json!({"type": "object", "properties": {"a": {}, "b": {}}}),
+        strict: None,
+        provider_options: None,
+    })
+}
+
+fn base_options() -> v2t::CallOptions {
+    v2t::CallOptions::new(vec![v2t::PromptMessage::User {
+        content: vec![v2t::UserPart::Text {
+            text: "hi".into(),
+            provider_options: None,
+        }],
+        provider_options: None,
+    }])
+    .with_temperature(0.7)
+}
+
+#[test]
+fn canonical_hash_ignores_tool_and_map_order() {
+    let mut a = base_options();
+    a.tools = vec![function_tool("search"), function_tool("fetch")];
+    a.provider_options = v2t::ProviderOptions::from([
+        (
+            "openai".into(),
+            HashMap::from([("store".into(), json!(false)), ("user".into(), json!("u"))]),
+        ),
+        ("anthropic".into(), HashMap::new()),
+    ]);
+
+    let mut b = base_options();
+    b.tools = vec![function_tool("fetch"), function_tool("search")];
+    b.provider_options = v2t::ProviderOptions::from([
+        ("anthropic".into(), HashMap::new()),
+        (
+            "openai".into(),
+            HashMap::from([("user".into(), json!("u")), ("store".into(), json!(false))]),
+        ),
+    ]);
+
+    assert_eq!(a.canonical_json(), b.canonical_json());
+    assert_eq!(a.canonical_hash(), b.canonical_hash());
+}
+
+#[test]
+fn canonical_hash_normalizes_floats_and_excludes_headers_and_abort() {
+    let a = base_options();
+    let mut b = base_options().with_abort(CancelToken::new());
+    b.temperature = Some(0.700_000_05);
+    b.headers = HashMap::from([("x-request-id".into(), "abc".into())]);
+    assert_eq!(a.canonical_hash(), b.canonical_hash());
+    assert_eq!(a.canonical_json()["temperature"], json!(0.7));
+}
+
+#[test]
+fn canonical_hash_changes_with_inputs() {
+    let a = base_options();
+    let b = base_options().with_max_output_tokens(16);
+    let c = base_options().with_temperature(0.2);
+    assert_ne!(a.canonical_hash(), b.canonical_hash());
+    assert_ne!(a.canonical_hash(), c.canonical_hash());
+}
+
+#[test]
+fn hash_end_user_id_is_hmac_sha256_hex() {
+    // RFC 4231 test case 2.
+    assert_eq!(
+        v2t::hash_end_user_id("what do ya want for nothing?", b"Jefe"),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn hashed_end_user_sets_stable_openai_safety_identifier() {
+    let first = base_options().hashed_end_user("user-42", b"app-salt");
+    let second = base_options().hashed_end_user("user-42", b"app-salt");
+    let other_salt = base_options().hashed_end_user("user-42", b"other-salt");
+
+    let identifier =
+        |options: &v2t::CallOptions| options.provider_options["openai"]["safetyIdentifier"].clone();
+    let value = identifier(&first);
+    assert_eq!(value, identifier(&second));
+    assert_ne!(value, identifier(&other_salt));
+    let text = value.as_str().expect("string identifier");
+    assert_eq!(text.len(), 64);
+    assert!(!text.contains("user-42"));
+}
@@ -0,0 +1,137 @@
+use super::{data_stream_frame, to_data_stream_frame, DataStreamEncoder};
+use crate::ai_sdk_types::{Event, TokenUsage};
+use serde_json::json;
+
+// Expected frames are the AI SDK's `formatDataStreamPart` output for the
+// same parts.
+
+#[test]
+fn text_and_error_frames_match_the_js_sdk() {
+    assert_eq!(
+        to_data_stream_frame(&Event::TextDelta {
+            delta: "Hello, \"world\"\n".into()
+        }),
+        "0:\"Hello, \\\"world\\\"\\n\"\n"
+    );
+    assert_eq!(
+        to_data_stream_frame(&Event::ReasoningDelta {
+            delta: "thinking".into()
+        }),
+        "g:\"thinking\"\n"
+    );
+    assert_eq!(
+        to_data_stream_frame(&Event::Error {
+            message: "error message".into()
+        }),
+        "3:\"error message\"\n"
+    );
+    assert_eq!(
+        to_data_stream_frame(&Event::Data {
+            key: "progress".into(),
+            value: json!({"step": 1})
+        }),
+        "2:[{\"progress\":{\"step\":1}}]\n"
+    );
+    assert_eq!(
+        to_data_stream_frame(&Event::Done),
+        "d:{\"finishReason\":\"stop\"}\n"
+    );
+    assert_eq!(to_data_stream_frame(&Event::ReasoningEnd), "");
+}
+
+#[test]
+fn tool_call_stream_frames_match_the_js_sdk() {
+    let mut encoder = DataStreamEncoder::new();
+    let events = [
+        Event::ToolCallStart {
+            id: "call-456".into(),
+            name: "streaming-tool".into(),
+        },
+        Event::ToolCallDelta {
+            id: "call-456".into(),
+            args_json: "{\"some\":".into(),
+        },
+        Event::ToolCallDelta {
+            id: "call-456".into(),
+            args_json: "\"argument\"}".into(),
+        },
+        Event::ToolCallEnd {
+            id: "call-456".into(),
+        },
+    ];
+    let frames: Vec<String> = events.iter().map(|event| encoder.encode(event)).collect();
+
+    assert_eq!(
+        frames,
+        [
+            "b:{\"toolCallId\":\"call-456\",\"toolName\":\"streaming-tool\"}\n",
+            "c:{\"toolCallId\":\"call-456\",\"argsTextDelta\":\"{\\\"some\\\":\"}\n",
+            "c:{\"toolCallId\":\"call-456\",\"argsTextDelta\":\"\\\"argument\\\"}\"}\n",
+            "9:{\"toolCallId\":\"call-456\",\"toolName\":\"streaming-tool\",\"args\":{\"some\":\"argument\"}}\n",
+        ]
+    );
+    assert_eq!(
+        encoder.encode(&Event::Done),
+        "e:{\"finishReason\":\"tool-calls\",\"isContinued\":false}\n\
+         d:{\"finishReason\":\"tool-calls\"}\n"
+    );
+}
+
+#[test]
+fn usage_is_reported_on_the_finish_frames() {
+    let mut encoder = DataStreamEncoder::new();
+    let text = encoder.encode(&Event::TextDelta {
+        delta: "example".into(),
+    });
+    let usage = encoder.encode(&Event::Usage {
+        usage: TokenUsage::new(10, 20),
+    });
+    let done = encoder.encode(&Event::Done);
+
+    assert_eq!(text, "0:\"example\"\n");
+    assert_eq!(usage, "");
+    assert_eq!(
+        done,
+        "e:{\"finishReason\":\"stop\",\"usage\":{\"promptTokens\":10,\"completionTokens\":20},\"isContinued\":false}\n\
+         d:{\"finishReason\":\"stop\",\"usage\":{\"promptTokens\":10,\"completionTokens\":20}}\n"
+    );
+}
+
+#[test]
+fn finish_frames_report_an_error_after_an_error_event() {
+    let mut encoder = DataStreamEncoder::new();
+    let error = encoder.encode(&Event::Error {
+        message: "upstream failed".into(),
+    });
+    let done = encoder.encode(&Event::Done);
+
+    assert_eq!(error, "3:\"upstream failed\"\n");
+    assert_eq!(
+        done,
+        "e:{\"finishReason\":\"error\",\"isContinued\":false}\n\
+         d:{\"finishReason\":\"error\"}\n"
+    );
+}
+
+#[test]
+fn ui_message_chunks_render_as_data_stream_frames() {
+    assert_eq!(
+        data_stream_frame(&json!({"type": "text-delta", "id": "t1", "delta": "Hi"})),
+        "0:\"Hi\"\n"
+    );
+    assert_eq!(
+        data_stream_frame(&json!({
+            "type": "tool-input-available",
+            "toolCallId": "call-1",
+            "toolName": "weather",
+            "input": {"city": "Paris"},
+            "providerExecuted": false
+        })),
+        "9:{\"toolCallId\":\"call-1\",\"toolName\":\"weather\",\"args\":{\"city\":\"Paris\"}}\n"
+    );
+    assert_eq!(
+        data_stream_frame(&json!({"type": "finish"})),
+        "d:{\"finishReason\":\"stop\"}\n"
+    );
+    assert_eq!(data_stream_frame(&json!({"type": "start"})), "");
+}
@@ -0,0 +1,26 @@
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+
+#[test]
+fn unknown_stream_part_type_is_kept_raw() {
+    let raw = json!({"type": "citation-delta", "id": "c1", "delta": {"url": "https://a.b"}});
+    let part: v2t::StreamPart = serde_json::from_value(raw.clone()).expect("future part");
+
+    match &part {
+        v2t::StreamPart::Unknown { raw: kept } => assert_eq!(kept, &raw),
+        other => panic!("expected unknown part, got {other:?}"),
+    }
+    assert_eq!(serde_json::to_value(&part).expect("serialize"), raw);
+}
+
+#[test]
+fn known_stream_part_types_still_deserialize() {
+    let part: v2t::StreamPart =
+        serde_json::from_value(json!({"type": "text-delta", "id": "t1", "delta": "hi"}))
+            .expect("text delta");
+
+    assert!(matches!(
+        part,
+        v2t::StreamPart::TextDelta { ref id, ref delta, .. } if id == "t1" && delta == "hi"
+    ));
+}
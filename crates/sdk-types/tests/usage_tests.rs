@@ -0,0 +1,162 @@
+use super::{
+    apply_gemini_modality_details, apply_openai_modality_details, from_anthropic, from_openai,
+    token_count,
+};
+use crate::ai_sdk_types::catalog::ModelCost;
+use crate::ai_sdk_types::v2::Usage;
+use crate::ai_sdk_types::TokenUsage;
+use serde_json::json;
+
+#[test]
+fn token_count_accepts_integers_floats_and_numeric_strings() {
+    assert_eq!(token_count(&json!(42)), Some(42));
+    assert_eq!(token_count(&json!(41.6)), Some(42));
+    assert_eq!(token_count(&json!(12.0)), Some(12));
+    assert_eq!(token_count(&json!("17")), Some(17));
+    assert_eq!(token_count(&json!(" 9.4 ")), Some(9));
+}
+
+#[test]
+fn token_count_rejects_non_counts() {
+    assert_eq!(token_count(&json!(-3)), None);
+    assert_eq!(token_count(&json!(-0.5)), None);
+    assert_eq!(token_count(&json!("many")), None);
+    assert_eq!(token_count(&json!("NaN")), None);
+    assert_eq!(token_count(&json!(null)), None);
+    assert_eq!(token_count(&json!(true)), None);
+}
+
+#[test]
+fn openai_usage_tolerates_float_and_string_counts() {
+    let usage = from_openai(&json!({
+        "prompt_tokens": 10.0,
+        "completion_tokens": "5",
+        "total_tokens": "15.0",
+        "cache_read_tokens": 2.2
+    }))
+    .expect("usage");
+    assert_eq!(usage.input_tokens, 10);
+    assert_eq!(usage.output_tokens, 5);
+    assert_eq!(usage.total_tokens, 15);
+    assert_eq!(usage.cache_read_tokens, Some(2));
+}
+
+#[test]
+fn anthropic_usage_tolerates_float_and_string_counts() {
+    let usage = from_anthropic(&json!({
+        "input_tokens": "120",
+        "output_tokens": 30.0,
+        "cache_read_input_tokens": "8",
+        "cache_creation": {
+            "ephemeral_5m_input_tokens": 4.0,
+            "ephemeral_1h_input_tokens": "1"
+        }
+    }));
+    assert_eq!(usage.input_tokens, 120);
+    assert_eq!(usage.output_tokens, 30);
+    assert_eq!(usage.total_tokens, 150);
+    assert_eq!(usage.cache_read_tokens, Some(8));
+    assert_eq!(usage.cache_write_tokens, Some(5));
+}
+
+#[test]
+fn modality_details_fill_audio_and_image_counts() {
+    let mut usage = Usage::default();
+    apply_openai_modality_details(
+        &json!({
+            "prompt_tokens_details": { "audio_tokens": 30, "image_tokens": "12" },
+            "completion_tokens_details": { "audio_tokens": 8 }
+        }),
+        &mut usage,
+    );
+    assert_eq!(usage.audio_input_tokens, Some(30));
+    assert_eq!(usage.image_input_tokens, Some(12));
+    assert_eq!(usage.audio_output_tokens, Some(8));
+
+    let mut usage = Usage::default();
+    apply_gemini_modality_details(
+        &json!({
+            "promptTokensDetails": [
+                { "modality": "TEXT", "tokenCount": 5 },
+                { "modality": "IMAGE", "tokenCount": 258 }
+            ]
+        }),
+        &mut usage,
+    );
+    assert_eq!(usage.image_input_tokens, Some(258));
+    assert_eq!(usage.audio_input_tokens, None);
+    assert_eq!(usage.audio_output_tokens, None);
+}
+
+#[test]
+fn cost_estimate_bills_modalities_at_their_own_rates() {
+    let cost = ModelCost {
+        input: Some(2.0),
+        output: Some(8.0),
+        cache_read: Some(0.5),
+        audio_input: Some(40.0),
+        audio_output: Some(80.0),
+        ..Default::default()
+    };
+    let usage = Usage {
+        input_tokens: Some(1_000_000),
+        output_tokens: Some(500_000),
+        cached_input_tokens: Some(100_000),
+        audio_input_tokens: Some(200_000),
+        image_input_tokens: Some(300_000),
+        audio_output_tokens: Some(250_000),
+        ..Default::default()
+    };
+    // text 400k * 2 + cached 100k * 0.5 + audio 200k * 40 + image 300k * 2 (input rate)
+    // + text out 250k * 8 + audio out 250k * 80
+    let expected = 0.8 + 0.05 + 8.0 + 0.6 + 2.0 + 20.0;
+    let estimate = cost.estimate(&usage).expect("estimate");
+    assert!((estimate - expected).abs() < 1e-9, "{estimate}");
+
+    assert_eq!(ModelCost::default().estimate(&usage), None);
+}
+
+#[test]
+fn token_usage_addition_merges_cache_fields() {
+    let first = TokenUsage {
+        cache_read_tokens: Some(80),
+        ..TokenUsage::new(100, 20)
+    };
+    let second = TokenUsage {
+        cache_write_tokens: Some(12),
+        ..TokenUsage::new(40, 10)
+    };
+
+    let sum = first.clone() + second.clone();
+    assert_eq!(sum.input_tokens, 140);
+    assert_eq!(sum.output_tokens, 30);
+    assert_eq!(sum.total_tokens, 170);
+    assert_eq!(sum.cache_read_tokens, Some(80));
+    assert_eq!(sum.cache_write_tokens, Some(12));
+
+    let mut total = TokenUsage::default();
+    for step in [&first, &second, &first] {
+        total.accumulate(step);
+    }
+    assert_eq!(total.cache_read_tokens, Some(160));
+    assert_eq!(total.cache_write_tokens, Some(12));
+    assert_eq!(total.total_tokens, 290);
+}
+
+#[test]
+fn token_usage_addition_keeps_missing_cache_fields_none() {
+    let mut usage = TokenUsage::new(5, 5);
+    usage += TokenUsage::new(3, 2);
+    usage += &TokenUsage::default();
+
+    assert_eq!(usage.cache_read_tokens, None);
+    assert_eq!(usage.cache_write_tokens, None);
+    assert_eq!(usage.total_tokens, 15);
+
+    // A stale total is replaced by input + output.
+    let stale = TokenUsage {
+        total_tokens: 999,
+        ..TokenUsage::new(1, 1)
+    };
+    assert_eq!((stale + TokenUsage::new(1, 1)).total_tokens, 4);
+}
@@ -1,4 +1,4 @@
-use crate::ai_sdk_streaming_sse::SseDecoder;
+use crate::ai_sdk_streaming_sse::{SseDecoder, SseError, SseOverflowPolicy};
 use bytes::Bytes;
 
 #[test]
@@ -70,6 +70,25 @@ fn test_crlf_split_across_chunks() {
     assert_eq!(events_second[0].data, Bytes::from("chunked"));
 }
 
+#[test]
+fn test_leading_bom_is_stripped() {
+    let mut decoder = SseDecoder::new();
+    let events: Vec<_> = decoder
+        .push(b"\xEF\xBB\xBFevent: start\r\ndata: first\r\n\r\n")
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event, Some("start".to_string()));
+    assert_eq!(events[0].data, Bytes::from("first"));
+
+    let mut decoder = SseDecoder::new();
+    assert_eq!(decoder.push(b"\xEF").count(), 0);
+    assert_eq!(decoder.push(b"\xBB\xBFdata: split\n").count(), 0);
+    let events: Vec<_> = decoder.push(b"\n\xEF\xBB\xBFdata: later\n\n").collect();
+    // Only the stream start is checked; a later BOM corrupts its field name.
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, Bytes::from("split"));
+}
+
 #[test]
 fn test_multiple_events() {
     let mut decoder = SseDecoder::new();
@@ -114,6 +133,25 @@ fn test_field_without_colon() {
     assert_eq!(events[0].event, Some("".to_string()));
 }
 
+#[test]
+fn test_empty_data_heartbeat_is_skipped() {
+    let mut decoder = SseDecoder::new();
+    let data = b"data: \n\ndata:\ndata: {\"ok\":true}\n\n";
+    let events: Vec<_> = decoder.push(data).collect();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, Bytes::from("{\"ok\":true}"));
+}
+
+#[test]
+fn test_blank_data_line_inside_payload_is_kept() {
+    let mut decoder = SseDecoder::new();
+    let events: Vec<_> = decoder.push(b"data: a\ndata:\ndata: b\n\n").collect();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, Bytes::from("a\n\nb"));
+}
+
 #[test]
 fn test_very_chunked_multiline() {
     let mut decoder = SseDecoder::new();
@@ -127,3 +165,140 @@ fn test_very_chunked_multiline() {
     let events: Vec<_> = decoder.push(b"").collect();
     assert_eq!(events.len(), 0);
 }
+
+#[test]
+fn test_backpressure_cap_decodes_frames_as_consumed() {
+    let mut decoder = SseDecoder::new().with_max_queued_events(2, SseOverflowPolicy::Backpressure);
+    let data = b"data: 1\n\ndata: 2\n\ndata: 3\n\ndata: 4\n\ndata: 5\n\n";
+
+    {
+        let mut events = decoder.push(data);
+        assert_eq!(events.next().map(|e| e.data), Some(Bytes::from("1")));
+    }
+    assert!(decoder.has_buffered_data());
+
+    let rest: Vec<_> = decoder.push(b"").map(|e| e.data).collect();
+    assert_eq!(rest, ["2", "3", "4", "5"]);
+    assert!(!decoder.has_buffered_data());
+}
+
+#[test]
+fn test_error_cap_rejects_oversized_chunk() {
+    let mut decoder = SseDecoder::new().with_max_queued_events(2, SseOverflowPolicy::Error);
+    assert_eq!(
+        decoder
+            .try_push(b"data: 1\n\ndata: 2\n\n")
+            .expect("within cap")
+            .count(),
+        2
+    );
+
+    let err = decoder
+        .try_push(b"data: 1\n\ndata: 2\n\ndata: 3\n\n")
+        .err()
+        .expect("over cap");
+    assert!(err.to_string().contains("exceeded 2 events"));
+}
+
+#[test]
+fn test_unbounded_by_default() {
+    let mut decoder = SseDecoder::new();
+    let data = "data: x\n\n".repeat(64);
+    assert_eq!(
+        decoder
+            .try_push(data.as_bytes())
+            .expect("unbounded")
+            .count(),
+        64
+    );
+}
+
+#[test]
+fn test_frame_limit_rejects_unterminated_stream() {
+    let mut decoder = SseDecoder::with_limit(64 * 1024);
+    let chunk = [b'x'; 16 * 1024];
+    let mut prefix = b"data: ".to_vec();
+    prefix.extend_from_slice(&chunk);
+
+    let err = std::iter::once(prefix.as_slice())
+        .chain(std::iter::repeat_n(&chunk[..], 63))
+        .find_map(|chunk| decoder.try_push(chunk).err())
+        .expect("1MB without a terminator must fail");
+    assert!(matches!(
+        err,
+        SseError::FrameTooLarge { limit: 65536, buffered } if buffered > 65536
+    ));
+
+    // The oversized frame is dropped and decoding resumes with the next one.
+    assert!(!decoder.has_buffered_data());
+    let events: Vec<_> = decoder
+        .try_push(b"data: ok\n\n")
+        .expect("fresh frame")
+        .collect();
+    assert_eq!(events[0].data, Bytes::from("ok"));
+}
+
+#[test]
+fn test_frame_limit_push_drops_oversized_frame() {
+    let mut decoder = SseDecoder::with_limit(16);
+    assert_eq!(decoder.push(b"data: 0123456789abcdefgh").count(), 0);
+    assert!(!decoder.has_buffered_data());
+
+    let long_but_terminated = b"data: 0123456789abcdefgh\n\n";
+    assert_eq!(decoder.push(long_but_terminated).count(), 1);
+}
+
+#[test]
+fn test_frame_limit_caps_frames_held_behind_a_full_queue() {
+    let mut decoder =
+        SseDecoder::with_limit(32).with_max_queued_events(1, SseOverflowPolicy::Backpressure);
+    // One event is queued and the second frame waits undecoded.
+    let _ = decoder
+        .try_push(b"data: a\n\ndata: b\n\n")
+        .expect("under the cap");
+
+    let err = match decoder.try_push(b"data: cccccccccccccccc\n\n") {
+        Ok(_) => panic!("backlog past the cap must fail"),
+        Err(err) => err,
+    };
+    assert!(matches!(err, SseError::BufferFull { limit: 32, buffered } if buffered > 32));
+
+    // Nothing was lost: draining the queue makes room for the same chunk.
+    let drained: Vec<_> = decoder.push(b"").collect();
+    assert_eq!(drained.len(), 2);
+    let events: Vec<_> = decoder
+        .try_push(b"data: cccccccccccccccc\n\n")
+        .expect("room after draining")
+        .collect();
+    assert_eq!(events[0].data, Bytes::from("cccccccccccccccc"));
+}
+
+#[test]
+fn test_last_event_id_updates_per_event() {
+    let mut decoder = SseDecoder::new();
+    assert_eq!(decoder.last_event_id(), None);
+
+    assert_eq!(decoder.push(b"id: 1\ndata: a\n\n").count(), 1);
+    assert_eq!(decoder.last_event_id(), Some("1"));
+
+    // Frames without an id, or with an empty one, keep the previous id.
+    assert_eq!(decoder.push(b"data: b\n\nid:\ndata: c\n\n").count(), 2);
+    assert_eq!(decoder.last_event_id(), Some("1"));
+
+    assert_eq!(decoder.push(b"id: 2\ndata: d\n\n").count(), 1);
+    assert_eq!(decoder.last_event_id(), Some("2"));
+}
+
+#[test]
+fn test_last_event_id_survives_partial_frames() {
+    let mut decoder = SseDecoder::new();
+    assert_eq!(decoder.push(b"id: 7\ndata: a\n\nid: 8\nda").count(), 1);
+    assert_eq!(decoder.last_event_id(), Some("7"));
+
+    assert_eq!(decoder.push(b"ta: b").count(), 0);
+    assert_eq!(decoder.last_event_id(), Some("7"));
+
+    let events: Vec<_> = decoder.push(b"\n\n").collect();
+    assert_eq!(events[0].id.as_deref(), Some("8"));
+    assert_eq!(decoder.last_event_id(), Some("8"));
+}
@@ -1,5 +1,7 @@
 use crate::ai_sdk_core::SdkError;
-use crate::ai_sdk_streaming_sse::{sse_to_events, PipelineBuilder, ProviderChunk, SseEvent};
+use crate::ai_sdk_streaming_sse::{
+    sse_to_events, PipelineBuilder, ProviderChunk, SseEvent, SseOverflowPolicy,
+};
 use crate::ai_sdk_types::Event;
 use bytes::Bytes;
 use futures_util::stream;
@@ -95,3 +97,29 @@ async fn done_passes_through() {
     assert_eq!(events.len(), 1);
     assert!(matches!(events.first(), Some(Event::Done)));
 }
+
+#[tokio::test]
+async fn queue_cap_errors_or_applies_backpressure() {
+    let burst = || {
+        stream::iter(vec![Ok::<Bytes, SdkError>(Bytes::from(
+            "data: a\n\ndata: b\n\ndata: c\n\ndata: done\n\n",
+        ))])
+    };
+
+    let results: Vec<_> = PipelineBuilder::<TestProvider>::new()
+        .max_queued_events(2, SseOverflowPolicy::Error)
+        .build(burst())
+        .collect()
+        .await;
+    assert_eq!(results.len(), 1);
+    assert!(matches!(&results[0], Err(err) if err.to_string().contains("SSE event queue")));
+
+    let events: Vec<Event> = PipelineBuilder::<TestProvider>::new()
+        .max_queued_events(1, SseOverflowPolicy::Backpressure)
+        .build(burst())
+        .map(|res| res.expect("event"))
+        .collect()
+        .await;
+    assert_eq!(events.len(), 4);
+    assert!(matches!(events.last(), Some(Event::Done)));
+}
@@ -49,3 +49,73 @@ async fn test_stream_with_errors() {
 
     assert!(sse_stream.next().await.is_none());
 }
+
+#[tokio::test]
+async fn test_reconnect_receives_last_event_id() {
+    use crate::ai_sdk_streaming_sse::stream::SseStream;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    let dial = |chunks: Vec<Result<Bytes, io::Error>>| stream::iter(chunks);
+    let first = dial(vec![
+        Ok(Bytes::from("id: 1\ndata: a\n\n")),
+        Ok(Bytes::from("id: 2\ndata: cut")),
+        Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")),
+    ]);
+    let redials = Arc::new(Mutex::new(Vec::new()));
+    let seen = redials.clone();
+    let mut sse_stream = SseStream::with_reconnect(first, move |retry| {
+        let mut seen = seen.lock().unwrap();
+        seen.push((
+            retry.attempt,
+            retry.last_event_id.map(str::to_string),
+            retry.error.to_string(),
+        ));
+        (seen.len() == 1).then(|| {
+            dial(vec![
+                Ok(Bytes::from("id: 2\ndata: b\n\n")),
+                Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")),
+            ])
+        })
+    });
+
+    let data: Vec<Bytes> = (&mut sse_stream)
+        .take(2)
+        .map(|event| event.unwrap().data)
+        .collect()
+        .await;
+    assert_eq!(data, ["a", "b"]);
+    assert_eq!(sse_stream.last_event_id(), Some("2"));
+
+    assert!(sse_stream.next().await.unwrap().is_err());
+    assert_eq!(
+        *redials.lock().unwrap(),
+        [
+            (1, Some("1".to_string()), "reset".to_string()),
+            (1, Some("2".to_string()), "reset".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_reconnect_attempts_count_until_an_event_arrives() {
+    use crate::ai_sdk_streaming_sse::stream::SseStream;
+    use std::io;
+
+    let failing = || {
+        stream::iter(vec![Err::<Bytes, _>(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            "refused",
+        ))])
+    };
+    let mut attempts = Vec::new();
+    let mut sse_stream = SseStream::with_reconnect(failing(), move |retry| {
+        attempts.push(retry.attempt);
+        assert_eq!(attempts, (1..=retry.attempt).collect::<Vec<_>>());
+        (retry.attempt < 3).then(failing)
+    });
+
+    let error = sse_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+    assert!(sse_stream.next().await.is_none());
+}
@@ -0,0 +1,117 @@
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_streaming_sse::ui_message::{
+    sse_data_frame, ui_message_chunk, ui_message_error_chunk,
+};
+use crate::ai_sdk_types::v2 as v2t;
+use serde_json::json;
+
+#[test]
+fn encodes_text_and_reasoning_parts() {
+    let text = v2t::StreamPart::TextDelta {
+        id: "t1".into(),
+        delta: "Hello".into(),
+        provider_metadata: None,
+    };
+    assert_eq!(
+        ui_message_chunk(&text),
+        Some(json!({"type": "text-delta", "id": "t1", "delta": "Hello"}))
+    );
+
+    let reasoning = v2t::StreamPart::ReasoningStart {
+        id: "r1".into(),
+        provider_metadata: None,
+    };
+    assert_eq!(
+        ui_message_chunk(&reasoning),
+        Some(json!({"type": "reasoning-start", "id": "r1"}))
+    );
+}
+
+#[test]
+fn encodes_tool_lifecycle() {
+    let start = v2t::StreamPart::ToolInputStart {
+        id: "call-1".into(),
+        tool_name: "weather".into(),
+        provider_executed: false,
+        provider_metadata: None,
+    };
+    assert_eq!(
+        ui_message_chunk(&start),
+        Some(json!({
+            "type": "tool-input-start",
+            "toolCallId": "call-1",
+            "toolName": "weather",
+            "providerExecuted": false
+        }))
+    );
+
+    let delta = v2t::StreamPart::ToolInputDelta {
+        id: "call-1".into(),
+        delta: "{\"city\"".into(),
+        provider_executed: false,
+        provider_metadata: None,
+    };
+    assert_eq!(
+        ui_message_chunk(&delta),
+        Some(
+            json!({"type": "tool-input-delta", "toolCallId": "call-1", "inputTextDelta": "{\"city\""})
+        )
+    );
+
+    let call = v2t::StreamPart::ToolCall(v2t::ToolCallPart {
+        tool_call_id: "call-1".into(),
+        tool_name: "weather".into(),
+        input: "{\"city\":\"SF\"}".into(),
+        provider_executed: false,
+        provider_metadata: None,
+        dynamic: false,
+        provider_options: None,
+    });
+    assert_eq!(
+        ui_message_chunk(&call),
+        Some(json!({
+            "type": "tool-input-available",
+            "toolCallId": "call-1",
+            "toolName": "weather",
+            "input": {"city": "SF"},
+            "providerExecuted": false
+        }))
+    );
+
+    let failed = v2t::StreamPart::ToolResult {
+        tool_call_id: "call-1".into(),
+        tool_name: "weather".into(),
+        result: json!({"message": "boom"}),
+        is_error: true,
+        preliminary: false,
+        provider_metadata: None,
+    };
+    assert_eq!(
+        ui_message_chunk(&failed),
+        Some(json!({"type": "tool-output-error", "toolCallId": "call-1", "errorText": "boom"}))
+    );
+}
+
+#[test]
+fn skips_parts_without_client_equivalent() {
+    let raw = v2t::StreamPart::Raw {
+        raw_value: json!({}),
+    };
+    assert_eq!(ui_message_chunk(&raw), None);
+    let end = v2t::StreamPart::ToolInputEnd {
+        id: "call-1".into(),
+        provider_executed: false,
+        provider_metadata: None,
+    };
+    assert_eq!(ui_message_chunk(&end), None);
+}
+
+#[test]
+fn frames_errors_as_sse_data() {
+    let chunk = ui_message_error_chunk(&SdkError::Timeout);
+    assert_eq!(chunk, json!({"type": "error", "errorText": "timeout"}));
+    assert_eq!(
+        sse_data_frame(&chunk.to_string()),
+        "data: {\"type\":\"error\",\"errorText\":\"timeout\"}\n\n"
+    );
+}
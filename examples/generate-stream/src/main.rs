@@ -138,7 +138,8 @@ fn handle_stream_part(part: v2t::StreamPart, state: &mut StreamDisplayState) ->
         | v2t::StreamPart::StreamStart { .. }
         | v2t::StreamPart::ReasoningSignature { .. }
         | v2t::StreamPart::SourceUrl { .. }
-        | v2t::StreamPart::File { .. } => {}
+        | v2t::StreamPart::File { .. }
+        | v2t::StreamPart::Unknown { .. } => {}
     }
     false
 }
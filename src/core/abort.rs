@@ -0,0 +1,70 @@
+//! Caller-side cancellation of model calls.
+//!
+//! Set with [`v2t::CallOptions::abort`]. Providers hand the token to the
+//! transport through [`call_transport_config`], so cancelling aborts the HTTP
+//! request and the body read, and wrap their part stream with
+//! [`StreamResponse::with_abort`] so the caller sees the stream end with an
+//! "aborted" error part right away.
+
+use std::borrow::Cow;
+
+use futures_util::future::{self, Either};
+use futures_util::StreamExt;
+
+use crate::core::transport::{CancelToken, TransportConfig};
+use crate::core::v2::{PartStream, StreamResponse};
+use crate::types::v2 as v2t;
+
+/// Message of the error part ending an aborted stream.
+pub const ABORTED_MESSAGE: &str = "aborted";
+
+/// Transport settings for one call: `cfg` with the call's abort token as its
+/// [`TransportConfig::cancel`], which takes precedence over a token already
+/// set there.
+pub fn call_transport_config<'a>(
+    cfg: &'a TransportConfig,
+    options: &v2t::CallOptions,
+) -> Cow<'a, TransportConfig> {
+    match &options.abort {
+        Some(token) => Cow::Owned(TransportConfig {
+            cancel: Some(token.clone()),
+            ..cfg.clone()
+        }),
+        None => Cow::Borrowed(cfg),
+    }
+}
+
+impl StreamResponse {
+    /// End the stream once `abort` is cancelled; see [`abort_parts`].
+    pub fn with_abort(mut self, abort: Option<&CancelToken>) -> Self {
+        if let Some(token) = abort {
+            self.stream = abort_parts(self.stream, token.clone());
+        }
+        self
+    }
+}
+
+/// Forward `parts` until `token` is cancelled, then drop the upstream, which
+/// cancels the provider request, and end with an [`ABORTED_MESSAGE`] error
+/// part.
+pub fn abort_parts(parts: PartStream, token: CancelToken) -> PartStream {
+    Box::pin(async_stream::stream! {
+        let mut parts = parts;
+        loop {
+            let cancelled = std::pin::pin!(token.cancelled());
+            match future::select(cancelled, parts.next()).await {
+                Either::Left(_) => break,
+                Either::Right((Some(item), _)) => yield item,
+                Either::Right((None, _)) => return,
+            }
+        }
+        drop(parts);
+        yield Ok(v2t::StreamPart::Error {
+            error: serde_json::json!({ "message": ABORTED_MESSAGE }),
+        });
+    })
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/abort_tests.rs"]
+mod tests;
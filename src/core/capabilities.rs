@@ -13,6 +13,10 @@ pub struct ModelCapabilities {
     // Placeholder for future endpoint hints, etc.
     #[serde(default)]
     pub supports_responses_api: Option<bool>,
+    /// Most tokens one response may generate, from `limit.output` or
+    /// `limits.context_output`/`limits.output`.
+    #[serde(default)]
+    pub max_output_tokens: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -93,6 +97,23 @@ fn load_index() -> Option<Value> {
     Some(val)
 }
 
+fn index_max_output_tokens(model: &Value) -> Option<u64> {
+    let output = |limits: &Value, key: &str| limits.get(key).and_then(|v| v.as_u64());
+    if let Some(limit) = model.get("limit") {
+        if let Some(out) = output(limit, "output") {
+            return Some(out);
+        }
+    }
+    let limits = model.get("limits")?;
+    output(limits, "context_output").or_else(|| output(limits, "output"))
+}
+
+/// Most tokens one response of `model` may generate, as reported by the
+/// providers index.
+pub fn get_max_output_tokens(provider: &str, model: &str) -> Option<u64> {
+    get_model_capabilities(provider, model)?.max_output_tokens
+}
+
 /// Return selected model capabilities from the providers index, if available.
 pub fn get_model_capabilities(provider: &str, model: &str) -> Option<ModelCapabilities> {
     let val = load_index()?;
@@ -156,6 +177,7 @@ pub fn get_model_capabilities(provider: &str, model: &str) -> Option<ModelCapabi
                         reasoning,
                         temperature,
                         supports_responses_api,
+                        max_output_tokens: index_max_output_tokens(m),
                     });
                 }
             }
@@ -163,3 +185,7 @@ pub fn get_model_capabilities(provider: &str, model: &str) -> Option<ModelCapabi
     }
     None
 }
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/capabilities_tests.rs"]
+mod tests;
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::ai_sdk_types::embedding as embt;
 use crate::ai_sdk_types::v2 as v2t;
 
@@ -27,6 +29,77 @@ impl EmbedResponse {
     }
 }
 
+/// Distinct embedding inputs and where each original value maps among them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupedValues {
+    /// Unique values in order of first occurrence.
+    pub unique: Vec<String>,
+    /// For each original position, the index of its value in `unique`.
+    pub positions: Vec<usize>,
+}
+
+impl DedupedValues {
+    /// Collapse repeated values, keeping first-occurrence order.
+    pub fn new(values: Vec<String>) -> Self {
+        let mut seen: HashMap<String, usize> = HashMap::with_capacity(values.len());
+        let mut unique = Vec::new();
+        let positions = values
+            .into_iter()
+            .map(|value| {
+                *seen.entry(value).or_insert_with_key(|value| {
+                    unique.push(value.clone());
+                    unique.len() - 1
+                })
+            })
+            .collect();
+        Self { unique, positions }
+    }
+
+    /// Whether any value was repeated.
+    pub fn has_duplicates(&self) -> bool {
+        self.unique.len() < self.positions.len()
+    }
+
+    /// Map embeddings of the unique values back to every original position.
+    pub fn expand(
+        &self,
+        embeddings: Vec<embt::Embedding>,
+    ) -> Result<Vec<embt::Embedding>, SdkError> {
+        if embeddings.len() != self.unique.len() {
+            return Err(SdkError::Upstream {
+                status: 200,
+                message: format!(
+                    "expected {} embeddings for deduplicated inputs, got {}",
+                    self.unique.len(),
+                    embeddings.len()
+                ),
+                source: None,
+            });
+        }
+        Ok(self
+            .positions
+            .iter()
+            .map(|&index| embeddings[index].clone())
+            .collect())
+    }
+}
+
+/// Scale `embedding` to unit L2 length in place. Zero (and non-finite
+/// length) vectors are left unchanged.
+pub fn l2_normalize(embedding: &mut [f32]) {
+    let norm = embedding
+        .iter()
+        .map(|&x| f64::from(x) * f64::from(x))
+        .sum::<f64>()
+        .sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return;
+    }
+    for x in embedding.iter_mut() {
+        *x = (f64::from(*x) / norm) as f32;
+    }
+}
+
 /// Embedding model interface (parity with Vercel EmbeddingModelV3).
 #[async_trait::async_trait]
 pub trait EmbeddingModel: Send + Sync {
@@ -49,3 +122,7 @@ pub trait EmbeddingModel: Send + Sync {
 
     async fn do_embed(&self, options: embt::EmbedOptions) -> Result<EmbedResponse, SdkError>;
 }
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/embedding_tests.rs"]
+mod tests;
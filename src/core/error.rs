@@ -1,4 +1,5 @@
 use serde_json::Value;
+use std::sync::OnceLock;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -13,6 +14,14 @@ pub enum SdkError {
         #[source]
         source: Option<Box<TransportError>>,
     },
+    /// Hard quota or billing exhaustion reported with a 429; retrying will
+    /// not succeed until the account is topped up.
+    #[error("quota exceeded: {message}")]
+    QuotaExceeded {
+        message: String,
+        #[source]
+        source: Option<Box<TransportError>>,
+    },
     #[error("timeout")]
     Timeout,
     #[error("cancelled")]
@@ -79,6 +88,7 @@ impl SdkError {
 
                 msg
             }
+            SdkError::QuotaExceeded { message, .. } => format!("quota exceeded: {}", message),
             SdkError::Timeout => "timeout".to_string(),
             SdkError::Unauthorized => "unauthorized".to_string(),
             SdkError::Cancelled => "cancelled".to_string(),
@@ -130,6 +140,17 @@ pub enum TransportError {
     BodyRead(String),
     #[error("stream closed")]
     StreamClosed,
+    /// The request's [`CancelToken`](crate::core::transport::CancelToken)
+    /// was triggered.
+    #[error("request cancelled")]
+    Cancelled,
+    /// The response body passed `TransportConfig::max_response_bytes`.
+    #[error("response body exceeds {limit} bytes ({seen} read)")]
+    BodyTooLarge { limit: usize, seen: usize },
+    /// A compressed response decoded past
+    /// `TransportConfig::max_decompressed_bytes`.
+    #[error("decompressed response body exceeds {limit} bytes ({seen} decoded)")]
+    ResponseTooLarge { limit: usize, seen: usize },
     #[error("other: {0}")]
     Other(String),
 }
@@ -181,7 +202,8 @@ pub fn map_http_status_to_upstream_error(
     headers: Vec<(String, String)>,
     message: Option<String>,
 ) -> SdkError {
-    let fallback = http_status_fallback_message(status);
+    let fallback =
+        html_error_message(&headers, &body).unwrap_or_else(|| http_status_fallback_message(status));
     let source = build_http_status_transport_error(status, body, retry_after_ms, headers);
     SdkError::Upstream {
         status,
@@ -197,31 +219,257 @@ pub fn map_http_status_to_rate_limited_error(
     headers: Vec<(String, String)>,
 ) -> SdkError {
     let source = build_http_status_transport_error(status, body, retry_after_ms, headers);
-    SdkError::RateLimited {
-        retry_after_ms,
-        source: Some(Box::new(source)),
+    rate_limited_or_quota_error(retry_after_ms, source)
+}
+
+/// Error codes that mark a 429 as quota/billing exhaustion rather than a
+/// transient rate limit.
+const QUOTA_EXHAUSTED_CODES: &[&str] = &["insufficient_quota", "billing_hard_limit_reached"];
+
+/// Returns the upstream message when a 429 body reports quota exhaustion via
+/// an OpenAI-style `error.code` or `error.type`.
+pub fn quota_exhausted_message(body: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(body.trim()).ok()?;
+    let error = value.get("error").unwrap_or(&value);
+    let is_quota = ["code", "type"].iter().any(|key| {
+        error
+            .get(*key)
+            .and_then(Value::as_str)
+            .is_some_and(|code| QUOTA_EXHAUSTED_CODES.contains(&code))
+    });
+    if !is_quota {
+        return None;
+    }
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "quota exhausted".to_string()),
+    )
+}
+
+/// Map a 429 transport error to [`SdkError::QuotaExceeded`] when the body
+/// reports quota exhaustion, otherwise to [`SdkError::RateLimited`].
+pub fn rate_limited_or_quota_error(
+    retry_after_ms: Option<u64>,
+    source: TransportError,
+) -> SdkError {
+    let quota_message = match &source {
+        TransportError::HttpStatus { body, .. } => quota_exhausted_message(body),
+        _ => None,
+    };
+    match quota_message {
+        Some(message) => SdkError::QuotaExceeded {
+            message,
+            source: Some(Box::new(source)),
+        },
+        None => SdkError::RateLimited {
+            retry_after_ms,
+            source: Some(Box::new(source)),
+        },
+    }
+}
+
+/// Longest page title or text excerpt quoted from an HTML error page.
+const HTML_SNIPPET_CHARS: usize = 80;
+
+/// Targeted message for an HTML page returned where JSON was expected,
+/// usually a base URL pointing at a website or a proxy error page.
+///
+/// Detected from a `text/html` content type or an HTML document body. The
+/// message quotes the page title, or the start of its text, stripped of
+/// markup and truncated.
+pub fn html_error_message(headers: &[(String, String)], body: &str) -> Option<String> {
+    let html_content_type = headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type")
+            && value.to_ascii_lowercase().contains("text/html")
+    });
+    let head = body
+        .trim_start()
+        .chars()
+        .take(15)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if !html_content_type && !head.starts_with("<!doctype html") && !head.starts_with("<html") {
+        return None;
+    }
+    let message = "endpoint returned HTML, not JSON; check base_url";
+    match html_snippet(body) {
+        Some(snippet) => Some(format!("{message} ({snippet})")),
+        None => Some(message.to_string()),
+    }
+}
+
+fn html_snippet(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let title = lower.find("<title").and_then(|start| {
+        let open_end = start + lower[start..].find('>')? + 1;
+        let close = open_end + lower[open_end..].find("</title")?;
+        Some(&body[open_end..close])
+    });
+    let source = title.unwrap_or(body);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in source.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    let words = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if words.is_empty() {
+        return None;
+    }
+    let mut snippet: String = words.chars().take(HTML_SNIPPET_CHARS).collect();
+    if snippet.len() < words.len() {
+        snippet.push_str("...");
+    }
+    Some(snippet)
+}
+
+/// Keys redacted from error bodies unless configured otherwise.
+pub const DEFAULT_REDACTED_KEYS: &[&str] = &[
+    "api_key",
+    "authorization",
+    "token",
+    "secret",
+    "password",
+    "credential",
+    "credentials",
+    "cookie",
+];
+
+/// Placeholder written in place of redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// How [`display_body_for_error`] scrubs upstream error bodies before they
+/// end up in error messages.
+///
+/// JSON bodies are minified with the value of every key matching one of
+/// `keys` replaced by [`REDACTED`], at any depth. Matching ignores case, `-`
+/// and `_`, and accepts a key ending in the pattern, so `api_key` also covers
+/// `apiKey` and `x-api-key`. The result is cut to `max_len` characters.
+/// Non-JSON bodies are reported by size only.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorBodyRedaction {
+    pub keys: Vec<String>,
+    pub max_len: usize,
+}
+
+impl Default for ErrorBodyRedaction {
+    fn default() -> Self {
+        Self {
+            keys: DEFAULT_REDACTED_KEYS
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
+            max_len: 2048,
+        }
+    }
+}
+
+impl ErrorBodyRedaction {
+    /// Default keys plus `keys`.
+    pub fn with_keys<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
     }
+
+    fn is_redacted(&self, key: &str) -> bool {
+        let key = normalize_key(key);
+        self.keys.iter().any(|pattern| {
+            let pattern = normalize_key(pattern);
+            !pattern.is_empty() && key.ends_with(&pattern)
+        })
+    }
+
+    pub(crate) fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, entry) in map.iter_mut() {
+                    if self.is_redacted(key) {
+                        *entry = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(entry);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact(item)),
+            _ => {}
+        }
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|ch| *ch != '_' && *ch != '-')
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
 }
 
+static ERROR_BODY_REDACTION: OnceLock<ErrorBodyRedaction> = OnceLock::new();
+
+/// Set the process-wide redaction used for error bodies (one-time).
+pub fn set_error_body_redaction(redaction: ErrorBodyRedaction) -> bool {
+    ERROR_BODY_REDACTION.set(redaction).is_ok()
+}
+
+/// Sanitized, redacted form of an upstream error body, using the
+/// process-wide [`ErrorBodyRedaction`].
+///
+/// Used for `TransportError::HttpStatus::sanitized` on streaming and
+/// non-streaming calls alike.
 pub fn display_body_for_error(body: &str) -> String {
+    display_body_for_error_with(
+        body,
+        ERROR_BODY_REDACTION.get_or_init(ErrorBodyRedaction::default),
+    )
+}
+
+/// [`display_body_for_error`] with an explicit redaction policy.
+pub fn display_body_for_error_with(body: &str, redaction: &ErrorBodyRedaction) -> String {
+    if let Some(message) = html_error_message(&[], body) {
+        return message;
+    }
     let trimmed = body.trim();
     let looks_like_json = trimmed.starts_with('{') || trimmed.starts_with('[');
-    if looks_like_json {
-        match serde_json::from_str::<Value>(trimmed) {
-            Ok(v) => v.to_string(), // minified JSON
-            Err(_) => format!("{} bytes", body.len()),
-        }
-    } else {
-        format!("{} bytes", body.len())
+    if !looks_like_json {
+        return format!("{} bytes", body.len());
+    }
+    let Ok(mut value) = serde_json::from_str::<Value>(trimmed) else {
+        return format!("{} bytes", body.len());
+    };
+    redaction.redact(&mut value);
+    let minified = value.to_string();
+    if minified.chars().count() <= redaction.max_len {
+        return minified;
     }
+    let mut truncated: String = minified.chars().take(redaction.max_len).collect();
+    truncated.push_str("...");
+    truncated
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        build_http_status_transport_error, http_status_fallback_message,
-        map_http_status_to_rate_limited_error, map_http_status_to_upstream_error, SdkError,
-        TransportError,
+        build_http_status_transport_error, display_body_for_error, display_body_for_error_with,
+        html_error_message, http_status_fallback_message, map_http_status_to_rate_limited_error,
+        map_http_status_to_upstream_error, ErrorBodyRedaction, SdkError, TransportError,
     };
 
     #[test]
@@ -274,6 +522,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rate_limited_helper_detects_quota_exhaustion() {
+        let openai_quota = r#"{"error":{"message":"You exceeded your current quota.","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#;
+        match map_http_status_to_rate_limited_error(429, openai_quota.into(), None, Vec::new()) {
+            SdkError::QuotaExceeded { message, source } => {
+                assert_eq!(message, "You exceeded your current quota.");
+                assert!(source.is_some());
+            }
+            other => panic!("unexpected error variant: {other:?}"),
+        }
+
+        let billing = r#"{"error":{"code":"billing_hard_limit_reached"}}"#;
+        assert!(matches!(
+            map_http_status_to_rate_limited_error(429, billing.into(), None, Vec::new()),
+            SdkError::QuotaExceeded { ref message, .. } if message == "quota exhausted"
+        ));
+
+        let transient = r#"{"error":{"message":"Rate limit reached","type":"requests","code":"rate_limit_exceeded"}}"#;
+        assert!(matches!(
+            map_http_status_to_rate_limited_error(429, transient.into(), Some(10), Vec::new()),
+            SdkError::RateLimited {
+                retry_after_ms: Some(10),
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn rate_limited_helper_preserves_retry_after() {
         let mapped =
@@ -318,4 +593,81 @@ mod tests {
             other => panic!("unexpected transport variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn html_error_pages_get_a_targeted_message() {
+        let page = "<!DOCTYPE html>\n<html><head><title>404 Not Found</title></head>\n<body><h1>Not Found</h1><script>secret()</script></body></html>";
+        assert_eq!(
+            html_error_message(&[], page).as_deref(),
+            Some("endpoint returned HTML, not JSON; check base_url (404 Not Found)")
+        );
+        assert_eq!(
+            display_body_for_error(page),
+            "endpoint returned HTML, not JSON; check base_url (404 Not Found)"
+        );
+
+        let headers = vec![(
+            "Content-Type".to_string(),
+            "text/html; charset=utf-8".to_string(),
+        )];
+        let untitled = format!("<h1>Bad   Gateway</h1><p>{}</p>", "x".repeat(200));
+        let message = html_error_message(&headers, &untitled).expect("html content type");
+        assert!(message
+            .starts_with("endpoint returned HTML, not JSON; check base_url (Bad Gateway xxx"));
+        assert!(message.ends_with("...)"));
+
+        assert_eq!(html_error_message(&[], "{\"error\":\"nope\"}"), None);
+        assert_eq!(html_error_message(&[], "upstream timed out"), None);
+    }
+
+    #[test]
+    fn upstream_helper_names_html_pages_without_parsed_message() {
+        let mapped = map_http_status_to_upstream_error(
+            404,
+            "<html><title>Welcome to nginx!</title></html>".into(),
+            None,
+            vec![("content-type".into(), "text/html".into())],
+            None,
+        );
+        match mapped {
+            SdkError::Upstream {
+                status, message, ..
+            } => {
+                assert_eq!(status, 404);
+                assert_eq!(
+                    message,
+                    "endpoint returned HTML, not JSON; check base_url (Welcome to nginx!)"
+                );
+            }
+            other => panic!("unexpected error variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn error_bodies_mask_secret_keys() {
+        let echoed = r#"{"error":{"message":"bad request","request":{"model":"m","api_key":"sk-live","headers":{"Authorization":"Bearer sk-live","X-Api-Key":"k"},"messages":[{"accessToken":"t"}],"max_tokens":5}}}"#;
+        let display = display_body_for_error(echoed);
+        assert!(!display.contains("sk-live"), "{display}");
+        assert!(!display.contains("\"k\""), "{display}");
+        assert!(!display.contains("\"t\""), "{display}");
+        assert!(display.contains(r#""api_key":"[REDACTED]""#));
+        assert!(display.contains(r#""max_tokens":5"#));
+        assert!(display.contains(r#""message":"bad request""#));
+        assert_eq!(display_body_for_error("Bearer sk-live"), "14 bytes");
+
+        let redaction = ErrorBodyRedaction::default().with_keys(["input"]);
+        let display =
+            display_body_for_error_with(r#"{"input":"my secret prompt","n":1}"#, &redaction);
+        assert_eq!(display, r#"{"input":"[REDACTED]","n":1}"#);
+    }
+
+    #[test]
+    fn error_bodies_are_truncated_to_max_len() {
+        let redaction = ErrorBodyRedaction::default().with_max_len(10);
+        let body = format!(r#"{{"message":"{}"}}"#, "x".repeat(100));
+        assert_eq!(
+            display_body_for_error_with(&body, &redaction),
+            r#"{"message"..."#
+        );
+    }
 }
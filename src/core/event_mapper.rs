@@ -7,7 +7,8 @@ use futures_core::Stream;
 use futures_util::StreamExt;
 
 use crate::ai_sdk_core::error::SdkError;
-use crate::ai_sdk_core::v2::PartStream;
+use crate::ai_sdk_core::stop_on_tool_call::is_stopping_tool_call;
+use crate::ai_sdk_core::v2::{PartStream, StreamResponse};
 
 pub type ProviderMetadata = HashMap<String, HashMap<String, serde_json::Value>>;
 
@@ -61,6 +62,9 @@ pub struct EventMapperConfig<Extra> {
     pub finish_reason_fallback: v2t::FinishReason,
     pub initial_extra: Extra,
     pub hooks: EventMapperHooks<Extra>,
+    /// End the stream after the first client-executed tool call, keeping the
+    /// usage reported so far; see [`crate::ai_sdk_core::stop_on_tool_call`].
+    pub stop_on_first_tool_call: bool,
 }
 
 impl<Extra> StreamNormalizationState<Extra> {
@@ -196,6 +200,11 @@ impl<Extra> StreamNormalizationState<Extra> {
         }
     }
 
+    /// Returns true if a tool call with this id has started and not yet finished.
+    pub fn is_tool_call_open(&self, id: &str) -> bool {
+        self.tool_names.contains_key(id)
+    }
+
     pub fn start_tool_call(
         &mut self,
         id: String,
@@ -258,6 +267,18 @@ impl<Extra> StreamNormalizationState<Extra> {
         parts
     }
 
+    /// Close every open tool call with a `ToolInputEnd` and no `ToolCall`,
+    /// so inputs cut off by a failure are never handed to consumers as
+    /// complete calls.
+    pub fn abort_tool_calls(&mut self) -> Vec<v2t::StreamPart> {
+        let mut ids: Vec<String> = self.tool_names.drain().map(|(id, _)| id).collect();
+        ids.sort();
+        self.tool_args.clear();
+        ids.into_iter()
+            .map(|id| self.tool_input_end_part(id, false, None))
+            .collect()
+    }
+
     pub fn tool_input_end_part(
         &self,
         id: String,
@@ -333,6 +354,62 @@ impl<Extra> StreamNormalizationState<Extra> {
     }
 }
 
+/// `SourceUrl` parts already emitted in a response.
+///
+/// Two sources are the same when url, title and provider metadata all match,
+/// so citations of one page that quote different spans are kept apart.
+#[derive(Debug, Default)]
+pub struct SeenSources {
+    seen: Vec<(String, Option<String>, Option<ProviderMetadata>)>,
+}
+
+impl SeenSources {
+    /// Record `part`, returning true when it repeats an earlier source.
+    /// Parts other than `SourceUrl` are never repeats.
+    pub fn is_repeat(&mut self, part: &v2t::StreamPart) -> bool {
+        let v2t::StreamPart::SourceUrl {
+            url,
+            title,
+            provider_metadata,
+            ..
+        } = part
+        else {
+            return false;
+        };
+        let repeat = self
+            .seen
+            .iter()
+            .any(|(seen_url, seen_title, seen_metadata)| {
+                seen_url == url && seen_title == title && seen_metadata == provider_metadata
+            });
+        if !repeat {
+            self.seen
+                .push((url.clone(), title.clone(), provider_metadata.clone()));
+        }
+        repeat
+    }
+}
+
+/// Drop `SourceUrl` parts that repeat an earlier one; see [`SeenSources`].
+pub fn dedupe_sources(parts: PartStream) -> PartStream {
+    let mut seen = SeenSources::default();
+    Box::pin(parts.filter(move |item| {
+        let repeat = matches!(item, Ok(part) if seen.is_repeat(part));
+        futures_util::future::ready(!repeat)
+    }))
+}
+
+impl StreamResponse {
+    /// Emit each source once when `enabled`; see
+    /// [`v2t::CallOptions::dedupe_sources`].
+    pub fn with_dedupe_sources(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.stream = dedupe_sources(self.stream);
+        }
+        self
+    }
+}
+
 /// Map a provider `Event` stream into provider-agnostic `StreamPart`s with
 /// configurable hooks for provider metadata and finish reasoning.
 pub fn map_events_to_parts<Extra, S>(stream: S, mut cfg: EventMapperConfig<Extra>) -> PartStream
@@ -344,7 +421,10 @@ where
         let mut state = StreamNormalizationState::new(cfg.initial_extra);
         yield v2t::StreamPart::StreamStart { warnings: cfg.warnings };
 
-        futures_util::pin_mut!(stream);
+        let mut stream = Box::pin(stream);
+        let mut stopped = false;
+        let mut errored = false;
+        let mut finished = false;
         while let Some(evt) = stream.next().await {
             match evt? {
                 ProviderEvent::TextDelta { delta } => {
@@ -386,6 +466,12 @@ where
                     }
                 }
                 ProviderEvent::ToolCallStart { id, name } => {
+                    // Some backends replay the start event for a call that is
+                    // already open (e.g. after an SSE reconnect); keep the
+                    // first one so consumers never register a duplicate call.
+                    if state.is_tool_call_open(&id) {
+                        continue;
+                    }
                     let treat_as_text = cfg.treat_tool_names_as_text.contains(&name);
                     if treat_as_text {
                         state.tool_names.insert(id.clone(), name.clone());
@@ -441,6 +527,7 @@ where
                             None
                         };
                         for part in state.finish_tool_call(id, false, None, md, false, None) {
+                            stopped |= cfg.stop_on_first_tool_call && is_stopping_tool_call(&part);
                             yield part;
                         }
                     }
@@ -455,31 +542,63 @@ where
                     if let Some(f) = cfg.hooks.data.as_mut() {
                         if let Some(extra_parts) = f(&mut state, &key, &value) {
                             for part in extra_parts {
+                                stopped |=
+                                    cfg.stop_on_first_tool_call && is_stopping_tool_call(&part);
+                                errored |= matches!(part, v2t::StreamPart::Error { .. });
                                 yield part;
                             }
                         }
                     }
                 }
-                ProviderEvent::Retrying { .. } => {}
+                ProviderEvent::Retrying { .. } | ProviderEvent::Unknown { .. } => {}
                 ProviderEvent::Error { message } => {
+                    errored = true;
                     yield v2t::StreamPart::Error {
                         error: serde_json::json!({"message": message}),
                     };
                 }
                 ProviderEvent::Done => {
+                    finished = true;
+                    if errored {
+                        for part in state.abort_tool_calls() {
+                            yield part;
+                        }
+                    }
                     let finish = cfg.hooks.finish.as_ref().map(|f| f(&state));
                     for part in state.finish_stream(finish, cfg.finish_reason_fallback.clone()) {
                         yield part;
                     }
                 }
             }
+            if stopped {
+                break;
+            }
+        }
+
+        if stopped {
+            // Dropping the upstream cancels the provider request.
+            drop(stream);
+            let finish = Some((v2t::FinishReason::ToolCalls, None));
+            for part in state.finish_stream(finish, cfg.finish_reason_fallback.clone()) {
+                yield part;
+            }
+        } else if errored && !finished {
+            // A mid-stream error ended the provider stream; close what is
+            // open and finish with the usage seen so far.
+            for part in state.abort_tool_calls() {
+                yield part;
+            }
+            let finish = Some((v2t::FinishReason::Error, None));
+            for part in state.finish_stream(finish, cfg.finish_reason_fallback.clone()) {
+                yield part;
+            }
         }
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{map_events_to_parts, EventMapperConfig, EventMapperHooks};
+    use super::{dedupe_sources, map_events_to_parts, EventMapperConfig, EventMapperHooks};
     use crate::ai_sdk_types::v2 as v2t;
     use crate::ai_sdk_types::{Event, TokenUsage};
     use futures_util::{stream, TryStreamExt};
@@ -524,6 +643,7 @@ mod tests {
                 finish_reason_fallback: v2t::FinishReason::Stop,
                 initial_extra: (),
                 hooks: EventMapperHooks::default(),
+                stop_on_first_tool_call: false,
             },
         )
         .try_collect()
@@ -586,4 +706,280 @@ mod tests {
             ])
         );
     }
+
+    #[tokio::test]
+    async fn duplicate_tool_call_start_for_open_call_is_ignored() {
+        let stream = stream::iter(vec![
+            Ok(Event::ToolCallStart {
+                id: "tool-1".into(),
+                name: "weather".into(),
+            }),
+            Ok(Event::ToolCallDelta {
+                id: "tool-1".into(),
+                args_json: "{\"city\":".into(),
+            }),
+            Ok(Event::ToolCallStart {
+                id: "tool-1".into(),
+                name: "weather".into(),
+            }),
+            Ok(Event::ToolCallDelta {
+                id: "tool-1".into(),
+                args_json: "\"SF\"}".into(),
+            }),
+            Ok(Event::ToolCallEnd {
+                id: "tool-1".into(),
+            }),
+            Ok(Event::Done),
+        ]);
+
+        let parts: Vec<v2t::StreamPart> = map_events_to_parts(
+            stream,
+            EventMapperConfig {
+                warnings: vec![],
+                treat_tool_names_as_text: HashSet::new(),
+                default_text_id: "text-1",
+                finish_reason_fallback: v2t::FinishReason::Stop,
+                initial_extra: (),
+                hooks: EventMapperHooks::default(),
+                stop_on_first_tool_call: false,
+            },
+        )
+        .try_collect()
+        .await
+        .expect("stream parts");
+
+        let starts = parts
+            .iter()
+            .filter(|part| matches!(part, v2t::StreamPart::ToolInputStart { .. }))
+            .count();
+        assert_eq!(starts, 1);
+
+        let calls: Vec<&v2t::ToolCallPart> = parts
+            .iter()
+            .filter_map(|part| match part {
+                v2t::StreamPart::ToolCall(call) => Some(call),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].tool_call_id, "tool-1");
+        assert_eq!(calls[0].input, "{\"city\":\"SF\"}");
+    }
+
+    #[tokio::test]
+    async fn dropping_parts_drops_source_stream() {
+        use futures_util::StreamExt;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        struct DropFlag(Arc<AtomicBool>);
+
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = DropFlag(dropped.clone());
+        let source = stream::iter(vec![Ok(Event::TextDelta { delta: "hi".into() })])
+            .chain(stream::pending())
+            .map(move |item| {
+                let _ = &guard;
+                item
+            });
+
+        let mut parts = map_events_to_parts(
+            source,
+            EventMapperConfig {
+                warnings: vec![],
+                treat_tool_names_as_text: HashSet::new(),
+                default_text_id: "text-1",
+                finish_reason_fallback: v2t::FinishReason::Stop,
+                initial_extra: (),
+                hooks: EventMapperHooks::default(),
+                stop_on_first_tool_call: false,
+            },
+        );
+        while let Some(part) = parts.next().await {
+            if matches!(part.expect("part"), v2t::StreamPart::TextDelta { .. }) {
+                break;
+            }
+        }
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(parts);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn stop_on_first_tool_call_keeps_partial_usage() {
+        let source = stream::iter(vec![
+            Ok(Event::Usage {
+                usage: TokenUsage {
+                    input_tokens: 7,
+                    output_tokens: 0,
+                    total_tokens: 7,
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                },
+            }),
+            Ok(Event::TextDelta {
+                delta: "Checking".into(),
+            }),
+            Ok(Event::ToolCallStart {
+                id: "tool-1".into(),
+                name: "weather".into(),
+            }),
+            Ok(Event::ToolCallDelta {
+                id: "tool-1".into(),
+                args_json: "{}".into(),
+            }),
+            Ok(Event::ToolCallEnd {
+                id: "tool-1".into(),
+            }),
+            Ok(Event::TextDelta {
+                delta: "trailing".into(),
+            }),
+            Ok(Event::Done),
+        ]);
+
+        let parts: Vec<v2t::StreamPart> = map_events_to_parts(
+            source,
+            EventMapperConfig {
+                warnings: vec![],
+                treat_tool_names_as_text: HashSet::new(),
+                default_text_id: "text-1",
+                finish_reason_fallback: v2t::FinishReason::Stop,
+                initial_extra: (),
+                hooks: EventMapperHooks::default(),
+                stop_on_first_tool_call: true,
+            },
+        )
+        .try_collect()
+        .await
+        .expect("stream parts");
+
+        assert!(!parts.iter().any(
+            |part| matches!(part, v2t::StreamPart::TextDelta { delta, .. } if delta == "trailing")
+        ));
+        assert!(matches!(
+            parts.last(),
+            Some(v2t::StreamPart::Finish {
+                usage: v2t::Usage {
+                    input_tokens: Some(7),
+                    ..
+                },
+                finish_reason: v2t::FinishReason::ToolCalls,
+                ..
+            })
+        ));
+        let finishes = parts
+            .iter()
+            .filter(|part| matches!(part, v2t::StreamPart::Finish { .. }))
+            .count();
+        assert_eq!(finishes, 1);
+    }
+
+    #[tokio::test]
+    async fn mid_stream_error_closes_open_parts_and_finishes_with_error() {
+        let source = stream::iter(vec![
+            Ok(Event::Usage {
+                usage: TokenUsage {
+                    input_tokens: 5,
+                    output_tokens: 2,
+                    total_tokens: 7,
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                },
+            }),
+            Ok(Event::TextDelta {
+                delta: "partial".into(),
+            }),
+            Ok(Event::ToolCallStart {
+                id: "tool-1".into(),
+                name: "weather".into(),
+            }),
+            Ok(Event::ToolCallDelta {
+                id: "tool-1".into(),
+                args_json: "{\"ci".into(),
+            }),
+            Ok(Event::Error {
+                message: "upstream failed".into(),
+            }),
+        ]);
+
+        let parts: Vec<v2t::StreamPart> = map_events_to_parts(
+            source,
+            EventMapperConfig {
+                warnings: vec![],
+                treat_tool_names_as_text: HashSet::new(),
+                default_text_id: "text-1",
+                finish_reason_fallback: v2t::FinishReason::Stop,
+                initial_extra: (),
+                hooks: EventMapperHooks::default(),
+                stop_on_first_tool_call: false,
+            },
+        )
+        .try_collect()
+        .await
+        .expect("stream parts");
+
+        let tail: Vec<&v2t::StreamPart> = parts.iter().rev().take(4).rev().collect();
+        assert!(matches!(tail[0], v2t::StreamPart::Error { .. }));
+        assert!(matches!(tail[1], v2t::StreamPart::ToolInputEnd { id, .. } if id == "tool-1"));
+        assert!(matches!(tail[2], v2t::StreamPart::TextEnd { id, .. } if id == "text-1"));
+        assert!(matches!(
+            tail[3],
+            v2t::StreamPart::Finish {
+                usage: v2t::Usage {
+                    input_tokens: Some(5),
+                    output_tokens: Some(2),
+                    ..
+                },
+                finish_reason: v2t::FinishReason::Error,
+                ..
+            }
+        ));
+        assert!(!parts
+            .iter()
+            .any(|part| matches!(part, v2t::StreamPart::ToolCall(_))));
+    }
+
+    fn source(id: &str, url: &str, title: &str, quote: Option<&str>) -> v2t::StreamPart {
+        v2t::StreamPart::SourceUrl {
+            id: id.into(),
+            url: url.into(),
+            title: Some(title.into()),
+            provider_metadata: quote.map(|quote| {
+                std::collections::HashMap::from([(
+                    "openai".to_string(),
+                    std::collections::HashMap::from([("quote".to_string(), json!(quote))]),
+                )])
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn dedupe_sources_keeps_first_of_identical_citations() {
+        let parts = vec![
+            source("s1", "https://a.test", "A", None),
+            source("s2", "https://a.test", "A", None),
+            source("s3", "https://a.test", "A (other)", None),
+            source("s4", "https://a.test", "A", Some("first span")),
+            source("s5", "https://a.test", "A", Some("second span")),
+            source("s6", "https://a.test", "A", Some("first span")),
+            source("s7", "https://b.test", "A", None),
+        ];
+        let kept: Vec<String> = dedupe_sources(Box::pin(stream::iter(parts.into_iter().map(Ok))))
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("stream parts")
+            .into_iter()
+            .filter_map(|part| match part {
+                v2t::StreamPart::SourceUrl { id, .. } => Some(id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kept, ["s1", "s3", "s4", "s5", "s7"]);
+    }
 }
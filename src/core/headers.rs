@@ -0,0 +1,148 @@
+//! Request header merging with explicit conflict handling.
+//!
+//! Providers layer headers from internal defaults, provider configuration
+//! and call options. [`HeaderMerger`] applies those layers in order and
+//! resolves repeated names (compared case-insensitively) according to a
+//! [`HeaderConflictPolicy`].
+
+use std::collections::BTreeMap;
+
+use crate::core::error::SdkError;
+use crate::core::transport::TransportConfig;
+
+/// Resolution for a header name that is set more than once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeaderConflictPolicy {
+    /// The later value replaces the earlier one.
+    #[default]
+    Override,
+    /// Reject conflicting values with [`SdkError::InvalidArgument`].
+    /// Repeating an identical value is not a conflict.
+    Error,
+    /// Keep both values as a comma-separated list, which HTTP treats as
+    /// equivalent to repeated header lines.
+    Append,
+}
+
+#[derive(Debug)]
+struct HeaderEntry {
+    name: String,
+    value: String,
+}
+
+/// Ordered, case-insensitive header set built from layered sources.
+#[derive(Debug, Default)]
+pub struct HeaderMerger {
+    policy: HeaderConflictPolicy,
+    preserve_case: bool,
+    entries: BTreeMap<String, HeaderEntry>,
+    defaults: Vec<(String, String)>,
+}
+
+impl HeaderMerger {
+    pub fn new(policy: HeaderConflictPolicy, preserve_case: bool) -> Self {
+        Self {
+            policy,
+            preserve_case,
+            ..Self::default()
+        }
+    }
+
+    /// Merger using the header settings from a transport config.
+    pub fn for_transport(cfg: &TransportConfig) -> Self {
+        Self::new(cfg.header_conflict_policy, cfg.preserve_header_case)
+    }
+
+    /// Whether emitted names keep the casing they were inserted with.
+    pub fn preserves_case(&self) -> bool {
+        self.preserve_case
+    }
+
+    /// Set a fallback value, used only if no other layer sets the header.
+    pub fn insert_default(&mut self, name: &str, value: impl Into<String>) {
+        self.defaults.push((name.to_string(), value.into()));
+    }
+
+    /// Set a header, resolving conflicts with earlier values per the policy.
+    pub fn insert(&mut self, name: &str, value: impl Into<String>) -> Result<(), SdkError> {
+        let value = value.into();
+        let key = name.to_ascii_lowercase();
+        let Some(existing) = self.entries.get_mut(&key) else {
+            self.force(name, value);
+            return Ok(());
+        };
+        match self.policy {
+            HeaderConflictPolicy::Override => {
+                existing.name = name.to_string();
+                existing.value = value;
+            }
+            HeaderConflictPolicy::Error if existing.value != value => {
+                return Err(SdkError::InvalidArgument {
+                    message: format!("conflicting values for header '{name}'"),
+                });
+            }
+            HeaderConflictPolicy::Error => {}
+            HeaderConflictPolicy::Append => {
+                existing.value.push_str(", ");
+                existing.value.push_str(&value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set a header unconditionally, replacing any earlier value. Used for
+    /// values the provider protocol requires.
+    pub fn force(&mut self, name: &str, value: impl Into<String>) {
+        self.entries.insert(
+            name.to_ascii_lowercase(),
+            HeaderEntry {
+                name: name.to_string(),
+                value: value.into(),
+            },
+        );
+    }
+
+    /// Value set by a non-default layer, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .get(&name.to_ascii_lowercase())
+            .map(|entry| entry.value.as_str())
+    }
+
+    /// Whether a header has been set by a non-default layer.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(&name.to_ascii_lowercase())
+    }
+
+    /// Finish merging, sorted by lowercase name. Names are emitted as
+    /// inserted when preserving case, otherwise passed through `normalize`
+    /// in lowercase form.
+    pub fn into_pairs_with(mut self, normalize: impl Fn(&str) -> String) -> Vec<(String, String)> {
+        for (name, value) in std::mem::take(&mut self.defaults) {
+            if !self.contains(&name) {
+                self.force(&name, value);
+            }
+        }
+        let preserve_case = self.preserve_case;
+        self.entries
+            .into_iter()
+            .map(|(key, entry)| {
+                let name = if preserve_case {
+                    entry.name
+                } else {
+                    normalize(&key)
+                };
+                (name, entry.value)
+            })
+            .collect()
+    }
+
+    /// Finish merging with lowercase names unless case is preserved.
+    pub fn into_pairs(self) -> Vec<(String, String)> {
+        self.into_pairs_with(str::to_string)
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/headers_tests.rs"]
+mod tests;
@@ -39,4 +39,38 @@ pub trait ImageModel: Send + Sync {
     }
 
     async fn do_generate(&self, options: imgt::ImageOptions) -> Result<ImageResponse, SdkError>;
+
+    /// Edit `image` as described by `options.prompt`, limited to the
+    /// transparent area of `mask` when given. `options.files` and
+    /// `options.mask` are ignored.
+    ///
+    /// Models without an edit endpoint reject the call with
+    /// [`SdkError::InvalidArgument`], the error every provider already returns
+    /// for a request the model cannot serve (tools on a tool-less model, file
+    /// URLs on Bedrock), so callers match on one variant either way.
+    async fn do_image_edit(
+        &self,
+        image: imgt::ImageFile,
+        mask: Option<imgt::ImageFile>,
+        options: imgt::ImageOptions,
+    ) -> Result<ImageResponse, SdkError> {
+        let _ = (image, mask, options);
+        Err(SdkError::InvalidArgument {
+            message: format!("{} does not support image edits", self.provider_name()),
+        })
+    }
+
+    /// Generate variations of `image`; `options.prompt`, `options.files` and
+    /// `options.mask` are ignored. Models without a variation endpoint return
+    /// [`SdkError::InvalidArgument`], as for edits.
+    async fn do_image_variation(
+        &self,
+        image: imgt::ImageFile,
+        options: imgt::ImageOptions,
+    ) -> Result<ImageResponse, SdkError> {
+        let _ = (image, options);
+        Err(SdkError::InvalidArgument {
+            message: format!("{} does not support image variations", self.provider_name()),
+        })
+    }
 }
@@ -1,15 +1,27 @@
+pub mod abort;
 pub mod capabilities;
 pub mod embedding;
 pub mod error;
 pub mod event_mapper;
+pub mod headers;
 pub mod image;
 pub mod json;
+pub mod multi_provider;
 pub mod options;
+pub mod raw_capture;
 pub mod request_builder;
 pub mod retry;
+pub mod stop_on_tool_call;
 pub mod stream_collect;
+pub mod stream_integrity;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod tool_call_accumulator;
+pub mod tool_result;
 pub mod transport;
+pub mod usage_estimate;
 pub mod v2;
+pub mod wire_trace;
 
 pub use crate::core::embedding::{EmbedResponse, EmbeddingModel};
 pub use crate::core::error::{SdkError, TransportError};
@@ -22,10 +34,12 @@ pub use crate::core::image::{ImageModel, ImageResponse, ImageResponseMeta};
 // Re-export v2 (Vercel parity) model trait and typed surfaces at the crate root
 pub use crate::core::v2::{
     BoxedLanguageModelTurnSession, GenerateResponse, LanguageModel, LanguageModelTurnSession,
-    PartStream, StreamResponse,
+    ModelCapabilities, PartStream, StreamResponse,
 };
 // Convenience re-exports of common types
 pub use crate::types::embedding::{EmbedOptions, EmbedUsage, Embedding};
-pub use crate::types::image::{ImageData, ImageFile, ImageOptions, ImageUsage, ImageWarning};
+pub use crate::types::image::{
+    ImageData, ImageFile, ImageOptions, ImageResponseFormat, ImageUsage, ImageWarning,
+};
 pub use crate::types::v2 as types;
 pub use crate::types::Event;
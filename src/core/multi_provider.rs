@@ -0,0 +1,134 @@
+//! Routing facade over several providers.
+//!
+//! [`MultiProviderModel`] takes model ids of the form `prefix/model`
+//! (`openai/gpt-4o`, `anthropic/claude-sonnet-4-5`) and dispatches each call to
+//! the provider registered under `prefix`. Providers are registered either as a
+//! fixed model or as a factory that receives the id with the prefix stripped.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::core::v2::{GenerateResponse, LanguageModel, ModelCapabilities, StreamResponse};
+use crate::core::SdkError;
+use crate::types::v2 as v2t;
+
+/// Builds the delegate model for an id with the routing prefix stripped.
+pub type ModelFactory = Arc<dyn Fn(&str) -> Result<Arc<dyn LanguageModel>, SdkError> + Send + Sync>;
+
+/// Picks the `prefix/model` route for a call; `None` uses the facade's id.
+pub type RouteFn = Arc<dyn Fn(&v2t::CallOptions) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+enum ProviderEntry {
+    Model(Arc<dyn LanguageModel>),
+    Factory(ModelFactory),
+}
+
+/// Language model that routes each call to a provider by model id prefix.
+#[derive(Clone)]
+pub struct MultiProviderModel {
+    model_id: String,
+    providers: HashMap<String, ProviderEntry>,
+    router: Option<RouteFn>,
+}
+
+impl MultiProviderModel {
+    /// Facade whose calls route by `model_id` unless a router overrides it.
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            providers: HashMap::new(),
+            router: None,
+        }
+    }
+
+    /// Route `prefix/...` to `model`, whatever follows the prefix.
+    pub fn with_model(mut self, prefix: impl Into<String>, model: Arc<dyn LanguageModel>) -> Self {
+        self.providers
+            .insert(prefix.into(), ProviderEntry::Model(model));
+        self
+    }
+
+    /// Route `prefix/...` to the model `factory` builds for the stripped id.
+    pub fn with_factory<F>(mut self, prefix: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&str) -> Result<Arc<dyn LanguageModel>, SdkError> + Send + Sync + 'static,
+    {
+        self.providers
+            .insert(prefix.into(), ProviderEntry::Factory(Arc::new(factory)));
+        self
+    }
+
+    /// Choose the route per call, e.g. from provider options.
+    pub fn with_router<F>(mut self, router: F) -> Self
+    where
+        F: Fn(&v2t::CallOptions) -> Option<String> + Send + Sync + 'static,
+    {
+        self.router = Some(Arc::new(router));
+        self
+    }
+
+    /// Same providers and router with a different default model id.
+    pub fn for_model(&self, model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Delegate model for `options`.
+    ///
+    /// Fails with `InvalidArgument` when the route has no `prefix/` or the
+    /// prefix is not registered.
+    pub fn resolve(&self, options: &v2t::CallOptions) -> Result<Arc<dyn LanguageModel>, SdkError> {
+        let route = self
+            .router
+            .as_ref()
+            .and_then(|router| router(options))
+            .unwrap_or_else(|| self.model_id.clone());
+        let Some((prefix, model_id)) = route.split_once('/') else {
+            return Err(SdkError::InvalidArgument {
+                message: format!("model '{route}' has no provider prefix"),
+            });
+        };
+        match self.providers.get(prefix) {
+            Some(ProviderEntry::Model(model)) => Ok(model.clone()),
+            Some(ProviderEntry::Factory(factory)) => factory(model_id),
+            None => Err(SdkError::InvalidArgument {
+                message: format!("unknown provider prefix '{prefix}' for model '{route}'"),
+            }),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LanguageModel for MultiProviderModel {
+    fn provider_name(&self) -> &'static str {
+        "multi-provider"
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    /// Capabilities of the model the default route resolves to.
+    fn capabilities(&self) -> ModelCapabilities {
+        self.resolve(&v2t::CallOptions::default())
+            .map(|model| model.capabilities())
+            .unwrap_or_default()
+    }
+
+    async fn do_generate(&self, options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
+        let model = self.resolve(&options)?;
+        model.do_generate(options).await
+    }
+
+    async fn do_stream(&self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
+        let model = self.resolve(&options)?;
+        model.do_stream(options).await
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/multi_provider_tests.rs"]
+mod tests;
@@ -1,4 +1,72 @@
 use serde_json::Value;
+use std::collections::HashMap;
+
+/// Key inside a provider options scope that selects per-key merge strategies,
+/// e.g. `{"include": [...], "$merge": {"include": "union"}}`. It is consumed
+/// while merging and never sent upstream.
+pub const MERGE_STRATEGIES_KEY: &str = "$merge";
+
+/// How an array option combines with the value it is merged into.
+///
+/// `Replace` is the default for every key: the higher-precedence value wins
+/// outright. `Append` keeps the lower-precedence entries and adds the others
+/// after them; `Union` does the same but skips entries already present.
+/// Strategies only affect arrays; other values always follow `Replace`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    #[default]
+    Replace,
+    Append,
+    Union,
+}
+
+impl MergeStrategy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "replace" => Some(Self::Replace),
+            "append" => Some(Self::Append),
+            "union" => Some(Self::Union),
+            _ => None,
+        }
+    }
+
+    /// Combine `base` with `extra`, `base` entries first. `None` for
+    /// `Replace` or when either side is not an array.
+    pub fn combine(self, base: &Value, extra: &Value) -> Option<Value> {
+        let (Value::Array(base), Value::Array(extra)) = (base, extra) else {
+            return None;
+        };
+        match self {
+            Self::Replace => None,
+            Self::Append => Some(Value::Array(base.iter().chain(extra).cloned().collect())),
+            Self::Union => {
+                let mut merged: Vec<Value> = Vec::with_capacity(base.len() + extra.len());
+                for item in base.iter().chain(extra) {
+                    if !merged.contains(item) {
+                        merged.push(item.clone());
+                    }
+                }
+                Some(Value::Array(merged))
+            }
+        }
+    }
+}
+
+/// Per-key strategies from the object stored under [`MERGE_STRATEGIES_KEY`].
+/// Unknown strategy names are ignored.
+pub fn merge_strategies(declared: &Value) -> HashMap<String, MergeStrategy> {
+    declared
+        .as_object()
+        .map(|declared| {
+            declared
+                .iter()
+                .filter_map(|(key, value)| {
+                    Some((key.clone(), MergeStrategy::parse(value.as_str()?)?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
 /// Returns true if a header key is reserved for internal SDK use.
 pub fn is_internal_sdk_header(key: &str) -> bool {
@@ -33,14 +101,28 @@ pub fn deep_merge(a: &mut Value, b: &Value) {
 }
 
 /// Merge provider options into a request body, skipping disallowed structural keys.
+///
+/// Arrays replace the body's value unless the options declare another
+/// strategy under [`MERGE_STRATEGIES_KEY`], in which case the option entries
+/// are added after the body's.
 pub fn merge_options_with_disallow(body: &mut Value, options: &Value, disallow: &[&str]) {
     if let (Value::Object(bm), Value::Object(om)) = (body, options) {
+        let strategies = om
+            .get(MERGE_STRATEGIES_KEY)
+            .map(merge_strategies)
+            .unwrap_or_default();
         for (k, v) in om {
-            if disallow.contains(&k.as_str()) {
+            if k == MERGE_STRATEGIES_KEY || disallow.contains(&k.as_str()) {
                 continue;
             }
             match bm.get_mut(k) {
-                Some(existing) => deep_merge(existing, v),
+                Some(existing) => {
+                    let strategy = strategies.get(k).copied().unwrap_or_default();
+                    match strategy.combine(existing, v) {
+                        Some(combined) => *existing = combined,
+                        None => deep_merge(existing, v),
+                    }
+                }
                 None => {
                     bm.insert(k.clone(), v.clone());
                 }
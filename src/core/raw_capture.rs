@@ -0,0 +1,112 @@
+//! Opt-in capture of raw provider output for debugging and replay.
+//!
+//! Enabled with [`v2t::CallOptions::capture_raw_response`]. Providers surface
+//! raw frames as [`v2t::StreamPart::Raw`]; [`StreamResponse::with_raw_capture`]
+//! records them into a shared [`RawResponseCapture`] and hides them from the
+//! caller unless `include_raw_chunks` was also requested.
+
+use std::sync::{Arc, Mutex};
+
+use futures_util::StreamExt;
+use serde_json::Value as JsonValue;
+
+use crate::core::v2::StreamResponse;
+use crate::types::v2 as v2t;
+
+/// Raw capture settings resolved from call options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawCaptureConfig {
+    /// Keep raw parts in the caller-visible stream.
+    pub forward_raw_chunks: bool,
+    /// Byte budget for captured frames.
+    pub max_bytes: Option<usize>,
+}
+
+impl RawCaptureConfig {
+    /// Returns `None` when raw capture is not requested.
+    pub fn from_options(options: &v2t::CallOptions) -> Option<Self> {
+        options.capture_raw_response.then_some(Self {
+            forward_raw_chunks: options.include_raw_chunks,
+            max_bytes: options.max_raw_response_bytes,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct CaptureState {
+    frames: Vec<JsonValue>,
+    bytes: usize,
+    truncated: bool,
+}
+
+/// Raw frames recorded while a stream is consumed.
+///
+/// Cloning shares the underlying buffer, so the handle can be kept while the
+/// stream is handed off and read once it has been drained.
+#[derive(Debug, Clone, Default)]
+pub struct RawResponseCapture {
+    state: Arc<Mutex<CaptureState>>,
+}
+
+impl RawResponseCapture {
+    fn record(&self, frame: &JsonValue, max_bytes: Option<usize>) {
+        let mut state = self.state.lock().unwrap();
+        if state.truncated {
+            return;
+        }
+        let size = frame.to_string().len();
+        if max_bytes.is_some_and(|max| state.bytes + size > max) {
+            state.truncated = true;
+            return;
+        }
+        state.bytes += size;
+        state.frames.push(frame.clone());
+    }
+
+    /// Frames captured so far, in arrival order.
+    pub fn frames(&self) -> Vec<JsonValue> {
+        self.state.lock().unwrap().frames.clone()
+    }
+
+    /// Whether frames were dropped because the byte budget was exhausted.
+    pub fn is_truncated(&self) -> bool {
+        self.state.lock().unwrap().truncated
+    }
+
+    /// Captured frames as a JSON array.
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.frames())
+    }
+}
+
+impl StreamResponse {
+    /// Record raw stream frames according to `config`.
+    ///
+    /// No-op when `config` is `None` or a capture is already attached.
+    pub fn with_raw_capture(mut self, config: Option<RawCaptureConfig>) -> Self {
+        let Some(config) = config else {
+            return self;
+        };
+        if self.raw_response.is_some() {
+            return self;
+        }
+        let capture = RawResponseCapture::default();
+        let recorder = capture.clone();
+        self.stream = Box::pin(self.stream.filter_map(move |item| {
+            let keep = match &item {
+                Ok(v2t::StreamPart::Raw { raw_value }) => {
+                    recorder.record(raw_value, config.max_bytes);
+                    config.forward_raw_chunks
+                }
+                _ => true,
+            };
+            std::future::ready(keep.then_some(item))
+        }));
+        self.raw_response = Some(capture);
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/raw_capture_tests.rs"]
+mod tests;
@@ -0,0 +1,54 @@
+//! Audio input handling for providers whose APIs take no audio.
+//!
+//! Audio travels in v2 prompts as a user file part with an `audio/*` media
+//! type. Providers that cannot send it drop those parts with a warning
+//! before building the request.
+
+use std::borrow::Cow;
+
+use crate::types::v2 as v2t;
+
+/// Whether `media_type` is an audio type.
+pub fn is_audio_media_type(media_type: &str) -> bool {
+    media_type.starts_with("audio/")
+}
+
+fn is_audio_part(part: &v2t::UserPart) -> bool {
+    matches!(part, v2t::UserPart::File { media_type, .. } if is_audio_media_type(media_type))
+}
+
+/// Drop the audio parts of the prompt, which `provider` cannot take, with a
+/// warning for each. User messages left empty are dropped too. Returns the
+/// options unchanged when the prompt carries no audio.
+pub fn drop_audio_input<'a>(
+    options: &'a v2t::CallOptions,
+    provider: &str,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Cow<'a, v2t::CallOptions> {
+    let has_audio = options.prompt.iter().any(|message| {
+        matches!(message, v2t::PromptMessage::User { content, .. } if content.iter().any(is_audio_part))
+    });
+    if !has_audio {
+        return Cow::Borrowed(options);
+    }
+
+    let mut options = options.clone();
+    options.prompt.retain_mut(|message| {
+        let v2t::PromptMessage::User { content, .. } = message else {
+            return true;
+        };
+        content.retain(|part| match part {
+            v2t::UserPart::File { media_type, .. } if is_audio_media_type(media_type) => {
+                warnings.push(v2t::CallWarning::Other {
+                    message: format!(
+                        "{provider} does not accept audio input; dropped {media_type} part"
+                    ),
+                });
+                false
+            }
+            _ => true,
+        });
+        !content.is_empty()
+    });
+    Cow::Owned(options)
+}
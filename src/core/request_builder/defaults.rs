@@ -6,9 +6,22 @@
 //! 3) Request overrides are merged last into the request body (minus disallowed keys).
 //!
 //! Only exact provider scope keys are accepted; inline or aliased scopes are ignored.
+//!
+//! An explicit value replaces the default wholesale. For array options such
+//! as `include`, the defaults scope may opt into appending instead by naming
+//! a strategy per key under `"$merge"`:
+//!
+//! ```json
+//! {"openai": {"include": ["reasoning.encrypted_content"], "$merge": {"include": "union"}}}
+//! ```
+//!
+//! `"append"` keeps the default entries and adds the call-time ones after
+//! them; `"union"` also drops duplicates. See
+//! [`MergeStrategy`](crate::core::options::MergeStrategy).
 
 use std::collections::HashMap;
 
+use crate::core::options::{merge_strategies, MERGE_STRATEGIES_KEY};
 use crate::types::embedding::EmbedOptions;
 use crate::types::image::ImageOptions;
 use crate::types::v2 as v2t;
@@ -47,9 +60,22 @@ fn merge_scope_defaults(
     defaults: &HashMap<String, JsonValue>,
 ) {
     let entry = target.entry(provider_scope.to_string()).or_default();
+    let strategies = defaults
+        .get(MERGE_STRATEGIES_KEY)
+        .map(merge_strategies)
+        .unwrap_or_default();
     for (key, val) in defaults {
+        if key == MERGE_STRATEGIES_KEY {
+            continue;
+        }
         match entry.get_mut(key) {
-            Some(existing) => merge_json_defaults(existing, val),
+            Some(existing) => {
+                let strategy = strategies.get(key).copied().unwrap_or_default();
+                match strategy.combine(val, existing) {
+                    Some(combined) => *existing = combined,
+                    None => merge_json_defaults(existing, val),
+                }
+            }
             None => {
                 entry.insert(key.clone(), val.clone());
             }
@@ -127,3 +153,7 @@ pub fn build_image_options(
     }
     opts
 }
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/request_builder_defaults_tests.rs"]
+mod tests;
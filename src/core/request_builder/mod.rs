@@ -1,4 +1,11 @@
 //! Shared helpers for constructing V2 call options and provider-specific
 //! overrides.
 
+pub mod audio_input;
 pub mod defaults;
+pub mod prompt_limit;
+pub mod raw_passthrough;
+pub mod system_prompt;
+pub mod tool_choice;
+pub mod tool_limits;
+pub mod unsupported_tools;
@@ -0,0 +1,74 @@
+//! Provider-agnostic prompt size guard.
+//!
+//! Enforces [`v2t::CallOptions::max_prompt_messages`] before a request body is
+//! built. This is a coarse message-count safety valve, independent of any
+//! token counting done by callers.
+
+use std::borrow::Cow;
+
+use crate::core::error::SdkError;
+use crate::types::v2 as v2t;
+
+fn is_system(message: &v2t::PromptMessage) -> bool {
+    message.is_instruction()
+}
+
+/// Apply the prompt message limit from `options`.
+///
+/// Returns the options unchanged when no limit is set or the prompt fits.
+/// With [`v2t::PromptOverflow::Error`] an oversized prompt is rejected. With
+/// [`v2t::PromptOverflow::SlidingWindow`] the oldest non-system messages are
+/// dropped and a warning is pushed; leading tool messages left without their
+/// originating assistant turn are dropped as well. System messages are kept
+/// even if they alone exceed the limit.
+pub fn apply_prompt_message_limit<'a>(
+    options: &'a v2t::CallOptions,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<Cow<'a, v2t::CallOptions>, SdkError> {
+    let Some(max) = options.max_prompt_messages else {
+        return Ok(Cow::Borrowed(options));
+    };
+    let total = options.prompt.len();
+    if total <= max {
+        return Ok(Cow::Borrowed(options));
+    }
+
+    if options.prompt_overflow == v2t::PromptOverflow::Error {
+        return Err(SdkError::InvalidArgument {
+            message: format!("prompt has {total} messages, exceeding max_prompt_messages ({max})"),
+        });
+    }
+
+    let system_count = options.prompt.iter().filter(|m| is_system(m)).count();
+    let budget = max.saturating_sub(system_count);
+    let mut to_drop = (total - system_count).saturating_sub(budget);
+    let mut orphaned_tools = false;
+    let mut prompt = Vec::with_capacity(max.max(system_count));
+    for message in &options.prompt {
+        if is_system(message) {
+            prompt.push(message.clone());
+        } else if to_drop > 0 {
+            to_drop -= 1;
+            orphaned_tools = true;
+        } else if orphaned_tools && matches!(message, v2t::PromptMessage::Tool { .. }) {
+            // Results whose tool calls were dropped would be rejected upstream.
+        } else {
+            orphaned_tools = false;
+            prompt.push(message.clone());
+        }
+    }
+
+    warnings.push(v2t::CallWarning::Other {
+        message: format!(
+            "Dropped {} oldest prompt messages to satisfy max_prompt_messages ({max})",
+            total - prompt.len()
+        ),
+    });
+    let mut limited = options.clone();
+    limited.prompt = prompt;
+    Ok(Cow::Owned(limited))
+}
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/prompt_limit_tests.rs"]
+mod tests;
@@ -0,0 +1,52 @@
+//! Raw request body passthrough.
+//!
+//! Fields under `provider_options["raw"]` are copied into the final request
+//! body verbatim, after the provider builder has run, so callers can reach
+//! upstream parameters the SDK does not model yet. The merge is shallow: a
+//! raw key replaces the builder's value for that key wholesale.
+//!
+//! Nothing is validated. A misspelled or mistyped raw field is sent as-is and
+//! can make the provider reject the request, or silently override settings
+//! the builder derived from typed options.
+
+use serde_json::Value as JsonValue;
+
+use crate::types::v2 as v2t;
+
+/// Provider options scope whose fields are merged into the request body.
+pub const RAW_PASSTHROUGH_SCOPE: &str = "raw";
+
+/// Merge `provider_options["raw"]` over the top level of `body`.
+///
+/// Pushes a warning naming the merged keys. No-op when the scope is absent
+/// or empty, or when `body` is not a JSON object.
+pub fn apply_raw_passthrough(
+    options: &v2t::CallOptions,
+    body: &mut JsonValue,
+    warnings: &mut Vec<v2t::CallWarning>,
+) {
+    let Some(raw) = options.provider_options.get(RAW_PASSTHROUGH_SCOPE) else {
+        return;
+    };
+    let Some(map) = body.as_object_mut() else {
+        return;
+    };
+    if raw.is_empty() {
+        return;
+    }
+    let mut keys: Vec<&str> = raw.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    for key in &keys {
+        map.insert((*key).to_string(), raw[*key].clone());
+    }
+    warnings.push(v2t::CallWarning::Other {
+        message: format!(
+            "raw provider options passthrough applied: {}",
+            keys.join(", ")
+        ),
+    });
+}
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/raw_passthrough_tests.rs"]
+mod tests;
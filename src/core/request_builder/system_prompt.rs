@@ -0,0 +1,96 @@
+//! Provider-agnostic handling of [`v2t::CallOptions::system_prompt_mode`].
+//!
+//! Providers declare which modes they can express natively; folding system
+//! content into the first user message is done here so every provider gets it
+//! for free.
+
+use std::borrow::Cow;
+
+use crate::types::v2 as v2t;
+
+fn mode_name(mode: v2t::SystemPromptMode) -> &'static str {
+    match mode {
+        v2t::SystemPromptMode::System => "system",
+        v2t::SystemPromptMode::Developer => "developer",
+        v2t::SystemPromptMode::FoldIntoUser => "fold-into-user",
+    }
+}
+
+/// Move all system and developer messages into a text part at the start of
+/// the first user message, inserting a user message when the prompt has none.
+pub fn fold_system_into_user(prompt: &[v2t::PromptMessage]) -> v2t::Prompt {
+    let system_text = prompt
+        .iter()
+        .filter_map(|message| match message {
+            v2t::PromptMessage::System { content, .. }
+            | v2t::PromptMessage::Developer { content, .. } => Some(content.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let mut folded: v2t::Prompt = prompt
+        .iter()
+        .filter(|message| !message.is_instruction())
+        .cloned()
+        .collect();
+    if system_text.is_empty() {
+        return folded;
+    }
+
+    let text = v2t::UserPart::Text {
+        text: system_text,
+        provider_options: None,
+    };
+    match folded.iter_mut().find_map(|message| match message {
+        v2t::PromptMessage::User { content, .. } => Some(content),
+        _ => None,
+    }) {
+        Some(content) => content.insert(0, text),
+        None => folded.insert(
+            0,
+            v2t::PromptMessage::User {
+                content: vec![text],
+                provider_options: None,
+            },
+        ),
+    }
+    folded
+}
+
+/// Resolve the requested system prompt mode for a provider.
+///
+/// `supported` lists the modes the provider can express natively besides
+/// [`v2t::SystemPromptMode::FoldIntoUser`], which is always available. An
+/// unsupported mode pushes a warning and resolves to `None` (the provider
+/// default). When folding, the returned options carry the rewritten prompt.
+pub fn apply_system_prompt_mode<'a>(
+    options: &'a v2t::CallOptions,
+    supported: &[v2t::SystemPromptMode],
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> (Cow<'a, v2t::CallOptions>, Option<v2t::SystemPromptMode>) {
+    let Some(mode) = options.system_prompt_mode else {
+        return (Cow::Borrowed(options), None);
+    };
+    match mode {
+        v2t::SystemPromptMode::FoldIntoUser => {
+            let mut folded = options.clone();
+            folded.prompt = fold_system_into_user(&options.prompt);
+            (Cow::Owned(folded), Some(mode))
+        }
+        _ if supported.contains(&mode) => (Cow::Borrowed(options), Some(mode)),
+        _ => {
+            warnings.push(v2t::CallWarning::UnsupportedSetting {
+                setting: "systemPromptMode".into(),
+                details: Some(format!(
+                    "{} is not supported by this provider; using the default system placement",
+                    mode_name(mode)
+                )),
+            });
+            (Cow::Borrowed(options), None)
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/system_prompt_tests.rs"]
+mod tests;
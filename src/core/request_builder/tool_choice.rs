@@ -0,0 +1,25 @@
+//! Pre-flight check of a forced tool choice.
+
+use crate::core::error::SdkError;
+use crate::types::v2 as v2t;
+
+/// Reject a [`v2t::ToolChoice::Tool`] naming a tool that is not among
+/// `options.tools`; providers would otherwise fail the request with a less
+/// specific error or ignore the choice.
+pub fn validate_tool_choice(options: &v2t::CallOptions) -> Result<(), SdkError> {
+    let Some(v2t::ToolChoice::Tool { name }) = &options.tool_choice else {
+        return Ok(());
+    };
+    let known = options.tools.iter().any(|tool| match tool {
+        v2t::Tool::Function(function) => &function.name == name,
+        v2t::Tool::Provider(provider) => &provider.name == name,
+    });
+    if known {
+        return Ok(());
+    }
+    Err(SdkError::InvalidArgument {
+        message: format!(
+            "tool_choice names tool \"{name}\", which is not among the provided tools"
+        ),
+    })
+}
@@ -0,0 +1,68 @@
+//! Pre-flight tool limit check.
+//!
+//! Providers reject requests carrying too many tools or oversized tool
+//! schemas, usually with an opaque error. This check runs before the request
+//! body is built and names the limit that was hit.
+
+use crate::core::error::SdkError;
+use crate::types::v2 as v2t;
+
+/// Known limits for the OpenAI Responses and Chat Completions APIs.
+pub const OPENAI_TOOL_LIMITS: v2t::ToolLimits = v2t::ToolLimits {
+    max_tools: Some(128),
+    max_schema_bytes: None,
+};
+
+/// Check `options.tools` against the call's limits, falling back to the
+/// provider's known `provider_limits` for unset dimensions.
+///
+/// Each exceeded limit pushes a warning; with
+/// [`v2t::CallOptions::strict_tool_limits`] the first one is returned as an
+/// invalid-argument error instead.
+pub fn check_tool_limits(
+    options: &v2t::CallOptions,
+    provider_limits: v2t::ToolLimits,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<(), SdkError> {
+    if options.tools.is_empty() {
+        return Ok(());
+    }
+    let limits = options.tool_limits.unwrap_or_default().or(provider_limits);
+
+    let mut exceeded = Vec::new();
+    if let Some(max) = limits.max_tools {
+        let count = options.tools.len();
+        if count > max {
+            exceeded.push(format!(
+                "request has {count} tools, exceeding max_tools ({max})"
+            ));
+        }
+    }
+    if let Some(max) = limits.max_schema_bytes {
+        let size = serde_json::to_vec(&options.tools)
+            .map(|bytes| bytes.len())
+            .unwrap_or(0);
+        if size > max {
+            exceeded.push(format!(
+                "tool definitions are {size} bytes, exceeding max_schema_bytes ({max})"
+            ));
+        }
+    }
+
+    if options.strict_tool_limits {
+        if let Some(message) = exceeded.into_iter().next() {
+            return Err(SdkError::InvalidArgument { message });
+        }
+        return Ok(());
+    }
+    warnings.extend(
+        exceeded
+            .into_iter()
+            .map(|message| v2t::CallWarning::Other { message }),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/tool_limits_tests.rs"]
+mod tests;
@@ -0,0 +1,108 @@
+//! Provider-agnostic handling of [`v2t::CallOptions::on_unsupported_tools`].
+//!
+//! Providers that know a model lacks native tool calling pass that here
+//! before building the request, so tools are rejected, dropped or described
+//! in the prompt instead of producing an upstream 400.
+
+use std::borrow::Cow;
+
+use crate::core::error::SdkError;
+use crate::types::v2 as v2t;
+
+/// Apply the unsupported-tools policy from `options`.
+///
+/// Returns the options unchanged when the model supports tools or none are
+/// passed. Otherwise the returned options carry no tools or tool choice; with
+/// [`v2t::UnsupportedToolsPolicy::InlineAsText`] the function tools are
+/// described in a system message added after the leading system messages.
+pub fn apply_unsupported_tools_policy<'a>(
+    options: &'a v2t::CallOptions,
+    supports_tools: bool,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<Cow<'a, v2t::CallOptions>, SdkError> {
+    if supports_tools || options.tools.is_empty() {
+        return Ok(Cow::Borrowed(options));
+    }
+    let count = options.tools.len();
+    let mut stripped = options.clone();
+    stripped.tools = Vec::new();
+    stripped.tool_choice = None;
+
+    match options.on_unsupported_tools {
+        v2t::UnsupportedToolsPolicy::Error => Err(SdkError::InvalidArgument {
+            message: format!("model does not support tool calling; {count} tools were provided"),
+        }),
+        v2t::UnsupportedToolsPolicy::DropWithWarning => {
+            warnings.push(v2t::CallWarning::UnsupportedSetting {
+                setting: "tools".into(),
+                details: Some(format!(
+                    "model does not support tool calling; dropped {count} tools"
+                )),
+            });
+            Ok(Cow::Owned(stripped))
+        }
+        v2t::UnsupportedToolsPolicy::InlineAsText => {
+            if matches!(options.tool_choice, Some(v2t::ToolChoice::None)) {
+                return Ok(Cow::Owned(stripped));
+            }
+            let mut functions = Vec::new();
+            for tool in &options.tools {
+                match tool {
+                    v2t::Tool::Function(function) => functions.push(function),
+                    v2t::Tool::Provider(provider) => {
+                        warnings.push(v2t::CallWarning::UnsupportedTool {
+                            tool_name: provider.name.clone(),
+                            details: Some(
+                                "provider tools cannot be described as text; dropped".into(),
+                            ),
+                        });
+                    }
+                }
+            }
+            if !functions.is_empty() {
+                let text = describe_tools(&functions, options.tool_choice.as_ref());
+                let at = stripped
+                    .prompt
+                    .iter()
+                    .take_while(|message| message.is_instruction())
+                    .count();
+                stripped.prompt.insert(
+                    at,
+                    v2t::PromptMessage::System {
+                        content: text,
+                        provider_options: None,
+                    },
+                );
+            }
+            Ok(Cow::Owned(stripped))
+        }
+    }
+}
+
+/// System prompt text asking the model to call `tools` as JSON.
+fn describe_tools(tools: &[&v2t::FunctionTool], choice: Option<&v2t::ToolChoice>) -> String {
+    let mut text = String::from(
+        "You can call the following tools. To call a tool, reply with only a JSON object of \
+         the form {\"tool\": \"<tool name>\", \"arguments\": {...}} matching the tool's \
+         parameters, and nothing else.\n\nTools:",
+    );
+    for tool in tools {
+        text.push_str(&format!("\n- {}", tool.name));
+        if let Some(description) = tool.description.as_deref().filter(|d| !d.is_empty()) {
+            text.push_str(&format!(": {description}"));
+        }
+        text.push_str(&format!("\n  Parameters: {}", tool.input_schema));
+    }
+    match choice {
+        Some(v2t::ToolChoice::Required) => text.push_str("\n\nYou must call one of these tools."),
+        Some(v2t::ToolChoice::Tool { name }) => {
+            text.push_str(&format!("\n\nYou must call the {name} tool."))
+        }
+        _ => {}
+    }
+    text
+}
+
+#[cfg(test)]
+#[path = "../../../crates/core/tests/unsupported_tools_tests.rs"]
+mod tests;
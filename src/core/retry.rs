@@ -3,6 +3,8 @@
 //! This module provides a flexible retry system with exponential backoff,
 //! configurable presets, and a trait-based approach for determining retryable errors.
 
+use crate::core::error::{SdkError, TransportError};
+use crate::types::Event;
 use std::future::Future;
 use std::time::Duration;
 
@@ -102,6 +104,59 @@ impl Retryable for std::io::Error {
     }
 }
 
+/// Default policy for SDK errors: rate limits, timeouts, network failures and
+/// 408/429/5xx responses are retried. Quota exhaustion, auth failures and
+/// client errors are returned immediately.
+impl Retryable for SdkError {
+    fn is_retryable(&self) -> bool {
+        let retryable_status = |status: u16| status == 408 || status == 429 || status >= 500;
+        match self {
+            SdkError::RateLimited { .. } | SdkError::Timeout => true,
+            SdkError::Upstream { status, .. } => retryable_status(*status),
+            SdkError::Transport(te) => match te {
+                TransportError::HttpStatus { status, .. } => retryable_status(*status),
+                TransportError::Network(_)
+                | TransportError::ConnectTimeout(_)
+                | TransportError::IdleReadTimeout(_)
+                | TransportError::BodyRead(_)
+                | TransportError::StreamClosed => true,
+                TransportError::Other(_)
+                | TransportError::Cancelled
+                | TransportError::BodyTooLarge { .. }
+                | TransportError::ResponseTooLarge { .. } => false,
+            },
+            SdkError::QuotaExceeded { .. }
+            | SdkError::Unauthorized
+            | SdkError::Cancelled
+            | SdkError::Serde(_)
+            | SdkError::InvalidArgument { .. } => false,
+        }
+    }
+
+    fn retry_after_ms(&self) -> Option<u64> {
+        sdk_retry_after(self).map(|delay| delay.as_millis() as u64)
+    }
+}
+
+/// Source of backoff delays for the retry loop.
+///
+/// The default [`TokioSleeper`] waits on the tokio timer; tests can supply a
+/// sleeper that records or fast-forwards delays instead of waiting in real time.
+pub trait Sleeper {
+    /// Wait for `duration` before the next attempt.
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// [`Sleeper`] backed by `tokio::time::sleep`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioSleeper;
+
+impl Sleeper for TokioSleeper {
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
+}
+
 /// Execute an operation with retry logic
 ///
 /// # Arguments
@@ -132,10 +187,27 @@ impl Retryable for std::io::Error {
 /// ```
 pub async fn retry_with_backoff<F, Fut, T, E, R>(
     config: RetryConfig,
+    operation: F,
+    on_retry: R,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Retryable + std::fmt::Debug,
+    R: FnMut(u32, Duration, &E),
+{
+    retry_with_backoff_and_sleeper(config, &TokioSleeper, operation, on_retry).await
+}
+
+/// Execute an operation with retry logic, waiting between attempts via `sleeper`
+pub async fn retry_with_backoff_and_sleeper<S, F, Fut, T, E, R>(
+    config: RetryConfig,
+    sleeper: &S,
     mut operation: F,
     mut on_retry: R,
 ) -> Result<T, E>
 where
+    S: Sleeper,
     F: FnMut() -> Fut,
     Fut: Future<Output = Result<T, E>>,
     E: Retryable + std::fmt::Debug,
@@ -166,7 +238,7 @@ where
                 on_retry(attempt, delay, &err);
 
                 // Wait before retrying
-                tokio::time::sleep(delay).await;
+                sleeper.sleep(delay).await;
             }
         }
     }
@@ -181,3 +253,457 @@ where
 {
     retry_with_backoff(config, operation, |_, _, _| {}).await
 }
+
+/// Randomization applied to the delays computed by [`RetryPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JitterKind {
+    /// Use the computed delay unchanged.
+    None,
+    /// Wait a uniformly random duration between zero and the computed delay.
+    #[default]
+    Full,
+    /// Keep half of the computed delay and randomize the other half.
+    Equal,
+}
+
+/// Retry policy for SDK calls, used by [`retry_async`].
+///
+/// Errors are retried according to the [`Retryable`] impl for [`SdkError`].
+/// A Retry-After hint from the server is a floor for the next delay, so it
+/// wins over both the computed backoff and `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the second attempt; doubled for each attempt after it.
+    pub base_delay: Duration,
+    /// Upper bound on the computed backoff.
+    pub max_delay: Duration,
+    /// Randomization applied to the computed backoff.
+    pub jitter: JitterKind,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 500ms base delay, 30s cap, full jitter.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: JitterKind::Full,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after failed attempt number `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let factor = 2f64.powi(attempt.saturating_sub(1).min(63) as i32);
+        let computed = self.base_delay.mul_f64(factor).min(self.max_delay);
+        let jittered = match self.jitter {
+            JitterKind::None => computed,
+            JitterKind::Full => computed.mul_f64(random_unit()),
+            JitterKind::Equal => computed / 2 + (computed / 2).mul_f64(random_unit()),
+        };
+        retry_after.map_or(jittered, |floor| jittered.max(floor))
+    }
+}
+
+/// Retry-After hint carried by `err`, including one on a wrapped transport error.
+fn sdk_retry_after(err: &SdkError) -> Option<Duration> {
+    let from_transport = |te: &TransportError| match te {
+        TransportError::HttpStatus { retry_after_ms, .. } => *retry_after_ms,
+        _ => None,
+    };
+    let ms = match err {
+        SdkError::RateLimited {
+            retry_after_ms,
+            source,
+        } => retry_after_ms.or_else(|| source.as_deref().and_then(from_transport)),
+        SdkError::Upstream { source, .. } => source.as_deref().and_then(from_transport),
+        SdkError::Transport(te) => from_transport(te),
+        _ => None,
+    };
+    ms.map(Duration::from_millis)
+}
+
+/// Uniform sample in `[0, 1)` from a per-thread xorshift64* generator.
+fn random_unit() -> f64 {
+    use std::cell::Cell;
+    use std::hash::BuildHasher;
+
+    thread_local! {
+        static STATE: Cell<u64> =
+            Cell::new(std::collections::hash_map::RandomState::new().hash_one(0u8) | 1);
+    }
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    })
+}
+
+/// Run `operation` under `policy`, reporting each retry as
+/// [`Event::Retrying`](crate::types::Event::Retrying) through `on_event`.
+///
+/// `provider` labels the emitted events. The last error is returned once the
+/// attempts run out or a non-retryable error occurs.
+pub async fn retry_async<F, Fut, T>(
+    policy: &RetryPolicy,
+    provider: &str,
+    operation: F,
+    on_event: Option<&mut (dyn FnMut(Event) + Send)>,
+) -> Result<T, SdkError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError>>,
+{
+    retry_async_with_sleeper(policy, &TokioSleeper, provider, operation, on_event).await
+}
+
+/// [`retry_async`], waiting between attempts via `sleeper`.
+pub async fn retry_async_with_sleeper<S, F, Fut, T>(
+    policy: &RetryPolicy,
+    sleeper: &S,
+    provider: &str,
+    mut operation: F,
+    mut on_event: Option<&mut (dyn FnMut(Event) + Send)>,
+) -> Result<T, SdkError>
+where
+    S: Sleeper,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SdkError>>,
+{
+    let mut attempt = 1u32;
+    loop {
+        let err = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if attempt >= policy.max_attempts || !err.is_retryable() {
+            return Err(err);
+        }
+
+        let delay = policy.backoff(attempt, sdk_retry_after(&err));
+        attempt += 1;
+        if let Some(on_event) = on_event.as_deref_mut() {
+            on_event(Event::Retrying {
+                provider: provider.to_string(),
+                attempt,
+                max_attempts: policy.max_attempts,
+                error: err.to_string(),
+                delay_secs: delay.as_millis().div_ceil(1000) as u64,
+            });
+        }
+        sleeper.sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        retry_async_with_sleeper, retry_with_backoff_and_sleeper, JitterKind, RetryConfig,
+        RetryPolicy, Retryable, SdkError, Sleeper,
+    };
+    use crate::core::error::TransportError;
+    use crate::types::Event;
+    use std::future::Future;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct RecordingSleeper {
+        delays: Mutex<Vec<Duration>>,
+    }
+
+    impl Sleeper for RecordingSleeper {
+        fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+            self.delays.lock().unwrap().push(duration);
+            std::future::ready(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Flaky {
+        retry_after_ms: Option<u64>,
+    }
+
+    impl Retryable for Flaky {
+        fn is_retryable(&self) -> bool {
+            true
+        }
+
+        fn retry_after_ms(&self) -> Option<u64> {
+            self.retry_after_ms
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retry_uses_injected_sleeper_for_backoff() {
+        let sleeper = RecordingSleeper::default();
+        let config = RetryConfig::custom(5, Duration::from_secs(30), Duration::from_secs(60 * 60));
+        let mut calls = 0;
+        let result = retry_with_backoff_and_sleeper(
+            config,
+            &sleeper,
+            || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    match attempt {
+                        1 | 2 => Err(Flaky {
+                            retry_after_ms: None,
+                        }),
+                        3 => Err(Flaky {
+                            retry_after_ms: Some(5_000),
+                        }),
+                        _ => Ok(attempt),
+                    }
+                }
+            },
+            |_, _, _| {},
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(
+            *sleeper.delays.lock().unwrap(),
+            vec![
+                Duration::from_secs(30),
+                Duration::from_secs(60),
+                Duration::from_secs(5),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn sdk_errors_retry_rate_limits_but_not_quota_exhaustion() {
+        let quota_body = r#"{"error":{"message":"You exceeded your current quota.","type":"insufficient_quota","code":"insufficient_quota"}}"#;
+        let rate_body = r#"{"error":{"message":"Rate limit reached.","type":"requests","code":"rate_limit_exceeded"}}"#;
+        let run = |body: &'static str| async move {
+            let sleeper = RecordingSleeper::default();
+            let mut calls = 0;
+            let result: Result<(), SdkError> = retry_with_backoff_and_sleeper(
+                RetryConfig::custom(2, Duration::from_millis(1), Duration::from_millis(1)),
+                &sleeper,
+                || {
+                    calls += 1;
+                    async move {
+                        Err(crate::core::error::map_http_status_to_rate_limited_error(
+                            429,
+                            body.into(),
+                            None,
+                            Vec::new(),
+                        ))
+                    }
+                },
+                |_, _, _| {},
+            )
+            .await;
+            (result.unwrap_err(), calls)
+        };
+
+        let (err, calls) = run(quota_body).await;
+        assert!(
+            matches!(err, SdkError::QuotaExceeded { ref message, .. } if message == "You exceeded your current quota.")
+        );
+        assert_eq!(calls, 1);
+
+        let (err, calls) = run(rate_body).await;
+        assert!(matches!(err, SdkError::RateLimited { .. }));
+        assert_eq!(calls, 3);
+    }
+
+    fn fixed_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: JitterKind::None,
+        }
+    }
+
+    fn http_status(status: u16, retry_after_ms: Option<u64>) -> SdkError {
+        SdkError::Transport(TransportError::HttpStatus {
+            status,
+            body: String::new(),
+            retry_after_ms,
+            sanitized: String::new(),
+            headers: Vec::new(),
+        })
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retry_async_succeeds_after_two_failures() {
+        let sleeper = RecordingSleeper::default();
+        let mut events = Vec::new();
+        let mut record = |event: Event| events.push(event);
+        let mut calls = 0;
+        let result = retry_async_with_sleeper(
+            &fixed_policy(3),
+            &sleeper,
+            "test",
+            || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    match attempt {
+                        1 => Err(SdkError::Timeout),
+                        2 => Err(SdkError::Transport(TransportError::Network("reset".into()))),
+                        _ => Ok(attempt),
+                    }
+                }
+            },
+            Some(&mut record),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+        assert_eq!(
+            *sleeper.delays.lock().unwrap(),
+            vec![Duration::from_millis(100), Duration::from_millis(200)]
+        );
+        assert!(matches!(
+            &events[..],
+            [
+                Event::Retrying {
+                    attempt: 2,
+                    max_attempts: 3,
+                    delay_secs: 1,
+                    ..
+                },
+                Event::Retrying {
+                    attempt: 3,
+                    max_attempts: 3,
+                    ..
+                },
+            ]
+        ));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retry_async_uses_retry_after_as_delay_floor() {
+        let sleeper = RecordingSleeper::default();
+        let mut calls = 0;
+        let result = retry_async_with_sleeper(
+            &fixed_policy(4),
+            &sleeper,
+            "test",
+            || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    match attempt {
+                        1 => Err(http_status(429, Some(5_000))),
+                        2 => Err(http_status(503, Some(10))),
+                        _ => Ok(()),
+                    }
+                }
+            },
+            None,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *sleeper.delays.lock().unwrap(),
+            vec![Duration::from_secs(5), Duration::from_millis(200)]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retry_async_stops_on_client_errors_and_exhausted_attempts() {
+        let sleeper = RecordingSleeper::default();
+        let mut calls = 0;
+        let result: Result<(), SdkError> = retry_async_with_sleeper(
+            &fixed_policy(3),
+            &sleeper,
+            "test",
+            || {
+                calls += 1;
+                async { Err(http_status(400, None)) }
+            },
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        calls = 0;
+        let result: Result<(), SdkError> = retry_async_with_sleeper(
+            &fixed_policy(2),
+            &sleeper,
+            "test",
+            || {
+                calls += 1;
+                async { Err(SdkError::Timeout) }
+            },
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err(SdkError::Timeout)));
+        assert_eq!(calls, 2);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retry_async_follows_the_retryable_impl() {
+        let sleeper = RecordingSleeper::default();
+        let run = |err: fn() -> SdkError| {
+            let sleeper = &sleeper;
+            async move {
+                let mut calls = 0;
+                let _: Result<(), SdkError> = retry_async_with_sleeper(
+                    &fixed_policy(2),
+                    sleeper,
+                    "test",
+                    || {
+                        calls += 1;
+                        async move { Err(err()) }
+                    },
+                    None,
+                )
+                .await;
+                calls
+            }
+        };
+
+        assert_eq!(
+            run(|| SdkError::Upstream {
+                status: 408,
+                message: "timeout".into(),
+                source: None,
+            })
+            .await,
+            2
+        );
+        assert_eq!(
+            run(|| SdkError::Transport(TransportError::IdleReadTimeout(Duration::from_secs(1))))
+                .await,
+            2
+        );
+        assert_eq!(
+            run(|| SdkError::QuotaExceeded {
+                message: "quota".into(),
+                source: None,
+            })
+            .await,
+            1
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_computed_backoff() {
+        let policy = RetryPolicy {
+            jitter: JitterKind::Equal,
+            ..fixed_policy(5)
+        };
+        for _ in 0..100 {
+            let delay = policy.backoff(3, None);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+}
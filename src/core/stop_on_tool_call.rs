@@ -0,0 +1,101 @@
+//! Early termination of streams at the first tool call.
+//!
+//! Enabled with [`v2t::CallOptions::stop_on_first_tool_call`]. Once a complete
+//! client-executed tool call is emitted the upstream stream is dropped, which
+//! cancels the provider request, and a `tool-calls` finish part ends the
+//! stream. Providers that track usage while streaming stop inside their own
+//! stream loop so the finish part carries the usage reported so far; the
+//! others use [`StreamResponse::with_stop_on_first_tool_call`].
+
+use std::collections::BTreeSet;
+
+use futures_util::StreamExt;
+
+use crate::core::v2::{PartStream, StreamResponse};
+use crate::types::v2 as v2t;
+
+/// Whether `part` ends a stream that stops on the first tool call.
+///
+/// Provider-executed calls are not actionable by the caller and do not stop
+/// the stream.
+pub fn is_stopping_tool_call(part: &v2t::StreamPart) -> bool {
+    matches!(part, v2t::StreamPart::ToolCall(call) if !call.provider_executed)
+}
+
+/// Clear the flag for a non-streaming call, returning the warning to report.
+///
+/// Generate calls cannot stop early; providers that collect a stream to
+/// answer them must not cut it short either.
+pub fn disable_stop_on_first_tool_call(options: &mut v2t::CallOptions) -> Option<v2t::CallWarning> {
+    std::mem::take(&mut options.stop_on_first_tool_call).then(|| {
+        v2t::CallWarning::UnsupportedSetting {
+            setting: "stopOnFirstToolCall".into(),
+            details: Some("only applies to streaming calls".into()),
+        }
+    })
+}
+
+impl StreamResponse {
+    /// End the stream after the first client-executed tool call when
+    /// `enabled`; see [`stop_on_first_tool_call`].
+    pub fn with_stop_on_first_tool_call(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.stream = stop_on_first_tool_call(self.stream);
+        }
+        self
+    }
+}
+
+/// End `parts` after the first client-executed tool call.
+///
+/// Text and reasoning blocks still open at that point are closed before the
+/// finish part. Usage is unknown at the cut-off, so it is left empty.
+pub fn stop_on_first_tool_call(parts: PartStream) -> PartStream {
+    Box::pin(async_stream::stream! {
+        let mut parts = parts;
+        let mut open_text = BTreeSet::new();
+        let mut open_reasoning = BTreeSet::new();
+        while let Some(item) = parts.next().await {
+            let stop = match &item {
+                Ok(v2t::StreamPart::TextStart { id, .. }) => {
+                    open_text.insert(id.clone());
+                    false
+                }
+                Ok(v2t::StreamPart::TextEnd { id, .. }) => {
+                    open_text.remove(id);
+                    false
+                }
+                Ok(v2t::StreamPart::ReasoningStart { id, .. }) => {
+                    open_reasoning.insert(id.clone());
+                    false
+                }
+                Ok(v2t::StreamPart::ReasoningEnd { id, .. }) => {
+                    open_reasoning.remove(id);
+                    false
+                }
+                Ok(part) => is_stopping_tool_call(part),
+                Err(_) => false,
+            };
+            yield item;
+            if stop {
+                drop(parts);
+                for id in open_text {
+                    yield Ok(v2t::StreamPart::TextEnd { id, provider_metadata: None });
+                }
+                for id in open_reasoning {
+                    yield Ok(v2t::StreamPart::ReasoningEnd { id, provider_metadata: None });
+                }
+                yield Ok(v2t::StreamPart::Finish {
+                    usage: v2t::Usage::default(),
+                    finish_reason: v2t::FinishReason::ToolCalls,
+                    provider_metadata: None,
+                });
+                return;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/stop_on_tool_call_tests.rs"]
+mod tests;
@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 
+use futures_core::Stream;
+use futures_util::task::{waker, ArcWake};
 use futures_util::StreamExt;
 
+use crate::ai_sdk_core::stream_integrity::IntegrityReport;
 use crate::ai_sdk_core::SdkError;
-use crate::ai_sdk_core::{GenerateResponse, StreamResponse};
+use crate::ai_sdk_core::{GenerateResponse, PartStream, StreamResponse};
 use crate::ai_sdk_types::v2 as v2t;
 
 /// Controls how stream parts are collapsed into a `GenerateResponse`.
@@ -39,7 +45,60 @@ impl Default for StreamCollectorConfig {
     }
 }
 
+impl StreamResponse {
+    /// Drain the stream into a `GenerateResponse` holding every completed
+    /// part: text, reasoning, tool calls and results, files and sources.
+    ///
+    /// This consumes the stream; use it when only the final turn matters,
+    /// e.g. to act on [`GenerateResponse::tool_calls`]. Stream errors and
+    /// `StreamPart::Error` parts fail the call.
+    pub async fn into_generate(self) -> Result<GenerateResponse, SdkError> {
+        let request_body = self.request_body.clone();
+        let cfg = StreamCollectorConfig {
+            allow_reasoning: true,
+            reasoning_metadata_scope: None,
+            allow_tool_calls: true,
+            allow_tool_results: true,
+            allow_files: true,
+            allow_source_urls: true,
+            fail_on_error: true,
+        };
+        let mut response = collect_stream_to_response(self, cfg).await?;
+        response.request_body = request_body;
+        Ok(response)
+    }
+}
+
+/// Collect a bare [`PartStream`] into a `GenerateResponse`, as
+/// [`StreamResponse::into_generate`] does.
+///
+/// Text and reasoning deltas are joined per block, and tool calls, tool
+/// results, files and sources are kept in stream order. Usage and finish
+/// reason come from the `Finish` part; the first stream error or
+/// `StreamPart::Error` fails the call.
+pub async fn collect_generate(stream: PartStream) -> Result<GenerateResponse, SdkError> {
+    StreamResponse {
+        stream,
+        request_body: None,
+        response_headers: None,
+        raw_response: None,
+    }
+    .into_generate()
+    .await
+}
+
+/// Progressive previews are superseded by the final file and not collected.
+fn is_partial_file(provider_metadata: Option<&v2t::ProviderMetadata>) -> bool {
+    provider_metadata.is_some_and(|scopes| {
+        scopes
+            .values()
+            .any(|scope| scope.get("partial").and_then(|v| v.as_bool()) == Some(true))
+    })
+}
+
 /// Collapse a `StreamResponse` into a `GenerateResponse`, honoring the provided config.
+///
+/// Captured raw frames, if any, become the response body as a JSON array.
 pub async fn collect_stream_to_response(
     stream_resp: StreamResponse,
     cfg: StreamCollectorConfig,
@@ -49,9 +108,12 @@ pub async fn collect_stream_to_response(
     let mut reasoning_buf: HashMap<String, String> = HashMap::new();
     let mut reasoning_signature: Option<String> = None;
     let mut usage = v2t::Usage::default();
+    let mut provider_metadata = None;
     let mut finish_reason = v2t::FinishReason::Unknown;
+    let mut response_metadata: Option<v2t::ResponseMetadata> = None;
     let mut warnings: Vec<v2t::CallWarning> = Vec::new();
 
+    let raw_response = stream_resp.raw_response;
     let stream = stream_resp.stream;
     futures_util::pin_mut!(stream);
     while let Some(item) = stream.next().await {
@@ -60,6 +122,9 @@ pub async fn collect_stream_to_response(
             v2t::StreamPart::StreamStart { warnings: w } => {
                 warnings = w;
             }
+            v2t::StreamPart::ResponseMetadata { meta } => {
+                response_metadata = Some(meta);
+            }
             v2t::StreamPart::TextStart { id, .. } => {
                 text_buf.entry(id).or_default();
             }
@@ -146,7 +211,11 @@ pub async fn collect_stream_to_response(
                     provider_metadata,
                 });
             }
-            v2t::StreamPart::File { media_type, data } if cfg.allow_files => {
+            v2t::StreamPart::File {
+                media_type,
+                data,
+                provider_metadata,
+            } if cfg.allow_files && !is_partial_file(provider_metadata.as_ref()) => {
                 content.push(v2t::Content::File { media_type, data });
             }
             v2t::StreamPart::SourceUrl {
@@ -165,10 +234,14 @@ pub async fn collect_stream_to_response(
             v2t::StreamPart::Finish {
                 usage: u,
                 finish_reason: fr,
-                ..
+                provider_metadata: pm,
             } => {
+                warnings.extend(
+                    IntegrityReport::from_metadata(pm.as_ref()).and_then(|report| report.warning()),
+                );
                 usage = u;
                 finish_reason = fr;
+                provider_metadata = pm;
                 break;
             }
             v2t::StreamPart::Error { error } if cfg.fail_on_error => {
@@ -186,10 +259,163 @@ pub async fn collect_stream_to_response(
         content,
         finish_reason,
         usage,
-        provider_metadata: None,
+        provider_metadata,
+        response_metadata,
         request_body: None,
         response_headers: stream_resp.response_headers,
-        response_body: None,
+        response_body: raw_response.map(|capture| capture.to_json()),
         warnings,
     })
 }
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    Text,
+    Reasoning,
+    Tail,
+}
+
+impl Channel {
+    fn of(item: &Result<v2t::StreamPart, SdkError>) -> Self {
+        match item {
+            Ok(
+                v2t::StreamPart::TextStart { .. }
+                | v2t::StreamPart::TextDelta { .. }
+                | v2t::StreamPart::TextEnd { .. },
+            ) => Channel::Text,
+            Ok(
+                v2t::StreamPart::ReasoningStart { .. }
+                | v2t::StreamPart::ReasoningDelta { .. }
+                | v2t::StreamPart::ReasoningEnd { .. }
+                | v2t::StreamPart::ReasoningSignature { .. },
+            ) => Channel::Reasoning,
+            _ => Channel::Tail,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChannelQueue {
+    parts: VecDeque<Result<v2t::StreamPart, SdkError>>,
+    dropped: bool,
+}
+
+/// Wakers of channels waiting on the source. Kept outside the `Demux` lock
+/// because sources may wake synchronously while being polled.
+#[derive(Default)]
+struct ChannelWakers(Mutex<[Option<Waker>; 3]>);
+
+impl ChannelWakers {
+    fn set(&self, channel: Channel, waker: Option<Waker>) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())[channel as usize] = waker;
+    }
+
+    fn wake_channel(&self, channel: Channel) {
+        let waker = self.0.lock().unwrap_or_else(|e| e.into_inner())[channel as usize].take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl ArcWake for ChannelWakers {
+    /// Wake every waiting channel, so whichever consumer is still polling
+    /// drives the source.
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        for channel in [Channel::Text, Channel::Reasoning, Channel::Tail] {
+            arc_self.wake_channel(channel);
+        }
+    }
+}
+
+struct Demux {
+    source: PartStream,
+    done: bool,
+    queues: [ChannelQueue; 3],
+}
+
+struct ChannelStream {
+    demux: Arc<Mutex<Demux>>,
+    wakers: Arc<ChannelWakers>,
+    channel: Channel,
+}
+
+impl Stream for ChannelStream {
+    type Item = Result<v2t::StreamPart, SdkError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut demux = this.demux.lock().unwrap_or_else(|e| e.into_inner());
+        let own = this.channel as usize;
+        loop {
+            if let Some(item) = demux.queues[own].parts.pop_front() {
+                return Poll::Ready(Some(item));
+            }
+            if demux.done {
+                return Poll::Ready(None);
+            }
+            this.wakers.set(this.channel, Some(cx.waker().clone()));
+            let source_waker = waker(this.wakers.clone());
+            let mut source_cx = Context::from_waker(&source_waker);
+            match demux.source.as_mut().poll_next(&mut source_cx) {
+                Poll::Ready(Some(item)) => {
+                    let target = Channel::of(&item);
+                    let queue = &mut demux.queues[target as usize];
+                    if !queue.dropped {
+                        queue.parts.push_back(item);
+                        if target != this.channel {
+                            this.wakers.wake_channel(target);
+                        }
+                    }
+                }
+                Poll::Ready(None) => {
+                    demux.done = true;
+                    ArcWake::wake_by_ref(&this.wakers);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Drop for ChannelStream {
+    fn drop(&mut self) {
+        self.wakers.set(self.channel, None);
+        let mut demux = self.demux.lock().unwrap_or_else(|e| e.into_inner());
+        let queue = &mut demux.queues[self.channel as usize];
+        queue.dropped = true;
+        queue.parts.clear();
+    }
+}
+
+/// Split `stream` into `(text, reasoning, tail)` streams.
+///
+/// Text and reasoning parts (including reasoning signatures) go to their own
+/// channel; everything else, including errors and the finish part with
+/// usage, goes to the tail. Each channel ends when the source ends. Polling
+/// any channel drives the shared source, and parts for a channel that is not
+/// being polled are buffered until it is, so drop channels you do not need.
+pub fn split_channels(stream: PartStream) -> (PartStream, PartStream, PartStream) {
+    let demux = Arc::new(Mutex::new(Demux {
+        source: stream,
+        done: false,
+        queues: Default::default(),
+    }));
+    let wakers = Arc::new(ChannelWakers::default());
+    let channel = |channel| -> PartStream {
+        Box::pin(ChannelStream {
+            demux: demux.clone(),
+            wakers: wakers.clone(),
+            channel,
+        })
+    };
+    (
+        channel(Channel::Text),
+        channel(Channel::Reasoning),
+        channel(Channel::Tail),
+    )
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/stream_collect_tests.rs"]
+mod tests;
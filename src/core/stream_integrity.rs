@@ -0,0 +1,173 @@
+//! Advisory check that streamed output matches the reported usage.
+//!
+//! Opt in with [`StreamResponse::with_integrity_check`] or
+//! [`GenerateResponse::with_integrity_check`]. The assembled text, reasoning
+//! and tool call input is counted with the registered
+//! [`TokenCounter`](crate::core::usage_estimate::TokenCounter) and compared
+//! to the provider's output token count. The figures are recorded under
+//! `provider_metadata["integrity"]`; a large gap, which usually means a
+//! truncated or corrupted stream, adds a warning. Nothing is ever rejected.
+
+use std::collections::HashMap;
+
+use futures_util::StreamExt;
+use serde_json::{json, Value as JsonValue};
+
+use crate::core::usage_estimate::count_tokens;
+use crate::core::v2::{GenerateResponse, StreamResponse};
+use crate::types::v2 as v2t;
+
+/// Provider metadata scope holding the integrity figures.
+pub const INTEGRITY_METADATA_SCOPE: &str = "integrity";
+
+/// Token gap always tolerated, covering counter drift on short outputs.
+const MIN_TOKEN_SLACK: u64 = 16;
+
+/// Assembled output measured against the reported output token count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Characters of text, reasoning and tool call input received.
+    pub output_chars: u64,
+    /// Tokens in that output according to the registered counter.
+    pub estimated_output_tokens: u64,
+    /// Output tokens reported by the provider, if any.
+    pub reported_output_tokens: Option<u64>,
+    /// Reported tokens the assembled output should account for: hidden
+    /// reasoning and audio tokens are excluded.
+    pub expected_output_tokens: Option<u64>,
+}
+
+impl IntegrityReport {
+    /// Measure `output` against `usage`. `reasoning_visible` says whether
+    /// reasoning text was part of `output`.
+    pub fn new(output: &str, usage: &v2t::Usage, reasoning_visible: bool) -> Self {
+        let hidden = usage.audio_output_tokens.unwrap_or(0)
+            + if reasoning_visible {
+                0
+            } else {
+                usage.reasoning_tokens.unwrap_or(0)
+            };
+        Self {
+            output_chars: output.chars().count() as u64,
+            estimated_output_tokens: count_tokens(output),
+            reported_output_tokens: usage.output_tokens,
+            expected_output_tokens: usage
+                .output_tokens
+                .map(|tokens| tokens.saturating_sub(hidden)),
+        }
+    }
+
+    /// Whether the estimate is off by more than half the expected count
+    /// (and more than a small fixed slack). Always false without a reported
+    /// figure.
+    pub fn mismatch(&self) -> bool {
+        let Some(expected) = self.expected_output_tokens else {
+            return false;
+        };
+        let gap = self.estimated_output_tokens.abs_diff(expected);
+        gap > MIN_TOKEN_SLACK.max(expected / 2)
+    }
+
+    /// Warning describing a mismatch, if there is one.
+    pub fn warning(&self) -> Option<v2t::CallWarning> {
+        let expected = self.expected_output_tokens?;
+        self.mismatch().then(|| v2t::CallWarning::Other {
+            message: format!(
+                "stream integrity: assembled output is ~{} tokens ({} chars) but the provider \
+                 reported {} output tokens; the response may be truncated or corrupted",
+                self.estimated_output_tokens, self.output_chars, expected
+            ),
+        })
+    }
+
+    /// Read a report recorded by [`IntegrityReport::record`].
+    pub fn from_metadata(metadata: Option<&v2t::ProviderMetadata>) -> Option<Self> {
+        let scope = metadata?.get(INTEGRITY_METADATA_SCOPE)?;
+        let number = |key: &str| scope.get(key).and_then(JsonValue::as_u64);
+        Some(Self {
+            output_chars: number("outputChars")?,
+            estimated_output_tokens: number("estimatedOutputTokens")?,
+            reported_output_tokens: number("reportedOutputTokens"),
+            expected_output_tokens: number("expectedOutputTokens"),
+        })
+    }
+
+    fn record(&self, metadata: &mut Option<v2t::ProviderMetadata>) {
+        let scope = metadata
+            .get_or_insert_with(HashMap::new)
+            .entry(INTEGRITY_METADATA_SCOPE.to_string())
+            .or_default();
+        scope.insert("outputChars".into(), json!(self.output_chars));
+        scope.insert(
+            "estimatedOutputTokens".into(),
+            json!(self.estimated_output_tokens),
+        );
+        if let Some(reported) = self.reported_output_tokens {
+            scope.insert("reportedOutputTokens".into(), json!(reported));
+        }
+        if let Some(expected) = self.expected_output_tokens {
+            scope.insert("expectedOutputTokens".into(), json!(expected));
+        }
+        scope.insert("mismatch".into(), JsonValue::Bool(self.mismatch()));
+    }
+}
+
+impl StreamResponse {
+    /// Record an [`IntegrityReport`] on the finish part, measuring the text,
+    /// reasoning and tool call input streamed before it.
+    ///
+    /// `provider_metadata["integrity"]["mismatch"]` flags a suspicious gap;
+    /// [`StreamResponse::into_generate`] turns it into a warning.
+    pub fn with_integrity_check(mut self) -> Self {
+        let mut output = String::new();
+        let mut reasoning_visible = false;
+        self.stream = Box::pin(self.stream.map(move |item| {
+            let mut part = item?;
+            match &mut part {
+                v2t::StreamPart::TextDelta { delta, .. } => output.push_str(delta),
+                v2t::StreamPart::ReasoningDelta { delta, .. } => {
+                    reasoning_visible = true;
+                    output.push_str(delta);
+                }
+                v2t::StreamPart::ToolCall(call) => output.push_str(&call.input),
+                v2t::StreamPart::Finish {
+                    usage,
+                    provider_metadata,
+                    ..
+                } => IntegrityReport::new(&output, usage, reasoning_visible)
+                    .record(provider_metadata),
+                _ => {}
+            }
+            Ok(part)
+        }));
+        self
+    }
+}
+
+impl GenerateResponse {
+    /// Record an [`IntegrityReport`] for the generated text, reasoning and
+    /// tool call input, pushing a warning on mismatch.
+    pub fn with_integrity_check(mut self) -> Self {
+        let mut output = String::new();
+        let mut reasoning_visible = false;
+        for part in &self.content {
+            match part {
+                v2t::Content::Text { text, .. } => output.push_str(text),
+                v2t::Content::Reasoning { text, .. } => {
+                    reasoning_visible = true;
+                    output.push_str(text);
+                }
+                v2t::Content::ToolCall(call) => output.push_str(&call.input),
+                _ => {}
+            }
+        }
+        let report = IntegrityReport::new(&output, &self.usage, reasoning_visible);
+        report.record(&mut self.provider_metadata);
+        self.warnings.extend(report.warning());
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/stream_integrity_tests.rs"]
+mod tests;
@@ -0,0 +1,374 @@
+//! Ordered assertions over streamed parts, for provider regression tests.
+//!
+//! Enabled by the `testing` feature.
+//!
+//! ```ignore
+//! StreamAsserter::new()
+//!     .text_start()
+//!     .text("Hello world")
+//!     .text_end()
+//!     .finish(FinishReason::Stop)
+//!     .assert(response.stream)
+//!     .await;
+//! ```
+
+use futures_util::StreamExt;
+use serde_json::Value;
+
+use crate::core::v2::PartStream;
+use crate::types::v2 as v2t;
+
+type PartPredicate = Box<dyn Fn(&v2t::StreamPart) -> bool + Send + Sync>;
+
+/// Longest part rendering in a failure transcript.
+const TRANSCRIPT_PART_CHARS: usize = 160;
+
+enum Expectation {
+    Part {
+        label: String,
+        matches: PartPredicate,
+    },
+    Deltas {
+        reasoning: bool,
+        expected: String,
+    },
+    Finish {
+        reason: v2t::FinishReason,
+        usage: Option<v2t::Usage>,
+    },
+}
+
+impl Expectation {
+    fn describe(&self) -> String {
+        match self {
+            Expectation::Part { label, .. } => label.clone(),
+            Expectation::Deltas {
+                reasoning: false,
+                expected,
+            } => format!("text {expected:?}"),
+            Expectation::Deltas {
+                reasoning: true,
+                expected,
+            } => format!("reasoning {expected:?}"),
+            Expectation::Finish { reason, usage } => match usage {
+                Some(_) => format!("finish {} with usage", json(reason)),
+                None => format!("finish {}", json(reason)),
+            },
+        }
+    }
+}
+
+/// Checks that a stream yields parts matching an ordered list of
+/// expectations.
+///
+/// `StreamStart`, `ResponseMetadata` and `Raw` parts are ignored unless
+/// [`strict`](Self::strict) is called. Failures report the expectation,
+/// expected versus actual values and a transcript of the stream.
+pub struct StreamAsserter {
+    expectations: Vec<Expectation>,
+    ignored: Vec<PartPredicate>,
+    skip_unmatched: bool,
+    allow_trailing: bool,
+}
+
+impl Default for StreamAsserter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamAsserter {
+    pub fn new() -> Self {
+        Self {
+            expectations: Vec::new(),
+            ignored: vec![Box::new(|part| {
+                matches!(
+                    part,
+                    v2t::StreamPart::StreamStart { .. }
+                        | v2t::StreamPart::ResponseMetadata { .. }
+                        | v2t::StreamPart::Raw { .. }
+                )
+            })],
+            skip_unmatched: false,
+            allow_trailing: false,
+        }
+    }
+
+    /// Stop ignoring `StreamStart`, `ResponseMetadata` and `Raw` parts.
+    pub fn strict(mut self) -> Self {
+        self.ignored.clear();
+        self
+    }
+
+    /// Also ignore parts matching `predicate`.
+    pub fn ignore<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&v2t::StreamPart) -> bool + Send + Sync + 'static,
+    {
+        self.ignored.push(Box::new(predicate));
+        self
+    }
+
+    /// Skip parts of another kind than the next expectation instead of
+    /// failing. A part of the right kind with wrong contents still fails.
+    pub fn skip_unmatched(mut self) -> Self {
+        self.skip_unmatched = true;
+        self
+    }
+
+    /// Accept parts after the last expectation.
+    pub fn allow_trailing(mut self) -> Self {
+        self.allow_trailing = true;
+        self
+    }
+
+    /// Next part satisfies `predicate`; `label` names it in failures.
+    pub fn part<F>(mut self, label: impl Into<String>, predicate: F) -> Self
+    where
+        F: Fn(&v2t::StreamPart) -> bool + Send + Sync + 'static,
+    {
+        self.expectations.push(Expectation::Part {
+            label: label.into(),
+            matches: Box::new(predicate),
+        });
+        self
+    }
+
+    pub fn text_start(self) -> Self {
+        self.part("text-start", |part| {
+            matches!(part, v2t::StreamPart::TextStart { .. })
+        })
+    }
+
+    pub fn text_end(self) -> Self {
+        self.part("text-end", |part| {
+            matches!(part, v2t::StreamPart::TextEnd { .. })
+        })
+    }
+
+    pub fn reasoning_start(self) -> Self {
+        self.part("reasoning-start", |part| {
+            matches!(part, v2t::StreamPart::ReasoningStart { .. })
+        })
+    }
+
+    pub fn reasoning_end(self) -> Self {
+        self.part("reasoning-end", |part| {
+            matches!(part, v2t::StreamPart::ReasoningEnd { .. })
+        })
+    }
+
+    /// One or more consecutive text deltas whose concatenation is `expected`.
+    pub fn text(mut self, expected: impl Into<String>) -> Self {
+        self.expectations.push(Expectation::Deltas {
+            reasoning: false,
+            expected: expected.into(),
+        });
+        self
+    }
+
+    /// One or more consecutive reasoning deltas whose concatenation is
+    /// `expected`.
+    pub fn reasoning(mut self, expected: impl Into<String>) -> Self {
+        self.expectations.push(Expectation::Deltas {
+            reasoning: true,
+            expected: expected.into(),
+        });
+        self
+    }
+
+    /// A tool call to `tool_name`.
+    pub fn tool_call(self, tool_name: impl Into<String>) -> Self {
+        let tool_name = tool_name.into();
+        self.part(format!("tool-call {tool_name:?}"), move |part| {
+            matches!(part, v2t::StreamPart::ToolCall(call) if call.tool_name == tool_name)
+        })
+    }
+
+    pub fn finish(mut self, reason: v2t::FinishReason) -> Self {
+        self.expectations.push(Expectation::Finish {
+            reason,
+            usage: None,
+        });
+        self
+    }
+
+    /// A finish part with `reason` and exactly `usage`.
+    pub fn finish_with_usage(mut self, reason: v2t::FinishReason, usage: v2t::Usage) -> Self {
+        self.expectations.push(Expectation::Finish {
+            reason,
+            usage: Some(usage),
+        });
+        self
+    }
+
+    /// Drain `stream` and check it, panicking with a report on mismatch.
+    /// Returns every collected part.
+    pub async fn assert(self, stream: PartStream) -> Vec<v2t::StreamPart> {
+        match self.check(stream).await {
+            Ok(parts) => parts,
+            Err(report) => panic!("{report}"),
+        }
+    }
+
+    /// Drain `stream` and check it, returning the report on mismatch.
+    pub async fn check(self, mut stream: PartStream) -> Result<Vec<v2t::StreamPart>, String> {
+        let mut parts = Vec::new();
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(part) => parts.push(part),
+                Err(err) => {
+                    return Err(format!(
+                        "stream failed after {} parts: {err}\n{}",
+                        parts.len(),
+                        transcript(&parts.iter().collect::<Vec<_>>(), None)
+                    ))
+                }
+            }
+        }
+        self.check_parts(&parts)?;
+        Ok(parts)
+    }
+
+    /// Check already collected parts.
+    pub fn check_parts(&self, parts: &[v2t::StreamPart]) -> Result<(), String> {
+        let parts: Vec<&v2t::StreamPart> = parts
+            .iter()
+            .filter(|part| !self.ignored.iter().any(|ignored| ignored(part)))
+            .collect();
+        let mut idx = 0;
+        for (n, expectation) in self.expectations.iter().enumerate() {
+            loop {
+                let fail = |detail: String, at: Option<usize>| {
+                    Err(format!(
+                        "stream assertion failed at expectation #{n} ({}): {detail}\n{}",
+                        expectation.describe(),
+                        transcript(&parts, at)
+                    ))
+                };
+                let Some(part) = parts.get(idx) else {
+                    return fail("stream ended".into(), None);
+                };
+                match self.match_at(expectation, &parts, idx) {
+                    Match::Consumed(next) => {
+                        idx = next;
+                        break;
+                    }
+                    Match::Mismatch(None) if self.skip_unmatched => idx += 1,
+                    Match::Mismatch(detail) => {
+                        return fail(
+                            detail.unwrap_or_else(|| format!("got {}", part_kind(part))),
+                            Some(idx),
+                        )
+                    }
+                }
+            }
+        }
+        if !self.allow_trailing && idx < parts.len() {
+            return Err(format!(
+                "stream assertion failed: unexpected {} after the last expectation\n{}",
+                part_kind(parts[idx]),
+                transcript(&parts, Some(idx))
+            ));
+        }
+        Ok(())
+    }
+
+    fn match_at(&self, expectation: &Expectation, parts: &[&v2t::StreamPart], idx: usize) -> Match {
+        match expectation {
+            Expectation::Part { matches, .. } => {
+                if matches(parts[idx]) {
+                    Match::Consumed(idx + 1)
+                } else {
+                    Match::Mismatch(None)
+                }
+            }
+            Expectation::Deltas {
+                reasoning,
+                expected,
+            } => {
+                let delta = |part: &v2t::StreamPart| match part {
+                    v2t::StreamPart::TextDelta { delta, .. } if !reasoning => Some(delta.clone()),
+                    v2t::StreamPart::ReasoningDelta { delta, .. } if *reasoning => {
+                        Some(delta.clone())
+                    }
+                    _ => None,
+                };
+                let deltas: Vec<String> = parts[idx..].iter().map_while(|p| delta(p)).collect();
+                if deltas.is_empty() {
+                    return Match::Mismatch(None);
+                }
+                let actual = deltas.concat();
+                if &actual == expected {
+                    Match::Consumed(idx + deltas.len())
+                } else {
+                    Match::Mismatch(Some(format!(
+                        "deltas differ\n  expected: {expected:?}\n  actual:   {actual:?}"
+                    )))
+                }
+            }
+            Expectation::Finish { reason, usage } => {
+                let v2t::StreamPart::Finish {
+                    finish_reason,
+                    usage: actual_usage,
+                    ..
+                } = parts[idx]
+                else {
+                    return Match::Mismatch(None);
+                };
+                if json(finish_reason) != json(reason) {
+                    return Match::Mismatch(Some(format!(
+                        "finish reason differs\n  expected: {}\n  actual:   {}",
+                        json(reason),
+                        json(finish_reason)
+                    )));
+                }
+                match usage {
+                    Some(usage) if json(usage) != json(actual_usage) => {
+                        Match::Mismatch(Some(format!(
+                            "usage differs\n  expected: {}\n  actual:   {}",
+                            json(usage),
+                            json(actual_usage)
+                        )))
+                    }
+                    _ => Match::Consumed(idx + 1),
+                }
+            }
+        }
+    }
+}
+
+enum Match {
+    Consumed(usize),
+    Mismatch(Option<String>),
+}
+
+fn json<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn part_kind(part: &v2t::StreamPart) -> String {
+    json(part)
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("part")
+        .to_string()
+}
+
+fn transcript(parts: &[&v2t::StreamPart], marker: Option<usize>) -> String {
+    let mut out = String::from("parts:");
+    for (idx, part) in parts.iter().enumerate() {
+        let mut rendered = json(*part).to_string();
+        if rendered.chars().count() > TRANSCRIPT_PART_CHARS {
+            rendered = rendered.chars().take(TRANSCRIPT_PART_CHARS).collect();
+            rendered.push_str("...");
+        }
+        let pointer = if marker == Some(idx) { ">" } else { " " };
+        out.push_str(&format!("\n {pointer} {idx:>3} {rendered}"));
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/testing_tests.rs"]
+mod tests;
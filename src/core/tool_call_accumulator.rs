@@ -0,0 +1,236 @@
+//! Aggregation of streamed tool-call input into completed tool calls.
+//!
+//! Providers stream tool input as start/delta/end lifecycles keyed by id, and
+//! several calls may be in flight with interleaved deltas. The accumulator
+//! buffers input per id and surfaces only finished [`v2t::ToolCallPart`]s, plus
+//! optional progress snapshots.
+
+use std::collections::{HashMap, HashSet};
+
+use futures_util::{Stream, StreamExt};
+
+use crate::core::error::SdkError;
+use crate::core::v2::PartStream;
+use crate::types::v2 as v2t;
+use crate::types::Event;
+
+/// Partial state of a tool call whose input is still streaming.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCallSnapshot {
+    pub tool_call_id: String,
+    pub tool_name: String,
+    /// Input text received so far (usually incomplete JSON).
+    pub input: String,
+    pub provider_executed: bool,
+}
+
+/// Output of [`ToolCallAccumulator`].
+#[derive(Debug, Clone)]
+pub enum ToolCallUpdate {
+    /// More input arrived for a pending call.
+    Progress(ToolCallSnapshot),
+    /// A call finished streaming.
+    Completed(v2t::ToolCallPart),
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    tool_name: String,
+    input: String,
+    provider_executed: bool,
+    ended: bool,
+}
+
+/// Buffers tool input deltas per call id and emits completed tool calls.
+///
+/// A `ToolCall` part is authoritative: it completes the call with the
+/// provider's final input and discards the buffer. Calls that only report
+/// `ToolInputEnd` are completed from the buffered input when the stream
+/// finishes (see [`ToolCallAccumulator::finish`]). Each id completes once.
+#[derive(Debug, Default)]
+pub struct ToolCallAccumulator {
+    pending: HashMap<String, PendingToolCall>,
+    order: Vec<String>,
+    completed: HashSet<String>,
+}
+
+impl ToolCallAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a stream part, returning an update when it affects a tool call.
+    pub fn push_part(&mut self, part: &v2t::StreamPart) -> Option<ToolCallUpdate> {
+        match part {
+            v2t::StreamPart::ToolInputStart {
+                id,
+                tool_name,
+                provider_executed,
+                ..
+            } => {
+                self.start(id, tool_name, *provider_executed);
+                None
+            }
+            v2t::StreamPart::ToolInputDelta { id, delta, .. } => self.delta(id, delta),
+            v2t::StreamPart::ToolInputEnd { id, .. } => {
+                self.end(id);
+                None
+            }
+            v2t::StreamPart::ToolCall(call) => self.complete(call.clone()),
+            _ => None,
+        }
+    }
+
+    /// Feed a legacy [`Event`], returning an update when it affects a tool call.
+    ///
+    /// Events carry no final tool-call record, so calls complete on `ToolCallEnd`.
+    pub fn push_event(&mut self, event: &Event) -> Option<ToolCallUpdate> {
+        match event {
+            Event::ToolCallStart { id, name } => {
+                self.start(id, name, false);
+                None
+            }
+            Event::ToolCallDelta { id, args_json } => self.delta(id, args_json),
+            Event::ToolCallEnd { id } => {
+                let call = self.take_buffered(id)?;
+                self.complete(call)
+            }
+            _ => None,
+        }
+    }
+
+    /// Snapshots of all calls still pending, in start order.
+    pub fn snapshots(&self) -> Vec<ToolCallSnapshot> {
+        self.order
+            .iter()
+            .filter_map(|id| self.pending.get(id).map(|call| snapshot(id, call)))
+            .collect()
+    }
+
+    /// Complete calls whose input ended without a final `ToolCall` part.
+    ///
+    /// Calls that never reported an end are incomplete and are dropped.
+    pub fn finish(&mut self) -> Vec<v2t::ToolCallPart> {
+        let ended: Vec<String> = self
+            .order
+            .iter()
+            .filter(|id| self.pending.get(*id).is_some_and(|call| call.ended))
+            .cloned()
+            .collect();
+        let calls = ended
+            .iter()
+            .filter_map(|id| self.take_buffered(id))
+            .collect::<Vec<_>>();
+        self.completed
+            .extend(calls.iter().map(|call| call.tool_call_id.clone()));
+        self.pending.clear();
+        self.order.clear();
+        calls
+    }
+
+    fn start(&mut self, id: &str, tool_name: &str, provider_executed: bool) {
+        if self.completed.contains(id) {
+            return;
+        }
+        let call = self.pending.entry(id.to_string()).or_insert_with(|| {
+            self.order.push(id.to_string());
+            PendingToolCall::default()
+        });
+        call.tool_name = tool_name.to_string();
+        call.provider_executed = provider_executed;
+    }
+
+    fn delta(&mut self, id: &str, delta: &str) -> Option<ToolCallUpdate> {
+        if self.completed.contains(id) {
+            return None;
+        }
+        let call = self.pending.entry(id.to_string()).or_insert_with(|| {
+            self.order.push(id.to_string());
+            PendingToolCall::default()
+        });
+        call.input.push_str(delta);
+        Some(ToolCallUpdate::Progress(snapshot(id, call)))
+    }
+
+    fn end(&mut self, id: &str) {
+        if let Some(call) = self.pending.get_mut(id) {
+            call.ended = true;
+        }
+    }
+
+    fn take_buffered(&mut self, id: &str) -> Option<v2t::ToolCallPart> {
+        let call = self.pending.remove(id)?;
+        self.order.retain(|pending| pending != id);
+        Some(v2t::ToolCallPart {
+            tool_call_id: id.to_string(),
+            tool_name: call.tool_name,
+            input: call.input,
+            provider_executed: call.provider_executed,
+            provider_metadata: None,
+            dynamic: false,
+            provider_options: None,
+        })
+    }
+
+    fn complete(&mut self, call: v2t::ToolCallPart) -> Option<ToolCallUpdate> {
+        if !self.completed.insert(call.tool_call_id.clone()) {
+            return None;
+        }
+        if self.pending.remove(&call.tool_call_id).is_some() {
+            self.order.retain(|pending| pending != &call.tool_call_id);
+        }
+        Some(ToolCallUpdate::Completed(call))
+    }
+}
+
+fn snapshot(id: &str, call: &PendingToolCall) -> ToolCallSnapshot {
+    ToolCallSnapshot {
+        tool_call_id: id.to_string(),
+        tool_name: call.tool_name.clone(),
+        input: call.input.clone(),
+        provider_executed: call.provider_executed,
+    }
+}
+
+/// Stream tool-call updates from a [`PartStream`], including progress snapshots.
+///
+/// Buffered calls that ended without a `ToolCall` part are flushed when the
+/// upstream stream ends. Stream errors are forwarded as-is.
+pub fn tool_call_updates(
+    parts: PartStream,
+) -> impl Stream<Item = Result<ToolCallUpdate, SdkError>> + Send {
+    async_stream::stream! {
+        let mut acc = ToolCallAccumulator::new();
+        futures_util::pin_mut!(parts);
+        while let Some(item) = parts.next().await {
+            match item {
+                Ok(part) => {
+                    if let Some(update) = acc.push_part(&part) {
+                        yield Ok(update);
+                    }
+                }
+                Err(err) => yield Err(err),
+            }
+        }
+        for call in acc.finish() {
+            yield Ok(ToolCallUpdate::Completed(call));
+        }
+    }
+}
+
+/// Stream only completed tool calls from a [`PartStream`].
+pub fn completed_tool_calls(
+    parts: PartStream,
+) -> impl Stream<Item = Result<v2t::ToolCallPart, SdkError>> + Send {
+    tool_call_updates(parts).filter_map(|update| {
+        std::future::ready(match update {
+            Ok(ToolCallUpdate::Completed(call)) => Some(Ok(call)),
+            Ok(ToolCallUpdate::Progress(_)) => None,
+            Err(err) => Some(Err(err)),
+        })
+    })
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/tool_call_accumulator_tests.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+//! Construction of tool result outputs from raw tool bytes.
+//!
+//! Tools may produce binary data (images, archives) or text that is not valid
+//! UTF-8. Lossy conversion into a `String` silently corrupts such output, so
+//! bytes are either validated as text or carried as base64 media content.
+
+use base64::Engine;
+
+use crate::core::error::SdkError;
+use crate::types::v2 as v2t;
+
+fn is_textual_media_type(media_type: &str) -> bool {
+    let essence = media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence.starts_with("text/")
+        || essence == "application/json"
+        || essence == "application/xml"
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+}
+
+/// Decode tool output bytes as UTF-8 text.
+///
+/// Returns [`SdkError::InvalidArgument`] describing whether the bytes contain
+/// an invalid sequence or end in a truncated one.
+pub fn tool_result_text_from_bytes(bytes: Vec<u8>) -> Result<String, SdkError> {
+    String::from_utf8(bytes).map_err(|err| {
+        let utf8 = err.utf8_error();
+        let offset = utf8.valid_up_to();
+        let message = match utf8.error_len() {
+            None => {
+                format!("tool result text ends with a truncated UTF-8 sequence at byte {offset}")
+            }
+            Some(_) => format!("tool result text contains invalid UTF-8 at byte {offset}"),
+        };
+        SdkError::InvalidArgument { message }
+    })
+}
+
+/// Build a tool result output from raw bytes and their media type.
+///
+/// Textual media types (`text/*`, JSON, XML) must be valid UTF-8 and become
+/// [`v2t::ToolResultOutput::Text`]. Anything else is base64-encoded into a
+/// [`v2t::ToolResultInlineContent::Media`] item, which providers map to their
+/// native image/file blocks where supported.
+pub fn tool_result_output_from_bytes(
+    bytes: Vec<u8>,
+    media_type: &str,
+) -> Result<v2t::ToolResultOutput, SdkError> {
+    if is_textual_media_type(media_type) {
+        return tool_result_text_from_bytes(bytes)
+            .map(|value| v2t::ToolResultOutput::Text { value });
+    }
+    Ok(v2t::ToolResultOutput::Content {
+        value: vec![v2t::ToolResultInlineContent::Media {
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            media_type: media_type.to_string(),
+        }],
+    })
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/tool_result_tests.rs"]
+mod tests;
@@ -1,9 +1,12 @@
 use crate::ai_sdk_core::error::TransportError;
+use crate::ai_sdk_core::headers::HeaderConflictPolicy;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures_core::Stream;
+use futures_util::StreamExt;
 use serde_json::Value;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::{Arc, OnceLock};
 use std::time::{Duration, SystemTime};
 
@@ -15,8 +18,295 @@ pub struct TransportConfig {
     pub connect_timeout: Duration,
     /// Per-chunk idle read timeout
     pub idle_read_timeout: Duration,
+    /// Wait for the first streamed body chunk (defaults to `idle_read_timeout`)
+    pub first_byte_timeout: Option<Duration>,
     /// Whether to strip object fields with null values from JSON bodies before sending
     pub strip_null_fields: bool,
+    /// Coalesce small streaming body chunks before yielding them (off by default)
+    pub stream_coalesce: Option<StreamCoalesceConfig>,
+    /// How compressed response bodies are negotiated and decoded. The
+    /// default, [`DecompressionMode::None`], sends no `Accept-Encoding` of
+    /// its own and decodes nothing.
+    pub decompression: DecompressionMode,
+    /// Resolution for request headers set by more than one layer (config,
+    /// call options, internal defaults).
+    pub header_conflict_policy: HeaderConflictPolicy,
+    /// Send header names with the casing they were configured with instead of
+    /// the provider's normalized form.
+    pub preserve_header_case: bool,
+    /// Outbound proxy for every request. When `None`, the standard
+    /// `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY`/`NO_PROXY` variables apply.
+    pub proxy: Option<ProxyConfig>,
+    /// Extra trusted CA certificates (PEM, possibly several per entry, or
+    /// DER), added to the roots of the enabled `tls-*` features.
+    pub root_certs: Vec<Vec<u8>>,
+    /// Client certificate presented for mutual TLS.
+    pub client_identity: Option<ClientIdentity>,
+    /// Largest response body read, in bytes after decompression; buffered
+    /// bodies (success and error) and the running total of a stream past it
+    /// fail with [`TransportError::BodyTooLarge`]. `None` is unlimited.
+    pub max_response_bytes: Option<usize>,
+    /// Largest body a compressed response may decode to, checked while
+    /// decoding so a small encoded body cannot expand unbounded in memory;
+    /// past it reads fail with [`TransportError::ResponseTooLarge`]. Defaults
+    /// to [`DEFAULT_MAX_DECOMPRESSED_BYTES`]; `None` is unlimited.
+    pub max_decompressed_bytes: Option<usize>,
+    /// Abort the request when triggered: pending HTTP sends and streamed
+    /// HTTP body reads fail with [`TransportError::Cancelled`].
+    pub cancel: Option<CancelToken>,
+    /// Re-dial websocket JSON streams that drop before their terminal event
+    /// (off by default).
+    pub ws_reconnect: Option<WsReconnect>,
+    /// Header names whose values are replaced with `"<redacted>"` in
+    /// [`TransportEvent`]s (case-insensitive); defaults to
+    /// [`DEFAULT_REDACTED_HEADERS`].
+    pub redact_headers: Vec<String>,
+}
+
+pub use crate::types::cancel::CancelToken;
+
+/// Client certificate chain and private key for mutual TLS.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// Leaf certificate first, then intermediates (PEM or a single DER).
+    pub cert: Vec<u8>,
+    /// PKCS#8, PKCS#1 or SEC1 private key (PEM or DER).
+    pub key: Vec<u8>,
+}
+
+impl ClientIdentity {
+    pub fn new(cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("cert", &format_args!("<{} bytes>", self.cert.len()))
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Outbound HTTP or SOCKS5 proxy.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// `http://`, `socks5://` or `socks5h://` (proxy-side DNS) URL.
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Hosts that bypass the proxy, in `NO_PROXY` syntax: `example.com` also
+    /// matches its subdomains, IPs may carry a `/size` subnet, `*` matches all.
+    pub no_proxy: Vec<String>,
+}
+
+impl ProxyConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            username: None,
+            password: None,
+            no_proxy: Vec::new(),
+        }
+    }
+
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn with_no_proxy<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.no_proxy.extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Proxy URL with the credentials embedded as userinfo.
+    pub fn url_with_credentials(&self) -> Result<String, TransportError> {
+        let mut url = url::Url::parse(&self.url).map_err(|err| {
+            TransportError::Other(format!("invalid proxy url '{}': {err}", self.url))
+        })?;
+        if let Some(username) = self.username.as_deref() {
+            url.set_username(username)
+                .and_then(|_| url.set_password(self.password.as_deref()))
+                .map_err(|_| {
+                    TransportError::Other(format!(
+                        "proxy url '{}' cannot carry credentials",
+                        self.url
+                    ))
+                })?;
+        }
+        Ok(url.to_string())
+    }
+}
+
+impl std::fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+/// Response compression handling.
+///
+/// The transport owns the `Accept-Encoding` request header in every mode
+/// except [`None`](Self::None): a value supplied through call or provider
+/// headers is dropped, or replaced by the advertised encodings, so that what
+/// providers send always matches what the transport can decode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DecompressionMode {
+    /// Advertise and decode every encoding in [`ContentEncoding::ALL`]
+    /// (`gzip, br, deflate`).
+    Auto,
+    /// Never decode. This is the default. No `Accept-Encoding` is added, a
+    /// caller-supplied one is forwarded, and encoded bodies are returned as
+    /// received from `get_bytes` and `post_json_stream`, with
+    /// `Content-Encoding` left in the response headers. JSON-parsing paths
+    /// (`post_json`, `post_multipart`) need an identity response.
+    #[default]
+    None,
+    /// Advertise exactly these encodings in `Accept-Encoding` and decode
+    /// responses that use one of them. `Content-Encoding` and
+    /// `Content-Length` are removed from the headers of decoded responses;
+    /// bodies in any other encoding are passed through untouched. An empty
+    /// list negotiates identity: `Accept-Encoding` is never sent.
+    Encodings(Vec<ContentEncoding>),
+}
+
+impl DecompressionMode {
+    /// Encodings advertised and decoded in this mode.
+    pub fn encodings(&self) -> &[ContentEncoding] {
+        match self {
+            DecompressionMode::Auto => &ContentEncoding::ALL,
+            DecompressionMode::None => &[],
+            DecompressionMode::Encodings(list) => list,
+        }
+    }
+
+    /// Whether a response with `Content-Encoding: encoding` is decoded.
+    pub fn decodes(&self, encoding: ContentEncoding) -> bool {
+        self.encodings().contains(&encoding)
+    }
+
+    /// `Accept-Encoding` value the transport sends, if it sets one.
+    pub fn accept_encoding(&self) -> Option<String> {
+        let encodings = self.encodings();
+        (!encodings.is_empty()).then(|| {
+            encodings
+                .iter()
+                .map(|encoding| encoding.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+}
+
+/// Content coding the built-in transport can decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    Gzip,
+    /// `br`
+    Brotli,
+    /// zlib-wrapped deflate, as HTTP defines it.
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Every encoding the built-in transport decodes, in preference order.
+    pub const ALL: [ContentEncoding; 3] = [
+        ContentEncoding::Gzip,
+        ContentEncoding::Brotli,
+        ContentEncoding::Deflate,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Deflate => "deflate",
+        }
+    }
+
+    /// Parse a single `Content-Encoding` token (case-insensitive).
+    pub fn parse(token: &str) -> Option<Self> {
+        let token = token.trim();
+        if token.eq_ignore_ascii_case("gzip") || token.eq_ignore_ascii_case("x-gzip") {
+            Some(ContentEncoding::Gzip)
+        } else if token.eq_ignore_ascii_case("br") {
+            Some(ContentEncoding::Brotli)
+        } else if token.eq_ignore_ascii_case("deflate") {
+            Some(ContentEncoding::Deflate)
+        } else {
+            None
+        }
+    }
+}
+
+/// Buffering policy for streaming response bodies.
+///
+/// Network chunks are accumulated until at least `min_bytes` are buffered or
+/// `max_delay` has elapsed since the first buffered byte, whichever comes first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamCoalesceConfig {
+    pub min_bytes: usize,
+    pub max_delay: Duration,
+}
+
+/// Reconnect policy for websocket JSON streams.
+///
+/// When the socket closes without a fatal close code, or a read times out,
+/// before the first event arrives, the same URL is dialed again with the
+/// same headers and the request payload is re-sent. Drops after an event has
+/// been delivered are returned as errors, since re-sending would start the
+/// response over and duplicate its output. Each reconnect
+/// is announced to the consumer with an SSE comment frame
+/// (`: reconnected attempt=N`). Delays double from `base_delay` up to
+/// `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WsReconnect {
+    /// Reconnects allowed per stream, counting failed dials.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl WsReconnect {
+    /// Wait before reconnect `attempt` (1-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for WsReconnect {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
 }
 
 /// Body payload captured for transport observers.
@@ -59,6 +349,27 @@ impl MultipartForm {
             },
         });
     }
+
+    /// Add a file part whose `len` bytes are read from `stream` while the
+    /// request is sent, instead of being buffered up front.
+    pub fn push_stream(
+        &mut self,
+        name: impl Into<String>,
+        stream: MultipartStream,
+        len: u64,
+        filename: Option<String>,
+        content_type: Option<String>,
+    ) {
+        self.fields.push(MultipartField {
+            name: name.into(),
+            value: MultipartValue::Stream {
+                stream,
+                len,
+                filename,
+                content_type,
+            },
+        });
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -75,6 +386,53 @@ pub enum MultipartValue {
         filename: Option<String>,
         content_type: Option<String>,
     },
+    /// File contents streamed during the send; `len` must match the number of
+    /// bytes the stream yields.
+    Stream {
+        stream: MultipartStream,
+        len: u64,
+        filename: Option<String>,
+        content_type: Option<String>,
+    },
+}
+
+/// Byte stream for a [`MultipartValue::Stream`] part.
+///
+/// Clones share the underlying stream, which can be taken once: a form with
+/// streamed parts can only be sent once.
+#[derive(Clone)]
+pub struct MultipartStream {
+    inner: Arc<std::sync::Mutex<Option<TransportStream>>>,
+}
+
+impl MultipartStream {
+    /// Wrap `stream`; its errors abort the upload as [`TransportError::Other`].
+    pub fn new<S, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let stream = stream.map(|chunk| {
+            chunk.map_err(|err| TransportError::Other(format!("multipart stream: {err}")))
+        });
+        Self {
+            inner: Arc::new(std::sync::Mutex::new(Some(Box::pin(stream)))),
+        }
+    }
+
+    /// Take the stream, or `None` if a previous send already consumed it.
+    pub fn take(&self) -> Option<TransportStream> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+impl std::fmt::Debug for MultipartStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartStream").finish_non_exhaustive()
+    }
 }
 
 /// Structured event emitted by transport implementations.
@@ -94,11 +452,67 @@ pub struct TransportEvent {
     pub is_stream: bool,
 }
 
+/// Default [`TransportConfig::max_decompressed_bytes`] (256 MiB).
+pub const DEFAULT_MAX_DECOMPRESSED_BYTES: usize = 256 * 1024 * 1024;
+
+/// Value recorded in transport events in place of a redacted header.
+pub const REDACTED_HEADER_VALUE: &str = "<redacted>";
+
+/// Headers redacted from transport events unless
+/// [`TransportConfig::redact_headers`] says otherwise.
+pub const DEFAULT_REDACTED_HEADERS: [&str; 7] = [
+    "authorization",
+    "x-api-key",
+    "x-goog-api-key",
+    "api-key",
+    "x-amz-security-token",
+    "cookie",
+    "set-cookie",
+];
+
+impl TransportEvent {
+    /// Replace the values of request and response headers named in `names`
+    /// (case-insensitive) with [`REDACTED_HEADER_VALUE`], keeping the names.
+    pub fn redacted(mut self, names: &[String]) -> Self {
+        redact_header_values(&mut self.request_headers, names);
+        redact_header_values(&mut self.response_headers, names);
+        self
+    }
+}
+
+/// Replace the values of `headers` named in `names` (case-insensitive) with
+/// [`REDACTED_HEADER_VALUE`].
+pub(crate) fn redact_header_values(headers: &mut [(String, String)], names: &[String]) {
+    for (name, value) in headers {
+        if names
+            .iter()
+            .any(|redacted| redacted.eq_ignore_ascii_case(name))
+        {
+            *value = REDACTED_HEADER_VALUE.to_string();
+        }
+    }
+}
+
 /// Observer hook for transport events.
 pub trait TransportObserver: Send + Sync {
     fn on_event(&self, event: TransportEvent);
 }
 
+/// Supplies W3C trace-context headers (`traceparent`, `tracestate`) for
+/// outbound requests so provider-side spans can link to the caller's trace.
+pub trait TraceContextProvider: Send + Sync {
+    fn trace_headers(&self) -> Vec<(String, String)>;
+}
+
+impl<F> TraceContextProvider for F
+where
+    F: Fn() -> Vec<(String, String)> + Send + Sync,
+{
+    fn trace_headers(&self) -> Vec<(String, String)> {
+        self()
+    }
+}
+
 static TRANSPORT_OBSERVER: OnceLock<Arc<dyn TransportObserver>> = OnceLock::new();
 
 /// Register a transport observer (one-time).
@@ -119,13 +533,65 @@ impl Default for TransportConfig {
             request_timeout: None,
             connect_timeout: Duration::from_secs(10),
             idle_read_timeout: Duration::from_secs(45),
+            first_byte_timeout: None,
             strip_null_fields: true,
+            stream_coalesce: None,
+            decompression: DecompressionMode::None,
+            header_conflict_policy: HeaderConflictPolicy::Override,
+            preserve_header_case: false,
+            proxy: None,
+            root_certs: Vec::new(),
+            client_identity: None,
+            max_response_bytes: None,
+            max_decompressed_bytes: Some(DEFAULT_MAX_DECOMPRESSED_BYTES),
+            cancel: None,
+            ws_reconnect: None,
+            redact_headers: DEFAULT_REDACTED_HEADERS.map(String::from).to_vec(),
+        }
+    }
+}
+
+impl TransportConfig {
+    /// Trust roots compiled into the built-in HTTP and websocket transports,
+    /// selected with the `tls-rustls` and `tls-native` cargo features. With
+    /// neither enabled the operating system store is used.
+    pub const fn tls_backend() -> TlsBackend {
+        if cfg!(all(feature = "tls-rustls", feature = "tls-native")) {
+            TlsBackend::RustlsAndNative
+        } else if cfg!(feature = "tls-rustls") {
+            TlsBackend::Rustls
+        } else {
+            TlsBackend::Native
         }
     }
 }
 
+/// Certificate trust configuration of the built-in transports. TLS is always
+/// negotiated by rustls; the backends differ in which root certificates they
+/// trust.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsBackend {
+    /// Mozilla roots bundled into the binary (`tls-rustls`).
+    Rustls,
+    /// The operating system certificate store (`tls-native`, the default,
+    /// also used when no `tls-*` feature is enabled).
+    Native,
+    /// Both root sets (`tls-rustls` and `tls-native`).
+    RustlsAndNative,
+}
+
 pub type TransportStream = Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>;
 
+/// Outcome of a websocket handshake check that sent no request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebsocketHandshake {
+    /// Upgrade status returned by the server (101).
+    pub status: u16,
+    /// Subprotocol the server accepted via `Sec-WebSocket-Protocol`, if any.
+    pub subprotocol: Option<String>,
+    pub response_headers: Vec<(String, String)>,
+}
+
 #[async_trait]
 pub trait JsonStreamWebsocketConnection: Send + Sync {
     async fn send_json_stream(
@@ -139,6 +605,54 @@ pub trait JsonStreamWebsocketConnection: Send + Sync {
     fn is_closed(&self) -> bool;
 }
 
+/// HTTP verb for [`HttpTransport::request_json`] and
+/// [`HttpTransport::request_json_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HttpMethod {
+    Get,
+    #[default]
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl HttpMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = TransportError;
+
+    /// Case-insensitive verb name.
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        [
+            HttpMethod::Get,
+            HttpMethod::Post,
+            HttpMethod::Put,
+            HttpMethod::Patch,
+            HttpMethod::Delete,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str().eq_ignore_ascii_case(method.trim()))
+        .ok_or_else(|| TransportError::Other(format!("invalid HTTP method '{method}'")))
+    }
+}
+
 #[async_trait]
 pub trait HttpTransport: Send + Sync {
     /// Response for a successful streaming HTTP request.
@@ -165,6 +679,44 @@ pub trait HttpTransport: Send + Sync {
         cfg: &TransportConfig,
     ) -> Result<(Value, Vec<(String, String)>), TransportError>;
 
+    /// Like [`post_json_stream`](Self::post_json_stream) but with the HTTP
+    /// method chosen by the caller, for gateways that expect another verb.
+    /// Transports without support only accept `POST`.
+    async fn request_json_stream(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        if method != HttpMethod::Post {
+            return Err(unsupported_method(method));
+        }
+        self.post_json_stream(url, headers, body, cfg).await
+    }
+
+    /// Perform a JSON request with any [`HttpMethod`] and return the parsed
+    /// JSON body along with response headers. `body: None` sends no body and
+    /// no content type, as for most `GET` and `DELETE` calls; an empty
+    /// success response (e.g. `204 No Content`) parses as `Value::Null`.
+    ///
+    /// [`post_json`](Self::post_json) is the `POST` case. Transports
+    /// without support only accept `POST` with a body.
+    async fn request_json(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&Value>,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        match (method, body) {
+            (HttpMethod::Post, Some(body)) => self.post_json(url, headers, body, cfg).await,
+            _ => Err(unsupported_method(method)),
+        }
+    }
+
     /// Perform a multipart/form-data POST request and return the parsed JSON body along with response headers.
     async fn post_multipart(
         &self,
@@ -178,6 +730,21 @@ pub trait HttpTransport: Send + Sync {
         ))
     }
 
+    /// POST a raw byte body and return the parsed JSON body along with
+    /// response headers. The content type comes from `headers`, defaulting
+    /// to `application/octet-stream`.
+    async fn post_bytes(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _body: Bytes,
+        _cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        Err(TransportError::Other(
+            "raw byte uploads are not supported by this transport".into(),
+        ))
+    }
+
     /// Perform a GET request and return the response bytes along with headers.
     async fn get_bytes(
         &self,
@@ -200,4 +767,24 @@ pub trait HttpTransport: Send + Sync {
             "persistent websocket streams are not supported by this transport".into(),
         ))
     }
+
+    /// Open a websocket with the given headers, confirm the upgrade and close
+    /// it again without sending a request. Connect failures are mapped the
+    /// same way as for [`connect_json_stream_websocket`](Self::connect_json_stream_websocket).
+    async fn check_websocket(
+        &self,
+        _url: &str,
+        _headers: &[(String, String)],
+        _cfg: &TransportConfig,
+    ) -> Result<WebsocketHandshake, TransportError> {
+        Err(TransportError::Other(
+            "websocket handshake checks are not supported by this transport".into(),
+        ))
+    }
+}
+
+fn unsupported_method(method: HttpMethod) -> TransportError {
+    TransportError::Other(format!(
+        "HTTP method {method} is not supported by this transport"
+    ))
 }
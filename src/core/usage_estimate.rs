@@ -0,0 +1,220 @@
+//! Estimated usage for providers that report none.
+//!
+//! Enabled with [`v2t::CallOptions::estimate_usage_if_missing`]. When a
+//! generate response or the finish part of a stream carries no token counts,
+//! the prompt and the generated text are counted with the registered
+//! [`TokenCounter`] instead, and the usage is flagged with
+//! `provider_metadata["usage"]["estimated"] = true` so dashboards can tell
+//! estimates from reported figures.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use futures_util::StreamExt;
+use serde_json::Value as JsonValue;
+
+use crate::core::v2::{GenerateResponse, StreamResponse};
+use crate::types::v2 as v2t;
+
+/// Provider metadata scope holding the `estimated` flag.
+pub const USAGE_METADATA_SCOPE: &str = "usage";
+
+/// Counts tokens in text for usage estimates.
+pub trait TokenCounter: Send + Sync {
+    fn count_tokens(&self, text: &str) -> u64;
+}
+
+/// Default counter: one token per four characters, rounded up.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CharTokenCounter;
+
+const CHARS_PER_TOKEN: u64 = 4;
+
+impl TokenCounter for CharTokenCounter {
+    fn count_tokens(&self, text: &str) -> u64 {
+        (text.chars().count() as u64).div_ceil(CHARS_PER_TOKEN)
+    }
+}
+
+static TOKEN_COUNTER: OnceLock<Arc<dyn TokenCounter>> = OnceLock::new();
+
+/// Register the counter used for usage estimates (one-time).
+pub fn set_token_counter(counter: Arc<dyn TokenCounter>) -> bool {
+    TOKEN_COUNTER.set(counter).is_ok()
+}
+
+/// Count `text` with the registered counter, or [`CharTokenCounter`].
+pub fn count_tokens(text: &str) -> u64 {
+    match TOKEN_COUNTER.get() {
+        Some(counter) => counter.count_tokens(text),
+        None => CharTokenCounter.count_tokens(text),
+    }
+}
+
+/// Usage estimate settings resolved from call options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsageEstimate {
+    /// Estimated prompt tokens.
+    pub input_tokens: u64,
+}
+
+impl UsageEstimate {
+    /// Returns `None` when estimates are not requested.
+    pub fn from_options(options: &v2t::CallOptions) -> Option<Self> {
+        options.estimate_usage_if_missing.then(|| Self {
+            input_tokens: count_tokens(&prompt_text(&options.prompt)),
+        })
+    }
+
+    /// Estimated usage for a response that generated `output_text`.
+    pub fn usage(&self, output_text: &str) -> v2t::Usage {
+        let output_tokens = count_tokens(output_text);
+        v2t::Usage {
+            input_tokens: Some(self.input_tokens),
+            output_tokens: Some(output_tokens),
+            total_tokens: Some(self.input_tokens + output_tokens),
+            ..Default::default()
+        }
+    }
+}
+
+/// No token counts were reported.
+fn is_missing(usage: &v2t::Usage) -> bool {
+    usage.input_tokens.is_none() && usage.output_tokens.is_none() && usage.total_tokens.is_none()
+}
+
+fn mark_estimated(metadata: &mut Option<v2t::ProviderMetadata>) {
+    metadata
+        .get_or_insert_with(HashMap::new)
+        .entry(USAGE_METADATA_SCOPE.to_string())
+        .or_default()
+        .insert("estimated".into(), JsonValue::Bool(true));
+}
+
+fn prompt_text(prompt: &v2t::Prompt) -> String {
+    let mut out = String::new();
+    for message in prompt {
+        match message {
+            v2t::PromptMessage::System { content, .. }
+            | v2t::PromptMessage::Developer { content, .. } => push_text(&mut out, content),
+            v2t::PromptMessage::User { content, .. } => {
+                for part in content {
+                    if let v2t::UserPart::Text { text, .. } = part {
+                        push_text(&mut out, text);
+                    }
+                }
+            }
+            v2t::PromptMessage::Assistant { content, .. } => {
+                for part in content {
+                    match part {
+                        v2t::AssistantPart::Text { text, .. }
+                        | v2t::AssistantPart::Reasoning { text, .. } => push_text(&mut out, text),
+                        v2t::AssistantPart::ToolCall(call) => push_text(&mut out, &call.input),
+                        v2t::AssistantPart::ToolResult(result) => {
+                            push_tool_output(&mut out, &result.output)
+                        }
+                        v2t::AssistantPart::File { .. } => {}
+                    }
+                }
+            }
+            v2t::PromptMessage::Tool { content, .. } => {
+                for part in content {
+                    if let v2t::ToolMessagePart::ToolResult(result) = part {
+                        push_tool_output(&mut out, &result.output);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn push_tool_output(out: &mut String, output: &v2t::ToolResultOutput) {
+    match output {
+        v2t::ToolResultOutput::Text { value } | v2t::ToolResultOutput::ErrorText { value } => {
+            push_text(out, value)
+        }
+        v2t::ToolResultOutput::Json { value } | v2t::ToolResultOutput::ErrorJson { value } => {
+            push_text(out, &value.to_string())
+        }
+        v2t::ToolResultOutput::Content { value } => {
+            for item in value {
+                if let v2t::ToolResultInlineContent::Text { text } = item {
+                    push_text(out, text);
+                }
+            }
+        }
+    }
+}
+
+fn push_text(out: &mut String, text: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(text);
+}
+
+impl StreamResponse {
+    /// Fill an empty usage on the finish part from `estimate`, counting the
+    /// text, reasoning and tool call input streamed before it.
+    ///
+    /// No-op when `estimate` is `None`.
+    pub fn with_usage_estimate(mut self, estimate: Option<UsageEstimate>) -> Self {
+        let Some(estimate) = estimate else {
+            return self;
+        };
+        let mut output = String::new();
+        self.stream = Box::pin(self.stream.map(move |item| {
+            let mut part = item?;
+            match &mut part {
+                v2t::StreamPart::TextDelta { delta, .. }
+                | v2t::StreamPart::ReasoningDelta { delta, .. } => output.push_str(delta),
+                v2t::StreamPart::ToolCall(call) => output.push_str(&call.input),
+                v2t::StreamPart::Finish {
+                    usage,
+                    provider_metadata,
+                    ..
+                } if is_missing(usage) => {
+                    *usage = estimate.usage(&output);
+                    mark_estimated(provider_metadata);
+                }
+                _ => {}
+            }
+            Ok(part)
+        }));
+        self
+    }
+}
+
+impl GenerateResponse {
+    /// Replace an empty usage with one estimated from the generated text,
+    /// reasoning and tool call input.
+    ///
+    /// No-op when `estimate` is `None`.
+    pub fn with_usage_estimate(mut self, estimate: Option<UsageEstimate>) -> Self {
+        let Some(estimate) = estimate else {
+            return self;
+        };
+        if !is_missing(&self.usage) {
+            return self;
+        }
+        let output: String = self
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                v2t::Content::Text { text, .. } | v2t::Content::Reasoning { text, .. } => {
+                    Some(text.as_str())
+                }
+                v2t::Content::ToolCall(call) => Some(call.input.as_str()),
+                _ => None,
+            })
+            .collect();
+        self.usage = estimate.usage(&output);
+        mark_estimated(&mut self.provider_metadata);
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/usage_estimate_tests.rs"]
+mod tests;
@@ -15,17 +15,89 @@ pub struct GenerateResponse {
     pub finish_reason: v2t::FinishReason,
     pub usage: v2t::Usage,
     pub provider_metadata: Option<v2t::ProviderMetadata>,
+    /// Response id, timestamp and served model as reported by the provider.
+    pub response_metadata: Option<v2t::ResponseMetadata>,
     pub request_body: Option<serde_json::Value>,
     pub response_headers: Option<v2t::Headers>,
     pub response_body: Option<serde_json::Value>,
     pub warnings: Vec<v2t::CallWarning>,
 }
 
+impl GenerateResponse {
+    /// Concrete model id reported by the provider, which may differ from the
+    /// requested id (dated snapshots, gateway fallbacks).
+    pub fn resolved_model_id(&self) -> Option<&str> {
+        self.response_metadata
+            .as_ref()
+            .and_then(|meta| meta.model_id.as_deref())
+    }
+
+    /// Concatenated text parts, in order.
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|part| match part {
+                v2t::Content::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Completed tool calls, in the order they were emitted.
+    pub fn tool_calls(&self) -> Vec<&v2t::ToolCallPart> {
+        self.content
+            .iter()
+            .filter_map(|part| match part {
+                v2t::Content::ToolCall(call) => Some(call),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Assistant message replaying this response's text, reasoning and client
+    /// tool calls for the next turn. Provider metadata is carried over as
+    /// provider options so item ids and encrypted reasoning round-trip.
+    pub fn to_assistant_message(&self) -> v2t::PromptMessage {
+        let content = self
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                v2t::Content::Text {
+                    text,
+                    provider_metadata,
+                } => Some(v2t::AssistantPart::Text {
+                    text: text.clone(),
+                    provider_options: provider_metadata.clone(),
+                }),
+                v2t::Content::Reasoning {
+                    text,
+                    provider_metadata,
+                } => Some(v2t::AssistantPart::Reasoning {
+                    text: text.clone(),
+                    provider_options: provider_metadata.clone(),
+                }),
+                v2t::Content::ToolCall(call) if !call.provider_executed => {
+                    let mut call = call.clone();
+                    call.provider_options = call.provider_metadata.clone();
+                    Some(v2t::AssistantPart::ToolCall(call))
+                }
+                _ => None,
+            })
+            .collect();
+        v2t::PromptMessage::Assistant {
+            content,
+            provider_options: None,
+        }
+    }
+}
+
 /// Stream response envelope.
 pub struct StreamResponse {
     pub stream: PartStream,
     pub request_body: Option<serde_json::Value>,
     pub response_headers: Option<v2t::Headers>,
+    /// Raw frames captured when `CallOptions::capture_raw_response` is set.
+    pub raw_response: Option<crate::ai_sdk_core::raw_capture::RawResponseCapture>,
 }
 
 #[async_trait::async_trait]
@@ -78,6 +150,75 @@ impl<M: LanguageModel + ?Sized> LanguageModelTurnSession
     }
 }
 
+/// Features a model supports, for capability-driven UIs (reasoning toggles,
+/// upload buttons).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ModelCapabilities {
+    /// Image input.
+    pub vision: bool,
+    pub audio_in: bool,
+    pub audio_out: bool,
+    /// Function/tool calling.
+    pub tools: bool,
+    /// Reasoning/thinking output or reasoning effort controls.
+    pub reasoning: bool,
+    /// JSON output constrained by a schema (`ResponseFormat::Json`).
+    pub structured_output: bool,
+    pub streaming: bool,
+    /// Source citations or grounding metadata in responses.
+    pub citations: bool,
+    /// Several tool calls in one response.
+    pub parallel_tool_calls: bool,
+    /// Most tokens one response may generate, when known.
+    pub max_output_tokens: Option<u64>,
+}
+
+impl ModelCapabilities {
+    /// Capabilities from the providers index, when one is loaded (see
+    /// [`get_model_capabilities`](crate::core::capabilities::get_model_capabilities)).
+    pub fn from_index(provider: &str, model_id: &str) -> Self {
+        let index = crate::core::capabilities::get_model_capabilities(provider, model_id);
+        Self {
+            reasoning: index
+                .as_ref()
+                .and_then(|caps| caps.reasoning)
+                .unwrap_or(false),
+            streaming: true,
+            max_output_tokens: index.and_then(|caps| caps.max_output_tokens),
+            ..Self::default()
+        }
+    }
+
+    /// Add the capabilities a catalog entry declares. Catalog flags only
+    /// ever enable a capability, since an unset flag is indistinguishable
+    /// from an unsupported one; a catalog output limit replaces the family's.
+    pub fn with_catalog(mut self, info: &crate::types::catalog::ModelInfo) -> Self {
+        if let Some(output) = info
+            .limits
+            .as_ref()
+            .and_then(|limits| limits.context_output.or(limits.output))
+        {
+            self.max_output_tokens = Some(output);
+        }
+        if let Some(caps) = &info.capabilities {
+            self.vision |= caps.vision || caps.attachment;
+            self.audio_in |= caps.audio;
+            self.tools |= caps.tool_call;
+            self.reasoning |= caps.reasoning;
+            self.structured_output |= caps.json_mode;
+        }
+        if let Some(modalities) = &info.modalities {
+            let has = |list: &[String], modality: &str| {
+                list.iter().any(|m| m.eq_ignore_ascii_case(modality))
+            };
+            self.vision |= has(&modalities.input, "image");
+            self.audio_in |= has(&modalities.input, "audio");
+            self.audio_out |= has(&modalities.output, "audio");
+        }
+        self
+    }
+}
+
 /// Language model interface (Vercel AI SDK parity).
 #[async_trait::async_trait]
 pub trait LanguageModel: Send + Sync {
@@ -93,6 +234,11 @@ pub trait LanguageModel: Send + Sync {
     fn supported_urls(&self) -> std::collections::HashMap<String, Vec<String>> {
         Default::default()
     }
+    /// What this model supports. Providers answer from what they know about
+    /// the model family; the default consults the providers index only.
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities::from_index(self.provider_name(), self.model_id())
+    }
 
     async fn do_generate(
         &self,
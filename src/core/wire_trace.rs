@@ -0,0 +1,567 @@
+//! Wire-level trace of HTTP traffic for support escalations.
+//!
+//! [`TracingTransport`] wraps any [`HttpTransport`] and writes each request
+//! and its response to a [`Write`] sink as JSON Lines, one record per line.
+//! All records of one exchange share an `id`:
+//!
+//! - `request`: `method`, `url`, `headers` and `body`.
+//! - `response`: `headers` and `body` for buffered calls, `"stream": true`
+//!   for streaming ones, or `"websocket": true` for a websocket handshake.
+//! - `frame`: one streamed chunk exactly as read from the transport, as
+//!   `data` (UTF-8) or `data_base64`, with its `elapsed_ms`.
+//! - `end`: the stream was read to completion, with `frames` and `bytes`.
+//! - `error`: the call or stream failed; HTTP failures add `status`,
+//!   `headers` and the raw `body`.
+//!
+//! Every record carries `ts_ms` (Unix milliseconds). Unlike
+//! [`TransportEvent`](crate::core::transport::TransportEvent)s, which
+//! summarize a finished call, the trace keeps the stream frame by frame so a
+//! failing exchange can be replayed or attached to a provider bug report.
+//!
+//! Header values named in [`DEFAULT_REDACTED_HEADERS`] and JSON body values
+//! under [`DEFAULT_REDACTED_KEYS`](crate::core::error::DEFAULT_REDACTED_KEYS)
+//! are replaced by default. Streamed frames are written verbatim.
+//!
+//! A websocket connection is traced as its `GET` handshake, then each message
+//! sent on it as an exchange of its own: a `request` with method `"WS"`, the
+//! connection URL and the message as `body`, followed by the received frames.
+//! [`HttpTransport::check_websocket`] probes are not traced.
+
+use std::future::Future;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use serde_json::{json, Map, Value};
+
+use crate::core::error::{ErrorBodyRedaction, TransportError};
+use crate::core::transport::{
+    redact_header_values, HttpMethod, HttpTransport, JsonStreamWebsocketConnection, MultipartForm,
+    MultipartValue, TransportConfig, TransportStream, WebsocketHandshake, DEFAULT_REDACTED_HEADERS,
+};
+
+type SharedSink = Arc<Mutex<Box<dyn Write + Send>>>;
+
+/// [`HttpTransport`] that records the exact traffic of an inner transport.
+///
+/// Tracing starts enabled; [`TracingTransport::set_enabled`] toggles it at
+/// runtime so a single failing request can be captured. Write errors on the
+/// sink are ignored and never fail the request.
+pub struct TracingTransport<T> {
+    inner: T,
+    trace: TraceState,
+}
+
+/// Sink, toggle and redaction settings, shared with traced websocket
+/// connections so they keep writing after the handshake returns.
+#[derive(Clone)]
+struct TraceState {
+    sink: SharedSink,
+    enabled: Arc<AtomicBool>,
+    next_id: Arc<AtomicU64>,
+    redact_headers: Vec<String>,
+    body_redaction: Option<ErrorBodyRedaction>,
+}
+
+impl<T: HttpTransport> TracingTransport<T> {
+    /// Trace `inner` into `sink` with the default redaction.
+    pub fn new(inner: T, sink: impl Write + Send + 'static) -> Self {
+        Self {
+            inner,
+            trace: TraceState {
+                sink: Arc::new(Mutex::new(Box::new(sink))),
+                enabled: Arc::new(AtomicBool::new(true)),
+                next_id: Arc::new(AtomicU64::new(1)),
+                redact_headers: DEFAULT_REDACTED_HEADERS
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                body_redaction: Some(ErrorBodyRedaction::default()),
+            },
+        }
+    }
+
+    /// Replace the header names whose values are redacted (case-insensitive).
+    pub fn with_redacted_headers<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trace.redact_headers = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keys redacted from JSON bodies; `None` writes bodies unchanged.
+    pub fn with_body_redaction(mut self, redaction: Option<ErrorBodyRedaction>) -> Self {
+        self.trace.body_redaction = redaction;
+        self
+    }
+
+    /// Turn tracing on or off for subsequent requests, including messages on
+    /// websocket connections that are already open.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.trace.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.trace.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    async fn traced_json<F>(
+        &self,
+        call: Option<TraceCall<'_>>,
+        send: F,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError>
+    where
+        F: Future<Output = Result<(Value, Vec<(String, String)>), TransportError>>,
+    {
+        let result = send.await;
+        if let Some(call) = call {
+            match &result {
+                Ok((body, headers)) => call.write(
+                    "response",
+                    [
+                        ("headers", self.trace.headers_json(headers)),
+                        ("body", self.trace.body_json(body)),
+                    ],
+                ),
+                Err(err) => call.error(err),
+            }
+        }
+        result
+    }
+
+    async fn traced_stream<F>(
+        &self,
+        call: Option<TraceCall<'_>>,
+        send: F,
+    ) -> Result<(TransportStream, Vec<(String, String)>), TransportError>
+    where
+        F: Future<Output = Result<T::StreamResponse, TransportError>>,
+    {
+        let result = send.await.map(T::into_stream);
+        let Some(call) = call else {
+            return result;
+        };
+        match result {
+            Ok((stream, headers)) => {
+                call.write(
+                    "response",
+                    [
+                        ("headers", self.trace.headers_json(&headers)),
+                        ("stream", json!(true)),
+                    ],
+                );
+                Ok((call.trace_stream(stream), headers))
+            }
+            Err(err) => {
+                call.error(&err);
+                Err(err)
+            }
+        }
+    }
+}
+
+impl TraceState {
+    /// Start an exchange, writing its `request` record. `body` is only built
+    /// when tracing is enabled.
+    fn begin(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+        body: impl FnOnce() -> Value,
+    ) -> Option<TraceCall<'_>> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let call = TraceCall {
+            trace: self,
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            started: Instant::now(),
+        };
+        call.write(
+            "request",
+            [
+                ("method", json!(method)),
+                ("url", json!(url)),
+                ("headers", self.headers_json(headers)),
+                ("body", body()),
+            ],
+        );
+        Some(call)
+    }
+
+    fn headers_json(&self, headers: &[(String, String)]) -> Value {
+        let mut headers = headers.to_vec();
+        redact_header_values(&mut headers, &self.redact_headers);
+        headers
+            .into_iter()
+            .map(|(name, value)| json!([name, value]))
+            .collect()
+    }
+
+    fn body_json(&self, body: &Value) -> Value {
+        let mut body = body.clone();
+        if let Some(redaction) = &self.body_redaction {
+            redaction.redact(&mut body);
+        }
+        body
+    }
+}
+
+/// One traced exchange.
+struct TraceCall<'a> {
+    trace: &'a TraceState,
+    id: u64,
+    started: Instant,
+}
+
+impl TraceCall<'_> {
+    fn write<const N: usize>(&self, kind: &str, fields: [(&str, Value); N]) {
+        write_record(&self.trace.sink, self.id, self.started, kind, fields);
+    }
+
+    fn error(&self, err: &TransportError) {
+        let error = json!(err.to_string());
+        match err {
+            TransportError::HttpStatus {
+                status,
+                body,
+                headers,
+                ..
+            } => {
+                let body = serde_json::from_str(body)
+                    .map(|body| self.trace.body_json(&body))
+                    .unwrap_or_else(|_| json!(body));
+                self.write(
+                    "error",
+                    [
+                        ("error", error),
+                        ("status", json!(status)),
+                        ("headers", self.trace.headers_json(headers)),
+                        ("body", body),
+                    ],
+                );
+            }
+            _ => self.write("error", [("error", error)]),
+        }
+    }
+
+    fn trace_stream(self, mut stream: TransportStream) -> TransportStream {
+        let sink = self.trace.sink.clone();
+        let (id, started) = (self.id, self.started);
+        Box::pin(async_stream::stream! {
+            let (mut frames, mut bytes) = (0u64, 0u64);
+            while let Some(item) = stream.next().await {
+                match &item {
+                    Ok(chunk) => {
+                        frames += 1;
+                        bytes += chunk.len() as u64;
+                        write_record(&sink, id, started, "frame", [frame_data(chunk)]);
+                    }
+                    Err(err) => {
+                        write_record(&sink, id, started, "error", [("error", json!(err.to_string()))]);
+                    }
+                }
+                yield item;
+            }
+            write_record(
+                &sink,
+                id,
+                started,
+                "end",
+                [("frames", json!(frames)), ("bytes", json!(bytes))],
+            );
+        })
+    }
+}
+
+/// Websocket connection whose messages are traced as exchanges of their own.
+struct TracedWebsocket {
+    inner: Box<dyn JsonStreamWebsocketConnection>,
+    trace: TraceState,
+    url: String,
+}
+
+#[async_trait]
+impl JsonStreamWebsocketConnection for TracedWebsocket {
+    async fn send_json_stream(
+        &self,
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<TransportStream, TransportError> {
+        let call = self
+            .trace
+            .begin("WS", &self.url, &[], || self.trace.body_json(body));
+        let result = self.inner.send_json_stream(body, cfg).await;
+        let Some(call) = call else {
+            return result;
+        };
+        match result {
+            Ok(stream) => Ok(call.trace_stream(stream)),
+            Err(err) => {
+                call.error(&err);
+                Err(err)
+            }
+        }
+    }
+
+    fn response_headers(&self) -> Vec<(String, String)> {
+        self.inner.response_headers()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+fn write_record<const N: usize>(
+    sink: &SharedSink,
+    id: u64,
+    started: Instant,
+    kind: &str,
+    fields: [(&str, Value); N],
+) {
+    let ts_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    let mut record = Map::new();
+    record.insert("id".into(), json!(id));
+    record.insert("kind".into(), json!(kind));
+    record.insert("ts_ms".into(), json!(ts_ms));
+    record.insert(
+        "elapsed_ms".into(),
+        json!(started.elapsed().as_millis() as u64),
+    );
+    for (key, value) in fields {
+        record.insert(key.into(), value);
+    }
+    let Ok(mut sink) = sink.lock() else {
+        return;
+    };
+    let _ = writeln!(sink, "{}", Value::Object(record)).and_then(|()| sink.flush());
+}
+
+fn frame_data(chunk: &[u8]) -> (&'static str, Value) {
+    match std::str::from_utf8(chunk) {
+        Ok(text) => ("data", json!(text)),
+        Err(_) => ("data_base64", json!(BASE64.encode(chunk))),
+    }
+}
+
+fn multipart_json(form: &MultipartForm) -> Value {
+    let fields: Vec<Value> = form
+        .fields
+        .iter()
+        .map(|field| match &field.value {
+            MultipartValue::Text(text) => json!({"name": field.name, "value": text}),
+            MultipartValue::Bytes {
+                data,
+                filename,
+                content_type,
+            } => json!({
+                "name": field.name,
+                "filename": filename,
+                "content_type": content_type,
+                "bytes": data.len(),
+            }),
+            MultipartValue::Stream {
+                len,
+                filename,
+                content_type,
+                ..
+            } => json!({
+                "name": field.name,
+                "filename": filename,
+                "content_type": content_type,
+                "bytes": len,
+            }),
+        })
+        .collect();
+    json!({ "fields": fields })
+}
+
+#[async_trait]
+impl<T: HttpTransport> HttpTransport for TracingTransport<T> {
+    type StreamResponse = (TransportStream, Vec<(String, String)>);
+
+    fn into_stream(resp: Self::StreamResponse) -> (TransportStream, Vec<(String, String)>) {
+        resp
+    }
+
+    async fn post_json_stream(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        let call = self
+            .trace
+            .begin("POST", url, headers, || self.trace.body_json(body));
+        self.traced_stream(call, self.inner.post_json_stream(url, headers, body, cfg))
+            .await
+    }
+
+    async fn post_json(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let call = self
+            .trace
+            .begin("POST", url, headers, || self.trace.body_json(body));
+        self.traced_json(call, self.inner.post_json(url, headers, body, cfg))
+            .await
+    }
+
+    async fn request_json_stream(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        let call = self
+            .trace
+            .begin(method.as_str(), url, headers, || self.trace.body_json(body));
+        self.traced_stream(
+            call,
+            self.inner
+                .request_json_stream(method, url, headers, body, cfg),
+        )
+        .await
+    }
+
+    async fn request_json(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&Value>,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let call = self.trace.begin(method.as_str(), url, headers, || {
+            body.map_or(Value::Null, |body| self.trace.body_json(body))
+        });
+        self.traced_json(
+            call,
+            self.inner.request_json(method, url, headers, body, cfg),
+        )
+        .await
+    }
+
+    async fn post_multipart(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        form: &MultipartForm,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let call = self
+            .trace
+            .begin("POST", url, headers, || multipart_json(form));
+        self.traced_json(call, self.inner.post_multipart(url, headers, form, cfg))
+            .await
+    }
+
+    async fn post_bytes(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Bytes,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let call = self
+            .trace
+            .begin("POST", url, headers, || json!({ "bytes": body.len() }));
+        self.traced_json(call, self.inner.post_bytes(url, headers, body, cfg))
+            .await
+    }
+
+    async fn get_bytes(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        cfg: &TransportConfig,
+    ) -> Result<(Bytes, Vec<(String, String)>), TransportError> {
+        let call = self.trace.begin("GET", url, headers, || Value::Null);
+        let result = self.inner.get_bytes(url, headers, cfg).await;
+        if let Some(call) = call {
+            match &result {
+                Ok((bytes, headers)) => call.write(
+                    "response",
+                    [
+                        ("headers", self.trace.headers_json(headers)),
+                        ("body_base64", json!(BASE64.encode(bytes))),
+                    ],
+                ),
+                Err(err) => call.error(err),
+            }
+        }
+        result
+    }
+
+    async fn connect_json_stream_websocket(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        cfg: &TransportConfig,
+    ) -> Result<Box<dyn JsonStreamWebsocketConnection>, TransportError> {
+        let call = self.trace.begin("GET", url, headers, || Value::Null);
+        let result = self
+            .inner
+            .connect_json_stream_websocket(url, headers, cfg)
+            .await;
+        if let Some(call) = call {
+            match &result {
+                Ok(connection) => call.write(
+                    "response",
+                    [
+                        (
+                            "headers",
+                            self.trace.headers_json(&connection.response_headers()),
+                        ),
+                        ("websocket", json!(true)),
+                    ],
+                ),
+                Err(err) => call.error(err),
+            }
+        }
+        result.map(|inner| {
+            Box::new(TracedWebsocket {
+                inner,
+                trace: self.trace.clone(),
+                url: url.to_string(),
+            }) as Box<dyn JsonStreamWebsocketConnection>
+        })
+    }
+
+    async fn check_websocket(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        cfg: &TransportConfig,
+    ) -> Result<WebsocketHandshake, TransportError> {
+        self.inner.check_websocket(url, headers, cfg).await
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/core/tests/wire_trace_tests.rs"]
+mod tests;
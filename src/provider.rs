@@ -6,7 +6,7 @@
 use crate::core::options as sdkopt;
 use crate::core::request_builder::defaults::provider_defaults_from_json;
 use crate::core::transport::TransportConfig;
-use crate::core::{LanguageModel, SdkError};
+use crate::core::{LanguageModel, ModelCapabilities, SdkError};
 use crate::types::{
     catalog::{ProviderDefinition, SdkType},
     v2::ProviderOptions as V2ProviderOptions,
@@ -125,6 +125,21 @@ pub fn apply_stream_idle_timeout_ms(def: &ProviderDefinition, cfg: &mut Transpor
     }
 }
 
+/// Apply per-provider connect, request and first-byte timeout overrides to the
+/// transport config. Zero values are ignored.
+pub fn apply_provider_timeouts_ms(def: &ProviderDefinition, cfg: &mut TransportConfig) {
+    let positive = |ms: Option<u64>| ms.filter(|ms| *ms > 0).map(Duration::from_millis);
+    if let Some(timeout) = positive(def.connect_timeout_ms) {
+        cfg.connect_timeout = timeout;
+    }
+    if let Some(timeout) = positive(def.request_timeout_ms) {
+        cfg.request_timeout = Some(timeout);
+    }
+    if let Some(timeout) = positive(def.first_byte_timeout_ms) {
+        cfg.first_byte_timeout = Some(timeout);
+    }
+}
+
 /// Clone query params from provider definition into request config shape.
 pub fn collect_query_params(def: &ProviderDefinition) -> Vec<(String, String)> {
     def.query_params
@@ -133,7 +148,8 @@ pub fn collect_query_params(def: &ProviderDefinition) -> Vec<(String, String)> {
         .collect()
 }
 
-/// Build a transport config with provider defaults and idle-timeout overrides.
+/// Build a transport config with provider defaults plus idle and other timeout
+/// overrides from the catalog.
 pub fn build_provider_transport_config(
     def: &ProviderDefinition,
     default_idle_timeout: Option<Duration>,
@@ -143,9 +159,28 @@ pub fn build_provider_transport_config(
         cfg.idle_read_timeout = timeout;
     }
     apply_stream_idle_timeout_ms(def, &mut cfg);
+    apply_provider_timeouts_ms(def, &mut cfg);
     cfg
 }
 
+/// Capabilities of a model built from `def`: what its provider knows about
+/// the model family plus whatever the catalog entry for it declares.
+pub fn catalog_model_capabilities(
+    def: &ProviderDefinition,
+    model: &dyn LanguageModel,
+) -> ModelCapabilities {
+    let model_id = model.model_id();
+    let info = def.models.get(model_id).or_else(|| {
+        def.models
+            .values()
+            .find(|info| info.id.eq_ignore_ascii_case(model_id))
+    });
+    match info {
+        Some(info) => model.capabilities().with_catalog(info),
+        None => model.capabilities(),
+    }
+}
+
 /// Filter provider definition headers for bootstrap.
 ///
 /// - Internal SDK headers are consumed as request/default options.
@@ -234,6 +269,22 @@ pub fn reasoning_scope_aliases(
     sdk_type: &SdkType,
     model_id: Option<&str>,
     base_url: Option<&str>,
+) -> Option<Vec<String>> {
+    reasoning_scope_aliases_with_override(provider_id, sdk_type, model_id, base_url, None)
+}
+
+/// [`reasoning_scope_aliases`] with an explicit provider-options scope, for
+/// gateways whose scope differs from what the provider id and host suggest
+/// (e.g. `"openai"` for a provider registered as `"my-gateway"`).
+///
+/// The override always resolves and comes first, followed by any aliases
+/// the registry hooks produce.
+pub fn reasoning_scope_aliases_with_override(
+    provider_id: &str,
+    sdk_type: &SdkType,
+    model_id: Option<&str>,
+    base_url: Option<&str>,
+    scope_override: Option<&str>,
 ) -> Option<Vec<String>> {
     let ctx = ReasoningScopeContext {
         provider_id,
@@ -257,6 +308,11 @@ pub fn reasoning_scope_aliases(
         }
     };
 
+    if let Some(scope) = scope_override.filter(|scope| !scope.trim().is_empty()) {
+        push(scope);
+        handled = true;
+    }
+
     for reg in registry::iter() {
         if !(reg.id.eq_ignore_ascii_case(provider_id) || &reg.sdk_type == sdk_type) {
             continue;
@@ -309,7 +365,35 @@ pub fn reasoning_stream_options(
     signature: Option<&str>,
     redacted_data: Option<&str>,
 ) -> Option<V2ProviderOptions> {
-    let aliases = reasoning_scope_aliases(provider_id, sdk_type, model_id, base_url)?;
+    reasoning_stream_options_with_override(
+        provider_id,
+        sdk_type,
+        model_id,
+        base_url,
+        None,
+        signature,
+        redacted_data,
+    )
+}
+
+/// [`reasoning_stream_options`] with an explicit scope; see
+/// [`reasoning_scope_aliases_with_override`].
+pub fn reasoning_stream_options_with_override(
+    provider_id: &str,
+    sdk_type: &SdkType,
+    model_id: Option<&str>,
+    base_url: Option<&str>,
+    scope_override: Option<&str>,
+    signature: Option<&str>,
+    redacted_data: Option<&str>,
+) -> Option<V2ProviderOptions> {
+    let aliases = reasoning_scope_aliases_with_override(
+        provider_id,
+        sdk_type,
+        model_id,
+        base_url,
+        scope_override,
+    )?;
     let mut scope = JsonMap::new();
     if let Some(sig) = signature {
         if !sig.is_empty() {
@@ -336,7 +420,35 @@ pub fn persisted_reasoning_options(
     text: &str,
     signature: Option<&str>,
 ) -> Option<V2ProviderOptions> {
-    let aliases = reasoning_scope_aliases(provider_id, sdk_type, model_id, base_url)?;
+    persisted_reasoning_options_with_override(
+        provider_id,
+        sdk_type,
+        model_id,
+        base_url,
+        None,
+        text,
+        signature,
+    )
+}
+
+/// [`persisted_reasoning_options`] with an explicit scope; see
+/// [`reasoning_scope_aliases_with_override`].
+pub fn persisted_reasoning_options_with_override(
+    provider_id: &str,
+    sdk_type: &SdkType,
+    model_id: Option<&str>,
+    base_url: Option<&str>,
+    scope_override: Option<&str>,
+    text: &str,
+    signature: Option<&str>,
+) -> Option<V2ProviderOptions> {
+    let aliases = reasoning_scope_aliases_with_override(
+        provider_id,
+        sdk_type,
+        model_id,
+        base_url,
+        scope_override,
+    )?;
     if text.trim().is_empty() {
         return None;
     }
@@ -356,6 +468,10 @@ pub fn persisted_reasoning_options(
     build_options_from_scope(&aliases, &scope)
 }
 
+#[cfg(test)]
+#[path = "../crates/provider/tests/reasoning_scope_tests.rs"]
+mod reasoning_scope_tests;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,9 +491,15 @@ mod tests {
             headers: HashMap::new(),
             query_params: HashMap::new(),
             stream_idle_timeout_ms: None,
+            connect_timeout_ms: None,
+            request_timeout_ms: None,
+            first_byte_timeout_ms: None,
             auth_type: "api-key".into(),
             models: HashMap::<String, ModelInfo>::new(),
             preserve_model_prefix: true,
+            field_aliases: HashMap::new(),
+            tool_limits: Default::default(),
+            fallback_models: Vec::new(),
         }
     }
 
@@ -420,6 +542,33 @@ mod tests {
         assert_eq!(cfg.idle_read_timeout, Duration::from_millis(12_345));
     }
 
+    #[test]
+    fn build_provider_transport_config_applies_catalog_timeouts() {
+        let mut def = test_provider_def();
+        def.connect_timeout_ms = Some(2_000);
+        def.request_timeout_ms = Some(600_000);
+        def.first_byte_timeout_ms = Some(300_000);
+
+        let cfg = build_provider_transport_config(&def, None);
+
+        assert_eq!(cfg.connect_timeout, Duration::from_secs(2));
+        assert_eq!(cfg.request_timeout, Some(Duration::from_secs(600)));
+        assert_eq!(cfg.first_byte_timeout, Some(Duration::from_secs(300)));
+        assert_eq!(
+            cfg.idle_read_timeout,
+            TransportConfig::default().idle_read_timeout
+        );
+
+        def.connect_timeout_ms = Some(0);
+        def.request_timeout_ms = None;
+        let cfg = build_provider_transport_config(&def, None);
+        assert_eq!(
+            cfg.connect_timeout,
+            TransportConfig::default().connect_timeout
+        );
+        assert_eq!(cfg.request_timeout, None);
+    }
+
     #[test]
     fn collect_query_params_clones_values() {
         let mut def = test_provider_def();
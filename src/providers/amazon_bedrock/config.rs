@@ -38,7 +38,55 @@ pub struct BedrockConfig<T: HttpTransport> {
 impl<T: HttpTransport> BedrockConfig<T> {
     pub fn endpoint_for_model(&self, model_id: &str, suffix: &str) -> String {
         let base = self.base_url.trim_end_matches('/');
-        let encoded_model = urlencoding::encode(model_id);
+        let (_, path_model_id) = resolve_inference_profile(model_id);
+        let encoded_model = urlencoding::encode(&path_model_id);
         format!("{}/model/{}{}", base, encoded_model, suffix)
     }
 }
+
+/// Geography prefixes of cross-region inference profile ids
+/// (`us.anthropic.claude-...`) and the source region each is called from
+/// when no region is configured.
+const INFERENCE_PROFILE_GEOGRAPHIES: &[(&str, &str)] = &[
+    ("us", "us-east-1"),
+    ("us-gov", "us-gov-west-1"),
+    ("eu", "eu-central-1"),
+    ("apac", "ap-northeast-1"),
+    ("jp", "ap-northeast-1"),
+    ("au", "ap-southeast-2"),
+    ("ca", "ca-central-1"),
+    ("global", "us-east-1"),
+];
+
+/// Split a model id into the region whose `bedrock-runtime` host serves it
+/// and the id placed in the endpoint path.
+///
+/// Cross-region inference profile ids (`us.`, `eu.`, `apac.`, ...) resolve to
+/// a source region in their geography and inference profile ARNs to the
+/// region in the ARN; both keep the full id in the path, since Bedrock routes
+/// on the profile. Bare model ids return no region, leaving the configured
+/// one in charge.
+pub fn resolve_inference_profile(model_id: &str) -> (Option<String>, String) {
+    let model_id = model_id.trim();
+    if let Some(rest) = model_id.strip_prefix("arn:") {
+        // arn:<partition>:bedrock:<region>:<account>:<resource>
+        let region = rest
+            .split(':')
+            .nth(2)
+            .filter(|region| !region.is_empty())
+            .map(str::to_string);
+        return (region, model_id.to_string());
+    }
+    let region = model_id.split_once('.').and_then(|(prefix, rest)| {
+        INFERENCE_PROFILE_GEOGRAPHIES
+            .iter()
+            .find(|(geography, _)| *geography == prefix)
+            .filter(|_| rest.contains('.'))
+            .map(|(_, region)| region.to_string())
+    });
+    (region, model_id.to_string())
+}
+
+#[cfg(test)]
+#[path = "../../../crates/providers/amazon-bedrock/tests/config_tests.rs"]
+mod tests;
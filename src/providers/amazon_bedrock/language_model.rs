@@ -1,34 +1,80 @@
+use crate::core::abort::call_transport_config;
+use crate::core::capabilities::get_max_output_tokens;
+use crate::core::headers::HeaderMerger;
 use crate::core::options as sdkopt;
+use crate::core::raw_capture::RawCaptureConfig;
+use crate::core::request_builder::audio_input::drop_audio_input;
 use crate::core::request_builder::defaults::build_call_options;
-use crate::core::transport::HttpTransport;
-use crate::core::{GenerateResponse, LanguageModel, SdkError, StreamResponse};
+use crate::core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::core::request_builder::tool_limits::check_tool_limits;
+use crate::core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::core::stop_on_tool_call::disable_stop_on_first_tool_call;
+use crate::core::transport::{HttpTransport, TransportConfig};
+use crate::core::usage_estimate::UsageEstimate;
+use crate::core::{
+    map_events_to_parts, EventMapperConfig, EventMapperHooks, EventMapperState, GenerateResponse,
+    LanguageModel, ModelCapabilities, SdkError, StreamResponse,
+};
 use crate::types::v2 as v2t;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Map as JsonMap, Value as JsonValue};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use tracing::instrument;
 
 use crate::providers::amazon_bedrock::config::BedrockConfig;
 use crate::providers::amazon_bedrock::error::map_transport_error;
 use crate::providers::amazon_bedrock::messages::{convert_prompt, ConvertedPrompt};
 use crate::providers::amazon_bedrock::options::{
-    map_to_owned, parse_bedrock_provider_options, BedrockProviderOptions, BedrockReasoningConfig,
+    map_to_owned, parse_bedrock_provider_options, parse_guardrail_config, BedrockProviderOptions,
+    BedrockReasoningConfig,
 };
 use crate::providers::amazon_bedrock::signing::{prepare_request, PreparedRequest};
+use crate::providers::amazon_bedrock::stream::{
+    converse_stream_events, FINISH_DATA_KEY, METADATA_DATA_KEY, REASONING_REDACTED_DATA_KEY,
+    REASONING_SIGNATURE_DATA_KEY,
+};
 
 const TRACE_PREFIX: &str = "[BEDROCK]";
 
+/// Capabilities of a Bedrock foundation model. Claude models (including
+/// cross-region `us.anthropic.*` profiles) keep their Anthropic capabilities;
+/// other vendors get tool use through Converse, with image input on the
+/// multimodal Nova models.
+fn model_capabilities(model_id: &str) -> ModelCapabilities {
+    if let Some((_, claude)) = model_id.split_once("anthropic.") {
+        return crate::providers::anthropic::messages::language_model::model_capabilities(claude);
+    }
+    let nova_multimodal = ["amazon.nova-lite", "amazon.nova-pro", "amazon.nova-premier"]
+        .iter()
+        .any(|family| model_id.contains(family));
+    ModelCapabilities {
+        vision: nova_multimodal,
+        tools: true,
+        reasoning: model_id.contains("deepseek.r1"),
+        structured_output: true,
+        streaming: true,
+        ..ModelCapabilities::default()
+    }
+}
+
 pub struct BedrockLanguageModel<T: HttpTransport = crate::transport_reqwest::ReqwestTransport> {
     pub model_id: String,
     pub cfg: BedrockConfig<T>,
+    /// Output limit reported by the providers index, looked up once when
+    /// the model is built.
+    max_output_tokens: Option<u64>,
 }
 
 impl<T: HttpTransport> BedrockLanguageModel<T> {
     pub fn new(model_id: impl Into<String>, cfg: BedrockConfig<T>) -> Self {
+        let model_id = model_id.into();
+        let max_output_tokens = get_max_output_tokens("amazon-bedrock", &model_id);
         Self {
-            model_id: model_id.into(),
+            model_id,
             cfg,
+            max_output_tokens,
         }
     }
 
@@ -64,9 +110,16 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for BedrockLanguageModel<T> {
         self.supported_urls_map()
     }
 
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            max_output_tokens: self.max_output_tokens,
+            ..model_capabilities(&self.model_id)
+        }
+    }
+
     #[instrument(name = "bedrock.do_generate", skip_all, fields(model = %self.model_id))]
     async fn do_generate(&self, options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
-        let options = build_call_options(
+        let mut options = build_call_options(
             options,
             &self.cfg.provider_scope_name,
             self.cfg.default_options.as_ref(),
@@ -74,13 +127,19 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for BedrockLanguageModel<T> {
 
         let BuildCommandResult {
             command,
-            warnings,
+            mut warnings,
             uses_json_response_tool,
             betas,
             provider_metadata_seed,
+            has_guardrail,
         } = build_command(&self.model_id, &options)?;
+        warnings.extend(disable_stop_on_first_tool_call(&mut options));
 
-        let mut headers = merge_headers(self.base_headers(), &options.headers);
+        let mut headers = merge_headers(
+            self.base_headers(),
+            &options.headers,
+            &self.cfg.transport_cfg,
+        )?;
         if !betas.is_empty() {
             headers.push((
                 "anthropic-beta".to_string(),
@@ -102,7 +161,12 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for BedrockLanguageModel<T> {
         let (resp_body, resp_headers) = match self
             .cfg
             .http
-            .post_json(&url, &headers, &body, &self.cfg.transport_cfg)
+            .post_json(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
             .await
         {
             Ok(v) => v,
@@ -124,14 +188,32 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for BedrockLanguageModel<T> {
 
         let content = map_response_content(&output.message.content, uses_json_response_tool)?;
 
-        let finish_reason = map_finish_reason(stop_reason.as_deref());
+        let reported_action = resp_body
+            .get(GUARDRAIL_ACTION_BODY_FIELD)
+            .and_then(JsonValue::as_str)
+            .or_else(|| {
+                resp_headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(GUARDRAIL_ACTION_HEADER))
+                    .map(|(_, value)| value.as_str())
+            });
+        let guardrail_action =
+            guardrail_action(reported_action, stop_reason.as_deref(), has_guardrail);
+        let finish_reason = if guardrail_action.as_deref() == Some(GUARDRAIL_INTERVENED) {
+            v2t::FinishReason::ContentFilter
+        } else {
+            map_finish_reason(stop_reason.as_deref())
+        };
         let usage = map_usage(raw_usage.as_ref());
 
-        let provider_metadata = build_provider_metadata(
-            provider_metadata_seed,
-            trace,
-            raw_usage.as_ref(),
-            uses_json_response_tool,
+        let provider_metadata = with_guardrail_action(
+            build_provider_metadata(
+                provider_metadata_seed,
+                trace,
+                raw_usage.as_ref(),
+                uses_json_response_tool,
+            ),
+            guardrail_action,
         );
 
         let response_headers = Some(headers_to_map(&resp_headers));
@@ -141,20 +223,193 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for BedrockLanguageModel<T> {
             finish_reason,
             usage,
             provider_metadata,
+            response_metadata: None,
             request_body: Some(JsonValue::Object(command)),
             response_headers,
             response_body: Some(resp_body),
             warnings,
-        })
+        }
+        .with_usage_estimate(UsageEstimate::from_options(&options)))
     }
 
+    #[instrument(name = "bedrock.do_stream", skip_all, fields(model = %self.model_id))]
     async fn do_stream(&self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
-        let _ = options;
-        Err(SdkError::Transport(
-            crate::core::error::TransportError::Other(
-                "Amazon Bedrock streaming not yet implemented".into(),
+        let options = build_call_options(
+            options,
+            &self.cfg.provider_scope_name,
+            self.cfg.default_options.as_ref(),
+        );
+
+        let BuildCommandResult {
+            command,
+            warnings,
+            uses_json_response_tool,
+            betas,
+            provider_metadata_seed,
+            has_guardrail,
+        } = build_command(&self.model_id, &options)?;
+
+        let mut headers = merge_headers(
+            self.base_headers(),
+            &options.headers,
+            &self.cfg.transport_cfg,
+        )?;
+        if !betas.is_empty() {
+            headers.push((
+                "anthropic-beta".to_string(),
+                betas.into_iter().collect::<Vec<_>>().join(","),
+            ));
+        }
+
+        let url = self.build_model_url("/converse-stream");
+        let PreparedRequest { body, headers } = prepare_request(
+            &self.cfg.auth,
+            &url,
+            JsonValue::Object(command.clone()),
+            &headers,
+            &self.cfg.transport_cfg,
+        )?;
+
+        tracing::info!("{}: POST {}", TRACE_PREFIX, url);
+
+        let resp = match self
+            .cfg
+            .http
+            .post_json_stream(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
+            .await
+        {
+            Ok(resp) => resp,
+            Err(err) => {
+                let mapped = map_transport_error(err);
+                tracing::info!("{}: request failed: {}", TRACE_PREFIX, mapped);
+                return Err(mapped);
+            }
+        };
+        let (inner, resp_headers) = <T as HttpTransport>::into_stream(resp);
+
+        let parts = map_events_to_parts(
+            Box::pin(converse_stream_events(inner, options.emit_raw_chunks())),
+            converse_event_mapper_config(
+                warnings,
+                uses_json_response_tool,
+                provider_metadata_seed,
+                has_guardrail,
+                options.stop_on_first_tool_call,
             ),
-        ))
+        );
+
+        Ok(StreamResponse {
+            stream: parts,
+            request_body: Some(JsonValue::Object(command)),
+            response_headers: Some(headers_to_map(&resp_headers)),
+            raw_response: None,
+        }
+        .with_dedupe_sources(options.dedupe_sources)
+        .with_raw_capture(RawCaptureConfig::from_options(&options))
+        .with_usage_estimate(UsageEstimate::from_options(&options))
+        .with_abort(options.abort.as_ref()))
+    }
+}
+
+/// Finish reason, guardrail action and provider metadata gathered from
+/// `messageStop` and `metadata` events.
+#[derive(Default)]
+struct ConverseStreamExtra {
+    finish_reason: Option<v2t::FinishReason>,
+    guardrail_action: Option<String>,
+    provider_metadata: Option<v2t::ProviderMetadata>,
+}
+
+fn converse_event_mapper_config(
+    warnings: Vec<v2t::CallWarning>,
+    uses_json_response_tool: bool,
+    provider_metadata_seed: Option<JsonMap<String, JsonValue>>,
+    has_guardrail: bool,
+    stop_on_first_tool_call: bool,
+) -> EventMapperConfig<ConverseStreamExtra> {
+    let finish_seed = provider_metadata_seed.clone();
+    let hooks = EventMapperHooks {
+        data: Some(Box::new(
+            move |state: &mut EventMapperState<ConverseStreamExtra>, key, value| match key {
+                REASONING_SIGNATURE_DATA_KEY => {
+                    let signature = value.get("signature").and_then(JsonValue::as_str)?;
+                    Some(vec![v2t::StreamPart::ReasoningSignature {
+                        signature: signature.to_string(),
+                        provider_metadata: None,
+                    }])
+                }
+                REASONING_REDACTED_DATA_KEY => {
+                    let data = value.get("data").and_then(JsonValue::as_str)?;
+                    let metadata = Some(HashMap::from([(
+                        "bedrock".to_string(),
+                        HashMap::from([("redactedData".to_string(), json!(data))]),
+                    )]));
+                    let mut parts = state.open_reasoning("redacted".into(), metadata.clone());
+                    parts.extend(state.close_reasoning(metadata));
+                    Some(parts)
+                }
+                FINISH_DATA_KEY => {
+                    let stop_reason = value.get("stopReason").and_then(JsonValue::as_str);
+                    state.extra.finish_reason = Some(map_finish_reason(stop_reason));
+                    state.extra.guardrail_action =
+                        guardrail_action(None, stop_reason, has_guardrail);
+                    None
+                }
+                METADATA_DATA_KEY => {
+                    let usage = value
+                        .get("usage")
+                        .cloned()
+                        .and_then(|usage| serde_json::from_value::<ConverseUsage>(usage).ok());
+                    state.usage = map_usage(usage.as_ref());
+                    state.extra.provider_metadata = build_provider_metadata(
+                        provider_metadata_seed.clone(),
+                        value.get("trace").cloned(),
+                        usage.as_ref(),
+                        uses_json_response_tool,
+                    );
+                    None
+                }
+                _ => None,
+            },
+        )),
+        finish: Some(Box::new(move |state| {
+            let finish_reason =
+                state
+                    .extra
+                    .finish_reason
+                    .clone()
+                    .unwrap_or(if state.has_tool_calls {
+                        v2t::FinishReason::ToolCalls
+                    } else {
+                        v2t::FinishReason::Unknown
+                    });
+            let provider_metadata = state.extra.provider_metadata.clone().or_else(|| {
+                build_provider_metadata(finish_seed.clone(), None, None, uses_json_response_tool)
+            });
+            (
+                finish_reason,
+                with_guardrail_action(provider_metadata, state.extra.guardrail_action.clone()),
+            )
+        })),
+        ..EventMapperHooks::default()
+    };
+    EventMapperConfig {
+        warnings,
+        treat_tool_names_as_text: if uses_json_response_tool {
+            HashSet::from(["json".to_string()])
+        } else {
+            HashSet::new()
+        },
+        default_text_id: "text-0",
+        finish_reason_fallback: v2t::FinishReason::Unknown,
+        initial_extra: ConverseStreamExtra::default(),
+        hooks,
+        stop_on_first_tool_call,
     }
 }
 
@@ -164,6 +419,7 @@ struct BuildCommandResult {
     uses_json_response_tool: bool,
     betas: HashSet<String>,
     provider_metadata_seed: Option<JsonMap<String, JsonValue>>,
+    has_guardrail: bool,
 }
 
 struct BedrockToolRequest {
@@ -425,14 +681,26 @@ fn insert_optional_bedrock_map(
 }
 
 fn build_command(
-    _model_id: &str,
+    model_id: &str,
     options: &v2t::CallOptions,
 ) -> Result<BuildCommandResult, SdkError> {
     let mut warnings = Vec::new();
+    let limited = apply_prompt_message_limit(options, &mut warnings)?;
+    let options = limited.as_ref();
+    let tooled =
+        apply_unsupported_tools_policy(options, model_capabilities(model_id).tools, &mut warnings)?;
+    let options = tooled.as_ref();
+    check_tool_limits(options, v2t::ToolLimits::default(), &mut warnings)?;
+    let (placed, _) =
+        apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+    let options = placed.as_ref();
     collect_bedrock_unsupported_option_warnings(options, &mut warnings);
+    let audioless = drop_audio_input(options, "Amazon Bedrock", &mut warnings);
+    let options = audioless.as_ref();
 
     let bedrock_opts =
         parse_bedrock_provider_options(&options.provider_options).unwrap_or_default();
+    let guardrail_config = parse_guardrail_config(&options.provider_options)?;
     let BedrockToolRequest {
         tools,
         tool_choice,
@@ -464,11 +732,9 @@ fn build_command(
     insert_nonempty_command_object(&mut command, "inferenceConfig", inference);
     insert_tool_config(&mut command, tool_config);
     merge_additional_model_request_fields(&mut additional_model_fields, &bedrock_opts);
-    insert_optional_bedrock_map(
-        &mut command,
-        "guardrailConfig",
-        &bedrock_opts.guardrail_config,
-    );
+    if let Some(config) = &guardrail_config {
+        command.insert("guardrailConfig".into(), serde_json::to_value(config)?);
+    }
     insert_optional_bedrock_map(
         &mut command,
         "guardrailStreamConfig",
@@ -492,24 +758,26 @@ fn build_command(
         } else {
             None
         },
+        has_guardrail: guardrail_config.is_some(),
     })
 }
 
 fn merge_headers(
     config_headers: Vec<(String, String)>,
     call_headers: &HashMap<String, String>,
-) -> Vec<(String, String)> {
-    let mut map: BTreeMap<String, (String, String)> = BTreeMap::new();
+    transport_cfg: &TransportConfig,
+) -> Result<Vec<(String, String)>, SdkError> {
+    let mut merger = HeaderMerger::for_transport(transport_cfg);
     for (k, v) in config_headers {
-        map.insert(k.to_ascii_lowercase(), (k, v));
+        merger.insert(&k, v)?;
     }
     for (k, v) in call_headers {
         if sdkopt::is_internal_sdk_header(k) {
             continue;
         }
-        map.insert(k.to_ascii_lowercase(), (k.clone(), v.clone()));
+        merger.insert(k, v.clone())?;
     }
-    map.into_values().collect()
+    Ok(merger.into_pairs())
 }
 
 fn filter_prompt_if_no_tools(
@@ -524,7 +792,9 @@ fn filter_prompt_if_no_tools(
     let mut filtered: Vec<v2t::PromptMessage> = Vec::with_capacity(prompt.len());
     for message in prompt {
         match message {
-            v2t::PromptMessage::System { .. } => filtered.push(message.clone()),
+            v2t::PromptMessage::System { .. } | v2t::PromptMessage::Developer { .. } => {
+                filtered.push(message.clone())
+            }
             v2t::PromptMessage::User { .. } => filtered.push(message.clone()),
             v2t::PromptMessage::Tool { .. } => {
                 mutated = true;
@@ -826,6 +1096,7 @@ fn map_usage(usage: Option<&ConverseUsage>) -> v2t::Usage {
             total_tokens: Some(u.total_tokens as u64),
             cached_input_tokens: u.cache_read_input_tokens.map(|n| n as u64),
             reasoning_tokens: None,
+            ..Default::default()
         })
         .unwrap_or_default()
 }
@@ -862,6 +1133,43 @@ fn build_provider_metadata(
     Some(out)
 }
 
+const GUARDRAIL_ACTION_BODY_FIELD: &str = "amazon-bedrock-guardrailAction";
+const GUARDRAIL_ACTION_HEADER: &str = "x-amzn-bedrock-guardrailaction";
+const GUARDRAIL_INTERVENED: &str = "INTERVENED";
+
+/// Guardrail verdict surfaced as `provider_metadata.bedrock.guardrailAction`:
+/// the action Bedrock reported (`amazon-bedrock-guardrailAction`), else
+/// `INTERVENED` when the stop reason says the guardrail stepped in, else
+/// `NONE` when a configured guardrail let the response through.
+fn guardrail_action(
+    reported: Option<&str>,
+    stop_reason: Option<&str>,
+    has_guardrail: bool,
+) -> Option<String> {
+    if let Some(action) = reported {
+        return Some(action.to_string());
+    }
+    if stop_reason == Some("guardrail_intervened") {
+        return Some(GUARDRAIL_INTERVENED.into());
+    }
+    has_guardrail.then(|| "NONE".to_string())
+}
+
+fn with_guardrail_action(
+    metadata: Option<v2t::ProviderMetadata>,
+    action: Option<String>,
+) -> Option<v2t::ProviderMetadata> {
+    let Some(action) = action else {
+        return metadata;
+    };
+    let mut metadata = metadata.unwrap_or_default();
+    metadata
+        .entry("bedrock".into())
+        .or_default()
+        .insert("guardrailAction".into(), JsonValue::String(action));
+    Some(metadata)
+}
+
 fn headers_to_map(headers: &[(String, String)]) -> v2t::Headers {
     headers
         .iter()
@@ -45,6 +45,10 @@ fn append_system_block(
         if let v2t::PromptMessage::System {
             content,
             provider_options,
+        }
+        | v2t::PromptMessage::Developer {
+            content,
+            provider_options,
         } = message
         {
             if content.trim().is_empty() {
@@ -377,15 +381,19 @@ fn group_into_blocks(prompt: &[v2t::PromptMessage]) -> Vec<Block<'_>> {
 
     for message in prompt {
         match message {
-            v2t::PromptMessage::System { .. } => match current.as_mut() {
-                Some(Block::System(msgs)) => msgs.push(message),
-                _ => {
-                    if let Some(b) = current.take() {
-                        blocks.push(b);
+            // Bedrock has no developer role; developer messages join the
+            // system block.
+            v2t::PromptMessage::System { .. } | v2t::PromptMessage::Developer { .. } => {
+                match current.as_mut() {
+                    Some(Block::System(msgs)) => msgs.push(message),
+                    _ => {
+                        if let Some(b) = current.take() {
+                            blocks.push(b);
+                        }
+                        current = Some(Block::System(vec![message]));
                     }
-                    current = Some(Block::System(vec![message]));
                 }
-            },
+            }
             v2t::PromptMessage::Assistant { .. } => match current.as_mut() {
                 Some(Block::Assistant(msgs)) => msgs.push(message),
                 _ => {
@@ -5,6 +5,7 @@ pub mod messages;
 pub mod options;
 pub mod provider;
 pub mod signing;
+pub mod stream;
 
 #[cfg(test)]
 #[path = "../../../crates/providers/amazon-bedrock/tests/language_model_tests.rs"]
@@ -1,6 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map as JsonMap, Value as JsonValue};
 
+use crate::core::SdkError;
 use crate::types::v2::ProviderOptions;
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -19,13 +20,6 @@ pub struct BedrockProviderOptions {
         skip_serializing_if = "Option::is_none"
     )]
     pub reasoning_config: Option<BedrockReasoningConfig>,
-    #[serde(
-        default,
-        rename = "guardrailConfig",
-        alias = "guardrail_config",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub guardrail_config: Option<JsonMap<String, JsonValue>>,
     #[serde(
         default,
         rename = "guardrailStreamConfig",
@@ -43,6 +37,26 @@ pub struct BedrockReasoningConfig {
     pub budget_tokens: Option<u32>,
 }
 
+/// Guardrail applied to the request, sent as the Converse `guardrailConfig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockGuardrailConfig {
+    #[serde(alias = "guardrail_identifier")]
+    pub guardrail_identifier: String,
+    #[serde(alias = "guardrail_version")]
+    pub guardrail_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<BedrockGuardrailTrace>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BedrockGuardrailTrace {
+    Enabled,
+    Disabled,
+    EnabledFull,
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct BedrockReasoningMetadata {
     #[serde(default)]
@@ -58,6 +72,26 @@ pub fn parse_bedrock_provider_options(opts: &ProviderOptions) -> Option<BedrockP
     serde_json::from_value(value).ok()
 }
 
+/// Parse the `bedrock.guardrailConfig` option. Unlike the other options a
+/// malformed config is an error rather than ignored, so a request is never
+/// sent without the guardrail the caller asked for.
+pub fn parse_guardrail_config(
+    opts: &ProviderOptions,
+) -> Result<Option<BedrockGuardrailConfig>, SdkError> {
+    let Some(value) = opts.get("bedrock").and_then(|bedrock| {
+        bedrock
+            .get("guardrailConfig")
+            .or_else(|| bedrock.get("guardrail_config"))
+    }) else {
+        return Ok(None);
+    };
+    serde_json::from_value(value.clone())
+        .map(Some)
+        .map_err(|err| SdkError::InvalidArgument {
+            message: format!("invalid bedrock guardrailConfig: {err}"),
+        })
+}
+
 /// Parse reasoning metadata for assistant reasoning parts coming from provider options.
 pub fn parse_reasoning_metadata(
     opts: &Option<ProviderOptions>,
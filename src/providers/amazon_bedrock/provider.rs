@@ -1,7 +1,9 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::core::headers::HeaderMerger;
 use crate::core::request_builder::defaults::provider_defaults_from_json;
+use crate::core::transport::TransportConfig;
 use crate::core::{LanguageModel, SdkError};
 use crate::provider::{
     build_provider_transport_config, registry::ProviderRegistration, Credentials,
@@ -12,7 +14,9 @@ use crate::types::v2 as v2t;
 use serde_json::Value as JsonValue;
 use tracing::info;
 
-use crate::providers::amazon_bedrock::config::{BedrockAuth, BedrockConfig, SigV4Config};
+use crate::providers::amazon_bedrock::config::{
+    resolve_inference_profile, BedrockAuth, BedrockConfig, SigV4Config,
+};
 use crate::providers::amazon_bedrock::language_model::BedrockLanguageModel;
 
 const TRACE_PREFIX: &str = "[BEDROCK]";
@@ -43,11 +47,17 @@ fn resolve_bedrock_api_key(creds: &Credentials) -> Option<String> {
         .filter(|value| !value.trim().is_empty())
 }
 
-fn resolve_bedrock_base_url_and_region(def: &ProviderDefinition) -> (String, String) {
+/// Base URL and SigV4 signing region. Without a configured region, an
+/// inference profile model id picks the regional host that serves it.
+fn resolve_bedrock_base_url_and_region(def: &ProviderDefinition, model: &str) -> (String, String) {
     let mut region = extract_region_hint(def);
+    let (profile_region, _) = resolve_inference_profile(model);
 
     let base_url = if def.base_url.trim().is_empty() {
-        let resolved_region = region.clone().unwrap_or_else(|| "us-east-1".into());
+        let resolved_region = region
+            .clone()
+            .or(profile_region)
+            .unwrap_or_else(|| "us-east-1".into());
         region.get_or_insert(resolved_region.clone());
         DEFAULT_BASE_URL_FMT.replace("{region}", &resolved_region)
     } else {
@@ -82,14 +92,19 @@ fn resolve_bedrock_auth(
     }))
 }
 
+/// Merged request headers and the provider defaults parsed from the
+/// internal options header.
+type MergedHeaders = (Vec<(String, String)>, Option<v2t::ProviderOptions>);
+
 fn merge_provider_headers(
     def: &ProviderDefinition,
     headers: Vec<(String, String)>,
-) -> (Vec<(String, String)>, Option<v2t::ProviderOptions>) {
-    let mut header_map: BTreeMap<String, (String, String)> = headers
-        .into_iter()
-        .map(|(key, value)| (key.to_ascii_lowercase(), (key, value)))
-        .collect();
+    transport_cfg: &TransportConfig,
+) -> Result<MergedHeaders, SdkError> {
+    let mut merger = HeaderMerger::for_transport(transport_cfg);
+    for (key, value) in headers {
+        merger.insert_default(&key, value);
+    }
 
     let mut default_options: Option<v2t::ProviderOptions> = None;
     for (key, value) in &def.headers {
@@ -101,10 +116,10 @@ fn merge_provider_headers(
             }
             continue;
         }
-        header_map.insert(key.to_ascii_lowercase(), (key.clone(), value.clone()));
+        merger.insert(key, value.clone())?;
     }
 
-    (header_map.into_values().collect(), default_options)
+    Ok((merger.into_pairs(), default_options))
 }
 
 fn build_bedrock(
@@ -114,14 +129,13 @@ fn build_bedrock(
 ) -> Result<Arc<dyn LanguageModel>, SdkError> {
     let mut headers = default_headers();
     let api_key = resolve_bedrock_api_key(creds);
-    let (base_url, region) = resolve_bedrock_base_url_and_region(def);
+    let (base_url, region) = resolve_bedrock_base_url_and_region(def, model);
     let auth = resolve_bedrock_auth(api_key, &region, &mut headers)?;
-    let (headers, default_options) = merge_provider_headers(def, headers);
-
     let transport_cfg = build_provider_transport_config(
         def,
         Some(std::time::Duration::from_secs(BEDROCK_TIMEOUT_SECS)),
     );
+    let (headers, default_options) = merge_provider_headers(def, headers, &transport_cfg)?;
 
     let http = crate::transport_reqwest::ReqwestTransport::try_new(&transport_cfg)
         .map_err(SdkError::Transport)?;
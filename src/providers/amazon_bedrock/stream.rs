@@ -0,0 +1,223 @@
+//! ConverseStream response decoding.
+//!
+//! Bedrock streams Converse output as AWS event stream frames
+//! (`application/vnd.amazon.eventstream`), not SSE. Each frame carries an
+//! `:event-type` header and a JSON payload; this module turns them into
+//! provider [`Event`]s for the shared event mapper. `messageStop` and
+//! `metadata` payloads are forwarded as `Data` events under
+//! [`FINISH_DATA_KEY`] and [`METADATA_DATA_KEY`].
+
+use std::collections::HashMap;
+use std::pin::Pin;
+
+use aws_smithy_eventstream::frame::{DecodedFrame, MessageFrameDecoder};
+use aws_smithy_types::event_stream::Message;
+use bytes::{Bytes, BytesMut};
+use futures_core::Stream;
+use futures_util::StreamExt;
+use serde_json::{json, Value as JsonValue};
+
+use crate::core::error::TransportError;
+use crate::core::SdkError;
+use crate::providers::amazon_bedrock::error::map_transport_error;
+use crate::types::Event;
+
+pub const FINISH_DATA_KEY: &str = "bedrock.finish";
+pub const METADATA_DATA_KEY: &str = "bedrock.metadata";
+pub const REASONING_SIGNATURE_DATA_KEY: &str = "bedrock.reasoning_signature";
+pub const REASONING_REDACTED_DATA_KEY: &str = "bedrock.reasoning_redacted";
+
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>;
+
+/// Decode a ConverseStream body into provider events, ending with
+/// [`Event::Done`] once the body is exhausted or right after the
+/// [`Event::Error`] of an exception frame.
+///
+/// With `include_raw`, each frame is also forwarded as an [`Event::Raw`]
+/// `{"<eventType>": payload}` object ahead of the events decoded from it.
+pub fn converse_stream_events(
+    mut inner: ByteStream,
+    include_raw: bool,
+) -> impl Stream<Item = Result<Event, SdkError>> + Send {
+    async_stream::try_stream! {
+        let mut decoder = MessageFrameDecoder::new();
+        let mut buffer = BytesMut::new();
+        let mut state = ConverseStreamState::default();
+        while let Some(chunk) = inner.next().await {
+            buffer.extend_from_slice(&chunk.map_err(map_transport_error)?);
+            loop {
+                let frame = decoder.decode_frame(&mut buffer).map_err(|err| {
+                    SdkError::Transport(TransportError::Other(format!(
+                        "invalid Bedrock event stream frame: {err}"
+                    )))
+                })?;
+                let DecodedFrame::Complete(message) = frame else {
+                    break;
+                };
+                if include_raw {
+                    yield Event::Raw { raw_value: raw_frame(&message) };
+                }
+                for event in state.handle_message(&message) {
+                    // An exception frame ends the stream; leave it to the
+                    // mapper to close open parts and finish with `Error`.
+                    let is_error = matches!(event, Event::Error { .. });
+                    yield event;
+                    if is_error {
+                        return;
+                    }
+                }
+            }
+        }
+        yield Event::Done;
+    }
+}
+
+enum ContentBlock {
+    Text,
+    Reasoning,
+    ToolUse(String),
+}
+
+#[derive(Default)]
+struct ConverseStreamState {
+    blocks: HashMap<u64, ContentBlock>,
+}
+
+impl ConverseStreamState {
+    fn handle_message(&mut self, message: &Message) -> Vec<Event> {
+        let payload: JsonValue =
+            serde_json::from_slice(message.payload()).unwrap_or(JsonValue::Null);
+        match header(message, ":message-type") {
+            Some("exception") | Some("error") => {
+                let kind = header(message, ":exception-type")
+                    .or_else(|| header(message, ":error-code"))
+                    .unwrap_or("error");
+                let detail = payload
+                    .get("message")
+                    .or_else(|| payload.get("Message"))
+                    .and_then(JsonValue::as_str)
+                    .or_else(|| header(message, ":error-message"))
+                    .unwrap_or("Bedrock stream failed");
+                vec![Event::Error {
+                    message: format!("{kind}: {detail}"),
+                }]
+            }
+            _ => match header(message, ":event-type") {
+                Some(event_type) => self.handle_event(event_type, &payload),
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn handle_event(&mut self, event_type: &str, payload: &JsonValue) -> Vec<Event> {
+        let index = payload
+            .get("contentBlockIndex")
+            .and_then(JsonValue::as_u64)
+            .unwrap_or(0);
+        match event_type {
+            "contentBlockStart" => {
+                let Some(tool_use) = payload.pointer("/start/toolUse") else {
+                    return Vec::new();
+                };
+                let id = tool_use
+                    .get("toolUseId")
+                    .and_then(JsonValue::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+                let name = tool_use
+                    .get("name")
+                    .and_then(JsonValue::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                self.blocks.insert(index, ContentBlock::ToolUse(id.clone()));
+                vec![Event::ToolCallStart { id, name }]
+            }
+            "contentBlockDelta" => payload
+                .get("delta")
+                .map(|delta| self.handle_delta(index, delta))
+                .unwrap_or_default(),
+            "contentBlockStop" => match self.blocks.remove(&index) {
+                Some(ContentBlock::ToolUse(id)) => vec![Event::ToolCallEnd { id }],
+                Some(ContentBlock::Reasoning) => vec![Event::ReasoningEnd],
+                Some(ContentBlock::Text) | None => Vec::new(),
+            },
+            "messageStop" => vec![Event::Data {
+                key: FINISH_DATA_KEY.into(),
+                value: payload.clone(),
+            }],
+            "metadata" => vec![Event::Data {
+                key: METADATA_DATA_KEY.into(),
+                value: payload.clone(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_delta(&mut self, index: u64, delta: &JsonValue) -> Vec<Event> {
+        if let Some(text) = delta.get("text").and_then(JsonValue::as_str) {
+            self.blocks.entry(index).or_insert(ContentBlock::Text);
+            return vec![Event::TextDelta {
+                delta: text.to_string(),
+            }];
+        }
+        if let Some(input) = delta.pointer("/toolUse/input").and_then(JsonValue::as_str) {
+            return match self.blocks.get(&index) {
+                Some(ContentBlock::ToolUse(id)) => vec![Event::ToolCallDelta {
+                    id: id.clone(),
+                    args_json: input.to_string(),
+                }],
+                _ => Vec::new(),
+            };
+        }
+        let Some(reasoning) = delta.get("reasoningContent") else {
+            return Vec::new();
+        };
+        if let Some(data) = reasoning.get("redactedContent") {
+            return vec![Event::Data {
+                key: REASONING_REDACTED_DATA_KEY.into(),
+                value: json!({ "data": data }),
+            }];
+        }
+        let mut events = Vec::new();
+        if !matches!(self.blocks.get(&index), Some(ContentBlock::Reasoning)) {
+            self.blocks.insert(index, ContentBlock::Reasoning);
+            events.push(Event::ReasoningStart {
+                id: format!("reasoning-{index}"),
+            });
+        }
+        if let Some(text) = reasoning.get("text").and_then(JsonValue::as_str) {
+            events.push(Event::ReasoningDelta {
+                delta: text.to_string(),
+            });
+        }
+        if let Some(signature) = reasoning.get("signature").and_then(JsonValue::as_str) {
+            events.push(Event::Data {
+                key: REASONING_SIGNATURE_DATA_KEY.into(),
+                value: json!({ "signature": signature }),
+            });
+        }
+        events
+    }
+}
+
+/// A frame as `{"<eventType>": payload}`, the shape ConverseStream fixtures
+/// are recorded in. Payloads that are not JSON are kept as a string.
+fn raw_frame(message: &Message) -> JsonValue {
+    let event_type = header(message, ":event-type")
+        .or_else(|| header(message, ":exception-type"))
+        .or_else(|| header(message, ":error-code"))
+        .unwrap_or("unknown");
+    let payload = serde_json::from_slice(message.payload()).unwrap_or_else(|_| {
+        JsonValue::String(String::from_utf8_lossy(message.payload()).into_owned())
+    });
+    json!({ event_type: payload })
+}
+
+fn header<'a>(message: &'a Message, name: &str) -> Option<&'a str> {
+    message
+        .headers()
+        .iter()
+        .find(|header| header.name().as_str() == name)
+        .and_then(|header| header.value().as_string().ok())
+        .map(|value| value.as_str())
+}
@@ -3,26 +3,47 @@ use std::collections::{HashMap, HashSet};
 use async_trait::async_trait;
 use base64::Engine;
 use futures_util::StreamExt;
+use regex::Regex;
 use serde_json::{json, Value as JsonValue};
 
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::capabilities::get_max_output_tokens;
+use crate::ai_sdk_core::headers::HeaderMerger;
 use crate::ai_sdk_core::options;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
+use crate::ai_sdk_core::request_builder::audio_input::drop_audio_input;
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::raw_passthrough::apply_raw_passthrough;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::ai_sdk_core::request_builder::tool_choice::validate_tool_choice;
+use crate::ai_sdk_core::request_builder::tool_limits::check_tool_limits;
+use crate::ai_sdk_core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::ai_sdk_core::stop_on_tool_call::disable_stop_on_first_tool_call;
 use crate::ai_sdk_core::stream_collect::{collect_stream_to_response, StreamCollectorConfig};
 use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
 use crate::ai_sdk_core::{
-    map_events_to_parts, EventMapperConfig, EventMapperHooks, LanguageModel, SdkError,
+    map_events_to_parts, EventMapperConfig, EventMapperHooks, LanguageModel, ModelCapabilities,
+    SdkError,
 };
-use crate::ai_sdk_streaming_sse::{sse_to_events, ProviderChunk, SseEvent};
+use crate::ai_sdk_streaming_sse::{PipelineBuilder, ProviderChunk, SseEvent};
 use crate::ai_sdk_types::v2 as v2t;
 use crate::ai_sdk_types::Event as ProviderEvent;
 
 use crate::provider_anthropic::error::map_transport_error_to_sdk_error;
 use crate::provider_anthropic::messages::options::{
-    parse_anthropic_file_part_options, parse_anthropic_provider_options, AnthropicMessagesModelId,
-    AnthropicProviderOptions, ThinkingOption,
+    parse_anthropic_cache_control, parse_anthropic_file_part_options,
+    parse_anthropic_provider_options, AnthropicMessagesModelId, AnthropicProviderOptions,
+    CacheBreakpointOverflow, ThinkingOption,
 };
 
 const TRACE_PREFIX: &str = "[ANTHROPIC-V2]";
 const REQ_TRACE_PREFIX: &str = "[REQTRACE]";
+const FINE_GRAINED_TOOL_STREAMING_BETA: &str = "fine-grained-tool-streaming-2025-05-14";
+/// Most `cache_control` breakpoints Anthropic accepts in one request.
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+/// `max_tokens` sent when neither the caller nor the config provides one.
+pub const DEFAULT_MAX_TOKENS: u64 = 1024;
 
 /// Configuration for the Anthropic Messages model.
 pub struct AnthropicMessagesConfig<T: HttpTransport> {
@@ -34,6 +55,10 @@ pub struct AnthropicMessagesConfig<T: HttpTransport> {
     pub transport_cfg: TransportConfig,
     pub supported_urls: HashMap<String, Vec<String>>,
     pub default_options: Option<v2t::ProviderOptions>,
+    /// `max_tokens` used when the call omits `max_output_tokens`, which
+    /// Anthropic requires. Registry-built models set the catalog's output
+    /// limit. Falls back to [`DEFAULT_MAX_TOKENS`].
+    pub default_max_tokens: Option<u64>,
 }
 
 pub struct AnthropicMessagesLanguageModel<
@@ -41,6 +66,9 @@ pub struct AnthropicMessagesLanguageModel<
 > {
     model_id: AnthropicMessagesModelId,
     cfg: AnthropicMessagesConfig<T>,
+    /// Output limit reported by the providers index, looked up once when
+    /// the model is built.
+    max_output_tokens: Option<u64>,
 }
 
 struct BuiltAnthropicRequest {
@@ -116,11 +144,75 @@ fn build_json_response_tool(
     }
 }
 
-fn get_cache_control(opts: &Option<v2t::ProviderOptions>) -> Option<JsonValue> {
-    let map = opts.as_ref()?.get("anthropic")?;
-    map.get("cacheControl")
-        .or_else(|| map.get("cache_control"))
-        .cloned()
+fn get_cache_control(
+    opts: &Option<v2t::ProviderOptions>,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Option<JsonValue> {
+    parse_anthropic_cache_control(opts, "anthropic", warnings)
+        .and_then(|cache_control| serde_json::to_value(cache_control).ok())
+}
+
+/// Keep [`MAX_CACHE_BREAKPOINTS`] `cache_control` markers, counted in prefix
+/// order (tools, system, then messages), and drop the rest with a warning.
+/// `overflow` picks whether the first or the last ones survive.
+fn limit_cache_breakpoints(
+    body: &mut JsonValue,
+    overflow: CacheBreakpointOverflow,
+    warnings: &mut Vec<v2t::CallWarning>,
+) {
+    let mut total = 0;
+    for_each_cache_breakpoint(body, |_| total += 1);
+    if total <= MAX_CACHE_BREAKPOINTS {
+        return;
+    }
+    let dropped = total - MAX_CACHE_BREAKPOINTS;
+    let (kept, from) = match overflow {
+        CacheBreakpointOverflow::KeepFirst => (0..MAX_CACHE_BREAKPOINTS, "end"),
+        CacheBreakpointOverflow::KeepLast => (dropped..total, "start"),
+    };
+    let mut index = 0;
+    for_each_cache_breakpoint(body, |block| {
+        if !kept.contains(&index) {
+            block.remove("cache_control");
+        }
+        index += 1;
+    });
+    warnings.push(v2t::CallWarning::Other {
+        message: format!(
+            "Anthropic allows at most {MAX_CACHE_BREAKPOINTS} cache breakpoints per request; \
+             dropped {dropped} from the {from} of the prompt."
+        ),
+    });
+}
+
+/// Call `visit` on every block carrying a `cache_control` marker, in prefix
+/// order.
+fn for_each_cache_breakpoint(
+    body: &mut JsonValue,
+    mut visit: impl FnMut(&mut serde_json::Map<String, JsonValue>),
+) {
+    let mut visit = |block: &mut JsonValue| {
+        let Some(block) = block.as_object_mut() else {
+            return;
+        };
+        if block.get("cache_control").map_or(true, JsonValue::is_null) {
+            return;
+        }
+        visit(block);
+    };
+    for key in ["tools", "system", "messages"] {
+        let Some(entries) = body.get_mut(key).and_then(JsonValue::as_array_mut) else {
+            continue;
+        };
+        for entry in entries {
+            if key != "messages" {
+                visit(entry);
+            } else if let Some(content) = entry.get_mut("content").and_then(JsonValue::as_array_mut)
+            {
+                content.iter_mut().for_each(&mut visit);
+            }
+        }
+    }
 }
 
 fn push_system_text_entry(
@@ -291,7 +383,9 @@ fn extract_persisted_reasoning(
 
 fn prompt_message_tag(message: &v2t::PromptMessage) -> u8 {
     match message {
-        v2t::PromptMessage::System { .. } => 0,
+        // Anthropic has no developer role; developer messages are sent as
+        // system content.
+        v2t::PromptMessage::System { .. } | v2t::PromptMessage::Developer { .. } => 0,
         v2t::PromptMessage::Assistant { .. } => 1,
         v2t::PromptMessage::User { .. } => 2,
         v2t::PromptMessage::Tool { .. } => 3,
@@ -339,18 +433,26 @@ fn group_prompt_blocks(prompt: &[v2t::PromptMessage]) -> Vec<PromptBlock<'_>> {
     blocks
 }
 
-fn build_system_entries(messages: &[&v2t::PromptMessage]) -> Vec<JsonValue> {
+fn build_system_entries(
+    messages: &[&v2t::PromptMessage],
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Vec<JsonValue> {
     let mut entries = Vec::new();
     for message in messages {
         if let v2t::PromptMessage::System {
             content,
             provider_options,
+        }
+        | v2t::PromptMessage::Developer {
+            content,
+            provider_options,
         } = message
         {
             if content.is_empty() {
                 continue;
             }
-            push_system_text_entry(&mut entries, content, get_cache_control(provider_options));
+            let cache_control = get_cache_control(provider_options, warnings);
+            push_system_text_entry(&mut entries, content, cache_control);
         }
     }
     entries
@@ -360,6 +462,7 @@ fn user_part_cache_control(
     part: &v2t::UserPart,
     is_last: bool,
     provider_options: &Option<v2t::ProviderOptions>,
+    warnings: &mut Vec<v2t::CallWarning>,
 ) -> Option<JsonValue> {
     let part_cache_control = match part {
         v2t::UserPart::Text {
@@ -367,10 +470,10 @@ fn user_part_cache_control(
         }
         | v2t::UserPart::File {
             provider_options, ..
-        } => get_cache_control(provider_options),
+        } => get_cache_control(provider_options, warnings),
     };
     let message_cache_control = if is_last {
-        get_cache_control(provider_options)
+        get_cache_control(provider_options, warnings)
     } else {
         None
     };
@@ -510,6 +613,60 @@ fn build_user_file_entry(
     None
 }
 
+/// Whether a PDF may be passed by URL: it must match one of the
+/// `supported_urls` patterns listed for PDFs. Without such an entry, any
+/// http(s) URL is accepted, since Anthropic fetches those itself.
+fn pdf_url_supported(url: &str, supported_urls: &HashMap<String, Vec<String>>) -> bool {
+    let mut patterns = ["application/pdf", "application/*", "*/*"]
+        .iter()
+        .filter_map(|media_type| supported_urls.get(*media_type))
+        .flatten()
+        .peekable();
+    if patterns.peek().is_none() {
+        return url.starts_with("https://") || url.starts_with("http://");
+    }
+    patterns.any(|pattern| Regex::new(pattern).is_ok_and(|re| re.is_match(url)))
+}
+
+/// Reject PDF file parts Anthropic cannot take: inline data that is not
+/// valid base64, and URLs the model does not fetch.
+fn validate_pdf_parts(
+    prompt: &[v2t::PromptMessage],
+    supported_urls: &HashMap<String, Vec<String>>,
+) -> Result<(), SdkError> {
+    let files = prompt.iter().flat_map(|message| match message {
+        v2t::PromptMessage::User { content, .. } => content.as_slice(),
+        _ => &[],
+    });
+    for part in files {
+        let v2t::UserPart::File {
+            data, media_type, ..
+        } = part
+        else {
+            continue;
+        };
+        if media_type != "application/pdf" {
+            continue;
+        }
+        match data {
+            v2t::DataContent::Base64 { base64 } => {
+                if let Err(err) = base64::engine::general_purpose::STANDARD.decode(base64) {
+                    return Err(SdkError::InvalidArgument {
+                        message: format!("PDF file part is not valid base64: {err}"),
+                    });
+                }
+            }
+            v2t::DataContent::Url { url } if !pdf_url_supported(url, supported_urls) => {
+                return Err(SdkError::InvalidArgument {
+                    message: format!("PDF URLs are not supported by this model: {url}"),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 fn build_user_block_content(
     messages: &[&v2t::PromptMessage],
     provider_scope_name: &str,
@@ -525,8 +682,12 @@ fn build_user_block_content(
         } = message
         {
             for (index, part) in content.iter().enumerate() {
-                let cache_control =
-                    user_part_cache_control(part, index + 1 == content.len(), provider_options);
+                let cache_control = user_part_cache_control(
+                    part,
+                    index + 1 == content.len(),
+                    provider_options,
+                    warnings,
+                );
                 match part {
                     v2t::UserPart::Text { text, .. } => {
                         anthropic_content.push(json!({
@@ -566,6 +727,7 @@ fn assistant_part_cache_control(
     part: &v2t::AssistantPart,
     is_last: bool,
     provider_options: &Option<v2t::ProviderOptions>,
+    warnings: &mut Vec<v2t::CallWarning>,
 ) -> Option<JsonValue> {
     let part_cache_control = match part {
         v2t::AssistantPart::Text {
@@ -576,24 +738,14 @@ fn assistant_part_cache_control(
         }
         | v2t::AssistantPart::File {
             provider_options, ..
-        } => get_cache_control(provider_options),
-        v2t::AssistantPart::ToolCall(part) => part.provider_options.as_ref().and_then(|opts| {
-            opts.get("anthropic").and_then(|map| {
-                map.get("cacheControl")
-                    .or_else(|| map.get("cache_control"))
-                    .cloned()
-            })
-        }),
-        v2t::AssistantPart::ToolResult(part) => part.provider_options.as_ref().and_then(|opts| {
-            opts.get("anthropic").and_then(|map| {
-                map.get("cacheControl")
-                    .or_else(|| map.get("cache_control"))
-                    .cloned()
-            })
-        }),
+        } => get_cache_control(provider_options, warnings),
+        v2t::AssistantPart::ToolCall(part) => get_cache_control(&part.provider_options, warnings),
+        v2t::AssistantPart::ToolResult(part) => {
+            get_cache_control(&part.provider_options, warnings)
+        }
     };
     let message_cache_control = if is_last {
-        get_cache_control(provider_options)
+        get_cache_control(provider_options, warnings)
     } else {
         None
     };
@@ -728,6 +880,7 @@ fn build_assistant_message_content(
     content: &[v2t::AssistantPart],
     provider_options: &Option<v2t::ProviderOptions>,
     missing_thinking_reasoning: &mut bool,
+    warnings: &mut Vec<v2t::CallWarning>,
 ) -> Vec<JsonValue> {
     log_assistant_part_diagnostics(content, provider_options);
 
@@ -735,8 +888,12 @@ fn build_assistant_message_content(
     let mut other_entries = Vec::new();
 
     for (index, part) in content.iter().enumerate() {
-        let cache_control =
-            assistant_part_cache_control(part, index + 1 == content.len(), provider_options);
+        let cache_control = assistant_part_cache_control(
+            part,
+            index + 1 == content.len(),
+            provider_options,
+            warnings,
+        );
         match part {
             v2t::AssistantPart::Text { text, .. } => {
                 other_entries.push(json!({
@@ -814,6 +971,7 @@ fn build_assistant_message_content(
 fn build_assistant_block_content(
     messages: &[&v2t::PromptMessage],
     missing_thinking_reasoning: &mut bool,
+    warnings: &mut Vec<v2t::CallWarning>,
 ) -> Vec<JsonValue> {
     let mut anthropic_content = Vec::new();
 
@@ -827,6 +985,7 @@ fn build_assistant_block_content(
                 content,
                 provider_options,
                 missing_thinking_reasoning,
+                warnings,
             ));
         }
     }
@@ -840,7 +999,32 @@ fn build_tool_result_value(output: &v2t::ToolResultOutput) -> JsonValue {
         v2t::ToolResultOutput::Json { value } => json!(value),
         v2t::ToolResultOutput::ErrorText { value } => json!(value),
         v2t::ToolResultOutput::ErrorJson { value } => json!(value),
-        v2t::ToolResultOutput::Content { value } => json!(value),
+        v2t::ToolResultOutput::Content { value } => {
+            JsonValue::Array(value.iter().map(build_tool_result_content_item).collect())
+        }
+    }
+}
+
+fn build_tool_result_content_item(item: &v2t::ToolResultInlineContent) -> JsonValue {
+    match item {
+        v2t::ToolResultInlineContent::Text { text } => json!({"type": "text", "text": text}),
+        v2t::ToolResultInlineContent::Media { data, media_type }
+            if media_type.starts_with("image/") =>
+        {
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": media_type, "data": data},
+            })
+        }
+        v2t::ToolResultInlineContent::Media { data, media_type }
+            if media_type == "application/pdf" =>
+        {
+            json!({
+                "type": "document",
+                "source": {"type": "base64", "media_type": media_type, "data": data},
+            })
+        }
+        other => json!(other),
     }
 }
 
@@ -924,7 +1108,7 @@ fn build_prompt_payload(
     for block in group_prompt_blocks(prompt) {
         match block {
             PromptBlock::System(block_messages) => {
-                let entries = build_system_entries(&block_messages);
+                let entries = build_system_entries(&block_messages, warnings);
                 if !entries.is_empty() {
                     match &mut system {
                         Some(existing) => existing.extend(entries),
@@ -940,8 +1124,11 @@ fn build_prompt_payload(
                 }
             }
             PromptBlock::Assistant(block_messages) => {
-                let content =
-                    build_assistant_block_content(&block_messages, &mut missing_thinking_reasoning);
+                let content = build_assistant_block_content(
+                    &block_messages,
+                    &mut missing_thinking_reasoning,
+                    warnings,
+                );
                 if !content.is_empty() {
                     messages.push(json!({"role":"assistant","content": content}));
                 }
@@ -1223,11 +1410,15 @@ fn build_anthropic_tools(
     for tool in &options.tools {
         match tool {
             v2t::Tool::Function(tool) => {
-                tools.push(json!({
+                let mut value = json!({
                     "name": tool.name,
                     "description": tool.description,
                     "input_schema": tool.input_schema
-                }));
+                });
+                if let Some(cache_control) = get_cache_control(&tool.provider_options, warnings) {
+                    value["cache_control"] = cache_control;
+                }
+                tools.push(value);
             }
             v2t::Tool::Provider(tool) => {
                 if let Some(value) = build_anthropic_provider_tool(tool, betas) {
@@ -1416,6 +1607,28 @@ fn maybe_warn_missing_reasoning_signature(
     });
 }
 
+/// Capabilities of a Claude model family. Extended thinking arrived with
+/// Claude 3.7; every Claude 3+ model takes images and documents with
+/// citations. The output limit is resolved when the model is built.
+pub(crate) fn model_capabilities(model_id: &str) -> ModelCapabilities {
+    let claude = model_id.starts_with("claude-")
+        && !model_id.starts_with("claude-2")
+        && !model_id.starts_with("claude-instant");
+    let claude_3 = model_id.starts_with("claude-3-");
+    ModelCapabilities {
+        vision: claude,
+        audio_in: false,
+        audio_out: false,
+        tools: true,
+        reasoning: claude && (!claude_3 || model_id.starts_with("claude-3-7")),
+        structured_output: true,
+        streaming: true,
+        citations: claude,
+        parallel_tool_calls: claude,
+        max_output_tokens: None,
+    }
+}
+
 fn apply_thinking_settings(body: &mut JsonValue, thinking_cfg: Option<&ThinkingOption>) {
     match thinking_cfg {
         Some(ThinkingOption::Enabled { budget_tokens }) => {
@@ -1434,6 +1647,17 @@ fn apply_thinking_settings(body: &mut JsonValue, thinking_cfg: Option<&ThinkingO
     }
 }
 
+/// Request body fields `/messages/count_tokens` accepts.
+const COUNT_TOKENS_FIELDS: &[&str] = &[
+    "model",
+    "system",
+    "messages",
+    "tools",
+    "tool_choice",
+    "thinking",
+    "mcp_servers",
+];
+
 fn log_payload_summary(body: &JsonValue) {
     let messages_count = body
         .get("messages")
@@ -1472,7 +1696,12 @@ fn log_payload_summary(body: &JsonValue) {
 
 impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
     pub fn new(model_id: AnthropicMessagesModelId, cfg: AnthropicMessagesConfig<T>) -> Self {
-        Self { model_id, cfg }
+        let max_output_tokens = get_max_output_tokens("anthropic", &model_id);
+        Self {
+            model_id,
+            cfg,
+            max_output_tokens,
+        }
     }
 
     fn build_request_url(&self, streaming: bool) -> String {
@@ -1481,12 +1710,99 @@ impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
         format!("{}{}", base, path)
     }
 
+    /// Configured headers without internal SDK ones, with `accept`, a JSON
+    /// content type and the configured plus request-specific betas merged
+    /// into one `anthropic-beta` header.
+    /// Configured and call headers merged per the transport's header policy.
+    /// `anthropic-beta` values from every layer are combined with `betas`
+    /// into one deduplicated list; `accept` is always `accept`.
+    fn request_headers(
+        &self,
+        betas: HashSet<String>,
+        accept: &str,
+        extra: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>, SdkError> {
+        let mut headers = HeaderMerger::for_transport(&self.cfg.transport_cfg);
+        headers.insert_default("content-type", "application/json");
+
+        let mut beta_values: Vec<String> = Vec::new();
+        let mut beta_seen: HashSet<String> = HashSet::new();
+        for (k, v) in self.cfg.headers.iter().map(|(k, v)| (k, v)).chain(extra) {
+            if options::is_internal_sdk_header(k) {
+                continue;
+            }
+            if k.eq_ignore_ascii_case("anthropic-beta") {
+                for token in v.split(',') {
+                    let trimmed = token.trim();
+                    if !trimmed.is_empty() && beta_seen.insert(trimmed.to_string()) {
+                        beta_values.push(trimmed.to_string());
+                    }
+                }
+                continue;
+            }
+            headers.insert(k, v.clone())?;
+        }
+
+        for beta in betas.into_iter() {
+            if beta.is_empty() {
+                continue;
+            }
+            if beta_seen.insert(beta.clone()) {
+                beta_values.push(beta);
+            }
+        }
+
+        headers.force("accept", accept);
+        if !beta_values.is_empty() {
+            headers.force("anthropic-beta", beta_values.join(","));
+        }
+        Ok(headers.into_pairs())
+    }
+
+    /// Caller's `max_output_tokens`, else the configured default with a
+    /// warning. Thinking overrides `max_tokens` later, so it is not warned on.
+    fn max_tokens(
+        &self,
+        options: &v2t::CallOptions,
+        thinking_cfg: Option<&ThinkingOption>,
+        warnings: &mut Vec<v2t::CallWarning>,
+    ) -> u64 {
+        if let Some(max_tokens) = options.max_output_tokens {
+            return u64::from(max_tokens);
+        }
+        let max_tokens = self.cfg.default_max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        if !matches!(thinking_cfg, Some(ThinkingOption::Enabled { .. })) {
+            warnings.push(v2t::CallWarning::Other {
+                message: format!(
+                    "maxOutputTokens is required by Anthropic; defaulting max_tokens to {max_tokens}"
+                ),
+            });
+        }
+        max_tokens
+    }
+
     fn build_request_body(
         &self,
         options: &v2t::CallOptions,
     ) -> Result<BuiltAnthropicRequest, SdkError> {
         let mut warnings = Vec::new();
+        let limited = apply_prompt_message_limit(options, &mut warnings)?;
+        let options = limited.as_ref();
+        let tooled = apply_unsupported_tools_policy(
+            options,
+            model_capabilities(&self.model_id).tools,
+            &mut warnings,
+        )?;
+        let options = tooled.as_ref();
+        check_tool_limits(options, v2t::ToolLimits::default(), &mut warnings)?;
+        validate_tool_choice(options)?;
+        let (placed, _) =
+            apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+        let options = placed.as_ref();
         collect_unsupported_option_warnings(options, &mut warnings);
+        let audioless = drop_audio_input(options, "Anthropic", &mut warnings);
+        let options = audioless.as_ref();
+        validate_pdf_parts(&options.prompt, &self.cfg.supported_urls)?;
 
         let json_response_tool = build_json_response_tool(options, &mut warnings);
         let provider_opts = parse_anthropic_provider_options(
@@ -1521,11 +1837,18 @@ impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
             &mut warnings,
             &mut betas,
         );
+        let tool_streaming = provider_opts
+            .as_ref()
+            .and_then(|opts| opts.tool_streaming)
+            .unwrap_or(false);
+        if tool_streaming && !tools.is_empty() {
+            betas.insert(FINE_GRAINED_TOOL_STREAMING_BETA.into());
+        }
 
         let mut body = json!({
             "model": self.model_id,
             "messages": messages,
-            "max_tokens": options.max_output_tokens.unwrap_or(1024),
+            "max_tokens": self.max_tokens(options, thinking_cfg, &mut warnings),
         });
         if let Some(system) = system {
             body["system"] = JsonValue::Array(system);
@@ -1538,6 +1861,11 @@ impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
             provider_opts.as_ref(),
             json_response_tool.is_some(),
         );
+        let cache_breakpoint_overflow = provider_opts
+            .as_ref()
+            .and_then(|opts| opts.cache_breakpoint_overflow)
+            .unwrap_or_default();
+        limit_cache_breakpoints(&mut body, cache_breakpoint_overflow, &mut warnings);
         apply_sampling_settings(&mut body, options);
         maybe_warn_missing_reasoning_signature(
             options,
@@ -1548,6 +1876,7 @@ impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
             &mut warnings,
         );
         apply_thinking_settings(&mut body, thinking_cfg);
+        apply_raw_passthrough(options, &mut body, &mut warnings);
         log_payload_summary(&body);
 
         Ok(BuiltAnthropicRequest {
@@ -1557,6 +1886,48 @@ impl<T: HttpTransport> AnthropicMessagesLanguageModel<T> {
             uses_json_response_tool: json_response_tool.is_some(),
         })
     }
+
+    /// Input tokens a request for `options` would use, counted by
+    /// `/messages/count_tokens`. The counted payload is the request body
+    /// reduced to the fields that endpoint accepts (model, system, messages,
+    /// tools, tool choice and thinking), so sampling settings and
+    /// `max_tokens` are left out.
+    pub async fn count_tokens(&self, options: &v2t::CallOptions) -> Result<u64, SdkError> {
+        let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
+            options.clone(),
+            &self.cfg.provider_scope_name,
+            self.cfg.default_options.as_ref(),
+        );
+        let BuiltAnthropicRequest { body, betas, .. } = self.build_request_body(&options)?;
+        let body: serde_json::Map<String, JsonValue> = COUNT_TOKENS_FIELDS
+            .iter()
+            .filter_map(|&field| body.get(field).map(|value| (field.into(), value.clone())))
+            .collect();
+        let body = JsonValue::Object(body);
+        let url = format!(
+            "{}/messages/count_tokens",
+            self.cfg.base_url.trim_end_matches('/')
+        );
+        let headers = self.request_headers(betas, "application/json", &options.headers)?;
+        let (json, _) = self
+            .cfg
+            .http
+            .post_json(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
+            .await
+            .map_err(map_transport_error_to_sdk_error)?;
+        json.get("input_tokens")
+            .and_then(JsonValue::as_u64)
+            .ok_or_else(|| SdkError::Upstream {
+                status: 200,
+                message: "count_tokens response has no input_tokens".into(),
+                source: None,
+            })
+    }
 }
 
 impl AnthropicMessagesLanguageModel<crate::reqwest_transport::ReqwestTransport> {
@@ -1578,13 +1949,21 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
     fn supported_urls(&self) -> HashMap<String, Vec<String>> {
         self.cfg.supported_urls.clone()
     }
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            max_output_tokens: self.max_output_tokens,
+            ..model_capabilities(&self.model_id)
+        }
+    }
 
     async fn do_generate(
         &self,
         options: v2t::CallOptions,
     ) -> Result<crate::ai_sdk_core::GenerateResponse, SdkError> {
+        let mut options = options;
+        let stop_warning = disable_stop_on_first_tool_call(&mut options);
         let stream_resp = self.do_stream(options).await?;
-        collect_stream_to_response(
+        let mut response = collect_stream_to_response(
             stream_resp,
             StreamCollectorConfig {
                 allow_reasoning: true,
@@ -1596,7 +1975,9 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
                 fail_on_error: true,
             },
         )
-        .await
+        .await?;
+        response.warnings.extend(stop_warning);
+        Ok(response)
     }
 
     async fn do_stream(
@@ -1615,57 +1996,7 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
             uses_json_response_tool: uses_json_tool,
         } = self.build_request_body(&options)?;
         let url = self.build_request_url(true);
-        let mut headers: Vec<(String, String)> = self
-            .cfg
-            .headers
-            .iter()
-            .filter(|(k, _)| !options::is_internal_sdk_header(k))
-            .cloned()
-            .collect();
-
-        let mut beta_values: Vec<String> = Vec::new();
-        let mut beta_seen: HashSet<String> = HashSet::new();
-        headers.retain(|(k, v)| {
-            if k.eq_ignore_ascii_case("anthropic-beta") {
-                for token in v.split(',') {
-                    let trimmed = token.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    let value = trimmed.to_string();
-                    if beta_seen.insert(value.clone()) {
-                        beta_values.push(value);
-                    }
-                }
-                false
-            } else {
-                true
-            }
-        });
-
-        for beta in betas.into_iter() {
-            if beta.is_empty() {
-                continue;
-            }
-            if beta_seen.insert(beta.clone()) {
-                beta_values.push(beta);
-            }
-        }
-
-        headers.retain(|(k, _)| !k.eq_ignore_ascii_case("accept"));
-        headers.push(("accept".into(), "text/event-stream".into()));
-
-        // Always set content-type
-        if !headers
-            .iter()
-            .any(|(k, _)| k.eq_ignore_ascii_case("content-type"))
-        {
-            headers.push(("content-type".into(), "application/json".into()));
-        }
-
-        if !beta_values.is_empty() {
-            headers.push(("anthropic-beta".into(), beta_values.join(",")));
-        }
+        let headers = self.request_headers(betas, "text/event-stream", &options.headers)?;
 
         // Ensure Anthropic returns SSE by setting stream: true in the request body
         let mut body = body;
@@ -1687,7 +2018,12 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
         let resp = match self
             .cfg
             .http
-            .post_json_stream(&url, &headers, &body, &self.cfg.transport_cfg)
+            .post_json_stream(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
             .await
         {
             Ok(r) => r,
@@ -1706,17 +2042,37 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
         tracing::info!("{}: SSE stream acquired; decoding events", TRACE_PREFIX);
 
         // Decode SSE and convert to provider-agnostic events, then map to v2 parts
-        let events = sse_to_events::<_, AnthropicChunk, SdkError>(bytes_stream.map(|chunk_res| {
-            chunk_res.map_err(|e| match e {
-                crate::ai_sdk_core::error::TransportError::IdleReadTimeout(_) => SdkError::Timeout,
-                crate::ai_sdk_core::error::TransportError::ConnectTimeout(_) => SdkError::Timeout,
-                other => SdkError::Transport(other),
-            })
-        }));
+        let events = PipelineBuilder::<AnthropicChunk>::new()
+            .include_raw(options.emit_raw_chunks())
+            .build(bytes_stream.map(|chunk_res| {
+                chunk_res.map_err(|e| match e {
+                    crate::ai_sdk_core::error::TransportError::IdleReadTimeout(_) => {
+                        SdkError::Timeout
+                    }
+                    crate::ai_sdk_core::error::TransportError::ConnectTimeout(_) => {
+                        SdkError::Timeout
+                    }
+                    other => SdkError::Transport(other),
+                })
+            }));
 
         let mut hooks = EventMapperHooks::default();
         hooks.data = Some(Box::new(
-            |_state: &mut crate::ai_sdk_core::EventMapperState<()>, key, value| {
+            |state: &mut crate::ai_sdk_core::EventMapperState<()>, key, value| {
+                if key == "reasoning_redacted" {
+                    let id = value
+                        .get("id")
+                        .and_then(|s| s.as_str())
+                        .unwrap_or("0")
+                        .to_string();
+                    let metadata = value
+                        .get("data")
+                        .and_then(|s| s.as_str())
+                        .map(redacted_reasoning_metadata);
+                    let mut parts = state.open_reasoning(id, metadata.clone());
+                    parts.extend(state.close_reasoning(metadata));
+                    return Some(parts);
+                }
                 if key == "reasoning_signature" {
                     if let Some(sig) = value.get("signature").and_then(|s| s.as_str()) {
                         return Some(vec![v2t::StreamPart::ReasoningSignature {
@@ -1725,6 +2081,17 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
                         }]);
                     }
                 }
+                if key == "response_metadata" {
+                    let meta = v2t::ResponseMetadata {
+                        id: value.get("id").and_then(|s| s.as_str()).map(str::to_string),
+                        timestamp_ms: None,
+                        model_id: value
+                            .get("model")
+                            .and_then(|s| s.as_str())
+                            .map(str::to_string),
+                    };
+                    return Some(vec![v2t::StreamPart::ResponseMetadata { meta }]);
+                }
                 None
             },
         ));
@@ -1742,6 +2109,7 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
                 finish_reason_fallback: v2t::FinishReason::Unknown,
                 initial_extra: (),
                 hooks,
+                stop_on_first_tool_call: options.stop_on_first_tool_call,
             },
         );
 
@@ -1749,7 +2117,12 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for AnthropicMessagesLanguage
             stream: parts,
             request_body: Some(body),
             response_headers: None,
-        })
+            raw_response: None,
+        }
+        .with_dedupe_sources(options.dedupe_sources)
+        .with_raw_capture(RawCaptureConfig::from_options(&options))
+        .with_usage_estimate(UsageEstimate::from_options(&options))
+        .with_abort(options.abort.as_ref()))
     }
 }
 
@@ -1796,6 +2169,9 @@ fn summarize_anthropic_request(body: &JsonValue) -> AnthropicRequestSummary {
 struct AnthropicChunk {
     tool_calls: HashMap<usize, AnthropicToolCallState>,
     pending_deltas: HashMap<usize, Vec<String>>,
+    /// Usage seen so far: `message_delta` may only carry `output_tokens`, so
+    /// the cache counts from `message_start` are merged rather than replaced.
+    usage: serde_json::Map<String, JsonValue>,
 }
 
 struct AnthropicToolCallState {
@@ -1833,18 +2209,41 @@ impl AnthropicChunk {
         out
     }
 
+    fn push_usage(&mut self, out: &mut Vec<ProviderEvent>, usage: &JsonValue) {
+        if let Some(fields) = usage.as_object() {
+            for (key, value) in fields {
+                if !value.is_null() {
+                    self.usage.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        push_anthropic_usage(out, &JsonValue::Object(self.usage.clone()));
+    }
+
     fn parse_anthropic_frame(&mut self, v: &JsonValue) -> Vec<ProviderEvent> {
         let mut out = Vec::new();
         if let Some(t) = v.get("type").and_then(|s| s.as_str()) {
             match t {
                 "message_start" => {
+                    if let Some(message) = v
+                        .get("message")
+                        .filter(|m| m.get("id").is_some() || m.get("model").is_some())
+                    {
+                        out.push(ProviderEvent::Data {
+                            key: "response_metadata".to_string(),
+                            value: json!({
+                                "id": message.get("id"),
+                                "model": message.get("model"),
+                            }),
+                        });
+                    }
                     if let Some(usage) = v.get("message").and_then(|m| m.get("usage")) {
-                        push_anthropic_usage(&mut out, usage);
+                        self.push_usage(&mut out, usage);
                     }
                 }
                 "message_delta" => {
                     if let Some(usage) = v.get("usage") {
-                        push_anthropic_usage(&mut out, usage);
+                        self.push_usage(&mut out, usage);
                     }
                 }
                 "content_block_delta" => self.push_content_block_delta(v, &mut out),
@@ -1929,12 +2328,24 @@ impl AnthropicChunk {
 
         match content_block.get("type").and_then(|s| s.as_str()) {
             Some("tool_use") => self.push_tool_call_start(idx, content_block, out),
-            Some("thinking") | Some("redacted_thinking") => {
+            Some("thinking") => {
                 let id = idx
                     .map(|i| i.to_string())
                     .unwrap_or_else(|| "0".to_string());
                 out.push(ProviderEvent::ReasoningStart { id });
             }
+            // Redacted blocks arrive whole in their start event and carry no
+            // visible text; surface them as an empty reasoning block holding
+            // the opaque data so callers can show a marker and replay it.
+            Some("redacted_thinking") => {
+                let id = idx
+                    .map(|i| i.to_string())
+                    .unwrap_or_else(|| "0".to_string());
+                out.push(ProviderEvent::Data {
+                    key: "reasoning_redacted".to_string(),
+                    value: json!({"id": id, "data": content_block.get("data")}),
+                });
+            }
             _ => {}
         }
     }
@@ -1973,6 +2384,15 @@ impl AnthropicChunk {
     }
 }
 
+/// Provider metadata carrying a redacted thinking block's opaque data, in the
+/// shape the prompt converter reads back as `redacted_thinking`.
+fn redacted_reasoning_metadata(data: &str) -> v2t::ProviderMetadata {
+    HashMap::from([(
+        "anthropic".to_string(),
+        HashMap::from([("redactedData".to_string(), JsonValue::String(data.into()))]),
+    )])
+}
+
 fn push_anthropic_usage(out: &mut Vec<ProviderEvent>, usage: &JsonValue) {
     use crate::ai_sdk_types::TokenUsage;
     let norm = crate::ai_sdk_types::usage::normalize_anthropic(usage);
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
-use crate::types::v2::ProviderOptions;
+use crate::types::v2::{CallWarning, ProviderOptions};
 
 pub type AnthropicMessagesModelId = String;
 
@@ -48,6 +48,40 @@ pub struct AnthropicProviderOptions {
         skip_serializing_if = "Option::is_none"
     )]
     pub disable_parallel_tool_use: Option<bool>,
+    /// Enables the fine-grained tool streaming beta so tool input JSON is
+    /// streamed as it is generated instead of being buffered per key.
+    #[serde(
+        default,
+        rename = "toolStreaming",
+        alias = "tool_streaming",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tool_streaming: Option<bool>,
+    /// Which `cache_control` breakpoints survive when a request carries more
+    /// than Anthropic accepts. Defaults to keeping the first ones.
+    #[serde(
+        default,
+        rename = "cacheBreakpointOverflow",
+        alias = "cache_breakpoint_overflow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cache_breakpoint_overflow: Option<CacheBreakpointOverflow>,
+}
+
+/// Breakpoints kept when a request exceeds Anthropic's breakpoint limit,
+/// counted in prompt order (tools, system, then messages).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheBreakpointOverflow {
+    /// Keep the earliest breakpoints. Tool definitions and the system prompt
+    /// rarely change, so their cached prefixes are reused across
+    /// conversations, while late message breakpoints only pay off if the next
+    /// request repeats the conversation up to them.
+    #[default]
+    KeepFirst,
+    /// Keep the latest breakpoints, for long conversations where caching the
+    /// growing message history matters more than the shared prefix.
+    KeepLast,
 }
 
 fn provider_scope_from_options<'a>(
@@ -79,3 +113,52 @@ pub fn parse_anthropic_file_part_options(
     let v = JsonValue::Object(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect());
     serde_json::from_value::<AnthropicFilePartProviderOptions>(v).ok()
 }
+
+/// Cache breakpoint for a system prompt, message part or tool definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AnthropicCacheControl {
+    #[serde(rename = "type", default)]
+    pub r#type: CacheControlType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<CacheTtl>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheControlType {
+    #[default]
+    Ephemeral,
+}
+
+/// How long a cache entry lives; Anthropic defaults to five minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CacheTtl {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+/// Extract a `cacheControl` (or `cache_control`) breakpoint from message,
+/// part or tool provider options. Malformed values are dropped with a
+/// warning.
+pub fn parse_anthropic_cache_control(
+    opts: &Option<ProviderOptions>,
+    provider_scope_name: &str,
+    warnings: &mut Vec<CallWarning>,
+) -> Option<AnthropicCacheControl> {
+    let map = provider_scope_from_options(opts.as_ref()?, provider_scope_name)?;
+    let value = map
+        .get("cacheControl")
+        .or_else(|| map.get("cache_control"))?;
+    match serde_json::from_value::<AnthropicCacheControl>(value.clone()) {
+        Ok(cache_control) => Some(cache_control),
+        Err(err) => {
+            warnings.push(CallWarning::UnsupportedSetting {
+                setting: "cacheControl".into(),
+                details: Some(format!("ignored malformed cache control {value}: {err}")),
+            });
+            None
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::ai_sdk_core::headers::HeaderMerger;
 use crate::ai_sdk_core::options as sdkopt;
 use crate::ai_sdk_core::request_builder::defaults::provider_defaults_from_json;
 use crate::ai_sdk_core::transport::TransportConfig;
@@ -102,6 +103,7 @@ pub struct AnthropicMessagesBuilder {
     bearer: Option<String>,
     headers: Vec<(String, String)>,
     default_options: Option<v2t::ProviderOptions>,
+    default_max_tokens: Option<u64>,
     transport_cfg: TransportConfig,
 }
 
@@ -115,6 +117,7 @@ impl AnthropicMessagesBuilder {
             bearer: None,
             headers: Vec::new(),
             default_options: None,
+            default_max_tokens: None,
             transport_cfg: {
                 let mut cfg = TransportConfig::default();
                 cfg.idle_read_timeout = DEFAULT_IDLE_READ_TIMEOUT;
@@ -171,6 +174,12 @@ impl AnthropicMessagesBuilder {
         self
     }
 
+    /// `max_tokens` sent when a call leaves `max_output_tokens` unset.
+    pub fn with_default_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.default_max_tokens = Some(max_tokens);
+        self
+    }
+
     pub fn with_transport_config(mut self, transport_cfg: TransportConfig) -> Self {
         self.transport_cfg = transport_cfg;
         self
@@ -180,26 +189,27 @@ impl AnthropicMessagesBuilder {
         self,
     ) -> Result<AnthropicMessagesLanguageModel<crate::reqwest_transport::ReqwestTransport>, SdkError>
     {
-        let mut header_map: BTreeMap<String, (String, String)> = BTreeMap::new();
+        let mut merger = HeaderMerger::for_transport(&self.transport_cfg);
         for (key, value) in default_headers_from_creds(self.api_key, self.bearer) {
-            header_map.insert(key.to_ascii_lowercase(), (key, value));
+            merger.insert_default(&key, value);
         }
         for (key, value) in self.headers {
-            header_map.insert(key.to_ascii_lowercase(), (key, value));
-        }
-
-        let mut headers = Vec::new();
-        for (_lc, (orig, value)) in header_map.into_iter() {
-            if sdkopt::is_internal_sdk_header(&orig) {
+            if sdkopt::is_internal_sdk_header(&key) {
                 continue;
             }
-            headers.push((orig, value));
+            merger.insert(&key, value)?;
         }
+        let headers = merger.into_pairs();
 
         let http = crate::reqwest_transport::ReqwestTransport::try_new(&self.transport_cfg)
             .map_err(SdkError::Transport)?;
-        let supported_urls =
-            HashMap::from([("image/*".to_string(), vec![r"^https?://.*$".to_string()])]);
+        let supported_urls = HashMap::from([
+            ("image/*".to_string(), vec![r"^https?://.*$".to_string()]),
+            (
+                "application/pdf".to_string(),
+                vec![r"^https?://.*$".to_string()],
+            ),
+        ]);
 
         Ok(AnthropicMessagesLanguageModel::new(
             self.model_id,
@@ -212,6 +222,7 @@ impl AnthropicMessagesBuilder {
                 transport_cfg: self.transport_cfg,
                 supported_urls,
                 default_options: self.default_options,
+                default_max_tokens: self.default_max_tokens,
             },
         ))
     }
@@ -237,20 +248,17 @@ fn build_anthropic(
         def.base_url.clone()
     };
 
-    let mut header_map: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let transport_cfg = build_provider_transport_config(def, Some(DEFAULT_IDLE_READ_TIMEOUT));
+    let mut merger = HeaderMerger::for_transport(&transport_cfg);
     for (k, v) in default_headers_from_creds(api_key.clone(), bearer.clone()) {
-        header_map.insert(k.to_ascii_lowercase(), (k, v));
-    }
-    for (k, v) in &def.headers {
-        header_map.insert(k.to_ascii_lowercase(), (k.clone(), v.clone()));
+        merger.insert_default(&k, v);
     }
 
     let mut default_options: Option<v2t::ProviderOptions> = None;
-    let mut headers: Vec<(String, String)> = Vec::new();
-    for (_lc, (orig, value)) in header_map.into_iter() {
-        if sdkopt::is_internal_sdk_header(&orig) {
+    for (k, v) in &def.headers {
+        if sdkopt::is_internal_sdk_header(k) {
             if default_options.is_none() {
-                if let Ok(json) = serde_json::from_str::<JsonValue>(&value) {
+                if let Ok(json) = serde_json::from_str::<JsonValue>(v) {
                     if let Some(parsed) = provider_defaults_from_json(&def.name, &json) {
                         default_options = Some(parsed);
                     }
@@ -258,10 +266,10 @@ fn build_anthropic(
             }
             continue;
         }
-        headers.push((orig, value));
+        merger.insert(k, v.clone())?;
     }
+    let headers = merger.into_pairs();
 
-    let transport_cfg = build_provider_transport_config(def, Some(DEFAULT_IDLE_READ_TIMEOUT));
     let mut builder = AnthropicMessagesBuilder::new(model)
         .with_provider_scope_name(def.name.clone())
         .with_base_url(base_url)
@@ -270,6 +278,14 @@ fn build_anthropic(
     if let Some(default_options) = default_options {
         builder = builder.with_default_options(default_options);
     }
+    if let Some(max_tokens) = def
+        .models
+        .get(model)
+        .and_then(|info| info.limits.as_ref())
+        .and_then(|limits| limits.get_output())
+    {
+        builder = builder.with_default_max_tokens(max_tokens);
+    }
     if let Some(bearer) = bearer {
         builder = builder.with_bearer(bearer);
     } else if let Some(api_key) = api_key {
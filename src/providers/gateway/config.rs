@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::core::transport::TransportConfig;
+use crate::core::error::TransportError;
+use crate::core::transport::{HttpMethod, TransportConfig};
 use crate::types::v2::ProviderOptions;
 
 #[derive(Clone, Debug)]
@@ -16,9 +17,18 @@ pub struct GatewayConfig {
     pub default_options: Option<ProviderOptions>,
     pub request_defaults: Option<serde_json::Value>,
     pub auth: Option<GatewayAuth>,
+    /// HTTP method for language model calls; `None` sends `POST`.
+    pub http_method: Option<String>,
 }
 
 impl GatewayConfig {
+    /// Parsed [`http_method`](Self::http_method); unknown verbs are an error.
+    pub fn http_method(&self) -> Result<HttpMethod, TransportError> {
+        self.http_method
+            .as_deref()
+            .map_or(Ok(HttpMethod::Post), str::parse)
+    }
+
     pub fn language_endpoint(&self) -> String {
         match self.endpoint_path.as_deref() {
             Some(path) if !path.is_empty() => {
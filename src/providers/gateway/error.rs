@@ -1,4 +1,6 @@
-use crate::core::error::{display_body_for_error, SdkError, TransportError};
+use crate::core::error::{
+    display_body_for_error, rate_limited_or_quota_error, SdkError, TransportError,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -46,10 +48,7 @@ pub fn map_transport_error(te: TransportError) -> SdkError {
             match *status {
                 401 | 403 => SdkError::Unauthorized,
                 408 => SdkError::Timeout,
-                425 | 429 => SdkError::RateLimited {
-                    retry_after_ms: *retry_after_ms,
-                    source: Some(Box::new(te)),
-                },
+                425 | 429 => rate_limited_or_quota_error(*retry_after_ms, te),
                 _ => SdkError::Upstream {
                     status: *status,
                     message,
@@ -1,13 +1,24 @@
-use std::collections::{BTreeMap, HashMap};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::headers::HeaderMerger;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
 use crate::ai_sdk_core::request_builder::defaults::{
     build_call_options, request_overrides_from_json,
 };
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::ai_sdk_core::request_builder::tool_limits::check_tool_limits;
+use crate::ai_sdk_core::retry::Retryable;
+use crate::ai_sdk_core::stop_on_tool_call::disable_stop_on_first_tool_call;
 use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
 use crate::ai_sdk_core::{
     GenerateResponse, LanguageModel, PartStream, SdkError, StreamNormalizationState, StreamResponse,
 };
 use crate::ai_sdk_streaming_sse::SseDecoder;
+use crate::ai_sdk_types::usage::token_count;
 use crate::ai_sdk_types::v2 as v2t;
 use async_stream::try_stream;
 use async_trait::async_trait;
@@ -30,6 +41,9 @@ pub struct GatewayLanguageModel<T: HttpTransport = crate::reqwest_transport::Req
     pub model_id: String,
     pub config: GatewayConfig,
     pub http: T,
+    /// Models tried in order when the previous one fails with a retryable
+    /// error (429, 5xx, timeouts); see [`with_fallbacks`](Self::with_fallbacks).
+    pub fallback_models: Vec<String>,
 }
 
 impl Default for GatewayLanguageModel<crate::reqwest_transport::ReqwestTransport> {
@@ -49,8 +63,10 @@ impl Default for GatewayLanguageModel<crate::reqwest_transport::ReqwestTransport
                 default_options: None,
                 request_defaults: None,
                 auth: None,
+                http_method: None,
             },
             http: crate::reqwest_transport::ReqwestTransport::new(&transport_cfg),
+            fallback_models: Vec::new(),
         }
     }
 }
@@ -61,9 +77,34 @@ impl<T: HttpTransport> GatewayLanguageModel<T> {
             model_id: model_id.into(),
             config,
             http,
+            fallback_models: Vec::new(),
         }
     }
 
+    /// Fall back to `models`, in order, when the primary model fails with a
+    /// retryable error. Other errors are returned without trying them. The
+    /// model that answered is recorded as `gateway.resolvedModel` in the
+    /// provider metadata.
+    pub fn with_fallbacks(mut self, models: Vec<String>) -> Self {
+        self.fallback_models = models;
+        self
+    }
+
+    fn candidate_models(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.model_id.as_str())
+            .chain(self.fallback_models.iter().map(String::as_str))
+    }
+
+    /// Whether to give up after candidate `attempt` (0-based) failed with
+    /// `err` rather than try the next one.
+    fn is_final_attempt(&self, attempt: usize, model: &str, err: &SdkError) -> bool {
+        if attempt == self.fallback_models.len() || !err.is_retryable() {
+            return true;
+        }
+        tracing::info!("gateway: model {model} failed ({err}); trying next fallback");
+        false
+    }
+
     fn transport_config(&self) -> &TransportConfig {
         &self.config.transport_cfg
     }
@@ -140,42 +181,68 @@ impl<T: HttpTransport> GatewayLanguageModel<T> {
 
     fn merge_headers(
         &self,
+        model_id: &str,
         call_headers: &HashMap<String, String>,
         streaming: bool,
-    ) -> Vec<(String, String)> {
-        let mut merged: BTreeMap<String, String> = BTreeMap::new();
-        merged.insert("content-type".into(), "application/json".into());
-        merged.insert("accept".into(), "application/json".into());
+    ) -> Result<Vec<(String, String)>, SdkError> {
+        let mut merged = HeaderMerger::for_transport(self.transport_config());
+        merged.insert_default("content-type", "application/json");
+        merged.insert_default("accept", "application/json");
         for (k, v) in &self.config.headers {
-            merged.insert(k.to_ascii_lowercase(), v.clone());
+            merged.insert(k, v.clone())?;
         }
-        merged.insert(SPEC_VERSION_HEADER.into(), "2".into());
-        merged.insert(MODEL_ID_HEADER.into(), self.model_id.clone());
-        merged.insert(STREAMING_HEADER.into(), streaming.to_string());
+        merged.force(SPEC_VERSION_HEADER, "2");
+        merged.force(MODEL_ID_HEADER, model_id.to_string());
+        merged.force(STREAMING_HEADER, streaming.to_string());
         if let Some(auth) = &self.config.auth {
             let mut token = auth.token.clone();
             if !token.to_ascii_lowercase().starts_with("bearer ") {
                 token = format!("Bearer {}", token);
             }
-            merged.insert("authorization".into(), token);
-            merged.insert(
-                "ai-gateway-auth-method".into(),
+            merged.force("authorization", token);
+            merged.force(
+                "ai-gateway-auth-method",
                 auth.method.as_header_value().to_string(),
             );
         }
         for (k, v) in Self::collect_o11y_headers() {
-            merged.insert(k.to_ascii_lowercase(), v);
+            merged.force(&k, v);
         }
         for (k, v) in call_headers {
             if v.trim().is_empty() {
                 continue;
             }
-            merged.insert(k.to_ascii_lowercase(), v.clone());
+            merged.insert(k, v.clone())?;
         }
-        merged
-            .into_iter()
-            .map(|(k, v)| (Self::canonicalize_header(&k), v))
-            .collect()
+        Ok(merged.into_pairs_with(Self::canonicalize_header))
+    }
+
+    /// Applies the prompt message limit, tool limits and system prompt mode
+    /// locally; the gateway API has no equivalent settings, so those fields
+    /// (and the client-side raw capture and early-stop settings) are not
+    /// forwarded.
+    fn apply_prompt_limit(
+        options: &mut v2t::CallOptions,
+    ) -> Result<Vec<v2t::CallWarning>, SdkError> {
+        let mut warnings = Vec::new();
+        if let Cow::Owned(limited) = apply_prompt_message_limit(options, &mut warnings)? {
+            *options = limited;
+        }
+        check_tool_limits(options, v2t::ToolLimits::default(), &mut warnings)?;
+        if let (Cow::Owned(placed), _) =
+            apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings)
+        {
+            *options = placed;
+        }
+        options.max_prompt_messages = None;
+        options.prompt_overflow = v2t::PromptOverflow::default();
+        options.system_prompt_mode = None;
+        options.capture_raw_response = false;
+        options.max_raw_response_bytes = None;
+        options.stop_on_first_tool_call = false;
+        options.tool_limits = None;
+        options.strict_tool_limits = false;
+        Ok(warnings)
     }
 
     fn encode_file_parts(options: &mut v2t::CallOptions) {
@@ -260,6 +327,10 @@ where
             &self.config.provider_scope_name,
             self.config.default_options.as_ref(),
         );
+        let stop_warning = disable_stop_on_first_tool_call(&mut options);
+        let mut local_warnings = Self::apply_prompt_limit(&mut options)?;
+        local_warnings.extend(stop_warning);
+        let usage_estimate = UsageEstimate::from_options(&options);
         Self::encode_file_parts(&mut options);
         let mut body = serde_json::to_value(&options)?;
         if let Some(defaults) = self.config.request_defaults.as_ref() {
@@ -273,48 +344,70 @@ where
             }
         }
 
-        let headers = self.merge_headers(&options.headers, false);
-        match self
-            .http
-            .post_json(
-                &self.endpoint_url(),
-                &headers,
-                &body,
-                self.transport_config(),
-            )
-            .await
-        {
-            Ok((response_body, response_headers)) => {
-                let content = content_from_value(response_body.get("content"))?;
-                let finish_reason = parse_finish_reason(
-                    response_body
-                        .get("finish_reason")
-                        .or_else(|| response_body.get("finishReason")),
-                );
-                let usage = parse_usage(response_body.get("usage"));
-                let provider_metadata = provider_metadata_from_value(
-                    response_body
-                        .get("provider_metadata")
-                        .or_else(|| response_body.get("providerMetadata")),
-                );
-                let warnings = response_body
+        let method = self.config.http_method().map_err(map_transport_error)?;
+        for (attempt, model) in self.candidate_models().enumerate() {
+            let headers = self.merge_headers(model, &options.headers, false)?;
+            let (response_body, response_headers) = match self
+                .http
+                .request_json(
+                    method,
+                    &self.endpoint_url(),
+                    &headers,
+                    Some(&body),
+                    &call_transport_config(self.transport_config(), &options),
+                )
+                .await
+            {
+                Ok(response) => response,
+                Err(err) => {
+                    let err = map_transport_error(err);
+                    if self.is_final_attempt(attempt, model, &err) {
+                        return Err(err);
+                    }
+                    continue;
+                }
+            };
+            let content = content_from_value(response_body.get("content"))?;
+            let finish_reason = parse_finish_reason(
+                response_body
+                    .get("finish_reason")
+                    .or_else(|| response_body.get("finishReason")),
+            );
+            let usage = parse_usage(response_body.get("usage"));
+            let mut provider_metadata = provider_metadata_from_value(
+                response_body
+                    .get("provider_metadata")
+                    .or_else(|| response_body.get("providerMetadata")),
+            );
+            if !self.fallback_models.is_empty() {
+                record_resolved_model(&mut provider_metadata, model);
+            }
+            let mut warnings = local_warnings;
+            warnings.extend(
+                response_body
                     .get("warnings")
                     .map(parse_call_warnings)
-                    .unwrap_or_default();
-
-                Ok(GenerateResponse {
-                    content,
-                    finish_reason,
-                    usage,
-                    provider_metadata,
-                    request_body: Some(body),
-                    response_headers: Some(Self::headers_vec_to_map(response_headers.clone())),
-                    response_body: Some(response_body.clone()),
-                    warnings,
-                })
+                    .unwrap_or_default(),
+            );
+            let response_metadata = response_body
+                .get("response_metadata")
+                .or_else(|| response_body.get("responseMetadata"))
+                .map(parse_response_metadata);
+
+            return Ok(GenerateResponse {
+                content,
+                finish_reason,
+                usage,
+                provider_metadata,
+                response_metadata,
+                request_body: Some(body),
+                response_headers: Some(Self::headers_vec_to_map(response_headers)),
+                response_body: Some(response_body),
+                warnings,
             }
-            Err(err) => Err(map_transport_error(err)),
+            .with_usage_estimate(usage_estimate));
         }
+        unreachable!("candidate_models always yields the primary model")
     }
 
     #[instrument(name = "gateway.do_stream", skip_all, fields(model = %self.model_id))]
@@ -324,7 +417,13 @@ where
             &self.config.provider_scope_name,
             self.config.default_options.as_ref(),
         );
-        let include_raw = options.include_raw_chunks;
+        let include_raw = options.emit_raw_chunks();
+        let raw_capture = RawCaptureConfig::from_options(&options);
+        let stop_on_first_tool_call = options.stop_on_first_tool_call;
+        let dedupe_sources = options.dedupe_sources;
+        options.include_raw_chunks = include_raw;
+        let local_warnings = Self::apply_prompt_limit(&mut options)?;
+        let usage_estimate = UsageEstimate::from_options(&options);
         Self::encode_file_parts(&mut options);
         let mut body = serde_json::to_value(&options)?;
         if let Some(defaults) = self.config.request_defaults.as_ref() {
@@ -337,39 +436,83 @@ where
                 );
             }
         }
-        let headers = self.merge_headers(&options.headers, true);
-        match self
-            .http
-            .post_json_stream(
-                &self.endpoint_url(),
-                &headers,
-                &body,
-                self.transport_config(),
-            )
-            .await
-        {
-            Ok(resp) => {
-                let (stream, response_headers) = T::into_stream(resp);
-                let mapped_stream = stream.map(|chunk| chunk.map_err(SdkError::from));
-                let part_stream = decode_gateway_stream(mapped_stream, include_raw);
-                Ok(StreamResponse {
-                    stream: part_stream,
-                    request_body: Some(body),
-                    response_headers: Some(Self::headers_vec_to_map(response_headers)),
-                })
+        let method = self.config.http_method().map_err(map_transport_error)?;
+        for (attempt, model) in self.candidate_models().enumerate() {
+            let headers = self.merge_headers(model, &options.headers, true)?;
+            let resp = match self
+                .http
+                .request_json_stream(
+                    method,
+                    &self.endpoint_url(),
+                    &headers,
+                    &body,
+                    &call_transport_config(self.transport_config(), &options),
+                )
+                .await
+            {
+                Ok(resp) => resp,
+                Err(err) => {
+                    let err = map_transport_error(err);
+                    if self.is_final_attempt(attempt, model, &err) {
+                        return Err(err);
+                    }
+                    continue;
+                }
+            };
+            let (stream, response_headers) = T::into_stream(resp);
+            let mapped_stream = stream.map(|chunk| chunk.map_err(SdkError::from));
+            let mut part_stream = decode_gateway_stream(mapped_stream, include_raw, local_warnings);
+            if !self.fallback_models.is_empty() {
+                let model = model.to_string();
+                part_stream = Box::pin(part_stream.map(move |mut part| {
+                    if let Ok(v2t::StreamPart::Finish {
+                        provider_metadata, ..
+                    }) = &mut part
+                    {
+                        record_resolved_model(provider_metadata, &model);
+                    }
+                    part
+                }));
             }
-            Err(err) => Err(map_transport_error(err)),
+            return Ok(StreamResponse {
+                stream: part_stream,
+                request_body: Some(body),
+                response_headers: Some(Self::headers_vec_to_map(response_headers)),
+                raw_response: None,
+            }
+            .with_stop_on_first_tool_call(stop_on_first_tool_call)
+            .with_dedupe_sources(dedupe_sources)
+            .with_raw_capture(raw_capture)
+            .with_usage_estimate(usage_estimate)
+            .with_abort(options.abort.as_ref()));
         }
+        unreachable!("candidate_models always yields the primary model")
     }
 }
 
-fn decode_gateway_stream<S>(bytes: S, include_raw: bool) -> PartStream
+/// Records the model that answered as `gateway.resolvedModel`.
+fn record_resolved_model(metadata: &mut Option<v2t::ProviderMetadata>, model: &str) {
+    metadata
+        .get_or_insert_with(HashMap::new)
+        .entry("gateway".into())
+        .or_default()
+        .insert("resolvedModel".into(), JsonValue::String(model.to_string()));
+}
+
+fn decode_gateway_stream<S>(
+    bytes: S,
+    include_raw: bool,
+    local_warnings: Vec<v2t::CallWarning>,
+) -> PartStream
 where
     S: Stream<Item = Result<Bytes, SdkError>> + Send + 'static,
 {
     Box::pin(try_stream! {
         let mut decoder = SseDecoder::new();
-        let mut state = GatewayStreamState::default();
+        let mut state = GatewayStreamState {
+            local_warnings,
+            ..GatewayStreamState::default()
+        };
         futures_util::pin_mut!(bytes);
 
         while let Some(chunk) = bytes.next().await {
@@ -409,6 +552,8 @@ fn decode_gateway_event_data(
 
 struct GatewayStreamState {
     stream_started: bool,
+    /// Client-side warnings prepended to the gateway's stream-start warnings.
+    local_warnings: Vec<v2t::CallWarning>,
     normalizer: StreamNormalizationState<()>,
     pending_tool_end_metadata: HashMap<String, Option<v2t::ProviderMetadata>>,
     text_counter: usize,
@@ -422,6 +567,7 @@ impl Default for GatewayStreamState {
     fn default() -> Self {
         Self {
             stream_started: false,
+            local_warnings: Vec::new(),
             normalizer: StreamNormalizationState::new(()),
             pending_tool_end_metadata: HashMap::new(),
             text_counter: 0,
@@ -436,7 +582,10 @@ impl Default for GatewayStreamState {
 impl GatewayStreamState {
     fn stream_start_parts(&mut self, value: &JsonValue) -> Vec<v2t::StreamPart> {
         self.stream_started = true;
-        let warnings = parse_call_warnings(value.get("warnings").unwrap_or(&JsonValue::Null));
+        let mut warnings = std::mem::take(&mut self.local_warnings);
+        warnings.extend(parse_call_warnings(
+            value.get("warnings").unwrap_or(&JsonValue::Null),
+        ));
         vec![v2t::StreamPart::StreamStart { warnings }]
     }
 
@@ -444,7 +593,7 @@ impl GatewayStreamState {
         if !self.stream_started {
             self.stream_started = true;
             parts.push(v2t::StreamPart::StreamStart {
-                warnings: Vec::new(),
+                warnings: std::mem::take(&mut self.local_warnings),
             });
         }
     }
@@ -676,6 +825,7 @@ impl GatewayStreamState {
             return vec![v2t::StreamPart::File {
                 media_type: media_type.to_string(),
                 data: data_str.to_string(),
+                provider_metadata: None,
             }];
         }
         if include_raw {
@@ -859,19 +1009,19 @@ fn parse_finish_reason(value: Option<&JsonValue>) -> v2t::FinishReason {
 fn parse_usage(value: Option<&JsonValue>) -> v2t::Usage {
     let mut usage = v2t::Usage::default();
     if let Some(JsonValue::Object(map)) = value {
-        if let Some(v) = map.get("prompt_tokens").and_then(|v| v.as_u64()) {
+        if let Some(v) = map.get("prompt_tokens").and_then(token_count) {
             usage.input_tokens = Some(v);
         }
-        if let Some(v) = map.get("completion_tokens").and_then(|v| v.as_u64()) {
+        if let Some(v) = map.get("completion_tokens").and_then(token_count) {
             usage.output_tokens = Some(v);
         }
-        if let Some(v) = map.get("total_tokens").and_then(|v| v.as_u64()) {
+        if let Some(v) = map.get("total_tokens").and_then(token_count) {
             usage.total_tokens = Some(v);
         }
-        if let Some(v) = map.get("reasoning_tokens").and_then(|v| v.as_u64()) {
+        if let Some(v) = map.get("reasoning_tokens").and_then(token_count) {
             usage.reasoning_tokens = Some(v);
         }
-        if let Some(v) = map.get("cached_input_tokens").and_then(|v| v.as_u64()) {
+        if let Some(v) = map.get("cached_input_tokens").and_then(token_count) {
             usage.cached_input_tokens = Some(v);
         }
     }
@@ -959,12 +1109,16 @@ fn content_from_value(value: Option<&JsonValue>) -> Result<Vec<v2t::Content>, Sd
 
 #[cfg(test)]
 mod tests {
-    use super::decode_gateway_stream;
-    use crate::ai_sdk_core::SdkError;
+    use super::{decode_gateway_stream, GatewayLanguageModel, MODEL_ID_HEADER};
+    use crate::ai_sdk_core::error::TransportError;
+    use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig, TransportStream};
+    use crate::ai_sdk_core::{LanguageModel, SdkError};
     use crate::ai_sdk_types::v2 as v2t;
     use bytes::Bytes;
     use futures_util::{stream, TryStreamExt};
     use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
 
     fn sse_chunk(value: serde_json::Value) -> Result<Bytes, SdkError> {
         Ok(Bytes::from(format!("data: {value}\n\n")))
@@ -1177,11 +1331,152 @@ mod tests {
     #[tokio::test]
     async fn decode_gateway_stream_normalizes_text_reasoning_tool_raw_and_finish() {
         let parts: Vec<v2t::StreamPart> =
-            decode_gateway_stream(stream::iter(gateway_stream_fixture()), true)
+            decode_gateway_stream(stream::iter(gateway_stream_fixture()), true, Vec::new())
                 .try_collect()
                 .await
                 .expect("gateway stream parts");
 
         assert_gateway_stream_parts(&parts);
     }
+
+    /// Fails every model listed in `failures` with its HTTP status and
+    /// records the model id header of each request.
+    struct FallbackTransport {
+        failures: HashMap<&'static str, u16>,
+        requested: Mutex<Vec<String>>,
+    }
+
+    impl FallbackTransport {
+        fn new(failures: &[(&'static str, u16)]) -> Self {
+            Self {
+                failures: failures.iter().copied().collect(),
+                requested: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn attempt(&self, headers: &[(String, String)]) -> Result<(), TransportError> {
+            let model = headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(MODEL_ID_HEADER))
+                .map(|(_, value)| value.clone())
+                .expect("model id header");
+            self.requested.lock().unwrap().push(model.clone());
+            match self.failures.get(model.as_str()) {
+                Some(&status) => Err(TransportError::HttpStatus {
+                    status,
+                    body: r#"{"error":{"message":"upstream unavailable"}}"#.into(),
+                    retry_after_ms: None,
+                    sanitized: "upstream unavailable".into(),
+                    headers: Vec::new(),
+                }),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpTransport for FallbackTransport {
+        type StreamResponse = TransportStream;
+
+        fn into_stream(resp: Self::StreamResponse) -> (TransportStream, Vec<(String, String)>) {
+            (resp, Vec::new())
+        }
+
+        async fn post_json_stream(
+            &self,
+            _url: &str,
+            headers: &[(String, String)],
+            _body: &serde_json::Value,
+            _cfg: &TransportConfig,
+        ) -> Result<Self::StreamResponse, TransportError> {
+            self.attempt(headers)?;
+            let finish = json!({"type": "finish", "finishReason": "stop", "usage": {}});
+            Ok(Box::pin(stream::iter([Ok(Bytes::from(format!(
+                "data: {finish}\n\n"
+            )))])))
+        }
+
+        async fn post_json(
+            &self,
+            _url: &str,
+            headers: &[(String, String)],
+            _body: &serde_json::Value,
+            _cfg: &TransportConfig,
+        ) -> Result<(serde_json::Value, Vec<(String, String)>), TransportError> {
+            self.attempt(headers)?;
+            Ok((
+                json!({
+                    "content": [{"type": "text", "text": "hi"}],
+                    "finishReason": "stop",
+                    "usage": {}
+                }),
+                Vec::new(),
+            ))
+        }
+    }
+
+    fn fallback_model(failures: &[(&'static str, u16)]) -> GatewayLanguageModel<FallbackTransport> {
+        let GatewayLanguageModel { config, .. } = GatewayLanguageModel::default();
+        GatewayLanguageModel::new("openai/gpt-5", config, FallbackTransport::new(failures))
+            .with_fallbacks(vec![
+                "anthropic/claude-sonnet-4".into(),
+                "google/gemini-2.5-pro".into(),
+            ])
+    }
+
+    fn resolved_model(metadata: &Option<v2t::ProviderMetadata>) -> Option<&serde_json::Value> {
+        metadata.as_ref()?.get("gateway")?.get("resolvedModel")
+    }
+
+    #[tokio::test]
+    async fn generate_falls_back_on_retryable_errors() {
+        let model = fallback_model(&[("openai/gpt-5", 429)]);
+        let response = model
+            .do_generate(v2t::CallOptions::new(Vec::new()))
+            .await
+            .expect("fallback response");
+
+        assert_eq!(
+            *model.http.requested.lock().unwrap(),
+            ["openai/gpt-5", "anthropic/claude-sonnet-4"]
+        );
+        assert_eq!(
+            resolved_model(&response.provider_metadata),
+            Some(&json!("anthropic/claude-sonnet-4"))
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_falls_back_and_records_resolved_model_on_finish() {
+        let model = fallback_model(&[("openai/gpt-5", 503), ("anthropic/claude-sonnet-4", 500)]);
+        let response = model
+            .do_stream(v2t::CallOptions::new(Vec::new()))
+            .await
+            .expect("fallback stream");
+        let parts: Vec<v2t::StreamPart> = response.stream.try_collect().await.expect("parts");
+
+        assert_eq!(model.http.requested.lock().unwrap().len(), 3);
+        let finish_metadata = parts.iter().find_map(|part| match part {
+            v2t::StreamPart::Finish {
+                provider_metadata, ..
+            } => Some(provider_metadata),
+            _ => None,
+        });
+        assert_eq!(
+            resolved_model(finish_metadata.expect("finish part")),
+            Some(&json!("google/gemini-2.5-pro"))
+        );
+    }
+
+    #[tokio::test]
+    async fn non_retryable_errors_skip_fallbacks() {
+        let model = fallback_model(&[("openai/gpt-5", 400)]);
+        let err = model
+            .do_generate(v2t::CallOptions::new(Vec::new()))
+            .await
+            .expect_err("bad request");
+
+        assert!(matches!(err, SdkError::Upstream { status: 400, .. }));
+        assert_eq!(*model.http.requested.lock().unwrap(), ["openai/gpt-5"]);
+    }
 }
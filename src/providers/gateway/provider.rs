@@ -142,9 +142,11 @@ fn build_gateway(
         default_options: bootstrap_headers.default_options,
         request_defaults: bootstrap_headers.request_defaults,
         auth,
+        http_method: None,
     };
 
-    let lm = GatewayLanguageModel::new(model.to_string(), config, http);
+    let lm = GatewayLanguageModel::new(model.to_string(), config, http)
+        .with_fallbacks(def.fallback_models.clone());
     Ok(Arc::new(lm))
 }
 
@@ -0,0 +1,174 @@
+//! Gemini Files API uploads.
+//!
+//! Uploads use the resumable protocol in two requests: a `start` call
+//! carrying the file metadata returns an upload URL in the
+//! `x-goog-upload-url` header, and a single `upload, finalize` call sends
+//! the bytes there. The resulting [`FileRef`] is referenced from a prompt
+//! through [`FileRef::user_part`], which the prompt converter emits as
+//! `fileData`.
+
+use bytes::Bytes;
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+
+use crate::core::transport::HttpTransport;
+use crate::core::SdkError;
+use crate::types::v2 as v2t;
+
+use crate::providers::google::gen_ai::language_model::GoogleGenAiLanguageModel;
+use crate::providers::google::shared::error::map_transport_error_to_sdk_error;
+
+const UPLOAD_URL_HEADER: &str = "x-goog-upload-url";
+
+/// A file stored with the Files API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRef {
+    /// Resource name, e.g. `files/abc-123`.
+    pub name: String,
+    /// URI to reference the file by in a prompt.
+    pub uri: String,
+    pub mime_type: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// `PROCESSING`, `ACTIVE` or `FAILED`; videos stay `PROCESSING` for a
+    /// while after the upload finishes.
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
+impl FileRef {
+    /// User prompt part referencing this file by URI.
+    pub fn user_part(&self) -> v2t::UserPart {
+        v2t::UserPart::File {
+            filename: self.display_name.clone(),
+            data: v2t::DataContent::Url {
+                url: self.uri.clone(),
+            },
+            media_type: self.mime_type.clone(),
+            provider_options: None,
+        }
+    }
+}
+
+impl<T: HttpTransport> GoogleGenAiLanguageModel<T> {
+    /// Upload `bytes` to the Files API and return the stored file.
+    pub async fn upload_file(
+        &self,
+        bytes: impl Into<Bytes>,
+        mime_type: &str,
+        display_name: Option<&str>,
+    ) -> Result<FileRef, SdkError> {
+        let bytes = bytes.into();
+        if mime_type.trim().is_empty() {
+            return Err(SdkError::InvalidArgument {
+                message: "upload_file requires a mime type".into(),
+            });
+        }
+        let headers: Vec<(String, String)> = self
+            .cfg
+            .headers
+            .iter()
+            .filter(|(k, _)| !crate::core::options::is_internal_sdk_header(k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let mut start_headers = headers.clone();
+        start_headers.extend([
+            ("x-goog-upload-protocol".into(), "resumable".into()),
+            ("x-goog-upload-command".into(), "start".into()),
+            (
+                "x-goog-upload-header-content-length".into(),
+                bytes.len().to_string(),
+            ),
+            ("x-goog-upload-header-content-type".into(), mime_type.into()),
+        ]);
+        let metadata = match display_name {
+            Some(name) => json!({ "file": { "displayName": name } }),
+            None => json!({ "file": {} }),
+        };
+        let (_, start_response_headers) = self
+            .cfg
+            .http
+            .post_json(
+                &with_query_params(upload_start_url(&self.cfg.base_url), &self.cfg.query_params),
+                &start_headers,
+                &metadata,
+                &self.cfg.transport_cfg,
+            )
+            .await
+            .map_err(map_transport_error_to_sdk_error)?;
+        let upload_url = start_response_headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(UPLOAD_URL_HEADER))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| upload_error(format!("upload start returned no {UPLOAD_URL_HEADER}")))?;
+
+        let mut upload_headers: Vec<(String, String)> = headers
+            .into_iter()
+            .filter(|(k, _)| !k.eq_ignore_ascii_case("content-type"))
+            .collect();
+        upload_headers.extend([
+            ("content-type".into(), mime_type.into()),
+            ("x-goog-upload-offset".into(), "0".into()),
+            ("x-goog-upload-command".into(), "upload, finalize".into()),
+        ]);
+        let (file_json, _) = self
+            .cfg
+            .http
+            .post_bytes(
+                &with_query_params(upload_url, &self.cfg.query_params),
+                &upload_headers,
+                bytes,
+                &self.cfg.transport_cfg,
+            )
+            .await
+            .map_err(map_transport_error_to_sdk_error)?;
+        let file = file_json.get("file").cloned().unwrap_or(JsonValue::Null);
+        serde_json::from_value(file)
+            .map_err(|err| upload_error(format!("upload finished without a file: {err}")))
+    }
+}
+
+/// `{origin}/upload{path}/files` for a base URL like `{origin}/v1beta`.
+fn upload_start_url(base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let path_start = base_url
+        .find("://")
+        .and_then(|scheme_end| {
+            let host_start = scheme_end + 3;
+            base_url[host_start..].find('/').map(|i| host_start + i)
+        })
+        .unwrap_or(base_url.len());
+    let (origin, path) = base_url.split_at(path_start);
+    format!("{origin}/upload{path}/files")
+}
+
+/// Append the configured query parameters, e.g. an API key passed as `key`,
+/// after any query the URL already carries.
+fn with_query_params(mut url: String, params: &[(String, String)]) -> String {
+    for (i, (key, value)) in params.iter().enumerate() {
+        let separator = if i == 0 && !url.contains('?') {
+            '?'
+        } else {
+            '&'
+        };
+        url.push(separator);
+        url.push_str(&urlencoding::encode(key));
+        url.push('=');
+        url.push_str(&urlencoding::encode(value));
+    }
+    url
+}
+
+fn upload_error(message: String) -> SdkError {
+    SdkError::Upstream {
+        status: 200,
+        message: format!("Gemini file upload failed: {message}"),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+#[path = "../../../../crates/providers/google/tests/files_tests.rs"]
+mod tests;
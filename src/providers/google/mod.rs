@@ -4,6 +4,7 @@ pub mod provider;
 pub(crate) mod shared;
 
 pub mod gen_ai {
+    pub mod files;
     pub mod language_model;
 }
 
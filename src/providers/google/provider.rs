@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::core::headers::HeaderMerger;
 use crate::core::{LanguageModel, SdkError};
 use crate::provider::{
     build_provider_transport_config, collect_query_params, filter_provider_bootstrap_headers,
@@ -60,7 +61,6 @@ fn build_google(
     } else {
         def.base_url.clone()
     };
-    let mut headers = default_headers_from_api_key(api_key);
     let bootstrap_headers = filter_provider_bootstrap_headers(
         &def.headers,
         &def.name,
@@ -72,7 +72,6 @@ fn build_google(
             "x-goog-api-key",
         ],
     );
-    headers.extend(bootstrap_headers.headers);
 
     let supported_urls = HashMap::from([(
         "*".to_string(),
@@ -87,6 +86,14 @@ fn build_google(
 
     let transport_cfg =
         build_provider_transport_config(def, Some(std::time::Duration::from_secs(45)));
+    let mut headers = HeaderMerger::for_transport(&transport_cfg);
+    for (k, v) in default_headers_from_api_key(api_key) {
+        headers.insert_default(&k, v);
+    }
+    for (k, v) in bootstrap_headers.headers {
+        headers.insert(&k, v)?;
+    }
+    let headers = headers.into_pairs();
 
     let http = crate::transport_reqwest::ReqwestTransport::try_new(&transport_cfg)
         .map_err(SdkError::Transport)?;
@@ -1,6 +1,7 @@
 use serde_json::{json, Value as JsonValue};
 use std::collections::HashMap;
 
+use crate::types::usage::token_count;
 use crate::types::v2 as v2t;
 
 pub(crate) struct ParsedGoogleGenerateResponse {
@@ -8,6 +9,16 @@ pub(crate) struct ParsedGoogleGenerateResponse {
     pub finish_reason: v2t::FinishReason,
     pub usage: v2t::Usage,
     pub provider_metadata: Option<v2t::ProviderMetadata>,
+    pub response_metadata: Option<v2t::ResponseMetadata>,
+}
+
+impl ParsedGoogleGenerateResponse {
+    /// Drop reasoning content, for calls that set `includeThoughts: false`.
+    pub fn strip_thoughts(mut self) -> Self {
+        self.content
+            .retain(|part| !matches!(part, v2t::Content::Reasoning { .. }));
+        self
+    }
 }
 
 #[derive(Default)]
@@ -262,6 +273,7 @@ pub(crate) fn parse_google_gen_ai_generate_response(
         finish_reason: google_finish_reason(candidate, &content),
         provider_metadata: google_provider_metadata(candidate, usage_metadata, "google"),
         usage: google_usage(usage_metadata),
+        response_metadata: google_response_metadata(resp_json),
         content,
     }
 }
@@ -282,6 +294,7 @@ pub(crate) fn parse_google_vertex_generate_response(
         finish_reason: v2t::FinishReason::Stop,
         provider_metadata: None,
         usage: google_vertex_usage(resp_json.get("usageMetadata")),
+        response_metadata: google_response_metadata(resp_json),
     }
 }
 
@@ -346,28 +359,46 @@ fn google_finish_reason(
     }
 }
 
-fn google_usage(usage_metadata: Option<&JsonValue>) -> v2t::Usage {
+pub(crate) fn google_response_metadata(resp_json: &JsonValue) -> Option<v2t::ResponseMetadata> {
+    let id = resp_json
+        .get("responseId")
+        .and_then(|value| value.as_str())
+        .map(ToString::to_string);
+    let model_id = resp_json
+        .get("modelVersion")
+        .and_then(|value| value.as_str())
+        .map(ToString::to_string);
+    if id.is_none() && model_id.is_none() {
+        return None;
+    }
+    Some(v2t::ResponseMetadata {
+        id,
+        timestamp_ms: None,
+        model_id,
+    })
+}
+
+pub(crate) fn google_usage(usage_metadata: Option<&JsonValue>) -> v2t::Usage {
     let Some(usage_metadata) = usage_metadata else {
         return v2t::Usage::default();
     };
 
-    v2t::Usage {
-        input_tokens: usage_metadata
-            .get("promptTokenCount")
-            .and_then(|value| value.as_u64()),
+    let mut usage = v2t::Usage {
+        input_tokens: usage_metadata.get("promptTokenCount").and_then(token_count),
         output_tokens: usage_metadata
             .get("candidatesTokenCount")
-            .and_then(|value| value.as_u64()),
-        total_tokens: usage_metadata
-            .get("totalTokenCount")
-            .and_then(|value| value.as_u64()),
+            .and_then(token_count),
+        total_tokens: usage_metadata.get("totalTokenCount").and_then(token_count),
         reasoning_tokens: usage_metadata
             .get("thoughtsTokenCount")
-            .and_then(|value| value.as_u64()),
+            .and_then(token_count),
         cached_input_tokens: usage_metadata
             .get("cachedContentTokenCount")
-            .and_then(|value| value.as_u64()),
-    }
+            .and_then(token_count),
+        ..Default::default()
+    };
+    crate::ai_sdk_types::usage::apply_gemini_modality_details(usage_metadata, &mut usage);
+    usage
 }
 
 fn google_vertex_usage(usage_metadata: Option<&JsonValue>) -> v2t::Usage {
@@ -376,15 +407,11 @@ fn google_vertex_usage(usage_metadata: Option<&JsonValue>) -> v2t::Usage {
     };
 
     let mut usage = v2t::Usage::default();
-    usage.input_tokens = usage_metadata
-        .get("promptTokenCount")
-        .and_then(|value| value.as_u64());
+    usage.input_tokens = usage_metadata.get("promptTokenCount").and_then(token_count);
     usage.output_tokens = usage_metadata
         .get("candidatesTokenCount")
-        .and_then(|value| value.as_u64());
-    usage.total_tokens = usage_metadata
-        .get("totalTokenCount")
-        .and_then(|value| value.as_u64());
+        .and_then(token_count);
+    usage.total_tokens = usage_metadata.get("totalTokenCount").and_then(token_count);
     usage
 }
 
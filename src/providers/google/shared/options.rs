@@ -1,3 +1,4 @@
+use crate::core::SdkError;
 use crate::types::v2::ProviderOptions;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -70,12 +71,55 @@ pub struct GoogleProviderOptions {
     pub labels: Option<std::collections::HashMap<String, String>>,
 }
 
+/// Harm categories Gemini accepts in `safetySettings`.
+pub const HARM_CATEGORIES: &[&str] = &[
+    "HARM_CATEGORY_UNSPECIFIED",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_CIVIC_INTEGRITY",
+    "HARM_CATEGORY_IMAGE_HATE",
+    "HARM_CATEGORY_IMAGE_DANGEROUS_CONTENT",
+    "HARM_CATEGORY_IMAGE_HARASSMENT",
+    "HARM_CATEGORY_IMAGE_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_JAILBREAK",
+];
+
+/// Block thresholds Gemini accepts in `safetySettings`.
+pub const HARM_BLOCK_THRESHOLDS: &[&str] = &[
+    "HARM_BLOCK_THRESHOLD_UNSPECIFIED",
+    "BLOCK_LOW_AND_ABOVE",
+    "BLOCK_MEDIUM_AND_ABOVE",
+    "BLOCK_ONLY_HIGH",
+    "BLOCK_NONE",
+    "OFF",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetySetting {
     pub category: String,
     pub threshold: String,
 }
 
+impl SafetySetting {
+    /// Reject categories and thresholds outside [`HARM_CATEGORIES`] and
+    /// [`HARM_BLOCK_THRESHOLDS`] instead of letting the API fail the call.
+    pub fn validate(&self) -> Result<(), SdkError> {
+        if !HARM_CATEGORIES.contains(&self.category.as_str()) {
+            return Err(SdkError::InvalidArgument {
+                message: format!("unknown safety setting category: {}", self.category),
+            });
+        }
+        if !HARM_BLOCK_THRESHOLDS.contains(&self.threshold.as_str()) {
+            return Err(SdkError::InvalidArgument {
+                message: format!("unknown safety setting threshold: {}", self.threshold),
+            });
+        }
+        Ok(())
+    }
+}
+
 pub fn parse_google_provider_options_for_scopes(
     opts: &ProviderOptions,
     provider_scopes: &[&str],
@@ -346,7 +346,10 @@ pub fn convert_to_google_prompt_with_scopes(
 
     for msg in prompt {
         match msg {
-            v2t::PromptMessage::System { content, .. } => {
+            // Gemini has no developer role; developer messages become part
+            // of the system instruction.
+            v2t::PromptMessage::System { content, .. }
+            | v2t::PromptMessage::Developer { content, .. } => {
                 if !system_allowed {
                     return Err(system_message_order_error());
                 }
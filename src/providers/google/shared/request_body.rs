@@ -1,10 +1,15 @@
 use serde_json::{json, Map, Value as JsonValue};
 
-use crate::core::SdkError;
+use crate::core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::core::request_builder::raw_passthrough::apply_raw_passthrough;
+use crate::core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::core::request_builder::tool_limits::check_tool_limits;
+use crate::core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::core::{ModelCapabilities, SdkError};
 use crate::types::v2 as v2t;
 
 use super::options::{
-    parse_google_provider_options_for_scopes, GoogleProviderOptions, ThinkingConfig,
+    parse_google_provider_options_for_scopes, GoogleProviderOptions, SafetySetting, ThinkingConfig,
 };
 use super::prepare_tools::{convert_json_schema_to_openapi_schema, prepare_tools, PreparedTools};
 use super::prompt::{convert_to_google_prompt_with_scopes, GooglePrompt};
@@ -25,6 +30,30 @@ const RESERVED_PROVIDER_OPTION_KEYS: &[&str] = &[
     "labels",
 ];
 
+/// Capabilities of a Gemini or Gemma model. Gemini takes image and audio
+/// input and grounds answers with citations; thinking starts with 2.5, and
+/// only the native-audio and TTS variants speak. The output limit is resolved
+/// when the model is built.
+pub fn model_capabilities(model_id: &str) -> ModelCapabilities {
+    let model_id = model_id.to_ascii_lowercase();
+    let model_id = model_id.strip_prefix("models/").unwrap_or(&model_id);
+    let gemini = model_id.starts_with("gemini-");
+    let gemini_1_or_2_0 = model_id.starts_with("gemini-1") || model_id.starts_with("gemini-2.0");
+    ModelCapabilities {
+        vision: gemini || model_id.starts_with("gemma-3"),
+        audio_in: gemini,
+        audio_out: gemini && (model_id.contains("native-audio") || model_id.contains("-tts")),
+        // Tuned models, Vertex endpoints and unrecognized ids get tools.
+        tools: !model_id.starts_with("gemma-"),
+        reasoning: gemini && (!gemini_1_or_2_0 || model_id.contains("thinking")),
+        structured_output: gemini,
+        streaming: true,
+        citations: gemini,
+        parallel_tool_calls: gemini,
+        max_output_tokens: None,
+    }
+}
+
 pub struct GoogleRequestBodyBuildConfig<'a> {
     pub scope_names: &'a [&'a str],
     pub raw_provider_option_keys: &'a [&'a str],
@@ -39,8 +68,26 @@ pub fn build_google_request_body(
     options: &v2t::CallOptions,
 ) -> Result<(JsonValue, Vec<v2t::CallWarning>), SdkError> {
     let mut warnings = Vec::new();
+    let limited = apply_prompt_message_limit(options, &mut warnings)?;
+    let options = limited.as_ref();
+    let tooled = apply_unsupported_tools_policy(
+        options,
+        model_capabilities(config.model_id).tools,
+        &mut warnings,
+    )?;
+    let options = tooled.as_ref();
+    check_tool_limits(options, v2t::ToolLimits::default(), &mut warnings)?;
+    let (placed, _) =
+        apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+    let options = placed.as_ref();
     let google_opts =
         parse_google_provider_options_for_scopes(&options.provider_options, config.scope_names);
+    if let Some(settings) = google_opts
+        .as_ref()
+        .and_then(|o| o.safety_settings.as_ref())
+    {
+        settings.iter().try_for_each(SafetySetting::validate)?;
+    }
     maybe_warn_for_include_thoughts(
         &mut warnings,
         google_opts.as_ref(),
@@ -66,10 +113,20 @@ pub fn build_google_request_body(
     apply_google_body_options(&mut body, google_opts.as_ref(), threshold_override);
     attach_prepared_tools(&mut body, tools, tool_config);
     merge_request_body_overrides(&mut body, request_body_overrides, config.trace_prefix);
+    apply_raw_passthrough(options, &mut body, &mut warnings);
 
     Ok((body, warnings))
 }
 
+/// Whether the built request turns thoughts off with
+/// `generationConfig.thinkingConfig.includeThoughts: false`, in which case no
+/// reasoning parts should reach the caller.
+pub fn thoughts_disabled(body: &JsonValue) -> bool {
+    body.pointer("/generationConfig/thinkingConfig/includeThoughts")
+        .and_then(JsonValue::as_bool)
+        == Some(false)
+}
+
 fn maybe_warn_for_include_thoughts(
     warnings: &mut Vec<v2t::CallWarning>,
     google_opts: Option<&GoogleProviderOptions>,
@@ -7,10 +7,13 @@ use futures_util::StreamExt;
 use serde_json::{json, Value as JsonValue};
 
 use crate::ai_sdk_core::error::TransportError;
+use crate::ai_sdk_core::stop_on_tool_call::is_stopping_tool_call;
 use crate::ai_sdk_core::{PartStream, StreamNormalizationState};
 use crate::ai_sdk_streaming_sse::SseDecoder;
 use crate::ai_sdk_types::v2 as v2t;
 
+use super::generate_response::{google_response_metadata, google_usage};
+
 type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>;
 
 struct GoogleStreamState {
@@ -19,6 +22,7 @@ struct GoogleStreamState {
     emitted_source_urls: HashSet<String>,
     finish_reason: v2t::FinishReason,
     provider_metadata: Option<v2t::ProviderMetadata>,
+    response_metadata_emitted: bool,
     block_counter: u64,
 }
 
@@ -30,6 +34,7 @@ impl Default for GoogleStreamState {
             emitted_source_urls: HashSet::new(),
             finish_reason: v2t::FinishReason::Unknown,
             provider_metadata: None,
+            response_metadata_emitted: false,
             block_counter: 0,
         }
     }
@@ -58,6 +63,12 @@ impl GoogleStreamState {
             });
         }
 
+        if !self.response_metadata_emitted {
+            if let Some(meta) = google_response_metadata(&parsed) {
+                self.response_metadata_emitted = true;
+                parts.push(v2t::StreamPart::ResponseMetadata { meta });
+            }
+        }
         self.apply_usage(&parsed);
         self.push_candidate_parts(&parsed, provider_scope, &mut parts);
         parts
@@ -65,15 +76,7 @@ impl GoogleStreamState {
 
     fn apply_usage(&mut self, parsed: &JsonValue) {
         if let Some(usage) = parsed.get("usageMetadata") {
-            self.normalizer.usage = v2t::Usage {
-                input_tokens: usage.get("promptTokenCount").and_then(|v| v.as_u64()),
-                output_tokens: usage.get("candidatesTokenCount").and_then(|v| v.as_u64()),
-                total_tokens: usage.get("totalTokenCount").and_then(|v| v.as_u64()),
-                reasoning_tokens: usage.get("thoughtsTokenCount").and_then(|v| v.as_u64()),
-                cached_input_tokens: usage
-                    .get("cachedContentTokenCount")
-                    .and_then(|v| v.as_u64()),
-            };
+            self.normalizer.usage = google_usage(Some(usage));
         }
     }
 
@@ -256,7 +259,11 @@ impl GoogleStreamState {
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string();
-        parts.push(v2t::StreamPart::File { media_type, data });
+        parts.push(v2t::StreamPart::File {
+            media_type,
+            data,
+            provider_metadata: None,
+        });
         true
     }
 
@@ -429,23 +436,42 @@ fn thought_signature_metadata(
     Some(outer)
 }
 
+/// Drop reasoning parts, for calls that set `includeThoughts: false`.
+pub fn strip_thought_parts(stream: PartStream) -> PartStream {
+    Box::pin(stream.filter(|item| {
+        let thought = matches!(
+            item,
+            Ok(v2t::StreamPart::ReasoningStart { .. }
+                | v2t::StreamPart::ReasoningDelta { .. }
+                | v2t::StreamPart::ReasoningEnd { .. })
+        );
+        std::future::ready(!thought)
+    }))
+}
+
 pub fn build_google_stream_part_stream(
     mut inner: ByteStream,
     warnings: Vec<v2t::CallWarning>,
     include_raw: bool,
     provider_scope: &'static str,
+    stop_on_first_tool_call: bool,
 ) -> PartStream {
     Box::pin(async_stream::try_stream! {
         yield v2t::StreamPart::StreamStart { warnings };
         let mut decoder = SseDecoder::new();
         let mut state = GoogleStreamState::default();
+        let mut stopped = false;
 
-        while let Some(chunk_res) = inner.next().await {
+        'read: while let Some(chunk_res) = inner.next().await {
             match chunk_res {
                 Ok(chunk) => {
                     for ev in decoder.push(&chunk) {
                         for part in state.decode_event(&ev.data, include_raw, provider_scope) {
+                            stopped = stop_on_first_tool_call && is_stopping_tool_call(&part);
                             yield part;
+                            if stopped {
+                                break 'read;
+                            }
                         }
                     }
                 }
@@ -457,9 +483,15 @@ pub fn build_google_stream_part_stream(
             }
         }
 
-        for ev in decoder.finish() {
-            for part in state.decode_event(&ev.data, include_raw, provider_scope) {
-                yield part;
+        if stopped {
+            // Dropping the body cancels the request; usage seen so far is kept.
+            drop(inner);
+            state.finish_reason = v2t::FinishReason::ToolCalls;
+        } else {
+            for ev in decoder.finish() {
+                for part in state.decode_event(&ev.data, include_raw, provider_scope) {
+                    yield part;
+                }
             }
         }
 
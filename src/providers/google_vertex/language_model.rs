@@ -2,16 +2,23 @@ use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
+use crate::core::abort::call_transport_config;
+use crate::core::capabilities::get_max_output_tokens;
+use crate::core::raw_capture::RawCaptureConfig;
+use crate::core::stop_on_tool_call::disable_stop_on_first_tool_call;
 use crate::core::transport::{HttpTransport, TransportConfig};
-use crate::core::{GenerateResponse, LanguageModel, SdkError, StreamResponse};
+use crate::core::usage_estimate::UsageEstimate;
+use crate::core::{GenerateResponse, LanguageModel, ModelCapabilities, SdkError, StreamResponse};
 use crate::types::v2 as v2t;
 
 use crate::providers::google::shared::error::map_transport_error_to_sdk_error;
 use crate::providers::google::shared::generate_response::parse_google_vertex_generate_response;
 use crate::providers::google::shared::request_body::{
-    build_google_request_body, GoogleRequestBodyBuildConfig,
+    build_google_request_body, thoughts_disabled, GoogleRequestBodyBuildConfig,
+};
+use crate::providers::google::shared::stream_core::{
+    build_google_stream_part_stream, strip_thought_parts,
 };
-use crate::providers::google::shared::stream_core::build_google_stream_part_stream;
 
 const TRACE_PREFIX: &str = "[GOOGLE-VERTEX]";
 
@@ -31,13 +38,19 @@ pub struct GoogleVertexLanguageModel<T: HttpTransport = crate::transport_reqwest
 {
     pub model_id: String,
     pub cfg: GoogleVertexConfig<T>,
+    /// Output limit reported by the providers index, looked up once when
+    /// the model is built.
+    max_output_tokens: Option<u64>,
 }
 
 impl<T: HttpTransport> GoogleVertexLanguageModel<T> {
     pub fn new(model_id: impl Into<String>, cfg: GoogleVertexConfig<T>) -> Self {
+        let model_id = model_id.into();
+        let max_output_tokens = get_max_output_tokens("google-vertex", &model_id);
         Self {
-            model_id: model_id.into(),
+            model_id,
             cfg,
+            max_output_tokens,
         }
     }
 
@@ -121,14 +134,21 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for GoogleVertexLan
     fn supported_urls(&self) -> HashMap<String, Vec<String>> {
         self.cfg.supported_urls.clone()
     }
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            max_output_tokens: self.max_output_tokens,
+            ..crate::providers::google::shared::request_body::model_capabilities(&self.model_id)
+        }
+    }
 
     async fn do_generate(&self, options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
-        let options = crate::core::request_builder::defaults::build_call_options(
+        let mut options = crate::core::request_builder::defaults::build_call_options(
             options,
             &self.cfg.provider_scope_name,
             self.cfg.default_options.as_ref(),
         );
-        let (body, warnings) = self.build_body(&options)?;
+        let (body, mut warnings) = self.build_body(&options)?;
+        warnings.extend(disable_stop_on_first_tool_call(&mut options));
         let url = self.url_generate();
 
         let headers: Vec<(String, String)> = self
@@ -142,7 +162,12 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for GoogleVertexLan
         let (resp_json, resp_headers) = match self
             .cfg
             .http
-            .post_json(&url, &headers, &body, &self.cfg.transport_cfg)
+            .post_json(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
             .await
         {
             Ok(ok) => ok,
@@ -151,18 +176,23 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for GoogleVertexLan
             }
         };
 
-        let parsed = parse_google_vertex_generate_response(&resp_json);
+        let mut parsed = parse_google_vertex_generate_response(&resp_json);
+        if thoughts_disabled(&body) {
+            parsed = parsed.strip_thoughts();
+        }
 
         Ok(GenerateResponse {
             content: parsed.content,
             finish_reason: parsed.finish_reason,
             usage: parsed.usage,
             provider_metadata: parsed.provider_metadata,
+            response_metadata: parsed.response_metadata,
             request_body: Some(body),
             response_headers: Some(resp_headers.into_iter().collect()),
             response_body: Some(resp_json),
             warnings,
-        })
+        }
+        .with_usage_estimate(UsageEstimate::from_options(&options)))
     }
 
     async fn do_stream(&self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
@@ -183,7 +213,12 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for GoogleVertexLan
         let resp = match self
             .cfg
             .http
-            .post_json_stream(&url, &headers, &body, &self.cfg.transport_cfg)
+            .post_json_stream(
+                &url,
+                &headers,
+                &body,
+                &call_transport_config(&self.cfg.transport_cfg, &options),
+            )
             .await
         {
             Ok(r) => r,
@@ -193,13 +228,27 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for GoogleVertexLan
         };
         let (inner, resp_headers) = <T as HttpTransport>::into_stream(resp);
 
-        let include_raw = options.include_raw_chunks;
-        let stream = build_google_stream_part_stream(inner, warnings, include_raw, "google-vertex");
+        let include_raw = options.emit_raw_chunks();
+        let mut stream = build_google_stream_part_stream(
+            inner,
+            warnings,
+            include_raw,
+            "google-vertex",
+            options.stop_on_first_tool_call,
+        );
+        if thoughts_disabled(&body) {
+            stream = strip_thought_parts(stream);
+        }
 
         Ok(StreamResponse {
             stream,
             request_body: Some(body),
             response_headers: Some(resp_headers.into_iter().collect()),
-        })
+            raw_response: None,
+        }
+        .with_dedupe_sources(options.dedupe_sources)
+        .with_raw_capture(RawCaptureConfig::from_options(&options))
+        .with_usage_estimate(UsageEstimate::from_options(&options))
+        .with_abort(options.abort.as_ref()))
     }
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::core::headers::HeaderMerger;
 use crate::core::{LanguageModel, SdkError};
 use crate::provider::{
     build_provider_transport_config, collect_query_params, filter_provider_bootstrap_headers,
@@ -83,7 +84,6 @@ fn build_google_vertex(
         .or_else(|| std::env::var("GOOGLE_VERTEX_API_KEY").ok());
 
     let base_url = resolve_base_url(def)?;
-    let mut headers = default_headers(bearer, api_key);
     let bootstrap_headers = filter_provider_bootstrap_headers(
         &def.headers,
         &def.name,
@@ -95,7 +95,6 @@ fn build_google_vertex(
             "x-goog-api-key",
         ],
     );
-    headers.extend(bootstrap_headers.headers);
 
     let supported_urls = HashMap::from([(
         "*".to_string(),
@@ -103,6 +102,14 @@ fn build_google_vertex(
     )]);
 
     let transport_cfg = build_provider_transport_config(def, Some(Duration::from_secs(45)));
+    let mut headers = HeaderMerger::for_transport(&transport_cfg);
+    for (k, v) in default_headers(bearer, api_key) {
+        headers.insert_default(&k, v);
+    }
+    for (k, v) in bootstrap_headers.headers {
+        headers.insert(&k, v)?;
+    }
+    let headers = headers.into_pairs();
 
     let http = crate::transport_reqwest::ReqwestTransport::try_new(&transport_cfg)
         .map_err(SdkError::Transport)?;
@@ -0,0 +1,681 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::error::SdkError;
+use crate::ai_sdk_core::headers::HeaderMerger;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::raw_passthrough::apply_raw_passthrough;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::ai_sdk_core::request_builder::tool_choice::validate_tool_choice;
+use crate::ai_sdk_core::request_builder::tool_limits::{check_tool_limits, OPENAI_TOOL_LIMITS};
+use crate::ai_sdk_core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::ai_sdk_core::stop_on_tool_call::disable_stop_on_first_tool_call;
+use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
+use crate::ai_sdk_core::{
+    map_events_to_parts, EventMapperConfig, EventMapperHooks, EventMapperState, GenerateResponse,
+    LanguageModel, StreamResponse,
+};
+use crate::ai_sdk_streaming_sse::{PipelineBuilder, ProviderChunk, SseEvent};
+use crate::ai_sdk_types::v2 as v2t;
+use crate::ai_sdk_types::Event;
+use futures_util::StreamExt;
+use serde_json::{json, Map, Value};
+
+use crate::provider_openai::config::OpenAIConfig;
+use crate::provider_openai::error::map_transport_error;
+use crate::provider_openai::responses::language_model::{
+    apply_openai_usage_details, extract_openai_generate_usage, maybe_openai_response_error,
+    parse_openai_usage,
+};
+use crate::provider_openai::responses::request_translation::model_capabilities;
+use crate::provider_openai_compatible::chat::convert::{
+    convert_to_openai_chat_messages as convert_messages, unsupported_file_warnings,
+};
+use crate::provider_openai_compatible::chat::options::parse_openai_compatible_chat_provider_options;
+use crate::provider_openai_compatible::chat::prepare_tools::prepare_tools;
+use crate::provider_openai_compatible::completion::finish_reason::map_openai_compatible_finish_reason;
+
+/// OpenAI Chat Completions (`/chat/completions`) language model.
+///
+/// Targets servers that do not implement the Responses API, such as vLLM or
+/// LM Studio, as well as OpenAI itself. Provider tools are not available on
+/// this endpoint and are dropped with a warning.
+pub struct OpenAIChatLanguageModel<T: HttpTransport = crate::reqwest_transport::ReqwestTransport> {
+    pub model_id: String,
+    pub config: OpenAIConfig,
+    pub http: T,
+    pub transport_cfg: TransportConfig,
+}
+
+impl<T: HttpTransport> OpenAIChatLanguageModel<T> {
+    pub fn new(
+        model_id: impl Into<String>,
+        config: OpenAIConfig,
+        http: T,
+        transport_cfg: TransportConfig,
+    ) -> Self {
+        Self {
+            model_id: model_id.into(),
+            config,
+            http,
+            transport_cfg,
+        }
+    }
+
+    fn request_headers(
+        &self,
+        extra: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>, SdkError> {
+        let mut hdrs = HeaderMerger::for_transport(&self.transport_cfg);
+        hdrs.insert_default("content-type", "application/json");
+        hdrs.insert_default("accept", "application/json");
+        for (k, v) in self.config.headers.iter().map(|(k, v)| (k, v)).chain(extra) {
+            if crate::ai_sdk_core::options::is_internal_sdk_header(k) {
+                continue;
+            }
+            hdrs.insert(k, v.clone())?;
+        }
+        Ok(hdrs.into_pairs())
+    }
+}
+
+impl OpenAIChatLanguageModel<crate::reqwest_transport::ReqwestTransport> {
+    pub fn builder(
+        model_id: impl Into<String>,
+    ) -> crate::provider_openai::provider::OpenAIChatBuilder {
+        crate::provider_openai::provider::OpenAIChatBuilder::new(model_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIChatLanguageModel<T> {
+    fn provider_name(&self) -> &'static str {
+        "OpenAI"
+    }
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+    fn supported_urls(&self) -> HashMap<String, Vec<String>> {
+        self.config.supported_urls.clone()
+    }
+
+    async fn do_generate(&self, options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
+        let mut options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
+            options,
+            &self.config.provider_scope_name,
+            self.config.default_options.as_ref(),
+        );
+        let (body, mut warnings) = build_chat_request_body(&options, &self.model_id, &self.config)?;
+        warnings.extend(disable_stop_on_first_tool_call(&mut options));
+        let headers = self.request_headers(&options.headers)?;
+        let (json, response_headers) = self
+            .http
+            .post_json(
+                &self.config.endpoint_url(),
+                &headers,
+                &body,
+                &call_transport_config(&self.transport_cfg, &options),
+            )
+            .await
+            .map_err(map_transport_error)?;
+        maybe_openai_response_error(&json)?;
+
+        let choice = json
+            .get("choices")
+            .and_then(Value::as_array)
+            .and_then(|choices| choices.first());
+        let content = choice
+            .and_then(|choice| choice.get("message"))
+            .map(extract_chat_message_content)
+            .unwrap_or_default();
+        let finish_reason = map_openai_compatible_finish_reason(
+            choice
+                .and_then(|choice| choice.get("finish_reason"))
+                .and_then(Value::as_str),
+        );
+
+        Ok(GenerateResponse {
+            content,
+            finish_reason,
+            usage: extract_openai_generate_usage(&json),
+            provider_metadata: None,
+            response_metadata: Some(chat_response_metadata(&json)),
+            request_body: Some(body),
+            response_headers: Some(response_headers.into_iter().collect()),
+            response_body: Some(json),
+            warnings,
+        }
+        .with_usage_estimate(UsageEstimate::from_options(&options)))
+    }
+
+    async fn do_stream(&self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
+        let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
+            options,
+            &self.config.provider_scope_name,
+            self.config.default_options.as_ref(),
+        );
+        let (mut body, warnings) = build_chat_request_body(&options, &self.model_id, &self.config)?;
+        body["stream"] = Value::Bool(true);
+        if chat_include_usage(&options, &self.config) {
+            body["stream_options"] = json!({ "include_usage": true });
+        }
+        let headers = self.request_headers(&options.headers)?;
+        let resp = self
+            .http
+            .post_json_stream(
+                &self.config.endpoint_url(),
+                &headers,
+                &body,
+                &call_transport_config(&self.transport_cfg, &options),
+            )
+            .await
+            .map_err(map_transport_error)?;
+        let (bytes_stream, response_headers) = <T as HttpTransport>::into_stream(resp);
+
+        let events = PipelineBuilder::<OpenAIChatChunk>::new()
+            .with_provider("openai_chat")
+            .include_raw(options.emit_raw_chunks())
+            .build(bytes_stream.map(|chunk| chunk.map_err(map_transport_error)));
+        let parts = map_events_to_parts(
+            Box::pin(events),
+            chat_event_mapper_config(warnings, options.stop_on_first_tool_call),
+        );
+
+        Ok(StreamResponse {
+            stream: parts,
+            request_body: Some(body),
+            response_headers: Some(response_headers.into_iter().collect()),
+            raw_response: None,
+        }
+        .with_dedupe_sources(options.dedupe_sources)
+        .with_raw_capture(RawCaptureConfig::from_options(&options))
+        .with_usage_estimate(UsageEstimate::from_options(&options))
+        .with_abort(options.abort.as_ref()))
+    }
+}
+
+// ----- Request building -----
+
+fn insert_json_value(body: &mut Map<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        body.insert(key.into(), value);
+    }
+}
+
+fn chat_response_format(response_format: &Option<v2t::ResponseFormat>) -> Option<Value> {
+    match response_format {
+        Some(v2t::ResponseFormat::Json {
+            schema: Some(schema),
+            name,
+            description,
+        }) => Some(json!({
+            "type": "json_schema",
+            "json_schema": {
+                "schema": schema,
+                "name": name.clone().unwrap_or_else(|| "response".into()),
+                "description": description,
+            }
+        })),
+        Some(v2t::ResponseFormat::Json { schema: None, .. }) => {
+            Some(json!({ "type": "json_object" }))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a stream asks for the terminal usage chunk; the `includeUsage`
+/// provider option, defaulting to `true`.
+fn chat_include_usage(options: &v2t::CallOptions, cfg: &OpenAIConfig) -> bool {
+    let scope_names = ["openai", cfg.provider_scope_name.as_str()];
+    parse_openai_compatible_chat_provider_options(&options.provider_options, &scope_names)
+        .0
+        .include_usage
+        .unwrap_or(true)
+}
+
+/// Build the `/chat/completions` request body for `options`.
+///
+/// Function tools map to `{"type":"function"}` entries; `user`,
+/// `reasoningEffort` and `textVerbosity` are read from the `openai` and
+/// configured provider scopes.
+pub fn build_chat_request_body(
+    options: &v2t::CallOptions,
+    model_id: &str,
+    cfg: &OpenAIConfig,
+) -> Result<(Value, Vec<v2t::CallWarning>), SdkError> {
+    let mut warnings: Vec<v2t::CallWarning> = Vec::new();
+    let limited = apply_prompt_message_limit(options, &mut warnings)?;
+    let options = limited.as_ref();
+    let tooled =
+        apply_unsupported_tools_policy(options, model_capabilities(model_id).tools, &mut warnings)?;
+    let options = tooled.as_ref();
+    check_tool_limits(options, OPENAI_TOOL_LIMITS, &mut warnings)?;
+    validate_tool_choice(options)?;
+    let (placed, _) =
+        apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+    let options = placed.as_ref();
+    if options.top_k.is_some() {
+        warnings.push(v2t::CallWarning::UnsupportedSetting {
+            setting: "topK".into(),
+            details: None,
+        });
+    }
+
+    let scope_names = ["openai", cfg.provider_scope_name.as_str()];
+    let (prov, _) =
+        parse_openai_compatible_chat_provider_options(&options.provider_options, &scope_names);
+    let prep = prepare_tools(&options.tools, &options.tool_choice);
+    warnings.extend(prep.warnings);
+    warnings.extend(unsupported_file_warnings(&options.prompt));
+
+    let mut body = Map::new();
+    body.insert("model".into(), json!(model_id));
+    body.insert(
+        "messages".into(),
+        json!(convert_messages(&cfg.provider_scope_name, &options.prompt)),
+    );
+    insert_json_value(&mut body, "user", prov.user.map(Value::String));
+    insert_json_value(
+        &mut body,
+        "max_tokens",
+        options.max_output_tokens.map(|value| json!(value)),
+    );
+    insert_json_value(
+        &mut body,
+        "temperature",
+        options.temperature.map(|value| json!(value)),
+    );
+    insert_json_value(&mut body, "top_p", options.top_p.map(|value| json!(value)));
+    insert_json_value(
+        &mut body,
+        "frequency_penalty",
+        options.frequency_penalty.map(|value| json!(value)),
+    );
+    insert_json_value(
+        &mut body,
+        "presence_penalty",
+        options.presence_penalty.map(|value| json!(value)),
+    );
+    insert_json_value(
+        &mut body,
+        "stop",
+        options.stop_sequences.as_ref().map(|value| json!(value)),
+    );
+    insert_json_value(
+        &mut body,
+        "seed",
+        provider_option(options, &scope_names, "seed")
+            .filter(|seed| seed.is_i64())
+            .cloned()
+            .or_else(|| options.seed.map(|value| json!(value))),
+    );
+    insert_json_value(
+        &mut body,
+        "logit_bias",
+        provider_option(options, &scope_names, "logitBias")
+            .and_then(Value::as_object)
+            .map(|bias| clamp_logit_bias(bias, &mut warnings)),
+    );
+    insert_json_value(&mut body, "tools", prep.tools.map(|value| json!(value)));
+    insert_json_value(&mut body, "tool_choice", prep.tool_choice);
+    insert_json_value(
+        &mut body,
+        "response_format",
+        chat_response_format(&options.response_format),
+    );
+    insert_json_value(
+        &mut body,
+        "reasoning_effort",
+        prov.reasoning_effort.map(Value::String),
+    );
+    insert_json_value(
+        &mut body,
+        "verbosity",
+        prov.text_verbosity.map(Value::String),
+    );
+
+    let mut body = Value::Object(body);
+    apply_raw_passthrough(options, &mut body, &mut warnings);
+    Ok((body, warnings))
+}
+
+/// Documented bounds of an OpenAI `logit_bias` value.
+const LOGIT_BIAS_RANGE: std::ops::RangeInclusive<f64> = -100.0..=100.0;
+
+/// `key` from the last of `scope_names` that sets it.
+fn provider_option<'a>(
+    options: &'a v2t::CallOptions,
+    scope_names: &[&str],
+    key: &str,
+) -> Option<&'a Value> {
+    scope_names
+        .iter()
+        .rev()
+        .find_map(|scope| options.provider_options.get(*scope)?.get(key))
+}
+
+/// `logit_bias` with numeric biases clamped to [`LOGIT_BIAS_RANGE`], warning
+/// for each clamped entry.
+fn clamp_logit_bias(bias: &Map<String, Value>, warnings: &mut Vec<v2t::CallWarning>) -> Value {
+    let mut tokens: Vec<&String> = bias.keys().collect();
+    tokens.sort();
+    let mut clamped = Map::new();
+    for token in tokens {
+        let Some(value) = bias[token].as_f64() else {
+            continue;
+        };
+        let within = value.clamp(*LOGIT_BIAS_RANGE.start(), *LOGIT_BIAS_RANGE.end());
+        if within != value {
+            warnings.push(v2t::CallWarning::UnsupportedSetting {
+                setting: "logitBias".into(),
+                details: Some(format!(
+                    "bias {value} for token {token} is outside [-100, 100]; clamped to {within}"
+                )),
+            });
+        }
+        clamped.insert(token.clone(), json!(within));
+    }
+    Value::Object(clamped)
+}
+
+// ----- Non-streaming response parsing -----
+
+fn extract_chat_message_content(message: &Value) -> Vec<v2t::Content> {
+    let mut content = Vec::new();
+    if let Some(reasoning) = message
+        .get("reasoning_content")
+        .or_else(|| message.get("reasoning"))
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+    {
+        content.push(v2t::Content::Reasoning {
+            text: reasoning.to_string(),
+            provider_metadata: None,
+        });
+    }
+    if let Some(text) = message
+        .get("content")
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+    {
+        content.push(v2t::Content::Text {
+            text: text.to_string(),
+            provider_metadata: None,
+        });
+    }
+    for call in message
+        .get("tool_calls")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(function) = call.get("function") else {
+            continue;
+        };
+        content.push(v2t::Content::ToolCall(v2t::ToolCallPart {
+            tool_call_id: call
+                .get("id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            tool_name: function
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            input: function
+                .get("arguments")
+                .and_then(Value::as_str)
+                .unwrap_or("{}")
+                .to_string(),
+            provider_executed: false,
+            provider_metadata: None,
+            dynamic: false,
+            provider_options: None,
+        }));
+    }
+    content
+}
+
+fn chat_response_metadata(json: &Value) -> v2t::ResponseMetadata {
+    v2t::ResponseMetadata {
+        id: json.get("id").and_then(Value::as_str).map(str::to_string),
+        timestamp_ms: json
+            .get("created")
+            .and_then(Value::as_i64)
+            .map(|created| created.saturating_mul(1000)),
+        model_id: json
+            .get("model")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    }
+}
+
+// ----- Streaming: chat.completion.chunk to Event to StreamPart -----
+
+fn chat_event_mapper_config(
+    warnings: Vec<v2t::CallWarning>,
+    stop_on_first_tool_call: bool,
+) -> EventMapperConfig<Option<v2t::FinishReason>> {
+    let hooks = EventMapperHooks {
+        data: Some(Box::new(
+            |state: &mut EventMapperState<Option<v2t::FinishReason>>, key, value| match key {
+                "openai.response_metadata" => Some(vec![v2t::StreamPart::ResponseMetadata {
+                    meta: chat_response_metadata(value),
+                }]),
+                "openai.finish" => {
+                    state.extra = Some(map_openai_compatible_finish_reason(
+                        value.get("finish_reason").and_then(Value::as_str),
+                    ));
+                    None
+                }
+                "usage" => {
+                    apply_openai_usage_details(value, &mut state.usage);
+                    None
+                }
+                _ => None,
+            },
+        )),
+        finish: Some(Box::new(|state| {
+            let finish_reason = state.extra.clone().unwrap_or(if state.has_tool_calls {
+                v2t::FinishReason::ToolCalls
+            } else {
+                v2t::FinishReason::Unknown
+            });
+            (finish_reason, None)
+        })),
+        ..EventMapperHooks::default()
+    };
+    EventMapperConfig {
+        warnings,
+        treat_tool_names_as_text: HashSet::new(),
+        default_text_id: "txt-0",
+        finish_reason_fallback: v2t::FinishReason::Unknown,
+        initial_extra: None,
+        hooks,
+        stop_on_first_tool_call,
+    }
+}
+
+/// Parser for `chat.completion.chunk` SSE frames.
+///
+/// Tool calls are keyed by the delta `index`; the first delta for an index
+/// must carry the call id and function name, later ones only argument
+/// fragments. Open calls are closed when a `finish_reason` arrives.
+#[derive(Default)]
+pub(crate) struct OpenAIChatChunk {
+    metadata_sent: bool,
+    reasoning_open: bool,
+    tool_calls: BTreeMap<u64, String>,
+}
+
+impl OpenAIChatChunk {
+    fn close_reasoning(&mut self, events: &mut Vec<Event>) {
+        if std::mem::take(&mut self.reasoning_open) {
+            events.push(Event::ReasoningEnd);
+        }
+    }
+
+    fn close_tool_calls(&mut self, events: &mut Vec<Event>) {
+        for (_index, id) in std::mem::take(&mut self.tool_calls) {
+            events.push(Event::ToolCallEnd { id });
+        }
+    }
+
+    fn handle_metadata(&mut self, json: &Value, events: &mut Vec<Event>) {
+        if self.metadata_sent {
+            return;
+        }
+        self.metadata_sent = true;
+        events.push(Event::Data {
+            key: "openai.response_metadata".into(),
+            value: json!({
+                "id": json.get("id").cloned().unwrap_or(Value::Null),
+                "model": json.get("model").cloned().unwrap_or(Value::Null),
+                "created": json.get("created").cloned().unwrap_or(Value::Null),
+            }),
+        });
+    }
+
+    fn handle_delta(&mut self, delta: &Map<String, Value>, events: &mut Vec<Event>) {
+        if let Some(reasoning) = delta
+            .get("reasoning_content")
+            .or_else(|| delta.get("reasoning"))
+            .and_then(Value::as_str)
+            .filter(|text| !text.is_empty())
+        {
+            self.reasoning_open = true;
+            events.push(Event::ReasoningDelta {
+                delta: reasoning.to_string(),
+            });
+        }
+        if let Some(text) = delta
+            .get("content")
+            .and_then(Value::as_str)
+            .filter(|text| !text.is_empty())
+        {
+            self.close_reasoning(events);
+            events.push(Event::TextDelta {
+                delta: text.to_string(),
+            });
+        }
+        for call in delta
+            .get("tool_calls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Err(message) = self.handle_tool_call_delta(call, events) {
+                events.push(Event::Error {
+                    message: message.into(),
+                });
+                return;
+            }
+        }
+    }
+
+    fn handle_tool_call_delta(
+        &mut self,
+        call: &Value,
+        events: &mut Vec<Event>,
+    ) -> Result<(), &'static str> {
+        let index = call
+            .get("index")
+            .and_then(Value::as_u64)
+            .ok_or("Expected 'index' to be a number.")?;
+        let function = call.get("function");
+        let id = match self.tool_calls.get(&index) {
+            Some(id) => id.clone(),
+            None => {
+                let id = call
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .ok_or("Expected 'id' to be a string.")?;
+                let name = function
+                    .and_then(|function| function.get("name"))
+                    .and_then(Value::as_str)
+                    .ok_or("Expected 'function.name' to be a string.")?;
+                self.close_reasoning(events);
+                self.tool_calls.insert(index, id.to_string());
+                events.push(Event::ToolCallStart {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                });
+                id.to_string()
+            }
+        };
+        if let Some(args) = function
+            .and_then(|function| function.get("arguments"))
+            .and_then(Value::as_str)
+            .filter(|args| !args.is_empty())
+        {
+            events.push(Event::ToolCallDelta {
+                id,
+                args_json: args.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ProviderChunk for OpenAIChatChunk {
+    fn try_from_sse(&mut self, event: &SseEvent) -> Result<Option<Vec<Event>>, SdkError> {
+        let trimmed = std::str::from_utf8(&event.data).unwrap_or("").trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        if trimmed.eq_ignore_ascii_case("[DONE]") {
+            let mut events = Vec::new();
+            self.close_tool_calls(&mut events);
+            events.push(Event::Done);
+            return Ok(Some(events));
+        }
+        let json: Value = match serde_json::from_slice(&event.data) {
+            Ok(v) => v,
+            Err(err) => {
+                return Ok(Some(vec![Event::Error {
+                    message: format!("Invalid JSON chunk: {}", err),
+                }]));
+            }
+        };
+        if let Some(error) = json.get("error").filter(|v| !v.is_null()) {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+                .unwrap_or_else(|| error.to_string());
+            return Ok(Some(vec![Event::Error { message }]));
+        }
+
+        let mut events = Vec::new();
+        self.handle_metadata(&json, &mut events);
+        if let Some(choice) = json
+            .get("choices")
+            .and_then(Value::as_array)
+            .and_then(|choices| choices.first())
+        {
+            if let Some(delta) = choice.get("delta").and_then(Value::as_object) {
+                self.handle_delta(delta, &mut events);
+            }
+            if let Some(finish_reason) = choice.get("finish_reason").filter(|v| !v.is_null()) {
+                self.close_tool_calls(&mut events);
+                events.push(Event::Data {
+                    key: "openai.finish".into(),
+                    value: json!({ "finish_reason": finish_reason }),
+                });
+            }
+        }
+        if let Some(usage_value) = json.get("usage").filter(|v| !v.is_null()) {
+            if let Some(usage) = parse_openai_usage(usage_value) {
+                events.push(Event::Usage { usage });
+            }
+            events.push(Event::Data {
+                key: "usage".into(),
+                value: usage_value.clone(),
+            });
+        }
+        Ok(Some(events))
+    }
+}
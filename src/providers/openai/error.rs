@@ -42,10 +42,7 @@ pub fn map_transport_error(te: TransportError) -> SdkError {
                     source: Some(Box::new(te)),
                 }
             }
-            429 => SdkError::RateLimited {
-                retry_after_ms: *retry_after_ms,
-                source: Some(Box::new(te)),
-            },
+            429 => crate::core::error::rate_limited_or_quota_error(*retry_after_ms, te),
             s => {
                 let message = parse_openai_error_message(body)
                     .unwrap_or_else(|| crate::core::error::display_body_for_error(body));
@@ -1,5 +1,6 @@
 //! OpenAI provider (modular v2 layout)
 
+pub mod chat;
 pub mod config;
 pub mod error;
 pub mod provider;
@@ -8,9 +9,14 @@ pub mod responses;
 // Keep overrides module available for typed provider overrides
 pub mod overrides;
 
-pub use provider::OpenAIResponsesBuilder;
+pub use chat::language_model::OpenAIChatLanguageModel;
+pub use provider::{OpenAIChatBuilder, OpenAIResponsesBuilder};
 pub use responses::language_model::OpenAIResponsesLanguageModel;
 
+#[cfg(test)]
+#[path = "../../../crates/providers/openai/tests/chat_stream_fixture_tests.rs"]
+mod chat_stream_fixture_tests;
+
 #[cfg(test)]
 #[path = "../../../crates/providers/openai/tests/responses_language_model_tests.rs"]
 mod responses_language_model_tests;
@@ -12,11 +12,13 @@ use crate::ai_sdk_types::catalog::{ProviderDefinition, SdkType};
 use crate::ai_sdk_types::v2 as v2t;
 use serde_json::Value as JsonValue;
 
+use crate::provider_openai::chat::language_model::OpenAIChatLanguageModel;
 use crate::provider_openai::config::OpenAIConfig;
 use crate::provider_openai::responses::language_model::OpenAIResponsesLanguageModel;
 
 const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
 const DEFAULT_ENDPOINT_PATH: &str = "/responses";
+const DEFAULT_CHAT_ENDPOINT_PATH: &str = "/chat/completions";
 
 fn default_transport_config() -> TransportConfig {
     let mut cfg = TransportConfig::default();
@@ -194,6 +196,112 @@ impl OpenAIResponsesBuilder {
     }
 }
 
+/// Builder for [`OpenAIChatLanguageModel`], targeting `/chat/completions`.
+#[derive(Clone, Debug)]
+pub struct OpenAIChatBuilder {
+    model_id: String,
+    provider_scope_name: String,
+    base_url: String,
+    endpoint_path: String,
+    api_key: Option<String>,
+    bearer: Option<String>,
+    headers: Vec<(String, String)>,
+    query_params: Vec<(String, String)>,
+    default_options: Option<v2t::ProviderOptions>,
+    transport_cfg: TransportConfig,
+}
+
+impl OpenAIChatBuilder {
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            provider_scope_name: "openai".into(),
+            base_url: DEFAULT_BASE_URL.into(),
+            endpoint_path: DEFAULT_CHAT_ENDPOINT_PATH.into(),
+            api_key: None,
+            bearer: None,
+            headers: Vec::new(),
+            query_params: Vec::new(),
+            default_options: None,
+            transport_cfg: default_transport_config(),
+        }
+    }
+
+    pub fn with_provider_scope_name(mut self, provider_scope_name: impl Into<String>) -> Self {
+        self.provider_scope_name = provider_scope_name.into();
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_endpoint_path(mut self, endpoint_path: impl Into<String>) -> Self {
+        self.endpoint_path = endpoint_path.into();
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_bearer(mut self, bearer: impl Into<String>) -> Self {
+        self.bearer = Some(bearer.into());
+        self
+    }
+
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_query_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query_params.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_default_options(mut self, default_options: v2t::ProviderOptions) -> Self {
+        self.default_options = Some(default_options);
+        self
+    }
+
+    pub fn with_transport_config(mut self, transport_cfg: TransportConfig) -> Self {
+        self.transport_cfg = transport_cfg;
+        self
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<OpenAIChatLanguageModel<crate::reqwest_transport::ReqwestTransport>, SdkError> {
+        let mut headers = default_headers_from_creds(self.api_key, self.bearer);
+        headers.extend(self.headers);
+
+        let config = OpenAIConfig {
+            provider_name: "openai.chat".into(),
+            provider_scope_name: self.provider_scope_name,
+            base_url: self.base_url,
+            endpoint_path: self.endpoint_path,
+            headers,
+            query_params: self.query_params,
+            supported_urls: supported_urls(),
+            file_id_prefixes: None,
+            default_options: self.default_options,
+            request_defaults: None,
+        };
+
+        let http = crate::reqwest_transport::ReqwestTransport::try_new(&self.transport_cfg)
+            .map_err(SdkError::Transport)?;
+        Ok(OpenAIChatLanguageModel::new(
+            self.model_id,
+            config,
+            http,
+            self.transport_cfg,
+        ))
+    }
+}
+
 fn match_openai(def: &ProviderDefinition) -> bool {
     matches!(def.sdk_type, SdkType::OpenAI)
 }
@@ -4,17 +4,25 @@ use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::capabilities::get_max_output_tokens;
 use crate::ai_sdk_core::error::{
     codex_websocket_reconnect_replay_retry_error, is_codex_websocket_reconnect_replay_retry_error,
     SdkError, TransportError,
 };
+use crate::ai_sdk_core::headers::HeaderMerger;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
+use crate::ai_sdk_core::stop_on_tool_call::disable_stop_on_first_tool_call;
 use crate::ai_sdk_core::transport::{
-    HttpTransport, JsonStreamWebsocketConnection, TransportConfig,
+    HttpTransport, JsonStreamWebsocketConnection, TransportConfig, WebsocketHandshake,
 };
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
 use crate::ai_sdk_core::{
-    map_events_to_parts, GenerateResponse, LanguageModel, LanguageModelTurnSession, StreamResponse,
+    map_events_to_parts, GenerateResponse, LanguageModel, LanguageModelTurnSession,
+    ModelCapabilities, StreamResponse,
 };
 use crate::ai_sdk_streaming_sse::{PipelineBuilder, ProviderChunk, SseEvent};
+use crate::ai_sdk_types::usage::token_count;
 use crate::ai_sdk_types::v2 as v2t;
 use crate::ai_sdk_types::{Event, TokenUsage};
 use futures_core::Stream;
@@ -27,8 +35,8 @@ use url::Url;
 use uuid::Uuid;
 
 use super::provider_tools::{
-    build_tool_name_mapping, provider_tool_data_from_output_item, provider_tool_parts_from_data,
-    ProviderToolParts, ToolNameMapping,
+    build_tool_name_mapping, mcp_tools_from_list_item, provider_tool_data_from_output_item,
+    provider_tool_parts_from_data, ProviderToolParts, ToolNameMapping,
 };
 use super::request_translation::{
     build_request_body, parse_openai_provider_options, OpenAIProviderOptionsParsed,
@@ -191,6 +199,9 @@ pub struct OpenAIResponsesLanguageModel<
     pub transport_cfg: TransportConfig,
     pub limiter: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     websocket_preconnect: Arc<Mutex<WebsocketPreconnectState>>,
+    /// Output limit reported by the providers index, looked up once when
+    /// the model is built.
+    max_output_tokens: Option<u64>,
 }
 
 impl Default for OpenAIResponsesLanguageModel<crate::reqwest_transport::ReqwestTransport> {
@@ -214,6 +225,7 @@ impl Default for OpenAIResponsesLanguageModel<crate::reqwest_transport::ReqwestT
             transport_cfg: cfg,
             limiter: None,
             websocket_preconnect: Arc::new(Mutex::new(WebsocketPreconnectState::default())),
+            max_output_tokens: None,
         }
     }
 }
@@ -225,13 +237,16 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         http: T,
         transport_cfg: TransportConfig,
     ) -> Self {
+        let model_id = model_id.into();
+        let max_output_tokens = get_max_output_tokens("openai", &model_id);
         Self {
-            model_id: model_id.into(),
+            model_id,
             config,
             http,
             transport_cfg,
             limiter: None,
             websocket_preconnect: Arc::new(Mutex::new(WebsocketPreconnectState::default())),
+            max_output_tokens,
         }
     }
 
@@ -322,23 +337,64 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
             .join("-")
     }
 
-    fn request_headers(&self, extra: &HashMap<String, String>) -> BTreeMap<String, String> {
-        let mut hdrs: BTreeMap<String, String> = BTreeMap::new();
-        hdrs.insert("content-type".into(), "application/json".into());
-        hdrs.insert("accept".into(), "application/json".into());
-        for (k, v) in &self.config.headers {
-            if crate::ai_sdk_core::options::is_internal_sdk_header(k) {
-                continue;
-            }
-            hdrs.insert(k.to_lowercase(), v.clone());
+    /// Header name as sent on the wire: the configured casing when
+    /// `preserve_header_case` is set, otherwise the canonical form.
+    fn header_name(&self, key: &str) -> String {
+        if self.transport_cfg.preserve_header_case {
+            key.to_string()
+        } else {
+            Self::canonicalize_header(key)
         }
-        for (k, v) in extra {
+    }
+
+    fn request_headers(
+        &self,
+        extra: &HashMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, SdkError> {
+        let mut hdrs = HeaderMerger::for_transport(&self.transport_cfg);
+        hdrs.insert_default("content-type", "application/json");
+        hdrs.insert_default("accept", "application/json");
+        for (k, v) in self.config.headers.iter().map(|(k, v)| (k, v)).chain(extra) {
             if crate::ai_sdk_core::options::is_internal_sdk_header(k) {
                 continue;
             }
-            hdrs.insert(k.to_lowercase(), v.clone());
+            hdrs.insert(k, v.clone())?;
+        }
+        Ok(hdrs.into_pairs().into_iter().collect())
+    }
+
+    /// `headers` plus the websocket `OpenAI-Beta` value unless one is
+    /// already set under any casing.
+    fn with_websocket_beta(&self, headers: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        let mut hdrs = HeaderMerger::for_transport(&self.transport_cfg);
+        for (k, v) in headers {
+            hdrs.force(k, v.clone());
         }
-        hdrs
+        hdrs.insert_default("openai-beta", OPENAI_WS_BETA_VALUE);
+        hdrs.into_pairs().into_iter().collect()
+    }
+
+    /// Websocket url and handshake headers of the Codex websocket transport.
+    fn websocket_handshake_target(&self) -> Result<(String, Vec<(String, String)>), SdkError> {
+        let url = to_websocket_url(&self.endpoint_url())?;
+        let hdrs = self.with_websocket_beta(&self.request_headers(&HashMap::new())?);
+        let headers = hdrs
+            .into_iter()
+            .map(|(k, v)| (self.header_name(&k), v))
+            .collect();
+        Ok((url, headers))
+    }
+
+    /// Open the websocket endpoint with the handshake headers a generation
+    /// would send, confirm the upgrade and close it, without sending a
+    /// request. Auth and header problems surface as the same errors a
+    /// websocket generation would report.
+    pub async fn check_websocket(&self) -> Result<WebsocketHandshake, SdkError> {
+        let (url, headers) = self.websocket_handshake_target()?;
+        self.http
+            .check_websocket(&url, &headers, &self.transport_cfg)
+            .await
+            .map_err(map_transport_error)
     }
 
     pub async fn compact_history_json(&self, options: v2t::CallOptions) -> Result<Value, SdkError> {
@@ -350,10 +406,10 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         let (body, _warnings) = build_request_body(&options, &self.model_id, &self.config)?;
         let compact_body = build_compaction_request_body(body)?;
         let url = self.compact_endpoint_url();
-        let hdrs = self.request_headers(&options.headers);
+        let hdrs = self.request_headers(&options.headers)?;
         let headers: Vec<(String, String)> = hdrs
             .into_iter()
-            .map(|(k, v)| (Self::canonicalize_header(&k), v))
+            .map(|(k, v)| (self.header_name(&k), v))
             .collect();
         let (json, _res_headers) = self
             .http
@@ -381,16 +437,17 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         &self,
         body: &Value,
         extra_headers: &HashMap<String, String>,
+        cfg: &TransportConfig,
     ) -> Result<Value, SdkError> {
         let url = self.endpoint_url();
         let headers: Vec<(String, String)> = self
-            .request_headers(extra_headers)
+            .request_headers(extra_headers)?
             .into_iter()
-            .map(|(key, value)| (Self::canonicalize_header(&key), value))
+            .map(|(key, value)| (self.header_name(&key), value))
             .collect();
         let (json, _response_headers) = self
             .http
-            .post_json(&url, &headers, body, &self.transport_cfg)
+            .post_json(&url, &headers, body, cfg)
             .await
             .map_err(map_transport_error)?;
         Ok(json)
@@ -401,27 +458,21 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         body: serde_json::Value,
         transport: ResponseTransportSelection,
         extra_headers: &HashMap<String, String>,
+        cfg: &TransportConfig,
     ) -> Result<(ByteStream, v2t::Headers), SdkError> {
         let requested = transport.requested;
-        let hdrs = self.request_headers(extra_headers);
+        let hdrs = self.request_headers(extra_headers)?;
         if let Some(l) = &self.limiter {
             let _ = l.until_ready().await;
         }
 
-        match self.send_once(&hdrs, &body, requested).await {
+        match self.send_once(&hdrs, &body, requested, cfg).await {
             Ok((stream, res_headers)) => {
-                self.finish_send(
-                    stream,
-                    res_headers,
-                    requested,
-                    transport.fallback_http,
-                    &hdrs,
-                    &body,
-                )
-                .await
+                self.finish_send(stream, res_headers, transport, &hdrs, &body, cfg)
+                    .await
             }
             Err(err) => {
-                self.handle_send_error(err, requested, transport.fallback_http, &hdrs, &body)
+                self.handle_send_error(err, requested, transport.fallback_http, &hdrs, &body, cfg)
                     .await
             }
         }
@@ -431,11 +482,12 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         &self,
         stream: RawByteStream,
         res_headers: Vec<(String, String)>,
-        requested: ResponseTransportMode,
-        fallback_http: bool,
+        transport: ResponseTransportSelection,
         headers: &BTreeMap<String, String>,
         body: &serde_json::Value,
+        cfg: &TransportConfig,
     ) -> Result<(ByteStream, v2t::Headers), SdkError> {
+        let requested = transport.requested;
         if requested != ResponseTransportMode::Websocket {
             return Ok(map_raw_transport_response(
                 stream,
@@ -451,8 +503,11 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
                 stream,
                 response_headers_with_transport(res_headers, requested, requested, None),
             )),
-            Err(err) if fallback_http && should_fallback_to_http_after_websocket_error(&err) => {
-                self.send_http_fallback(headers, body, requested).await
+            Err(err)
+                if transport.fallback_http
+                    && should_fallback_to_http_after_websocket_error(&err) =>
+            {
+                self.send_http_fallback(headers, body, requested, cfg).await
             }
             Err(err) => Err(err),
         }
@@ -465,12 +520,13 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         fallback_http: bool,
         headers: &BTreeMap<String, String>,
         body: &serde_json::Value,
+        cfg: &TransportConfig,
     ) -> Result<(ByteStream, v2t::Headers), SdkError> {
         if requested == ResponseTransportMode::Websocket
             && fallback_http
             && should_fallback_to_http_after_websocket_error(&err)
         {
-            return self.send_http_fallback(headers, body, requested).await;
+            return self.send_http_fallback(headers, body, requested, cfg).await;
         }
 
         Err(err)
@@ -481,9 +537,10 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         headers: &BTreeMap<String, String>,
         body: &serde_json::Value,
         requested: ResponseTransportMode,
+        cfg: &TransportConfig,
     ) -> Result<(ByteStream, v2t::Headers), SdkError> {
         let (stream, res_headers) = self
-            .send_once(headers, body, ResponseTransportMode::Http)
+            .send_once(headers, body, ResponseTransportMode::Http, cfg)
             .await?;
         Ok(map_raw_transport_response(
             stream,
@@ -499,26 +556,27 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         base_headers: &BTreeMap<String, String>,
         body: &serde_json::Value,
         transport: ResponseTransportMode,
+        cfg: &TransportConfig,
     ) -> Result<(RawByteStream, Vec<(String, String)>), SdkError> {
         let mut url = self.endpoint_url();
         if transport == ResponseTransportMode::Websocket {
             url = to_websocket_url(&url)?;
         }
 
-        let mut hdrs = base_headers.clone();
-        if transport == ResponseTransportMode::Websocket {
-            hdrs.entry("openai-beta".into())
-                .or_insert_with(|| OPENAI_WS_BETA_VALUE.to_string());
-        }
+        let hdrs = if transport == ResponseTransportMode::Websocket {
+            self.with_websocket_beta(base_headers)
+        } else {
+            base_headers.clone()
+        };
         let headers: Vec<(String, String)> = hdrs
             .into_iter()
-            .map(|(k, v)| (Self::canonicalize_header(&k), v))
+            .map(|(k, v)| (self.header_name(&k), v))
             .collect();
         let request_body = transport_request_body(body, transport, &self.config.endpoint_path);
 
         match self
             .http
-            .post_json_stream(&url, &headers, &request_body, &self.transport_cfg)
+            .post_json_stream(&url, &headers, &request_body, cfg)
             .await
         {
             Ok(resp) => {
@@ -591,21 +649,13 @@ impl OpenAIResponsesLanguageModel<crate::reqwest_transport::ReqwestTransport> {
         let Ok(runtime) = tokio::runtime::Handle::try_current() else {
             return;
         };
-        let url = match to_websocket_url(&self.endpoint_url()) {
-            Ok(url) => url,
+        let (url, headers) = match self.websocket_handshake_target() {
+            Ok(target) => target,
             Err(err) => {
-                tracing::debug!(error = %err, "openai websocket preconnect url resolution failed");
+                tracing::debug!(error = %err, "openai websocket preconnect setup failed");
                 return;
             }
         };
-        let headers = {
-            let mut hdrs = self.request_headers(&HashMap::new());
-            hdrs.entry("openai-beta".into())
-                .or_insert_with(|| OPENAI_WS_BETA_VALUE.to_string());
-            hdrs.into_iter()
-                .map(|(k, v)| (Self::canonicalize_header(&k), v))
-                .collect::<Vec<_>>()
-        };
         let http = self.http.clone();
         let transport_cfg = self.transport_cfg.clone();
         let task_headers = headers.clone();
@@ -655,7 +705,10 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
         state.last_reset_reason = Some(reason.to_string());
     }
 
-    fn websocket_headers(&self, extra_headers: &HashMap<String, String>) -> Vec<(String, String)> {
+    fn websocket_headers(
+        &self,
+        extra_headers: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>, SdkError> {
         let turn_state = self.state.lock().unwrap().turn_state.clone();
         let mut merged = extra_headers.clone();
         if let Some(turn_state) = turn_state {
@@ -663,17 +716,13 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
                 .entry(CODEX_TURN_STATE_HEADER.to_string())
                 .or_insert(turn_state);
         }
-        let mut hdrs = self.model.request_headers(&merged);
-        hdrs.entry("openai-beta".into())
-            .or_insert_with(|| OPENAI_WS_BETA_VALUE.to_string());
-        hdrs.into_iter()
-            .map(|(k, v)| {
-                (
-                    OpenAIResponsesLanguageModel::<T>::canonicalize_header(&k),
-                    v,
-                )
-            })
-            .collect()
+        let hdrs = self
+            .model
+            .with_websocket_beta(&self.model.request_headers(&merged)?);
+        Ok(hdrs
+            .into_iter()
+            .map(|(k, v)| (self.model.header_name(&k), v))
+            .collect())
     }
 
     async fn ensure_websocket_connection(
@@ -798,6 +847,7 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
                 HashMap::new(),
                 false,
                 false,
+                false,
             ),
         );
         let mut completed_response_id = None;
@@ -1045,12 +1095,13 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
         mut body: Value,
         include_raw: bool,
         requested: ResponseTransportMode,
-        extra_headers: &HashMap<String, String>,
+        options: &v2t::CallOptions,
         warnings: Vec<v2t::CallWarning>,
         tool_name_mapping: ToolNameMapping,
         approval_request_id_map: HashMap<String, String>,
         store_for_stream: bool,
         logprobs_enabled: bool,
+        stop_on_first_tool_call: bool,
     ) -> Result<StreamResponse, SdkError> {
         if let Some(limiter) = &self.model.limiter {
             let _ = limiter.until_ready().await;
@@ -1058,7 +1109,7 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
         if let Some(object) = body.as_object_mut() {
             object.remove("previous_response_id");
         }
-        let base_headers = self.model.request_headers(extra_headers);
+        let base_headers = self.model.request_headers(&options.headers)?;
         let request_body = transport_request_body(
             &body,
             ResponseTransportMode::Http,
@@ -1066,7 +1117,12 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
         );
         let (stream, transport_headers) = self
             .model
-            .send_once(&base_headers, &body, ResponseTransportMode::Http)
+            .send_once(
+                &base_headers,
+                &body,
+                ResponseTransportMode::Http,
+                &call_transport_config(&self.model.transport_cfg, options),
+            )
             .await?;
         let response_headers = self.response_headers(
             transport_headers,
@@ -1089,13 +1145,16 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
                 approval_request_id_map,
                 store_for_stream,
                 logprobs_enabled,
+                stop_on_first_tool_call,
             ),
         );
         Ok(StreamResponse {
             stream: self.wrap_stream_state(parts, request_body.clone(), false),
             request_body: Some(request_body),
             response_headers: Some(response_headers),
-        })
+            raw_response: None,
+        }
+        .with_abort(options.abort.as_ref()))
     }
 
     async fn stream_http_on_websocket_error(
@@ -1104,12 +1163,13 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
         transport_selection: ResponseTransportSelection,
         body: Value,
         include_raw: bool,
-        extra_headers: &HashMap<String, String>,
+        options: &v2t::CallOptions,
         warnings: Vec<v2t::CallWarning>,
         tool_name_mapping: ToolNameMapping,
         approval_request_id_map: HashMap<String, String>,
         store_for_stream: bool,
         logprobs_enabled: bool,
+        stop_on_first_tool_call: bool,
     ) -> Result<StreamResponse, SdkError> {
         if transport_selection.fallback_http && should_fallback_to_http_after_websocket_error(&err)
         {
@@ -1118,12 +1178,13 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
                 body,
                 include_raw,
                 transport_selection.requested,
-                extra_headers,
+                options,
                 warnings,
                 tool_name_mapping,
                 approval_request_id_map,
                 store_for_stream,
                 logprobs_enabled,
+                stop_on_first_tool_call,
             )
             .await
         } else {
@@ -1157,49 +1218,8 @@ impl<'a, T: HttpTransport + Send + Sync + 'static> OpenAIResponsesTurnSession<'a
             Err(err) => Err(err),
         }
     }
-}
-
-#[async_trait::async_trait]
-impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
-    for OpenAIResponsesTurnSession<'_, T>
-{
-    fn provider_name(&self) -> &'static str {
-        "OpenAI"
-    }
-
-    fn model_id(&self) -> &str {
-        &self.model.model_id
-    }
 
-    async fn prewarm_stream(&mut self, options: v2t::CallOptions) -> Result<(), SdkError> {
-        let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
-            options,
-            &self.model.config.provider_scope_name,
-            self.model.config.default_options.as_ref(),
-        );
-        let prov = parse_openai_provider_options(
-            &options.provider_options,
-            &self.model.config.provider_scope_name,
-        );
-        let transport_selection =
-            resolve_transport_selection(&self.model.config.endpoint_path, &prov);
-        if transport_selection.requested != ResponseTransportMode::Websocket
-            || !should_use_codex_oauth_websocket_transport(&self.model.config.endpoint_path)
-            || self.state.lock().unwrap().force_http
-        {
-            return Ok(());
-        }
-
-        if let Some(limiter) = &self.model.limiter {
-            let _ = limiter.until_ready().await;
-        }
-
-        let websocket_headers = self.websocket_headers(&options.headers);
-        self.ensure_websocket_connection(&websocket_headers).await?;
-        Ok(())
-    }
-
-    async fn do_stream(&mut self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
+    async fn stream_turn(&mut self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
         let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
             options,
             &self.model.config.provider_scope_name,
@@ -1233,14 +1253,15 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
             return self
                 .stream_http_request(
                     body,
-                    options.include_raw_chunks,
+                    options.emit_raw_chunks(),
                     transport_selection.requested,
-                    &options.headers,
+                    &options,
                     warnings,
                     tool_name_mapping,
                     approval_request_id_map,
                     store_for_stream,
                     logprobs_enabled,
+                    options.stop_on_first_tool_call,
                 )
                 .await;
         }
@@ -1249,7 +1270,7 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
             let _ = limiter.until_ready().await;
         }
 
-        let websocket_headers = self.websocket_headers(&options.headers);
+        let websocket_headers = self.websocket_headers(&options.headers)?;
         let request_has_turn_state =
             has_nonempty_header(&websocket_headers, CODEX_TURN_STATE_HEADER);
         let reused = match self.ensure_websocket_connection(&websocket_headers).await {
@@ -1260,13 +1281,14 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
                         err,
                         transport_selection,
                         body,
-                        options.include_raw_chunks,
-                        &options.headers,
+                        options.emit_raw_chunks(),
+                        &options,
                         warnings,
                         tool_name_mapping,
                         approval_request_id_map,
                         store_for_stream,
                         logprobs_enabled,
+                        options.stop_on_first_tool_call,
                     )
                     .await;
             }
@@ -1293,13 +1315,14 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
                         err,
                         transport_selection,
                         body,
-                        options.include_raw_chunks,
-                        &options.headers,
+                        options.emit_raw_chunks(),
+                        &options,
                         warnings,
                         tool_name_mapping,
                         approval_request_id_map,
                         store_for_stream,
                         logprobs_enabled,
+                        options.stop_on_first_tool_call,
                     )
                     .await;
             }
@@ -1315,7 +1338,7 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
         );
         let pipeline = PipelineBuilder::<OpenAIResponsesChunk>::new()
             .with_provider("openai_official")
-            .include_raw(options.include_raw_chunks)
+            .include_raw(options.emit_raw_chunks())
             .build(stream);
         let parts = map_events_to_parts(
             Box::pin(pipeline),
@@ -1325,12 +1348,68 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
                 approval_request_id_map,
                 store_for_stream,
                 logprobs_enabled,
+                options.stop_on_first_tool_call,
             ),
         );
         Ok(StreamResponse {
             stream: self.wrap_stream_state(parts, session_body, true),
             request_body: Some(transport_body),
             response_headers: Some(response_headers),
+            raw_response: None,
+        }
+        .with_abort(options.abort.as_ref()))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: HttpTransport + Send + Sync + 'static> LanguageModelTurnSession
+    for OpenAIResponsesTurnSession<'_, T>
+{
+    fn provider_name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model.model_id
+    }
+
+    async fn prewarm_stream(&mut self, options: v2t::CallOptions) -> Result<(), SdkError> {
+        let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
+            options,
+            &self.model.config.provider_scope_name,
+            self.model.config.default_options.as_ref(),
+        );
+        let prov = parse_openai_provider_options(
+            &options.provider_options,
+            &self.model.config.provider_scope_name,
+        );
+        let transport_selection =
+            resolve_transport_selection(&self.model.config.endpoint_path, &prov);
+        if transport_selection.requested != ResponseTransportMode::Websocket
+            || !should_use_codex_oauth_websocket_transport(&self.model.config.endpoint_path)
+            || self.state.lock().unwrap().force_http
+        {
+            return Ok(());
+        }
+
+        if let Some(limiter) = &self.model.limiter {
+            let _ = limiter.until_ready().await;
+        }
+
+        let websocket_headers = self.websocket_headers(&options.headers)?;
+        self.ensure_websocket_connection(&websocket_headers).await?;
+        Ok(())
+    }
+
+    async fn do_stream(&mut self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
+        let raw_capture = RawCaptureConfig::from_options(&options);
+        let usage_estimate = UsageEstimate::from_options(&options);
+        let dedupe_sources = options.dedupe_sources;
+        self.stream_turn(options).await.map(|response| {
+            response
+                .with_dedupe_sources(dedupe_sources)
+                .with_raw_capture(raw_capture)
+                .with_usage_estimate(usage_estimate)
         })
     }
 }
@@ -1464,6 +1543,7 @@ fn should_fallback_to_http_after_websocket_error(err: &SdkError) -> bool {
         && !matches!(
             err,
             SdkError::RateLimited { .. }
+                | SdkError::QuotaExceeded { .. }
                 | SdkError::Upstream {
                     status: 401 | 403,
                     ..
@@ -1532,6 +1612,12 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIResponses
     fn model_id(&self) -> &str {
         &self.model_id
     }
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            max_output_tokens: self.max_output_tokens,
+            ..super::request_translation::model_capabilities(&self.model_id)
+        }
+    }
 
     fn new_turn_session(&self) -> crate::ai_sdk_core::BoxedLanguageModelTurnSession<'_> {
         Box::new(OpenAIResponsesTurnSession::new(self))
@@ -1542,14 +1628,18 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIResponses
     }
 
     async fn do_generate(&self, options: v2t::CallOptions) -> Result<GenerateResponse, SdkError> {
-        let options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
+        let mut options = crate::ai_sdk_core::request_builder::defaults::build_call_options(
             options,
             &self.config.provider_scope_name,
             self.config.default_options.as_ref(),
         );
         let tool_name_mapping = build_tool_name_mapping(&options.tools);
-        let (body, warnings) = build_request_body(&options, &self.model_id, &self.config)?;
-        let json = self.post_response_json(&body, &options.headers).await?;
+        let (body, mut warnings) = build_request_body(&options, &self.model_id, &self.config)?;
+        warnings.extend(disable_stop_on_first_tool_call(&mut options));
+        let transport_cfg = call_transport_config(&self.transport_cfg, &options);
+        let json = self
+            .post_response_json(&body, &options.headers, &transport_cfg)
+            .await?;
         maybe_openai_response_error(&json)?;
 
         let approval_request_id_map = extract_approval_request_id_to_tool_call_id(
@@ -1561,17 +1651,20 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIResponses
         let usage = extract_openai_generate_usage(&json);
         let finish_reason = extract_openai_finish_reason(&json, has_function_calls);
         let provider_metadata = extract_openai_generate_provider_metadata(&json);
+        let response_metadata = extract_openai_generate_response_metadata(&json);
 
         Ok(GenerateResponse {
             content,
             finish_reason,
             usage,
             provider_metadata,
+            response_metadata,
             request_body: None,
             response_headers: None,
             response_body: Some(json),
             warnings,
-        })
+        }
+        .with_usage_estimate(UsageEstimate::from_options(&options)))
     }
 
     async fn do_stream(&self, options: v2t::CallOptions) -> Result<StreamResponse, SdkError> {
@@ -1603,9 +1696,10 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIResponses
         let (stream, response_headers) = self
             .stream_with_body(
                 body,
-                options.include_raw_chunks,
+                options.emit_raw_chunks(),
                 transport_selection,
                 &options.headers,
+                &call_transport_config(&self.transport_cfg, &options),
             )
             .await?;
         let parts = map_events_to_parts(
@@ -1616,43 +1710,49 @@ impl<T: HttpTransport + Send + Sync + 'static> LanguageModel for OpenAIResponses
                 approval_request_id_map,
                 store_for_stream,
                 logprobs_enabled,
+                options.stop_on_first_tool_call,
             ),
         );
         Ok(StreamResponse {
             stream: parts,
             request_body: Some(request_body),
             response_headers: Some(response_headers),
-        })
+            raw_response: None,
+        }
+        .with_dedupe_sources(options.dedupe_sources)
+        .with_raw_capture(RawCaptureConfig::from_options(&options))
+        .with_usage_estimate(UsageEstimate::from_options(&options))
+        .with_abort(options.abort.as_ref()))
     }
 }
 
 // ----- Helpers: request building and SSE mapping -----
 
-pub(super) fn parse_openai_usage(u: &serde_json::Value) -> Option<TokenUsage> {
+pub(crate) fn parse_openai_usage(u: &serde_json::Value) -> Option<TokenUsage> {
     let obj = u.as_object()?;
     let input = obj
         .get("input_tokens")
         .or_else(|| obj.get("prompt_tokens"))
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0) as usize;
     let output = obj
         .get("output_tokens")
         .or_else(|| obj.get("completion_tokens"))
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0) as usize;
     let total = obj
         .get("total_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize)
         .unwrap_or(input + output);
     let cache_read_tokens = obj
         .get("cache_read_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize)
         .or_else(|| parse_openai_cached_input_tokens(u).map(|v| v as usize));
     let cache_write_tokens = obj
         .get("cache_write_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize)
         .or_else(|| {
             obj.get("cache_creation")
@@ -1660,11 +1760,11 @@ pub(super) fn parse_openai_usage(u: &serde_json::Value) -> Option<TokenUsage> {
                 .and_then(|co| {
                     let a = co
                         .get("ephemeral_5m_input_tokens")
-                        .and_then(|v| v.as_u64())
+                        .and_then(token_count)
                         .unwrap_or(0);
                     let b = co
                         .get("ephemeral_1h_input_tokens")
-                        .and_then(|v| v.as_u64())
+                        .and_then(token_count)
                         .unwrap_or(0);
                     let sum = a + b;
                     if sum > 0 {
@@ -1683,7 +1783,7 @@ pub(super) fn parse_openai_usage(u: &serde_json::Value) -> Option<TokenUsage> {
     })
 }
 
-fn maybe_openai_response_error(json: &Value) -> Result<(), SdkError> {
+pub(crate) fn maybe_openai_response_error(json: &Value) -> Result<(), SdkError> {
     let Some(error) = json.get("error").filter(|value| !value.is_null()) else {
         return Ok(());
     };
@@ -1700,7 +1800,7 @@ fn maybe_openai_response_error(json: &Value) -> Result<(), SdkError> {
     })
 }
 
-fn extract_openai_generate_usage(json: &Value) -> v2t::Usage {
+pub(crate) fn extract_openai_generate_usage(json: &Value) -> v2t::Usage {
     let mut usage = v2t::Usage::default();
     let usage_val = json.get("usage").or_else(|| {
         json.get("response")
@@ -1733,7 +1833,8 @@ fn extract_openai_finish_reason(json: &Value, has_function_calls: bool) -> v2t::
 fn extract_openai_generate_provider_metadata(json: &Value) -> Option<v2t::ProviderMetadata> {
     let response_id = openai_response_field(json, "id");
     let service_tier = openai_response_field(json, "service_tier");
-    if response_id.is_none() && service_tier.is_none() {
+    let mcp_tools = extract_openai_mcp_tools(json);
+    if response_id.is_none() && service_tier.is_none() && mcp_tools.is_empty() {
         return None;
     }
 
@@ -1745,10 +1846,43 @@ fn extract_openai_generate_provider_metadata(json: &Value) -> Option<v2t::Provid
     if let Some(service_tier) = service_tier {
         inner.insert("serviceTier".into(), json!(service_tier));
     }
+    if !mcp_tools.is_empty() {
+        inner.insert("mcpTools".into(), Value::Array(mcp_tools));
+    }
     outer.insert("openai".into(), inner);
     Some(outer)
 }
 
+fn extract_openai_mcp_tools(json: &Value) -> Vec<Value> {
+    json.get("output")
+        .and_then(|value| value.as_array())
+        .map(|output| {
+            output
+                .iter()
+                .filter_map(|item| item.as_object())
+                .filter_map(mcp_tools_from_list_item)
+                .flatten()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn extract_openai_generate_response_metadata(json: &Value) -> Option<v2t::ResponseMetadata> {
+    let response = json.get("response").unwrap_or(json);
+    let created_at = response
+        .get("created_at")
+        .and_then(|value| value.as_i64().or_else(|| value.as_u64().map(|n| n as i64)));
+    let meta = v2t::ResponseMetadata {
+        id: openai_response_field(json, "id").map(ToString::to_string),
+        timestamp_ms: created_at.map(|value| value.saturating_mul(1000)),
+        model_id: openai_response_field(json, "model").map(ToString::to_string),
+    };
+    if meta.id.is_none() && meta.timestamp_ms.is_none() && meta.model_id.is_none() {
+        return None;
+    }
+    Some(meta)
+}
+
 fn openai_response_field<'a>(json: &'a Value, field: &str) -> Option<&'a str> {
     json.get(field)
         .and_then(|value| value.as_str())
@@ -1766,7 +1900,7 @@ fn parse_openai_cached_input_tokens(u: &serde_json::Value) -> Option<u64> {
             u.get("prompt_tokens_details")
                 .and_then(|v| v.get("cached_tokens"))
         })
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
 }
 
 fn parse_openai_reasoning_tokens(u: &serde_json::Value) -> Option<u64> {
@@ -1776,16 +1910,17 @@ fn parse_openai_reasoning_tokens(u: &serde_json::Value) -> Option<u64> {
             u.get("completion_tokens_details")
                 .and_then(|v| v.get("reasoning_tokens"))
         })
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
 }
 
-pub(super) fn apply_openai_usage_details(u: &serde_json::Value, usage: &mut v2t::Usage) {
+pub(crate) fn apply_openai_usage_details(u: &serde_json::Value, usage: &mut v2t::Usage) {
     if let Some(cached) = parse_openai_cached_input_tokens(u) {
         usage.cached_input_tokens = Some(cached);
     }
     if let Some(reasoning) = parse_openai_reasoning_tokens(u) {
         usage.reasoning_tokens = Some(reasoning);
     }
+    crate::ai_sdk_types::usage::apply_openai_modality_details(u, usage);
 }
 
 // Ensure tool schemas always have a top-level "type":"object".
@@ -1859,6 +1994,9 @@ pub(super) fn escape_json_delta(delta: &str) -> String {
 pub(crate) struct OpenAIResponsesChunk {
     tool_calls: HashMap<usize, OpenAIToolCallState>,
     pending_deltas: HashMap<usize, Vec<String>>,
+    /// An `error` event arrived; the stream may end without a terminal
+    /// `response.*` event.
+    errored: bool,
 }
 
 struct OpenAIToolCallState {
@@ -2177,6 +2315,11 @@ impl OpenAIResponsesChunk {
             Some("function_call") => {
                 Self::push_data(events, "openai.function_call_done", json!({}));
             }
+            Some("mcp_list_tools") => {
+                if let Some(tools) = mcp_tools_from_list_item(item) {
+                    Self::push_data(events, "openai.mcp_list_tools", json!({ "tools": tools }));
+                }
+            }
             _ => {}
         }
 
@@ -2470,6 +2613,8 @@ impl OpenAIResponsesChunk {
             json!({
                 "tool_call_id": tool_call_id,
                 "partial_image_b64": partial_image_b64,
+                "partial_image_index": json.get("partial_image_index"),
+                "output_format": json.get("output_format"),
             }),
         );
     }
@@ -2480,7 +2625,10 @@ impl OpenAIResponsesChunk {
                 self.handle_response_terminal(json, events);
             }
             "response.failed" => self.handle_response_failed(json, events),
-            "error" => Self::push_data(events, "openai.error", json.clone()),
+            "error" => {
+                self.errored = true;
+                Self::push_data(events, "openai.error", json.clone());
+            }
             _ => {}
         }
     }
@@ -2590,6 +2738,12 @@ impl ProviderChunk for OpenAIResponsesChunk {
             Ok(Some(events))
         }
     }
+
+    /// After a mid-stream `error` event the server may close the stream
+    /// without `response.failed`; finish instead of reporting an EOF.
+    fn on_eof(&mut self) -> Option<Vec<Event>> {
+        self.errored.then(|| vec![Event::Done])
+    }
 }
 
 pub(super) fn openai_item_metadata(
@@ -2670,6 +2824,7 @@ fn extract_response_output_item(
         .unwrap_or("")
     {
         "message" => push_response_message_content(item, &mut state.content),
+        "reasoning" => push_response_reasoning_content(item, &mut state.content),
         "function_call" => push_response_function_call(item, state),
         _ => push_response_provider_tool_content(item, tool_name_mapping, state),
     }
@@ -2698,6 +2853,42 @@ fn push_response_message_content(item: &Map<String, Value>, content: &mut Vec<v2
     }
 }
 
+fn push_response_reasoning_content(item: &Map<String, Value>, content: &mut Vec<v2t::Content>) {
+    let Some(item_id) = item.get("id").and_then(|value| value.as_str()) else {
+        return;
+    };
+    let encrypted_content = item
+        .get("encrypted_content")
+        .cloned()
+        .unwrap_or(Value::Null);
+    let mut summaries: Vec<&str> = item
+        .get("summary")
+        .and_then(|value| value.as_array())
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    // Emit a part even without summaries so the encrypted content can be sent back.
+    if summaries.is_empty() {
+        summaries.push("");
+    }
+    for text in summaries {
+        content.push(v2t::Content::Reasoning {
+            text: text.to_string(),
+            provider_metadata: Some(openai_item_metadata(
+                item_id,
+                [(
+                    "reasoningEncryptedContent".into(),
+                    encrypted_content.clone(),
+                )],
+            )),
+        });
+    }
+}
+
 fn push_response_function_call(item: &Map<String, Value>, state: &mut ResponseContentAccumulator) {
     let (Some(call_id), Some(name)) = (
         item.get("call_id").and_then(|value| value.as_str()),
@@ -2883,9 +3074,10 @@ impl<T: HttpTransport> OpenAIResponsesLanguageModel<T> {
         include_raw: bool,
         transport: ResponseTransportSelection,
         extra_headers: &HashMap<String, String>,
+        cfg: &TransportConfig,
     ) -> Result<(EventStream, v2t::Headers), SdkError> {
         // Build headers for logging
-        let (bytes, response_headers) = match self.send(body, transport, extra_headers).await {
+        let (bytes, response_headers) = match self.send(body, transport, extra_headers, cfg).await {
             Ok(ok) => ok,
             Err(e) => {
                 return Err(e);
@@ -1163,6 +1163,41 @@ pub(super) fn provider_tool_data_from_output_item(
         .and_then(|(_, mapper)| mapper(item, &item_id))
 }
 
+/// Tools advertised by an MCP server in an `mcp_list_tools` output item.
+pub(super) fn mcp_tools_from_list_item(
+    item: &serde_json::Map<String, Value>,
+) -> Option<Vec<Value>> {
+    if item.get("type").and_then(|v| v.as_str()) != Some("mcp_list_tools") {
+        return None;
+    }
+    let server_label = item.get("server_label").cloned();
+    let tools = item
+        .get("tools")
+        .and_then(|v| v.as_array())
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| {
+                    let name = tool.get("name").and_then(|v| v.as_str())?;
+                    let mut obj = serde_json::Map::new();
+                    if let Some(server_label) = server_label.as_ref() {
+                        obj.insert("serverLabel".into(), server_label.clone());
+                    }
+                    obj.insert("name".into(), json!(name));
+                    if let Some(description) = tool.get("description").filter(|v| !v.is_null()) {
+                        obj.insert("description".into(), description.clone());
+                    }
+                    if let Some(schema) = tool.get("input_schema").filter(|v| !v.is_null()) {
+                        obj.insert("inputSchema".into(), schema.clone());
+                    }
+                    Some(Value::Object(obj))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(tools)
+}
+
 pub(super) struct ProviderToolParts {
     pub(super) tool_call_id: String,
     pub(super) tool_name: String,
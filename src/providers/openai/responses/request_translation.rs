@@ -2,7 +2,15 @@ use std::collections::{HashMap, HashSet};
 
 use crate::ai_sdk_core::error::SdkError;
 use crate::ai_sdk_core::options::merge_options_with_disallow;
+use crate::ai_sdk_core::request_builder::audio_input::is_audio_media_type;
 use crate::ai_sdk_core::request_builder::defaults::request_overrides_from_json;
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::raw_passthrough::apply_raw_passthrough;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::ai_sdk_core::request_builder::tool_choice::validate_tool_choice;
+use crate::ai_sdk_core::request_builder::tool_limits::{check_tool_limits, OPENAI_TOOL_LIMITS};
+use crate::ai_sdk_core::request_builder::unsupported_tools::apply_unsupported_tools_policy;
+use crate::ai_sdk_core::ModelCapabilities;
 use crate::ai_sdk_types::v2 as v2t;
 use base64::Engine;
 use serde_json::{json, Value};
@@ -12,6 +20,7 @@ use super::language_model::{
 };
 use super::provider_tools::{build_openai_provider_tool, build_tool_name_mapping, ToolNameMapping};
 use crate::provider_openai::config::OpenAIConfig;
+use crate::provider_openai_compatible::chat::convert::convert_audio_part;
 
 const TOP_LOGPROBS_MAX: u32 = 20;
 
@@ -68,6 +77,40 @@ fn is_reasoning_model(model_id: &str) -> bool {
         || is_non_chat_gpt_5_model(model_id)
 }
 
+/// Capabilities of an OpenAI model family served through the Responses API.
+/// The output limit is resolved when the model is built.
+pub(crate) fn model_capabilities(model_id: &str) -> ModelCapabilities {
+    let early_o1 = model_id.starts_with("o1-mini") || model_id.starts_with("o1-preview");
+    let audio = model_id.contains("audio") || model_id.contains("realtime");
+    let legacy = model_id.starts_with("gpt-3.5")
+        || (model_id.starts_with("gpt-4")
+            && !model_id.starts_with("gpt-4o")
+            && !model_id.starts_with("gpt-4.1")
+            && !model_id.starts_with("gpt-4-turbo"));
+    let vision = !audio
+        && !early_o1
+        && (model_id.starts_with("gpt-4o")
+            || model_id.starts_with("gpt-4.1")
+            || model_id.starts_with("gpt-4-turbo")
+            || model_id.starts_with("gpt-5")
+            || model_id.starts_with("o1")
+            || model_id.starts_with("o3")
+            || model_id.starts_with("o4")
+            || model_id.starts_with("computer-use-preview"));
+    ModelCapabilities {
+        vision,
+        audio_in: audio,
+        audio_out: audio,
+        tools: !early_o1,
+        reasoning: is_reasoning_model(model_id),
+        structured_output: !early_o1 && !legacy,
+        streaming: true,
+        citations: true,
+        parallel_tool_calls: !early_o1,
+        max_output_tokens: None,
+    }
+}
+
 fn system_message_mode_for_model(model_id: &str) -> SystemMessageMode {
     if is_reasoning_model(model_id) {
         SystemMessageMode::Developer
@@ -113,6 +156,9 @@ pub(super) struct OpenAIProviderOptionsParsed {
     pub(super) system_message_mode: Option<SystemMessageMode>,
     pub(super) force_reasoning: Option<bool>,
     pub(super) strict_json_schema: Option<bool>,
+    pub(super) response_format: Option<Value>,
+    pub(super) seed: Option<i64>,
+    pub(super) logit_bias: Option<HashMap<String, f32>>,
     pub(super) truncation: Option<String>,
     pub(super) reasoning_effort: Option<String>,
     pub(super) reasoning_summary: Option<String>,
@@ -174,6 +220,16 @@ pub(super) fn parse_openai_provider_options(
         .and_then(parse_system_message_mode);
     parsed.force_reasoning = get_bool("forceReasoning");
     parsed.strict_json_schema = get_bool("strictJsonSchema");
+    parsed.response_format = map.get("responseFormat").cloned();
+    parsed.seed = map.get("seed").and_then(|v| v.as_i64());
+    parsed.logit_bias = map
+        .get("logitBias")
+        .and_then(|v| v.as_object())
+        .map(|bias| {
+            bias.iter()
+                .filter_map(|(token, value)| Some((token.clone(), value.as_f64()? as f32)))
+                .collect()
+        });
     parsed.truncation = get_str("truncation");
     parsed.reasoning_effort = get_str("reasoningEffort");
     parsed.reasoning_summary = get_str("reasoningSummary");
@@ -193,16 +249,28 @@ pub(super) fn parse_openai_provider_options(
     parsed
 }
 
+/// Codex requests always carry tool settings. Elsewhere `parallelToolCalls`
+/// only means something alongside tools, so it is left out of tool-less
+/// requests.
 fn resolve_request_tool_settings(
     endpoint_path: &str,
     provider_options: &OpenAIProviderOptionsParsed,
-    tool_choice: &Option<v2t::ToolChoice>,
+    options: &v2t::CallOptions,
 ) -> OpenAIRequestToolSettings {
     let codex_defaults = should_use_codex_oauth_websocket_transport(endpoint_path);
-    OpenAIRequestToolSettings {
-        parallel_tool_calls: provider_options
+    let tool_choice = &options.tool_choice;
+    let parallel_tool_calls = if codex_defaults || !options.tools.is_empty() {
+        provider_options
             .parallel_tool_calls
-            .or_else(|| codex_defaults.then_some(true)),
+            .or_else(|| codex_defaults.then_some(true))
+    } else {
+        if provider_options.parallel_tool_calls.is_some() {
+            tracing::debug!("openai: parallelToolCalls omitted from a request without tools");
+        }
+        None
+    };
+    OpenAIRequestToolSettings {
+        parallel_tool_calls,
         tool_choice: tool_choice
             .clone()
             .or_else(|| codex_defaults.then_some(v2t::ToolChoice::Auto)),
@@ -359,6 +427,18 @@ fn push_user_file_part(
         return;
     }
 
+    if is_audio_media_type(media_type) {
+        match convert_audio_part(media_type, data) {
+            Some(block) => parts.push(block),
+            None => warnings.push(v2t::CallWarning::Other {
+                message: format!(
+                    "{media_type} audio must be inline WAV or MP3 for the Responses API; dropped"
+                ),
+            }),
+        }
+        return;
+    }
+
     if media_type == "application/pdf" {
         match data {
             v2t::DataContent::Url { url } => {
@@ -913,6 +993,13 @@ fn handle_prompt_message(
         v2t::PromptMessage::System { content, .. } => {
             handle_system_prompt_message(messages, warnings, content, system_mode)
         }
+        v2t::PromptMessage::Developer { content, .. } => {
+            let mode = match system_mode {
+                SystemMessageMode::Remove => SystemMessageMode::Remove,
+                _ => SystemMessageMode::Developer,
+            };
+            handle_system_prompt_message(messages, warnings, content, mode)
+        }
         v2t::PromptMessage::User { content, .. } => {
             handle_user_prompt_message(messages, warnings, content, settings.file_id_prefixes)
         }
@@ -1135,9 +1222,13 @@ fn build_responses_include(
 fn build_responses_text_object(
     options: &v2t::CallOptions,
     prov: &OpenAIProviderOptionsParsed,
-) -> Option<Value> {
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<Option<Value>, SdkError> {
     let mut text_obj: Option<Value> = None;
-    if let Some(v2t::ResponseFormat::Json {
+    if let Some(format) = prov.response_format.as_ref() {
+        let format_obj = build_provider_response_format(format, prov, warnings)?;
+        text_obj = Some(json!({"format": format_obj}));
+    } else if let Some(v2t::ResponseFormat::Json {
         schema,
         name,
         description,
@@ -1145,9 +1236,11 @@ fn build_responses_text_object(
     {
         let mut format_obj = json!({"type":"json_object"});
         if let Some(schema) = schema {
+            let strict = prov.strict_json_schema.unwrap_or(true);
+            check_json_schema_format(schema, strict, warnings)?;
             format_obj = json!({
                 "type": "json_schema",
-                "strict": prov.strict_json_schema.unwrap_or(true),
+                "strict": strict,
                 "name": name.clone().unwrap_or_else(|| "response".into()),
                 "description": description.clone(),
                 "schema": schema,
@@ -1161,7 +1254,74 @@ fn build_responses_text_object(
         obj.insert("verbosity".into(), Value::String(verbosity.clone()));
         text_obj = Some(Value::Object(obj));
     }
-    text_obj
+    Ok(text_obj)
+}
+
+/// Translates the chat-completions shaped `responseFormat` provider option
+/// (`{ type, json_schema: { name, schema, strict } }`) into the flattened
+/// Responses API `text.format` object.
+fn build_provider_response_format(
+    format: &Value,
+    prov: &OpenAIProviderOptionsParsed,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<Value, SdkError> {
+    let invalid = |message: &str| SdkError::InvalidArgument {
+        message: format!("responseFormat: {message}"),
+    };
+    let format = format
+        .as_object()
+        .ok_or_else(|| invalid("must be an object"))?;
+    match format.get("type").and_then(Value::as_str) {
+        Some("json_object") => Ok(json!({"type": "json_object"})),
+        Some("json_schema") => {
+            let spec = format
+                .get("json_schema")
+                .and_then(Value::as_object)
+                .ok_or_else(|| invalid("json_schema must be an object"))?;
+            let schema = spec
+                .get("schema")
+                .ok_or_else(|| invalid("json_schema.schema is required"))?;
+            let strict = spec
+                .get("strict")
+                .and_then(Value::as_bool)
+                .or(prov.strict_json_schema)
+                .unwrap_or(true);
+            check_json_schema_format(schema, strict, warnings)?;
+            let mut format_obj = json!({
+                "type": "json_schema",
+                "strict": strict,
+                "name": spec.get("name").and_then(Value::as_str).unwrap_or("response"),
+                "schema": schema,
+            });
+            if let Some(description) = spec.get("description").and_then(Value::as_str) {
+                format_obj["description"] = json!(description);
+            }
+            Ok(format_obj)
+        }
+        Some(other) => Err(invalid(&format!("unsupported type '{other}'"))),
+        None => Err(invalid("type is required")),
+    }
+}
+
+fn check_json_schema_format(
+    schema: &Value,
+    strict: bool,
+    warnings: &mut Vec<v2t::CallWarning>,
+) -> Result<(), SdkError> {
+    let Some(obj) = schema.as_object() else {
+        return Err(SdkError::InvalidArgument {
+            message: "responseFormat: json schema must be a JSON object".into(),
+        });
+    };
+    if strict && obj.get("additionalProperties") != Some(&Value::Bool(false)) {
+        warnings.push(v2t::CallWarning::UnsupportedSetting {
+            setting: "responseFormat".into(),
+            details: Some(
+                "strict json schemas require additionalProperties: false at the top level".into(),
+            ),
+        });
+    }
+    Ok(())
 }
 
 fn build_responses_tools(
@@ -1249,6 +1409,20 @@ fn apply_responses_base_body(
     }
 }
 
+/// `openai.seed` and `openai.logitBias` are chat-completions parameters the
+/// Responses API rejects, so they are dropped with a warning.
+fn warn_unsupported_sampling_provider_options(
+    prov: &OpenAIProviderOptionsParsed,
+    warnings: &mut Vec<v2t::CallWarning>,
+) {
+    if prov.seed.is_some() {
+        push_unsupported_responses_option_warning(warnings, "seed");
+    }
+    if prov.logit_bias.is_some() {
+        push_unsupported_responses_option_warning(warnings, "logitBias");
+    }
+}
+
 fn apply_reasoning_model_settings(
     body: &mut Value,
     warnings: &mut Vec<v2t::CallWarning>,
@@ -1405,8 +1579,7 @@ fn build_request_state(
     cfg: &OpenAIConfig,
 ) -> OpenAIRequestBuildState {
     let prov = parse_openai_provider_options(&options.provider_options, &cfg.provider_scope_name);
-    let request_tool_settings =
-        resolve_request_tool_settings(&cfg.endpoint_path, &prov, &options.tool_choice);
+    let request_tool_settings = resolve_request_tool_settings(&cfg.endpoint_path, &prov, options);
     let model_cfg = get_responses_model_config(model_id);
     let is_reasoning_model = prov.force_reasoning.unwrap_or(model_cfg.is_reasoning_model);
     let system_message_mode = prov.system_message_mode.unwrap_or_else(|| {
@@ -1478,9 +1651,34 @@ pub(super) fn build_request_body(
     cfg: &OpenAIConfig,
 ) -> Result<(Value, Vec<v2t::CallWarning>), SdkError> {
     let mut warnings: Vec<v2t::CallWarning> = Vec::new();
+    let limited = apply_prompt_message_limit(options, &mut warnings)?;
+    let options = limited.as_ref();
+    let tooled =
+        apply_unsupported_tools_policy(options, model_capabilities(model_id).tools, &mut warnings)?;
+    let options = tooled.as_ref();
+    check_tool_limits(options, OPENAI_TOOL_LIMITS, &mut warnings)?;
+    validate_tool_choice(options)?;
+    let (placed, system_prompt_mode) = apply_system_prompt_mode(
+        options,
+        &[
+            v2t::SystemPromptMode::System,
+            v2t::SystemPromptMode::Developer,
+        ],
+        &mut warnings,
+    );
+    let options = placed.as_ref();
     collect_unsupported_responses_option_warnings(options, &mut warnings);
 
-    let state = build_request_state(options, model_id, cfg);
+    let mut state = build_request_state(options, model_id, cfg);
+    match system_prompt_mode {
+        Some(v2t::SystemPromptMode::System) => {
+            state.system_message_mode = SystemMessageMode::System
+        }
+        Some(v2t::SystemPromptMode::Developer) => {
+            state.system_message_mode = SystemMessageMode::Developer
+        }
+        _ => {}
+    }
     let (messages, mut message_warnings) = build_request_input_messages(options, cfg, &state);
     warnings.append(&mut message_warnings);
 
@@ -1496,7 +1694,7 @@ pub(super) fn build_request_body(
         .or_else(|| (state.prov.logprobs_bool == Some(true)).then_some(TOP_LOGPROBS_MAX));
     let include =
         build_responses_include(&state.prov, &state.tool_presence, state.is_reasoning_model);
-    let text_obj = build_responses_text_object(options, &state.prov);
+    let text_obj = build_responses_text_object(options, &state.prov, &mut warnings)?;
 
     let mut body = build_initial_request_body(
         model_id,
@@ -1513,6 +1711,7 @@ pub(super) fn build_request_body(
         &state.request_tool_settings,
         options,
     );
+    warn_unsupported_sampling_provider_options(&state.prov, &mut warnings);
 
     if !options.tools.is_empty() {
         let tools = build_responses_tools(options, &mut warnings)?;
@@ -1542,6 +1741,7 @@ pub(super) fn build_request_body(
 
     merge_openai_request_defaults(&mut body, cfg, &state.prov, state.is_reasoning_model);
     apply_service_tier(&mut body, &mut warnings, &state.model_cfg, &state.prov);
+    apply_raw_passthrough(options, &mut body, &mut warnings);
 
     Ok((body, warnings))
 }
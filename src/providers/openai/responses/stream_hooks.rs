@@ -2,6 +2,8 @@ use std::collections::{HashMap, HashSet};
 
 use crate::ai_sdk_core::{EventMapperConfig, EventMapperHooks, EventMapperState};
 use crate::ai_sdk_types::v2 as v2t;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
 use serde_json::json;
 use uuid::Uuid;
 
@@ -47,6 +49,7 @@ pub(super) struct OpenAIStreamExtras {
     logprobs_enabled: bool,
     has_function_calls: bool,
     logprobs: Vec<serde_json::Value>,
+    mcp_tools: Vec<serde_json::Value>,
     message_annotations: HashMap<String, Vec<serde_json::Value>>,
     active_reasoning: HashMap<String, OpenAIReasoningState>,
     open_tool_inputs: HashSet<String>,
@@ -123,6 +126,23 @@ fn make_openai_provider_metadata(values: Vec<(&str, serde_json::Value)>) -> v2t:
     outer
 }
 
+/// Media type of a partial image: the requested output format when the event
+/// names it, otherwise sniffed from the leading image bytes.
+fn partial_image_media_type(output_format: Option<&str>, b64: &str) -> String {
+    if let Some(format) = output_format.filter(|format| !format.is_empty()) {
+        return format!("image/{format}");
+    }
+    let head: String = b64.chars().take(16).collect();
+    let bytes = BASE64_STANDARD.decode(head).unwrap_or_default();
+    let media_type = match bytes.as_slice() {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => "image/png",
+    };
+    media_type.to_string()
+}
+
 fn build_source_url_part(
     url: String,
     title: Option<String>,
@@ -331,6 +351,20 @@ fn handle_message_done_event(
     handled_part(state.text_end_part(item_id.to_string(), Some(metadata)))
 }
 
+/// Finish hint for a mid-stream `error` event: content filtering keeps its
+/// own reason, anything else finishes the stream as an error.
+fn error_finish_hint(value: &serde_json::Value) -> &'static str {
+    let code = value
+        .get("code")
+        .or_else(|| value.get("error").and_then(|error| error.get("code")))
+        .and_then(|v| v.as_str());
+    if code == Some("content_filter") {
+        "content_filter"
+    } else {
+        "error"
+    }
+}
+
 fn handle_message_event(
     state: &mut EventMapperState<OpenAIStreamExtras>,
     key: &str,
@@ -342,7 +376,7 @@ fn handle_message_event(
         "openai.text_annotation" => handle_text_annotation_event(state, value),
         "openai.message_done" => handle_message_done_event(state, value),
         "openai.error" => {
-            state.extra.finish_hint = Some("error".into());
+            state.extra.finish_hint = Some(error_finish_hint(value).into());
             handled_part(v2t::StreamPart::Error {
                 error: value.clone(),
             })
@@ -601,14 +635,26 @@ fn handle_search_and_image_tool_event(
                 .tool_name_mapping
                 .to_custom_tool_name("image_generation")
                 .to_string();
-            handled_part(v2t::StreamPart::ToolResult {
-                tool_call_id: tool_call_id.to_string(),
-                tool_name,
-                result: json!({ "result": partial }),
-                is_error: false,
-                preliminary: true,
-                provider_metadata: None,
-            })
+            let index = value.get("partial_image_index").cloned();
+            let output_format = value.get("output_format").and_then(|v| v.as_str());
+            Some(Some(vec![
+                v2t::StreamPart::ToolResult {
+                    tool_call_id: tool_call_id.to_string(),
+                    tool_name,
+                    result: json!({ "result": partial }),
+                    is_error: false,
+                    preliminary: true,
+                    provider_metadata: None,
+                },
+                v2t::StreamPart::File {
+                    media_type: partial_image_media_type(output_format, partial),
+                    data: partial.to_string(),
+                    provider_metadata: Some(make_openai_provider_metadata(vec![
+                        ("partial", json!(true)),
+                        ("index", index.unwrap_or(serde_json::Value::Null)),
+                    ])),
+                },
+            ]))
         }
         _ => None,
     }
@@ -1029,6 +1075,12 @@ fn handle_tail_state_event(
             state.extra.has_function_calls = true;
             handled_none()
         }
+        "openai.mcp_list_tools" => {
+            if let Some(tools) = value.get("tools").and_then(|v| v.as_array()) {
+                state.extra.mcp_tools.extend(tools.iter().cloned());
+            }
+            handled_none()
+        }
         "openai.finish" => {
             if let Some(reason) = value.get("incomplete_reason").and_then(|v| v.as_str()) {
                 state.extra.finish_hint = Some(reason.to_string());
@@ -1063,6 +1115,7 @@ pub(super) fn build_stream_mapper_config(
     approval_request_id_map: HashMap<String, String>,
     store: bool,
     logprobs_enabled: bool,
+    stop_on_first_tool_call: bool,
 ) -> EventMapperConfig<OpenAIStreamExtras> {
     let mut hooks: EventMapperHooks<OpenAIStreamExtras> = EventMapperHooks::default();
 
@@ -1109,6 +1162,8 @@ pub(super) fn build_stream_mapper_config(
     hooks.finish = Some(Box::new(|state: &EventMapperState<OpenAIStreamExtras>| {
         let reason = if state.extra.saw_response_failed {
             v2t::FinishReason::Other
+        } else if state.extra.finish_hint.as_deref() == Some("error") {
+            v2t::FinishReason::Error
         } else {
             map_finish_reason(
                 state.extra.finish_hint.as_deref(),
@@ -1130,6 +1185,12 @@ pub(super) fn build_stream_mapper_config(
                 serde_json::Value::Array(state.extra.logprobs.clone()),
             );
         }
+        if !state.extra.mcp_tools.is_empty() {
+            inner.insert(
+                "mcpTools".into(),
+                serde_json::Value::Array(state.extra.mcp_tools.clone()),
+            );
+        }
         let metadata = if inner.is_empty() {
             None
         } else {
@@ -1153,5 +1214,6 @@ pub(super) fn build_stream_mapper_config(
             ..Default::default()
         },
         hooks,
+        stop_on_first_tool_call,
     }
 }
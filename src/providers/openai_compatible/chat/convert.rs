@@ -1,3 +1,4 @@
+use crate::core::request_builder::audio_input::is_audio_media_type;
 use crate::types::v2 as v2t;
 use serde_json::{Map, Value};
 
@@ -33,11 +34,12 @@ fn object_with_metadata(value: Value, metadata: Map<String, Value>) -> Value {
 
 fn build_system_message(
     provider_scope: &str,
+    role: &str,
     content: &str,
     provider_options: &Option<v2t::ProviderOptions>,
 ) -> Value {
     object_with_metadata(
-        serde_json::json!({"role":"system","content": content}),
+        serde_json::json!({"role": role, "content": content}),
         get_openai_metadata(provider_scope, provider_options),
     )
 }
@@ -92,6 +94,33 @@ fn file_data_to_url(media_type: &str, data: &v2t::DataContent) -> String {
     }
 }
 
+/// `input_audio` format for an audio media type; chat completions only take
+/// WAV and MP3.
+fn input_audio_format(media_type: &str) -> Option<&'static str> {
+    match media_type {
+        "audio/wav" | "audio/wave" | "audio/x-wav" => Some("wav"),
+        "audio/mpeg" | "audio/mp3" => Some("mp3"),
+        _ => None,
+    }
+}
+
+/// Inline audio as an `input_audio` block. URLs are not accepted there.
+pub(crate) fn convert_audio_part(media_type: &str, data: &v2t::DataContent) -> Option<Value> {
+    use base64::engine::general_purpose::STANDARD as B64;
+    use base64::Engine;
+
+    let format = input_audio_format(media_type)?;
+    let data = match data {
+        v2t::DataContent::Base64 { base64 } => base64.clone(),
+        v2t::DataContent::Bytes { bytes } => B64.encode(bytes),
+        v2t::DataContent::Url { .. } => return None,
+    };
+    Some(serde_json::json!({
+        "type":"input_audio",
+        "input_audio":{"data": data, "format": format}
+    }))
+}
+
 fn convert_user_part(provider_scope: &str, part: &v2t::UserPart) -> Option<Value> {
     let metadata = user_part_metadata(provider_scope, part);
     match part {
@@ -102,6 +131,10 @@ fn convert_user_part(provider_scope: &str, part: &v2t::UserPart) -> Option<Value
         v2t::UserPart::File {
             data, media_type, ..
         } => {
+            if is_audio_media_type(media_type) {
+                return convert_audio_part(media_type, data)
+                    .map(|block| object_with_metadata(block, metadata));
+            }
             if !media_type.starts_with("image/") {
                 return None;
             }
@@ -116,6 +149,36 @@ fn convert_user_part(provider_scope: &str, part: &v2t::UserPart) -> Option<Value
     }
 }
 
+/// Warnings for the user file parts the chat converters drop: anything but
+/// images and inline WAV/MP3 audio.
+pub fn unsupported_file_warnings(prompt: &v2t::Prompt) -> Vec<v2t::CallWarning> {
+    prompt
+        .iter()
+        .filter_map(|message| match message {
+            v2t::PromptMessage::User { content, .. } => Some(content),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|part| match part {
+            v2t::UserPart::File {
+                data, media_type, ..
+            } if !media_type.starts_with("image/")
+                && convert_audio_part(media_type, data).is_none() =>
+            {
+                let what = if input_audio_format(media_type).is_some() {
+                    format!("{media_type} audio given by URL")
+                } else {
+                    format!("file part with media type {media_type}")
+                };
+                Some(v2t::CallWarning::Other {
+                    message: format!("{what} is not supported by chat completions; dropped"),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn build_user_message(
     provider_scope: &str,
     content: &[v2t::UserPart],
@@ -214,13 +277,31 @@ fn build_tool_messages(
         .collect()
 }
 
-fn convert_prompt_message(provider_scope: &str, message: &v2t::PromptMessage) -> Vec<Value> {
+fn convert_prompt_message(
+    provider_scope: &str,
+    message: &v2t::PromptMessage,
+    developer_role: bool,
+) -> Vec<Value> {
     match message {
         v2t::PromptMessage::System {
             content,
             provider_options,
         } => vec![build_system_message(
             provider_scope,
+            "system",
+            content,
+            provider_options,
+        )],
+        v2t::PromptMessage::Developer {
+            content,
+            provider_options,
+        } => vec![build_system_message(
+            provider_scope,
+            if developer_role {
+                "developer"
+            } else {
+                "system"
+            },
             content,
             provider_options,
         )],
@@ -247,13 +328,33 @@ fn convert_prompt_message(provider_scope: &str, message: &v2t::PromptMessage) ->
     }
 }
 
+/// Chat messages for an OpenAI-compatible endpoint. These endpoints lack the
+/// developer role, so developer messages are sent as system messages.
 pub fn convert_to_openai_compatible_chat_messages(
     provider_scope: &str,
     prompt: &v2t::Prompt,
+) -> Vec<Value> {
+    convert_chat_messages(provider_scope, prompt, false)
+}
+
+/// Chat messages for OpenAI's own `/chat/completions`, which keeps developer
+/// messages in the `developer` role.
+pub fn convert_to_openai_chat_messages(provider_scope: &str, prompt: &v2t::Prompt) -> Vec<Value> {
+    convert_chat_messages(provider_scope, prompt, true)
+}
+
+fn convert_chat_messages(
+    provider_scope: &str,
+    prompt: &v2t::Prompt,
+    developer_role: bool,
 ) -> Vec<Value> {
     let mut messages = Vec::new();
     for message in prompt {
-        messages.extend(convert_prompt_message(provider_scope, message));
+        messages.extend(convert_prompt_message(
+            provider_scope,
+            message,
+            developer_role,
+        ));
     }
     messages
 }
@@ -3,12 +3,21 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use serde_json::{json, Value as JsonValue};
 
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
+use crate::ai_sdk_core::request_builder::tool_limits::check_tool_limits;
+use crate::ai_sdk_core::stop_on_tool_call::disable_stop_on_first_tool_call;
 use crate::ai_sdk_core::stream_collect::{collect_stream_to_response, StreamCollectorConfig};
 use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
 use crate::ai_sdk_core::{LanguageModel, SdkError};
 use crate::ai_sdk_types::v2 as v2t;
 
-use crate::provider_openai_compatible::chat::convert::convert_to_openai_compatible_chat_messages as convert_messages;
+use crate::provider_openai_compatible::chat::convert::{
+    convert_to_openai_compatible_chat_messages as convert_messages, unsupported_file_warnings,
+};
 use crate::provider_openai_compatible::chat::options::{
     parse_openai_compatible_chat_provider_options, OpenAICompatibleChatProviderOptions,
 };
@@ -25,6 +34,15 @@ pub struct OpenAICompatibleChatConfig<T: HttpTransport> {
     pub query_params: Vec<(String, String)>,
     pub supports_structured_outputs: bool,
     pub default_options: Option<v2t::ProviderOptions>,
+    /// Top-level request body keys to rename before sending (sent name ->
+    /// server name), for near-compatible servers. Nested keys are not
+    /// remapped.
+    pub field_aliases: HashMap<String, String>,
+    /// JSON chunk that ends the stream in addition to `[DONE]`.
+    pub done_sentinel: Option<JsonValue>,
+    /// Tool limits the server enforces, checked before sending. Defaults to
+    /// none; calls can tighten them with [`v2t::CallOptions::tool_limits`].
+    pub tool_limits: v2t::ToolLimits,
 }
 
 pub struct OpenAICompatibleChatLanguageModel<
@@ -112,6 +130,12 @@ impl<T: HttpTransport> OpenAICompatibleChatLanguageModel<T> {
         options: &v2t::CallOptions,
     ) -> Result<(JsonValue, Vec<v2t::CallWarning>), SdkError> {
         let mut warnings: Vec<v2t::CallWarning> = vec![];
+        let limited = apply_prompt_message_limit(options, &mut warnings)?;
+        check_tool_limits(options, self.cfg.tool_limits, &mut warnings)?;
+        let options = limited.as_ref();
+        let (placed, _) =
+            apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+        let options = placed.as_ref();
         if options.top_k.is_some() {
             warnings.push(v2t::CallWarning::UnsupportedSetting {
                 setting: "topK".into(),
@@ -145,11 +169,13 @@ impl<T: HttpTransport> OpenAICompatibleChatLanguageModel<T> {
         // Prepare tools & tool_choice
         let prep = prepare_tools(&options.tools, &options.tool_choice);
         warnings.extend(prep.warnings);
+        warnings.extend(unsupported_file_warnings(&options.prompt));
 
         let OpenAICompatibleChatProviderOptions {
             user,
             reasoning_effort,
             text_verbosity,
+            include_usage: _,
         } = prov_opts;
 
         let mut body_map = serde_json::Map::new();
@@ -244,8 +270,10 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for OpenAICompatibleChatLangu
         &self,
         options: v2t::CallOptions,
     ) -> Result<crate::ai_sdk_core::GenerateResponse, SdkError> {
+        let mut options = options;
+        let stop_warning = disable_stop_on_first_tool_call(&mut options);
         let stream_resp = self.do_stream(options).await?;
-        collect_stream_to_response(
+        let mut response = collect_stream_to_response(
             stream_resp,
             StreamCollectorConfig {
                 allow_reasoning: true,
@@ -253,7 +281,9 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for OpenAICompatibleChatLangu
                 ..StreamCollectorConfig::default()
             },
         )
-        .await
+        .await?;
+        response.warnings.extend(stop_warning);
+        Ok(response)
     }
 
     async fn do_stream(
@@ -266,9 +296,15 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for OpenAICompatibleChatLangu
             self.cfg.default_options.as_ref(),
         );
         let (mut body, warnings) = self.build_request_body(&options)?;
+        let scope_names = ["openai-compatible", self.cfg.provider_scope_name.as_str()];
+        let include_usage =
+            parse_openai_compatible_chat_provider_options(&options.provider_options, &scope_names)
+                .0
+                .include_usage
+                .unwrap_or(self.cfg.include_usage);
         if let Some(map) = body.as_object_mut() {
             map.insert("stream".into(), JsonValue::Bool(true));
-            if self.cfg.include_usage {
+            if include_usage {
                 map.insert("stream_options".into(), json!({"include_usage": true}));
             }
         }
@@ -281,15 +317,24 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for OpenAICompatibleChatLangu
             url,
             headers,
             body,
-            &self.cfg.transport_cfg,
+            &call_transport_config(&self.cfg.transport_cfg, &options),
             crate::provider_openai_compatible::stream::StreamSettings {
                 warnings,
-                include_raw: options.include_raw_chunks,
-                include_usage: self.cfg.include_usage,
+                include_raw: options.emit_raw_chunks(),
+                include_usage,
                 provider_scope_name: self.cfg.provider_scope_name.clone(),
+                field_aliases: self.cfg.field_aliases.clone(),
+                done_sentinel: self.cfg.done_sentinel.clone(),
             },
             crate::provider_openai_compatible::stream::StreamMode::Chat,
         )
         .await
+        .map(|response| {
+            response
+                .with_stop_on_first_tool_call(options.stop_on_first_tool_call)
+                .with_raw_capture(RawCaptureConfig::from_options(&options))
+                .with_usage_estimate(UsageEstimate::from_options(&options))
+                .with_abort(options.abort.as_ref())
+        })
     }
 }
@@ -8,6 +8,8 @@ pub struct OpenAICompatibleChatProviderOptions {
     pub user: Option<String>,
     pub reasoning_effort: Option<String>,
     pub text_verbosity: Option<String>,
+    /// `includeUsage`: request `stream_options.include_usage` when streaming.
+    pub include_usage: Option<bool>,
 }
 
 /// Parse providerOptions for the given provider scope names.
@@ -33,6 +35,9 @@ pub fn parse_openai_compatible_chat_provider_options(
             if let Some(text_verbosity) = map.get("textVerbosity").and_then(|v| v.as_str()) {
                 merged.text_verbosity = Some(text_verbosity.to_string());
             }
+            if let Some(include_usage) = map.get("includeUsage").and_then(|v| v.as_bool()) {
+                merged.include_usage = Some(include_usage);
+            }
         }
     }
 
@@ -46,7 +51,11 @@ pub fn parse_openai_compatible_chat_provider_options(
         .map(|map| {
             let mut extras = JsonMap::new();
             for (k, v) in map.iter() {
-                if k == "user" || k == "reasoningEffort" || k == "textVerbosity" {
+                if k == "user"
+                    || k == "reasoningEffort"
+                    || k == "textVerbosity"
+                    || k == "includeUsage"
+                {
                     continue;
                 }
                 extras.insert(k.clone(), v.clone());
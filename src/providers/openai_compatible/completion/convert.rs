@@ -11,7 +11,10 @@ pub fn convert_to_openai_compatible_completion_prompt(
     let mut text = String::new();
 
     let mut idx = 0usize;
-    if let Some(v2t::PromptMessage::System { content, .. }) = prompt.get(0) {
+    if let Some(
+        v2t::PromptMessage::System { content, .. } | v2t::PromptMessage::Developer { content, .. },
+    ) = prompt.get(0)
+    {
         text.push_str(content);
         text.push_str("\n\n");
         idx = 1;
@@ -19,7 +22,8 @@ pub fn convert_to_openai_compatible_completion_prompt(
 
     for m in &prompt[idx..] {
         match m {
-            v2t::PromptMessage::System { content, .. } => {
+            v2t::PromptMessage::System { content, .. }
+            | v2t::PromptMessage::Developer { content, .. } => {
                 return Err(SdkError::Upstream {
                     status: 400,
                     message: format!("Unexpected system message in prompt: {}", content),
@@ -3,8 +3,13 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use serde_json::{json, Value as JsonValue};
 
+use crate::ai_sdk_core::abort::call_transport_config;
+use crate::ai_sdk_core::raw_capture::RawCaptureConfig;
+use crate::ai_sdk_core::request_builder::prompt_limit::apply_prompt_message_limit;
+use crate::ai_sdk_core::request_builder::system_prompt::apply_system_prompt_mode;
 use crate::ai_sdk_core::stream_collect::{collect_stream_to_response, StreamCollectorConfig};
 use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
+use crate::ai_sdk_core::usage_estimate::UsageEstimate;
 use crate::ai_sdk_core::{LanguageModel, SdkError};
 use crate::ai_sdk_types::v2 as v2t;
 
@@ -23,6 +28,12 @@ pub struct OpenAICompatibleCompletionConfig<T: HttpTransport> {
     pub supported_urls: HashMap<String, Vec<String>>,
     pub query_params: Vec<(String, String)>,
     pub default_options: Option<v2t::ProviderOptions>,
+    /// Top-level request body keys to rename before sending (sent name ->
+    /// server name), for near-compatible servers. Nested keys are not
+    /// remapped.
+    pub field_aliases: HashMap<String, String>,
+    /// JSON chunk that ends the stream in addition to `[DONE]`.
+    pub done_sentinel: Option<JsonValue>,
 }
 
 pub struct OpenAICompatibleCompletionLanguageModel<
@@ -62,6 +73,11 @@ impl<T: HttpTransport> OpenAICompatibleCompletionLanguageModel<T> {
         options: &v2t::CallOptions,
     ) -> Result<(JsonValue, Vec<v2t::CallWarning>), SdkError> {
         let mut warnings: Vec<v2t::CallWarning> = vec![];
+        let limited = apply_prompt_message_limit(options, &mut warnings)?;
+        let options = limited.as_ref();
+        let (placed, _) =
+            apply_system_prompt_mode(options, &[v2t::SystemPromptMode::System], &mut warnings);
+        let options = placed.as_ref();
 
         // Unsupported knobs for completion
         if options.top_k.is_some() {
@@ -244,15 +260,23 @@ impl<T: HttpTransport + Send + Sync> LanguageModel for OpenAICompatibleCompletio
             url,
             headers,
             body,
-            &self.cfg.transport_cfg,
+            &call_transport_config(&self.cfg.transport_cfg, &options),
             crate::provider_openai_compatible::stream::StreamSettings {
                 warnings,
-                include_raw: options.include_raw_chunks,
+                include_raw: options.emit_raw_chunks(),
                 include_usage: self.cfg.include_usage,
                 provider_scope_name: self.cfg.provider_scope_name.clone(),
+                field_aliases: self.cfg.field_aliases.clone(),
+                done_sentinel: self.cfg.done_sentinel.clone(),
             },
             crate::provider_openai_compatible::stream::StreamMode::Completion,
         )
         .await
+        .map(|response| {
+            response
+                .with_raw_capture(RawCaptureConfig::from_options(&options))
+                .with_usage_estimate(UsageEstimate::from_options(&options))
+                .with_abort(options.abort.as_ref())
+        })
     }
 }
@@ -1,11 +1,13 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
-use crate::ai_sdk_core::embedding::{EmbedResponse, EmbeddingModel};
+use crate::ai_sdk_core::embedding::{l2_normalize, DedupedValues, EmbedResponse, EmbeddingModel};
 use crate::ai_sdk_core::error::SdkError;
+use crate::ai_sdk_core::headers::HeaderMerger;
 use crate::ai_sdk_core::options::is_internal_sdk_header;
 use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
 use crate::ai_sdk_types::embedding::{EmbedOptions, EmbedUsage};
 use crate::ai_sdk_types::v2 as v2t;
+use futures_util::future::join_all;
 use serde::Deserialize;
 use serde_json::{json, Value as JsonValue};
 
@@ -77,30 +79,27 @@ impl<T: HttpTransport> OpenAICompatibleEmbeddingModel<T> {
             .join("-")
     }
 
-    fn build_headers(&self, extra: &HashMap<String, String>) -> Vec<(String, String)> {
-        let mut hdrs: BTreeMap<String, String> = BTreeMap::new();
-        for (k, v) in &self.cfg.headers {
+    fn build_headers(
+        &self,
+        extra: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>, SdkError> {
+        let mut hdrs = HeaderMerger::for_transport(&self.cfg.transport_cfg);
+        for (k, v) in self.cfg.headers.iter().map(|(k, v)| (k, v)).chain(extra) {
             if is_internal_sdk_header(k) {
                 continue;
             }
-            hdrs.insert(k.to_ascii_lowercase(), v.clone());
+            hdrs.insert(k, v.clone())?;
         }
-        for (k, v) in extra {
-            if is_internal_sdk_header(k) {
-                continue;
-            }
-            hdrs.insert(k.to_ascii_lowercase(), v.clone());
-        }
-        hdrs.entry("content-type".into())
-            .or_insert_with(|| "application/json".into());
-        hdrs.entry("accept".into())
-            .or_insert_with(|| "application/json".into());
-        hdrs.into_iter()
-            .map(|(k, v)| (Self::canonicalize_header(&k), v))
-            .collect()
+        hdrs.insert_default("content-type", "application/json");
+        hdrs.insert_default("accept", "application/json");
+        Ok(hdrs.into_pairs_with(Self::canonicalize_header))
     }
 
-    fn build_request_body(&self, options: &EmbedOptions) -> Result<JsonValue, SdkError> {
+    fn build_request_body(
+        &self,
+        options: &EmbedOptions,
+        values: &[String],
+    ) -> Result<JsonValue, SdkError> {
         // Provider options and extras
         let scope_names = ["openai-compatible", self.cfg.provider_scope_name.as_str()];
         let (prov_opts, prov_extras) = parse_openai_compatible_embedding_provider_options(
@@ -119,9 +118,9 @@ impl<T: HttpTransport> OpenAICompatibleEmbeddingModel<T> {
 
         let mut body_map = serde_json::Map::new();
         body_map.insert("model".into(), model_value);
-        body_map.insert("input".into(), json!(options.values));
+        body_map.insert("input".into(), json!(values));
         body_map.insert("encoding_format".into(), JsonValue::String("float".into()));
-        if let Some(dimensions) = dimensions {
+        if let Some(dimensions) = options.dimensions.or(dimensions) {
             body_map.insert("dimensions".into(), json!(dimensions));
         }
         if let Some(user) = user {
@@ -137,15 +136,41 @@ impl<T: HttpTransport> OpenAICompatibleEmbeddingModel<T> {
         Ok(JsonValue::Object(body_map))
     }
 
-    fn too_many_values_error(&self, limit: usize, actual: usize) -> SdkError {
-        SdkError::Upstream {
-            status: 400,
-            message: format!(
-                "too many embedding values: {} (max {} per call)",
-                actual, limit
-            ),
-            source: None,
-        }
+    async fn embed_batch(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &JsonValue,
+    ) -> Result<EmbeddingBatch, SdkError> {
+        let (json, headers) = self
+            .cfg
+            .http
+            .post_json(url, headers, body, &self.cfg.transport_cfg)
+            .await
+            .map_err(map_transport_error_to_sdk_error)?;
+        let parsed: EmbeddingResponse =
+            serde_json::from_value(json.clone()).map_err(SdkError::Serde)?;
+        Ok(EmbeddingBatch {
+            parsed,
+            json,
+            headers,
+        })
+    }
+}
+
+/// One `/embeddings` response of a possibly split call.
+struct EmbeddingBatch {
+    parsed: EmbeddingResponse,
+    json: JsonValue,
+    headers: Vec<(String, String)>,
+}
+
+/// The single value when there is one, otherwise a JSON array of all of them.
+fn single_or_array(mut values: Vec<JsonValue>) -> JsonValue {
+    if values.len() == 1 {
+        values.remove(0)
+    } else {
+        JsonValue::Array(values)
     }
 }
 
@@ -176,41 +201,82 @@ impl<T: HttpTransport + Send + Sync> EmbeddingModel for OpenAICompatibleEmbeddin
     }
 
     async fn do_embed(&self, options: EmbedOptions) -> Result<EmbedResponse, SdkError> {
-        let options = apply_provider_defaults(
+        let mut options = apply_provider_defaults(
             options,
             &self.cfg.provider_scope_name,
             self.cfg.default_options.as_ref(),
         );
+        let deduped = options.deduplicate.then(|| {
+            let deduped = DedupedValues::new(std::mem::take(&mut options.values));
+            options.values = deduped.unique.clone();
+            deduped
+        });
 
-        if let Some(limit) = self.cfg.max_embeddings_per_call {
-            if options.values.len() > limit {
-                return Err(self.too_many_values_error(limit, options.values.len()));
-            }
-        }
-
-        let body = self.build_request_body(&options)?;
-        let headers = self.build_headers(&options.headers);
+        // Inputs past `max_embeddings_per_call` are split into several
+        // requests whose results are concatenated in input order.
+        let batch_size = self
+            .cfg
+            .max_embeddings_per_call
+            .unwrap_or(usize::MAX)
+            .max(1);
+        let batches: Vec<&[String]> = if options.values.is_empty() {
+            vec![&[]]
+        } else {
+            options.values.chunks(batch_size).collect()
+        };
+        let bodies = batches
+            .into_iter()
+            .map(|values| self.build_request_body(&options, values))
+            .collect::<Result<Vec<_>, _>>()?;
+        let headers = self.build_headers(&options.headers)?;
         let url = self.build_request_url();
 
-        let (json, res_headers) = self
-            .cfg
-            .http
-            .post_json(&url, &headers, &body, &self.cfg.transport_cfg)
+        let responses = if self.cfg.supports_parallel_calls && bodies.len() > 1 {
+            join_all(
+                bodies
+                    .iter()
+                    .map(|body| self.embed_batch(&url, &headers, body)),
+            )
             .await
-            .map_err(map_transport_error_to_sdk_error)?;
-
-        let parsed: EmbeddingResponse =
-            serde_json::from_value(json.clone()).map_err(|se| SdkError::Serde(se))?;
-
-        let embeddings = parsed
-            .data
             .into_iter()
-            .map(|item| item.embedding)
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?
+        } else {
+            let mut responses = Vec::with_capacity(bodies.len());
+            for body in &bodies {
+                responses.push(self.embed_batch(&url, &headers, body).await?);
+            }
+            responses
+        };
 
-        let usage = parsed.usage.map(|u| EmbedUsage {
-            tokens: u.prompt_tokens,
-        });
+        let mut embeddings = Vec::with_capacity(options.values.len());
+        let mut usage: Option<EmbedUsage> = None;
+        let mut provider_metadata = None;
+        let mut res_headers = Vec::new();
+        let mut response_bodies = Vec::with_capacity(responses.len());
+        for batch in responses {
+            embeddings.extend(batch.parsed.data.into_iter().map(|item| item.embedding));
+            if let Some(tokens) = batch.parsed.usage.map(|u| u.prompt_tokens) {
+                let total = usage.get_or_insert(EmbedUsage { tokens: None });
+                if let Some(tokens) = tokens {
+                    total.tokens = Some(total.tokens.unwrap_or(0) + tokens);
+                }
+            }
+            if provider_metadata.is_none() {
+                provider_metadata = batch.parsed.provider_metadata;
+            }
+            res_headers = batch.headers;
+            response_bodies.push(batch.json);
+        }
+
+        if options.normalize {
+            embeddings
+                .iter_mut()
+                .for_each(|embedding| l2_normalize(embedding));
+        }
+        let embeddings = match &deduped {
+            Some(deduped) => deduped.expand(embeddings)?,
+            None => embeddings,
+        };
 
         let response_headers: Option<v2t::Headers> = if res_headers.is_empty() {
             None
@@ -226,10 +292,10 @@ impl<T: HttpTransport + Send + Sync> EmbeddingModel for OpenAICompatibleEmbeddin
         Ok(EmbedResponse {
             embeddings,
             usage,
-            provider_metadata: parsed.provider_metadata,
+            provider_metadata,
             response_headers,
-            response_body: Some(json),
-            request_body: Some(body),
+            response_body: Some(single_or_array(response_bodies)),
+            request_body: Some(single_or_array(bodies)),
         })
     }
 }
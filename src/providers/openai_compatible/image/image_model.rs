@@ -1,12 +1,16 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::time::SystemTime;
 
+use crate::ai_sdk_core::headers::HeaderMerger;
 use crate::ai_sdk_core::image::{ImageModel, ImageResponse, ImageResponseMeta};
 use crate::ai_sdk_core::options::is_internal_sdk_header;
 use crate::ai_sdk_core::transport::{HttpTransport, MultipartForm, TransportConfig};
 use crate::ai_sdk_core::SdkError;
-use crate::ai_sdk_types::image::{ImageData, ImageFile, ImageOptions, ImageUsage, ImageWarning};
+use crate::ai_sdk_types::image::{
+    ImageData, ImageFile, ImageOptions, ImageResponseFormat, ImageUsage, ImageWarning,
+};
 use crate::ai_sdk_types::v2 as v2t;
+use futures_util::{stream, StreamExt};
 use serde::Deserialize;
 use serde_json::{json, Value as JsonValue};
 
@@ -16,6 +20,12 @@ use crate::provider_openai_compatible::image::options::{
     OpenAICompatibleImageProviderOptions,
 };
 
+/// Concurrent requests when a call is split by `max_images_per_call`.
+const DEFAULT_MAX_PARALLEL_IMAGE_CALLS: usize = 4;
+
+/// Most images a single `/images/generations` request may ask for.
+const MAX_IMAGES_PER_CALL: u32 = 10;
+
 pub struct OpenAICompatibleImageConfig<T: HttpTransport> {
     pub provider_scope_name: String,
     pub base_url: String,
@@ -76,37 +86,22 @@ impl<T: HttpTransport> OpenAICompatibleImageModel<T> {
         &self,
         extra: &HashMap<String, String>,
         include_content_type: bool,
-    ) -> Vec<(String, String)> {
-        let mut hdrs: BTreeMap<String, String> = BTreeMap::new();
-        for (k, v) in &self.cfg.headers {
-            if is_internal_sdk_header(k) {
-                continue;
-            }
-            let kl = k.to_ascii_lowercase();
-            if !include_content_type && kl == "content-type" {
-                continue;
-            }
-            hdrs.insert(kl, v.clone());
-        }
-        for (k, v) in extra {
+    ) -> Result<Vec<(String, String)>, SdkError> {
+        let mut hdrs = HeaderMerger::for_transport(&self.cfg.transport_cfg);
+        for (k, v) in self.cfg.headers.iter().map(|(k, v)| (k, v)).chain(extra) {
             if is_internal_sdk_header(k) {
                 continue;
             }
-            let kl = k.to_ascii_lowercase();
-            if !include_content_type && kl == "content-type" {
+            if !include_content_type && k.eq_ignore_ascii_case("content-type") {
                 continue;
             }
-            hdrs.insert(kl, v.clone());
+            hdrs.insert(k, v.clone())?;
         }
         if include_content_type {
-            hdrs.entry("content-type".into())
-                .or_insert_with(|| "application/json".into());
+            hdrs.insert_default("content-type", "application/json");
         }
-        hdrs.entry("accept".into())
-            .or_insert_with(|| "application/json".into());
-        hdrs.into_iter()
-            .map(|(k, v)| (Self::canonicalize_header(&k), v))
-            .collect()
+        hdrs.insert_default("accept", "application/json");
+        Ok(hdrs.into_pairs_with(Self::canonicalize_header))
     }
 
     fn headers_vec_to_map(headers: Vec<(String, String)>) -> Option<v2t::Headers> {
@@ -165,7 +160,10 @@ impl<T: HttpTransport> OpenAICompatibleImageModel<T> {
             }
         }
 
-        body_map.insert("response_format".into(), json!("b64_json"));
+        let response_format = options
+            .response_format
+            .unwrap_or(ImageResponseFormat::B64Json);
+        body_map.insert("response_format".into(), json!(response_format.as_str()));
         Ok(JsonValue::Object(body_map))
     }
 
@@ -240,7 +238,14 @@ impl<T: HttpTransport> OpenAICompatibleImageModel<T> {
         }
     }
 
-    async fn build_edit_form(&self, options: &ImageOptions) -> Result<MultipartForm, SdkError> {
+    /// Multipart body for `/images/edits` and `/images/variations`. With
+    /// `require_png`, the source images and mask must be PNGs and the mask
+    /// must match the first image's dimensions.
+    async fn build_edit_form(
+        &self,
+        options: &ImageOptions,
+        require_png: bool,
+    ) -> Result<MultipartForm, SdkError> {
         let (prov_opts, prov_extras) =
             parse_openai_compatible_image_provider_options(&options.provider_options);
 
@@ -255,19 +260,40 @@ impl<T: HttpTransport> OpenAICompatibleImageModel<T> {
         if let Some(size) = &options.size {
             form.push_text("size", size.clone());
         }
+        if let Some(response_format) = options.response_format {
+            form.push_text("response_format", response_format.as_str());
+        }
         if let Some(user) = user {
             form.push_text("user", user);
         }
 
+        let mut image_dimensions = None;
         for (idx, file) in options.files.iter().enumerate() {
             let (bytes, filename, content_type) =
                 self.file_to_form_part(file, "image", Some(idx)).await?;
+            if require_png {
+                let dimensions = png_dimensions("image", &bytes, content_type.as_deref())?;
+                image_dimensions.get_or_insert(dimensions);
+            }
             form.push_bytes("image", bytes, filename, content_type);
         }
 
         if let Some(mask) = &options.mask {
             let (bytes, filename, content_type) =
                 self.file_to_form_part(mask, "mask", None).await?;
+            if require_png {
+                let (width, height) = png_dimensions("mask", &bytes, content_type.as_deref())?;
+                if let Some((image_width, image_height)) = image_dimensions {
+                    if (width, height) != (image_width, image_height) {
+                        return Err(SdkError::InvalidArgument {
+                            message: format!(
+                                "mask is {width}x{height} but the image is \
+                                 {image_width}x{image_height}; they must match"
+                            ),
+                        });
+                    }
+                }
+            }
             form.push_bytes("mask", bytes, filename, content_type);
         }
 
@@ -284,11 +310,23 @@ impl<T: HttpTransport> OpenAICompatibleImageModel<T> {
     ) -> Result<ImageResponse, SdkError> {
         let parsed: OpenAICompatibleImageResponse =
             serde_json::from_value(json.clone()).map_err(SdkError::Serde)?;
-        let images = parsed
-            .data
-            .into_iter()
-            .map(|item| ImageData::Base64(item.b64_json))
-            .collect::<Vec<_>>();
+        let mut images = Vec::with_capacity(parsed.data.len());
+        for item in parsed.data {
+            images.push(match item {
+                OpenAICompatibleImageData::Base64 { b64_json } => ImageData::Base64(b64_json),
+                OpenAICompatibleImageData::Url { url } => {
+                    let (bytes, _) = self
+                        .cfg
+                        .http
+                        .get_bytes(&url, &[], &self.cfg.transport_cfg)
+                        .await
+                        .map_err(SdkError::from)?;
+                    ImageData::Bytes {
+                        bytes: bytes.to_vec(),
+                    }
+                }
+            });
+        }
 
         Ok(ImageResponse {
             images,
@@ -325,38 +363,112 @@ impl<T: HttpTransport + Send + Sync> ImageModel for OpenAICompatibleImageModel<T
     }
 
     fn max_images_per_call(&self) -> Option<usize> {
-        Some(10)
+        Some(MAX_IMAGES_PER_CALL as usize)
     }
 
     async fn do_generate(&self, options: ImageOptions) -> Result<ImageResponse, SdkError> {
-        let scope_name = if self.cfg.provider_scope_name == "openai-compatible" {
-            "openai"
-        } else {
-            &self.cfg.provider_scope_name
+        let mut options = self.options_with_defaults(options);
+        if options.n == 0 {
+            return Err(SdkError::InvalidArgument {
+                message: "image count n must be at least 1".into(),
+            });
+        }
+        let mut warnings = self.warnings_for_options(&options);
+
+        // Without a split, one request carries every image, so `n` itself is
+        // held to the per-call limit.
+        let per_call = match options.max_images_per_call.filter(|&per_call| per_call > 0) {
+            Some(per_call) if per_call > MAX_IMAGES_PER_CALL => {
+                warnings.push(ImageWarning::Compatibility {
+                    feature: "maxImagesPerCall".into(),
+                    details: Some(format!(
+                        "{per_call} exceeds the limit of {MAX_IMAGES_PER_CALL} images per call; \
+                         using {MAX_IMAGES_PER_CALL}"
+                    )),
+                });
+                MAX_IMAGES_PER_CALL
+            }
+            Some(per_call) => per_call,
+            None => {
+                if options.n > MAX_IMAGES_PER_CALL {
+                    warnings.push(ImageWarning::Compatibility {
+                        feature: "n".into(),
+                        details: Some(format!(
+                            "{} exceeds the limit of {MAX_IMAGES_PER_CALL} images per call; \
+                             clamped to {MAX_IMAGES_PER_CALL}",
+                            options.n
+                        )),
+                    });
+                    options.n = MAX_IMAGES_PER_CALL;
+                }
+                options.n
+            }
         };
-        let options =
-            apply_provider_defaults(options, scope_name, self.cfg.default_options.as_ref());
 
+        if options.n > per_call {
+            self.generate_split(&options, per_call, warnings).await
+        } else {
+            self.generate_once(&options, warnings).await
+        }
+    }
+
+    async fn do_image_edit(
+        &self,
+        image: ImageFile,
+        mask: Option<ImageFile>,
+        options: ImageOptions,
+    ) -> Result<ImageResponse, SdkError> {
+        let options = ImageOptions {
+            files: vec![image],
+            mask,
+            ..self.options_with_defaults(options)
+        };
         let warnings = self.warnings_for_options(&options);
-        let has_files = !options.files.is_empty();
+        let form = self.build_edit_form(&options, true).await?;
+        self.post_form("/images/edits", &form, &options, warnings)
+            .await
+    }
 
-        if has_files {
-            let form = self.build_edit_form(&options).await?;
-            let headers = self.build_headers(&options.headers, false);
-            let url = self.build_request_url("/images/edits");
-            let (json, res_headers) = self
-                .cfg
-                .http
-                .post_multipart(&url, &headers, &form, &self.cfg.transport_cfg)
-                .await
-                .map_err(map_transport_error_to_sdk_error)?;
+    async fn do_image_variation(
+        &self,
+        image: ImageFile,
+        options: ImageOptions,
+    ) -> Result<ImageResponse, SdkError> {
+        let options = self.options_with_defaults(options);
+        let mut warnings = self.warnings_for_options(&options);
+        if options.prompt.is_some() {
+            warnings.push(ImageWarning::Unsupported {
+                feature: "prompt".into(),
+                details: Some("Image variations do not take a prompt.".into()),
+            });
+        }
+        let options = ImageOptions {
+            prompt: None,
+            files: vec![image],
+            mask: None,
+            ..options
+        };
+        let form = self.build_edit_form(&options, true).await?;
+        self.post_form("/images/variations", &form, &options, warnings)
+            .await
+    }
+}
+
+impl<T: HttpTransport + Send + Sync> OpenAICompatibleImageModel<T> {
+    async fn generate_once(
+        &self,
+        options: &ImageOptions,
+        warnings: Vec<ImageWarning>,
+    ) -> Result<ImageResponse, SdkError> {
+        if !options.files.is_empty() {
+            let form = self.build_edit_form(options, false).await?;
             return self
-                .handle_response(json, res_headers, warnings, None)
+                .post_form("/images/edits", &form, options, warnings)
                 .await;
         }
 
-        let body = self.build_generation_body(&options)?;
-        let headers = self.build_headers(&options.headers, true);
+        let body = self.build_generation_body(options)?;
+        let headers = self.build_headers(&options.headers, true)?;
         let url = self.build_request_url("/images/generations");
         let (json, res_headers) = self
             .cfg
@@ -368,6 +480,146 @@ impl<T: HttpTransport + Send + Sync> ImageModel for OpenAICompatibleImageModel<T
         self.handle_response(json, res_headers, warnings, Some(body))
             .await
     }
+
+    async fn post_form(
+        &self,
+        path: &str,
+        form: &MultipartForm,
+        options: &ImageOptions,
+        warnings: Vec<ImageWarning>,
+    ) -> Result<ImageResponse, SdkError> {
+        let headers = self.build_headers(&options.headers, false)?;
+        let url = self.build_request_url(path);
+        let (json, res_headers) = self
+            .cfg
+            .http
+            .post_multipart(&url, &headers, form, &self.cfg.transport_cfg)
+            .await
+            .map_err(map_transport_error_to_sdk_error)?;
+        self.handle_response(json, res_headers, warnings, None)
+            .await
+    }
+
+    fn options_with_defaults(&self, options: ImageOptions) -> ImageOptions {
+        let scope_name = if self.cfg.provider_scope_name == "openai-compatible" {
+            "openai"
+        } else {
+            &self.cfg.provider_scope_name
+        };
+        apply_provider_defaults(options, scope_name, self.cfg.default_options.as_ref())
+    }
+
+    /// Generate `options.n` images as concurrent calls of at most `per_call`
+    /// images each, keeping image order and summing usage.
+    ///
+    /// Failed calls are reported as warnings naming their images; the call
+    /// only fails when every request does.
+    async fn generate_split(
+        &self,
+        options: &ImageOptions,
+        per_call: u32,
+        mut warnings: Vec<ImageWarning>,
+    ) -> Result<ImageResponse, SdkError> {
+        let total = options.n;
+        let batches: Vec<(u32, u32)> = (0..total)
+            .step_by(per_call as usize)
+            .map(|start| (start, per_call.min(total - start)))
+            .collect();
+        let parallel = options
+            .max_parallel_calls
+            .map_or(DEFAULT_MAX_PARALLEL_IMAGE_CALLS, |max| max.max(1) as usize);
+        let calls: Vec<_> = batches
+            .iter()
+            .map(|&(_, count)| {
+                let options = ImageOptions {
+                    n: count,
+                    ..options.clone()
+                };
+                async move { self.generate_once(&options, Vec::new()).await }
+            })
+            .collect();
+        let results: Vec<Result<ImageResponse, SdkError>> =
+            stream::iter(calls).buffered(parallel).collect().await;
+
+        let mut merged: Option<ImageResponse> = None;
+        let mut first_error = None;
+        let mut request_bodies = Vec::new();
+        let mut response_bodies = Vec::new();
+        for (&(start, count), result) in batches.iter().zip(results) {
+            match result {
+                Ok(response) => {
+                    request_bodies.extend(response.request_body.clone());
+                    response_bodies.extend(response.response_body.clone());
+                    match merged.as_mut() {
+                        Some(merged) => {
+                            merged.images.extend(response.images);
+                            merged.usage = add_usage(merged.usage.take(), response.usage);
+                        }
+                        None => merged = Some(response),
+                    }
+                }
+                Err(err) => {
+                    let images = if count == 1 {
+                        format!("image {}", start + 1)
+                    } else {
+                        format!("images {}-{}", start + 1, start + count)
+                    };
+                    warnings.push(ImageWarning::Other {
+                        message: format!("{images} of {total} failed: {err}"),
+                    });
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match merged {
+            Some(mut response) => {
+                response.warnings = warnings;
+                response.request_body =
+                    (!request_bodies.is_empty()).then(|| JsonValue::Array(request_bodies));
+                response.response_body = Some(JsonValue::Array(response_bodies));
+                Ok(response)
+            }
+            None => Err(first_error.expect("split image call without batches")),
+        }
+    }
+}
+
+/// Width and height of a PNG, or `InvalidArgument` naming `field` when
+/// `bytes` (or its declared media type) is not a PNG.
+fn png_dimensions(
+    field: &str,
+    bytes: &[u8],
+    media_type: Option<&str>,
+) -> Result<(u32, u32), SdkError> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    let not_png = |found: &str| SdkError::InvalidArgument {
+        message: format!("{field} must be a PNG image, got {found}"),
+    };
+    if let Some(media_type) = media_type.filter(|media_type| *media_type != "image/png") {
+        return Err(not_png(media_type));
+    }
+    if bytes.len() < 24 || !bytes.starts_with(SIGNATURE) || &bytes[12..16] != b"IHDR" {
+        return Err(not_png("data without a PNG header"));
+    }
+    let read_u32 =
+        |at: usize| u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+    Ok((read_u32(16), read_u32(20)))
+}
+
+fn add_usage(total: Option<ImageUsage>, next: Option<ImageUsage>) -> Option<ImageUsage> {
+    let sum = |a: Option<u64>, b: Option<u64>| match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    };
+    match (total, next) {
+        (Some(total), Some(next)) => Some(ImageUsage {
+            input_tokens: sum(total.input_tokens, next.input_tokens),
+            output_tokens: sum(total.output_tokens, next.output_tokens),
+            total_tokens: sum(total.total_tokens, next.total_tokens),
+        }),
+        (total, next) => total.or(next),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -380,7 +632,8 @@ struct OpenAICompatibleImageResponse {
 }
 
 #[derive(Debug, Deserialize)]
-struct OpenAICompatibleImageData {
-    #[serde(rename = "b64_json")]
-    b64_json: String,
+#[serde(untagged)]
+enum OpenAICompatibleImageData {
+    Base64 { b64_json: String },
+    Url { url: String },
 }
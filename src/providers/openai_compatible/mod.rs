@@ -1,6 +1,7 @@
 //! OpenAI-compatible provider (completion-first) aligned to Vercel AI SDK.
 
 pub mod error;
+pub mod models;
 pub mod provider;
 mod stream;
 pub mod completion {
@@ -28,6 +29,7 @@ pub use chat::language_model::OpenAICompatibleChatLanguageModel;
 pub use completion::language_model::OpenAICompatibleCompletionLanguageModel;
 pub use embedding::embedding_model::OpenAICompatibleEmbeddingModel;
 pub use image::image_model::OpenAICompatibleImageModel;
+pub use models::list_models;
 pub use provider::{
     build_openai_compatible_embedding, OpenAICompatibleChatBuilder,
     OpenAICompatibleCompletionBuilder, OpenAICompatibleEmbeddingBuilder,
@@ -55,6 +57,10 @@ mod embedding_model_tests;
 #[path = "../../../crates/providers/openai-compatible/tests/image_model_tests.rs"]
 mod image_model_tests;
 
+#[cfg(test)]
+#[path = "../../../crates/providers/openai-compatible/tests/models_tests.rs"]
+mod models_tests;
+
 #[cfg(test)]
 #[path = "../../../crates/providers/openai-compatible/tests/provider_registry_tests.rs"]
 mod provider_registry_tests;
@@ -0,0 +1,81 @@
+//! Model listing from an OpenAI-compatible `/models` endpoint.
+
+use serde_json::Value as JsonValue;
+
+use crate::ai_sdk_core::transport::{HttpMethod, HttpTransport, TransportConfig};
+use crate::ai_sdk_core::SdkError;
+use crate::ai_sdk_types::catalog::{ModelInfo, ModelLimits};
+use crate::provider_openai_compatible::error::map_transport_error_to_sdk_error;
+
+/// GET `{base_url}/models` and map each listed model to a [`ModelInfo`].
+///
+/// Accepts the OpenAI `{"data": [...]}` shape as well as a `models` wrapper
+/// or a bare array. Entries without an `id` (or `name`) are skipped; every
+/// other field is optional.
+pub async fn list_models<T: HttpTransport>(
+    transport: &T,
+    base_url: &str,
+    headers: &[(String, String)],
+    cfg: &TransportConfig,
+) -> Result<Vec<ModelInfo>, SdkError> {
+    let url = format!("{}/models", base_url.trim_end_matches('/'));
+    let (json, _) = transport
+        .request_json(HttpMethod::Get, &url, headers, None, cfg)
+        .await
+        .map_err(map_transport_error_to_sdk_error)?;
+    let entries = match &json {
+        JsonValue::Array(entries) => entries,
+        _ => json
+            .get("data")
+            .or_else(|| json.get("models"))
+            .and_then(JsonValue::as_array)
+            .ok_or_else(|| SdkError::Upstream {
+                status: 200,
+                message: "models response has no model list".into(),
+                source: None,
+            })?,
+    };
+    Ok(entries.iter().filter_map(model_info).collect())
+}
+
+fn model_info(entry: &JsonValue) -> Option<ModelInfo> {
+    let str_field = |key: &str| entry.get(key).and_then(JsonValue::as_str);
+    let u64_field = |key: &str| entry.get(key).and_then(JsonValue::as_u64);
+    let id = match entry {
+        JsonValue::String(id) => id.as_str(),
+        _ => str_field("id").or_else(|| str_field("name"))?,
+    };
+    let context = u64_field("context_length").or_else(|| u64_field("context_window"));
+    let output = u64_field("max_completion_tokens")
+        .or_else(|| u64_field("max_output_tokens"))
+        .or_else(|| {
+            entry
+                .pointer("/top_provider/max_completion_tokens")
+                .and_then(JsonValue::as_u64)
+        });
+    let limits = (context.is_some() || output.is_some()).then(|| ModelLimits {
+        context,
+        output,
+        ..ModelLimits::default()
+    });
+    Some(ModelInfo {
+        id: id.to_string(),
+        display_name: str_field("display_name")
+            .or_else(|| str_field("name"))
+            .unwrap_or(id)
+            .to_string(),
+        provider: str_field("owned_by").map(str::to_string),
+        description: str_field("description").map(str::to_string),
+        release_date: u64_field("created")
+            .and_then(|created| i64::try_from(created).ok())
+            .and_then(|created| chrono::DateTime::from_timestamp(created, 0))
+            .map(|created| created.format("%Y-%m-%d").to_string()),
+        last_updated: None,
+        knowledge: None,
+        capabilities: None,
+        modalities: None,
+        limits,
+        cost: None,
+        open_weights: None,
+    })
+}
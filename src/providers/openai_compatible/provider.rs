@@ -1,6 +1,7 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::ai_sdk_core::headers::HeaderMerger;
 use crate::ai_sdk_core::options as sdkopt;
 use crate::ai_sdk_core::request_builder::defaults::provider_defaults_from_json;
 use crate::ai_sdk_core::transport::TransportConfig;
@@ -147,10 +148,9 @@ impl OpenAICompatibleBuilderBase {
             });
         }
 
-        let mut headers: BTreeMap<String, String> = BTreeMap::from([
-            ("accept".to_string(), "application/json".to_string()),
-            ("content-type".to_string(), "application/json".to_string()),
-        ]);
+        let mut headers = HeaderMerger::for_transport(&self.transport_cfg);
+        headers.insert_default("accept", "application/json");
+        headers.insert_default("content-type", "application/json");
 
         if let Some(bearer) = self.bearer {
             let authorization = if bearer.to_lowercase().starts_with("bearer ") {
@@ -158,38 +158,37 @@ impl OpenAICompatibleBuilderBase {
             } else {
                 format!("Bearer {bearer}")
             };
-            headers.insert("authorization".into(), authorization);
+            headers.force("authorization", authorization);
         } else if let Some(api_key) = self.api_key {
-            headers.insert("authorization".into(), format!("Bearer {api_key}"));
+            headers.force("authorization", format!("Bearer {api_key}"));
         }
 
         for (key, value) in self.headers {
-            let key = key.to_ascii_lowercase();
             if matches!(
-                key.as_str(),
+                key.to_ascii_lowercase().as_str(),
                 "accept" | "authorization" | "content-type" | "x-api-key"
             ) || sdkopt::is_internal_sdk_header(&key)
             {
                 continue;
             }
-            headers.insert(key, value);
+            headers.insert(&key, value)?;
         }
 
         let user_agent_suffix = format!("ai-sdk/openai-compatible/{}", env!("CARGO_PKG_VERSION"));
-        let user_agent = headers.remove("user-agent").unwrap_or_default();
-        let user_agent = if user_agent.trim().is_empty() {
-            user_agent_suffix
-        } else {
-            format!("{user_agent} {user_agent_suffix}")
+        let user_agent = match headers.get("user-agent") {
+            Some(user_agent) if !user_agent.trim().is_empty() => {
+                format!("{user_agent} {user_agent_suffix}")
+            }
+            _ => user_agent_suffix,
         };
-        headers.insert("user-agent".into(), user_agent);
+        headers.force("user-agent", user_agent);
 
         let transport_cfg = self.transport_cfg;
         let http = crate::reqwest_transport::ReqwestTransport::try_new(&transport_cfg)
             .map_err(SdkError::Transport)?;
         let base = BaseConfig {
             base_url,
-            headers: headers.into_iter().collect(),
+            headers: headers.into_pairs(),
             http,
             transport_cfg,
             query_params: self.query_params,
@@ -277,6 +276,9 @@ pub struct OpenAICompatibleChatBuilder {
     base: OpenAICompatibleBuilderBase,
     include_usage: bool,
     supports_structured_outputs: bool,
+    field_aliases: HashMap<String, String>,
+    done_sentinel: Option<serde_json::Value>,
+    tool_limits: v2t::ToolLimits,
 }
 
 impl OpenAICompatibleChatBuilder {
@@ -285,6 +287,9 @@ impl OpenAICompatibleChatBuilder {
             base: OpenAICompatibleBuilderBase::new(model_id),
             include_usage: true,
             supports_structured_outputs: false,
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
+            tool_limits: v2t::ToolLimits::default(),
         }
     }
 
@@ -293,11 +298,47 @@ impl OpenAICompatibleChatBuilder {
         self
     }
 
+    /// Rename a top-level request body key before sending, e.g.
+    /// `max_tokens` -> `max_new_tokens`. Nested keys are not remapped.
+    pub fn with_field_alias(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.field_aliases.insert(from.into(), to.into());
+        self
+    }
+
+    /// [`with_field_alias`](Self::with_field_alias) for each `(from, to)` pair.
+    pub fn with_field_aliases<I, K, V>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.field_aliases.extend(
+            aliases
+                .into_iter()
+                .map(|(from, to)| (from.into(), to.into())),
+        );
+        self
+    }
+
+    /// End the stream at a chunk containing every field of `sentinel`, e.g.
+    /// `{"done": true}`, for servers that never send `[DONE]`.
+    pub fn with_done_sentinel(mut self, sentinel: serde_json::Value) -> Self {
+        self.done_sentinel = Some(sentinel);
+        self
+    }
+
     pub fn with_structured_outputs(mut self, supports_structured_outputs: bool) -> Self {
         self.supports_structured_outputs = supports_structured_outputs;
         self
     }
 
+    /// Tool count and schema size limits the server enforces, checked
+    /// before each request. No limits are checked by default.
+    pub fn with_tool_limits(mut self, tool_limits: v2t::ToolLimits) -> Self {
+        self.tool_limits = tool_limits;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<
@@ -321,6 +362,9 @@ impl OpenAICompatibleChatBuilder {
                 query_params: base.query_params,
                 supports_structured_outputs: self.supports_structured_outputs,
                 default_options: base.default_options,
+                field_aliases: self.field_aliases,
+                done_sentinel: self.done_sentinel,
+                tool_limits: self.tool_limits,
             },
         ))
     }
@@ -332,6 +376,8 @@ impl_openai_compatible_builder_common!(OpenAICompatibleChatBuilder);
 pub struct OpenAICompatibleCompletionBuilder {
     base: OpenAICompatibleBuilderBase,
     include_usage: bool,
+    field_aliases: HashMap<String, String>,
+    done_sentinel: Option<serde_json::Value>,
 }
 
 impl OpenAICompatibleCompletionBuilder {
@@ -339,6 +385,8 @@ impl OpenAICompatibleCompletionBuilder {
         Self {
             base: OpenAICompatibleBuilderBase::new(model_id),
             include_usage: true,
+            field_aliases: HashMap::new(),
+            done_sentinel: None,
         }
     }
 
@@ -347,6 +395,35 @@ impl OpenAICompatibleCompletionBuilder {
         self
     }
 
+    /// Rename a top-level request body key before sending, e.g.
+    /// `max_tokens` -> `max_new_tokens`. Nested keys are not remapped.
+    pub fn with_field_alias(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.field_aliases.insert(from.into(), to.into());
+        self
+    }
+
+    /// [`with_field_alias`](Self::with_field_alias) for each `(from, to)` pair.
+    pub fn with_field_aliases<I, K, V>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.field_aliases.extend(
+            aliases
+                .into_iter()
+                .map(|(from, to)| (from.into(), to.into())),
+        );
+        self
+    }
+
+    /// End the stream at a chunk containing every field of `sentinel`, e.g.
+    /// `{"done": true}`, for servers that never send `[DONE]`.
+    pub fn with_done_sentinel(mut self, sentinel: serde_json::Value) -> Self {
+        self.done_sentinel = Some(sentinel);
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<
@@ -372,6 +449,8 @@ impl OpenAICompatibleCompletionBuilder {
                 )]),
                 query_params: base.query_params,
                 default_options: base.default_options,
+                field_aliases: self.field_aliases,
+                done_sentinel: self.done_sentinel,
             },
         ))
     }
@@ -492,7 +571,8 @@ fn build_openai_compatible(
         )
         .with_query_params(collect_query_params(def))
         .with_transport_config(build_provider_transport_config(def, None))
-        .with_include_usage(include_usage_flag);
+        .with_include_usage(include_usage_flag)
+        .with_field_aliases(def.field_aliases.clone());
     if let Some(default_options) = extract_default_options(def) {
         builder = builder.with_default_options(default_options);
     }
@@ -526,7 +606,9 @@ pub fn build_openai_compatible_chat(
         .with_query_params(collect_query_params(def))
         .with_transport_config(build_provider_transport_config(def, None))
         .with_include_usage(include_usage_flag)
-        .with_structured_outputs(supports_structured_outputs_flag);
+        .with_structured_outputs(supports_structured_outputs_flag)
+        .with_field_aliases(def.field_aliases.clone())
+        .with_tool_limits(def.tool_limits);
     if let Some(default_options) = extract_default_options(def) {
         builder = builder.with_default_options(default_options);
     }
@@ -2,6 +2,7 @@ use crate::ai_sdk_core::transport::{HttpTransport, TransportConfig};
 use crate::ai_sdk_core::{SdkError, StreamNormalizationState, StreamResponse};
 use crate::ai_sdk_streaming_sse::SseDecoder;
 use crate::ai_sdk_types::json::parse_json_loose;
+use crate::ai_sdk_types::usage::token_count;
 use crate::ai_sdk_types::v2 as v2t;
 use async_stream::try_stream;
 use bytes::Bytes;
@@ -24,6 +25,33 @@ pub struct StreamSettings {
     pub include_raw: bool,
     pub include_usage: bool,
     pub provider_scope_name: String,
+    /// Renames applied to top-level request body keys just before sending;
+    /// see [`apply_field_aliases`].
+    pub field_aliases: HashMap<String, String>,
+    /// JSON chunk that ends the stream like `[DONE]`, for servers with a
+    /// custom terminator; see [`is_done_sentinel`].
+    pub done_sentinel: Option<JsonValue>,
+}
+
+/// Whether an SSE `data` payload is the `[DONE]` terminator, ignoring case
+/// and surrounding whitespace.
+pub fn is_done_marker(data: &[u8]) -> bool {
+    std::str::from_utf8(data).is_ok_and(|data| data.trim().eq_ignore_ascii_case("[DONE]"))
+}
+
+/// Whether `chunk` carries every field of `sentinel` with the same value, so
+/// `{"done": true}` also matches a final chunk that includes usage.
+pub fn is_done_sentinel(chunk: &JsonValue, sentinel: &JsonValue) -> bool {
+    match (chunk, sentinel) {
+        (JsonValue::Object(chunk), JsonValue::Object(sentinel)) => {
+            sentinel.iter().all(|(key, expected)| {
+                chunk
+                    .get(key)
+                    .is_some_and(|actual| is_done_sentinel(actual, expected))
+            })
+        }
+        _ => chunk == sentinel,
+    }
 }
 
 #[derive(Default)]
@@ -72,7 +100,7 @@ where
         macro_rules! handle_sse_event {
             ($ev:expr) => {{
                 let ev = $ev;
-                if ev.data.as_ref() == b"[DONE]" {
+                if is_done_marker(&ev.data) {
                     for part in emit_finish(
                         mode,
                         &mut chat_state,
@@ -137,6 +165,25 @@ where
                 for part in parts {
                     yield part;
                 }
+
+                if settings
+                    .done_sentinel
+                    .as_ref()
+                    .is_some_and(|sentinel| is_done_sentinel(&val, sentinel))
+                {
+                    for part in emit_finish(
+                        mode,
+                        &mut chat_state,
+                        &mut completion_state,
+                        completion_started,
+                        usage.clone(),
+                        finish_reason,
+                        provider_metadata.clone(),
+                    ) {
+                        yield part;
+                    }
+                    return;
+                }
             }};
         }
 
@@ -246,6 +293,7 @@ pub async fn start_streaming<T: HttpTransport + Send + Sync>(
             );
         }
     }
+    apply_field_aliases(&mut body, &settings.field_aliases);
 
     // Ensure content-type for SSE
     if !headers
@@ -269,9 +317,28 @@ pub async fn start_streaming<T: HttpTransport + Send + Sync>(
         stream: part_stream,
         request_body: Some(body),
         response_headers: Some(headers_map),
+        raw_response: None,
     })
 }
 
+/// Rename top-level keys of `body` from each alias key to its value, for
+/// servers that use different names for standard fields (e.g.
+/// `max_tokens` -> `max_new_tokens`). Only top-level keys are remapped;
+/// nested objects such as messages and tools are sent unchanged. Renames are
+/// applied together, so aliases can swap names without chaining.
+pub fn apply_field_aliases(body: &mut JsonValue, aliases: &HashMap<String, String>) {
+    let Some(map) = body.as_object_mut() else {
+        return;
+    };
+    let renamed: Vec<(String, JsonValue)> = aliases
+        .iter()
+        .filter_map(|(from, to)| map.remove(from).map(|value| (to.clone(), value)))
+        .collect();
+    for (key, value) in renamed {
+        map.insert(key, value);
+    }
+}
+
 fn update_usage(
     include_usage: bool,
     usage: &mut v2t::Usage,
@@ -295,7 +362,7 @@ fn update_usage(
         if let Some(cache_read) = u
             .get("prompt_tokens_details")
             .and_then(|v| v.get("cached_tokens"))
-            .and_then(|v| v.as_u64())
+            .and_then(token_count)
         {
             usage.cached_input_tokens = Some(cache_read);
         }
@@ -303,15 +370,16 @@ fn update_usage(
         if let Some(reasoning) = u
             .get("completion_tokens_details")
             .and_then(|v| v.get("reasoning_tokens"))
-            .and_then(|v| v.as_u64())
+            .and_then(token_count)
         {
             usage.reasoning_tokens = Some(reasoning);
         }
+        crate::ai_sdk_types::usage::apply_openai_modality_details(u, usage);
 
         if let Some(accepted) = u
             .get("completion_tokens_details")
             .and_then(|v| v.get("accepted_prediction_tokens"))
-            .and_then(|v| v.as_u64())
+            .and_then(token_count)
         {
             set_provider_metadata_value(
                 provider_metadata,
@@ -323,7 +391,7 @@ fn update_usage(
         if let Some(rejected) = u
             .get("completion_tokens_details")
             .and_then(|v| v.get("rejected_prediction_tokens"))
-            .and_then(|v| v.as_u64())
+            .and_then(token_count)
         {
             set_provider_metadata_value(
                 provider_metadata,
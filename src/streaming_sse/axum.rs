@@ -0,0 +1,131 @@
+//! axum responder that streams model output to a browser client.
+//!
+//! Enabled with the `integrations-axum` feature.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures_util::{stream, StreamExt};
+
+use super::ui_message::{
+    ui_message_chunk, ui_message_error_chunk, UI_MESSAGE_STREAM_DONE, UI_MESSAGE_STREAM_HEADER,
+};
+use crate::core::PartStream;
+
+/// Interval between SSE keep-alive comments while the model is idle.
+pub const SSE_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Turn a model [`PartStream`] into an SSE response speaking the AI SDK UI
+/// message stream protocol.
+///
+/// Stream errors are sent as `error` chunks and end the stream. When the
+/// client disconnects, axum drops the response body and with it the upstream
+/// stream, which cancels the in-flight provider request.
+pub fn sse_response(parts: PartStream) -> impl IntoResponse {
+    let events = parts
+        .scan(false, |failed, item| {
+            if *failed {
+                return std::future::ready(None);
+            }
+            let payload = match item {
+                Ok(part) => ui_message_chunk(&part),
+                Err(err) => {
+                    *failed = true;
+                    Some(ui_message_error_chunk(&err))
+                }
+            };
+            std::future::ready(Some(payload))
+        })
+        .filter_map(|payload| {
+            std::future::ready(payload.map(|value| Event::default().data(value.to_string())))
+        })
+        .chain(stream::once(std::future::ready(
+            Event::default().data(UI_MESSAGE_STREAM_DONE),
+        )))
+        .map(Ok::<_, Infallible>);
+
+    let mut response: Response = Sse::new(events)
+        .keep_alive(KeepAlive::new().interval(SSE_KEEP_ALIVE_INTERVAL))
+        .into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        HeaderName::from_static(UI_MESSAGE_STREAM_HEADER.0),
+        HeaderValue::from_static(UI_MESSAGE_STREAM_HEADER.1),
+    );
+    headers.insert(
+        HeaderName::from_static("x-accel-buffering"),
+        HeaderValue::from_static("no"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sse_response;
+    use crate::core::PartStream;
+    use crate::types::v2 as v2t;
+    use axum::body::to_bytes;
+    use axum::response::IntoResponse;
+    use futures_util::{stream, StreamExt};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn sse_response_encodes_parts_and_sets_headers() {
+        let parts: PartStream = Box::pin(stream::iter(vec![
+            Ok(v2t::StreamPart::StreamStart { warnings: vec![] }),
+            Ok(v2t::StreamPart::TextDelta {
+                id: "t1".into(),
+                delta: "Hi".into(),
+                provider_metadata: None,
+            }),
+            Ok(v2t::StreamPart::Finish {
+                usage: v2t::Usage::default(),
+                finish_reason: v2t::FinishReason::Stop,
+                provider_metadata: None,
+            }),
+        ]));
+
+        let response = sse_response(parts).into_response();
+        let headers = response.headers();
+        assert_eq!(headers["content-type"], "text/event-stream");
+        assert_eq!(headers["x-vercel-ai-ui-message-stream"], "v1");
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            "data: {\"type\":\"start\"}\n\n\
+             data: {\"type\":\"text-delta\",\"id\":\"t1\",\"delta\":\"Hi\"}\n\n\
+             data: {\"type\":\"finish\"}\n\n\
+             data: [DONE]\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn dropping_response_drops_upstream_stream() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = DropFlag(dropped.clone());
+        let parts: PartStream = Box::pin(stream::pending::<Result<v2t::StreamPart, _>>().map(
+            move |item| {
+                let _ = &guard;
+                item
+            },
+        ));
+
+        let response = sse_response(parts).into_response();
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(response);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
@@ -4,14 +4,18 @@
 //!
 //! This crate provides:
 //! - `SseEvent`: Core SSE event representation
-//! - `SseDecoder`: Incremental SSE frame decoder
+//! - `SseDecoder`: Incremental SSE frame decoder with optional event queue and frame size caps
 //! - `ProviderChunk`: Trait for provider-specific event parsing
+//! - `ui_message_chunk`: Encoder from model stream parts to the UI message stream protocol
 
-use crate::core::error::SdkError;
+use crate::core::error::{SdkError, TransportError};
 use crate::types::Event;
 use bytes::Bytes;
 use std::collections::VecDeque;
 
+/// UTF-8 byte order mark some proxies prepend to the stream
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 /// Represents a single Server-Sent Event
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SseEvent {
@@ -49,6 +53,41 @@ impl SseEvent {
     }
 }
 
+/// What the decoder does once its event queue reaches the configured cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SseOverflowPolicy {
+    /// Leave further frames undecoded in the byte buffer until the consumer
+    /// has taken queued events.
+    Backpressure,
+    /// Fail [`SseDecoder::try_push`] when a chunk holds more complete frames
+    /// than the cap.
+    Error,
+}
+
+/// Error reported by [`SseDecoder::try_push`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SseError {
+    /// A chunk held more complete frames than the event queue cap under
+    /// [`SseOverflowPolicy::Error`].
+    #[error("SSE event queue exceeded {max} events")]
+    QueueOverflow { max: usize },
+    /// The buffered bytes passed the limit set with
+    /// [`SseDecoder::with_limit`] without a frame terminator.
+    #[error("SSE frame exceeded {limit} bytes without a terminator ({buffered} bytes buffered)")]
+    FrameTooLarge { limit: usize, buffered: usize },
+    /// Complete frames waiting behind a full event queue would take the
+    /// buffer past the [`SseDecoder::with_limit`] cap. The chunk was not
+    /// buffered; take the queued events and push it again.
+    #[error("SSE buffer would exceed {limit} bytes while the event queue is full ({buffered} bytes)")]
+    BufferFull { limit: usize, buffered: usize },
+}
+
+impl From<SseError> for SdkError {
+    fn from(err: SseError) -> Self {
+        SdkError::Transport(TransportError::Other(err.to_string()))
+    }
+}
+
 /// Incremental SSE decoder that handles chunk boundaries correctly
 pub struct SseDecoder {
     /// Internal buffer for incomplete frames
@@ -57,32 +96,158 @@ pub struct SseDecoder {
     current_event: EventBuilder,
     /// Queue of completed events ready to be yielded
     event_queue: VecDeque<SseEvent>,
+    /// High-water mark for `event_queue`; `None` is unbounded
+    max_queued_events: Option<usize>,
+    overflow_policy: SseOverflowPolicy,
+    /// Cap on buffered bytes, whether an unterminated frame or complete
+    /// frames held back by a full queue; `None` is unbounded
+    max_buffer_bytes: Option<usize>,
+    /// Most recent non-empty `id:` field, kept across frames
+    last_event_id: Option<String>,
+    /// Whether the start of the stream has been checked for a byte order mark
+    bom_checked: bool,
 }
 
 impl SseDecoder {
     /// Create a new SSE decoder
+    ///
+    /// The event queue is unbounded: every complete frame in a pushed chunk
+    /// is decoded at once.
     pub fn new() -> Self {
         Self {
             buffer: Vec::new(),
             current_event: EventBuilder::new(),
             event_queue: VecDeque::new(),
+            max_queued_events: None,
+            overflow_policy: SseOverflowPolicy::Backpressure,
+            max_buffer_bytes: None,
+            last_event_id: None,
+            bom_checked: false,
+        }
+    }
+
+    /// Create a decoder that buffers at most `max_buffer_bytes` of undecoded
+    /// data
+    ///
+    /// A stream that never sends the blank line ending a frame would
+    /// otherwise grow the buffer without bound. Past the limit the partial
+    /// frame is discarded and [`try_push`](Self::try_push) reports
+    /// [`SseError::FrameTooLarge`]. The cap also covers complete frames left
+    /// undecoded behind a full event queue: a chunk that would pass it is
+    /// not buffered and `try_push` reports [`SseError::BufferFull`].
+    pub fn with_limit(max_buffer_bytes: usize) -> Self {
+        Self {
+            max_buffer_bytes: Some(max_buffer_bytes),
+            ..Self::new()
         }
     }
 
+    /// Cap the number of decoded events held at once (minimum 1)
+    ///
+    /// With [`SseOverflowPolicy::Backpressure`] remaining frames are decoded
+    /// as the returned iterator is consumed. With [`SseOverflowPolicy::Error`]
+    /// [`try_push`](Self::try_push) fails instead; `push` always applies
+    /// backpressure.
+    pub fn with_max_queued_events(mut self, max: usize, policy: SseOverflowPolicy) -> Self {
+        self.max_queued_events = Some(max.max(1));
+        self.overflow_policy = policy;
+        self
+    }
+
     /// Push new data chunk and get any complete events
     ///
     /// This method handles partial chunks correctly and will buffer
-    /// incomplete events until they are complete.
-    pub fn push(&mut self, chunk: &[u8]) -> impl Iterator<Item = SseEvent> + '_ {
-        self.buffer.extend_from_slice(chunk);
+    /// incomplete events until they are complete. A UTF-8 byte order mark at
+    /// the start of the stream is dropped. A frame passing the
+    /// [`with_limit`](Self::with_limit) cap is dropped silently, as is a
+    /// chunk that would pass it while queued events are not being taken.
+    pub fn push(&mut self, chunk: &[u8]) -> SseEvents<'_> {
+        if self.check_backlog(chunk.len()).is_err() {
+            return SseEvents { decoder: self };
+        }
+        self.append(chunk);
         self.process_buffer();
-        self.event_queue.drain(..)
+        let _ = self.check_frame_limit();
+        SseEvents { decoder: self }
+    }
+
+    /// Like [`push`](Self::push), but reports an overflow of the event queue
+    /// cap under [`SseOverflowPolicy::Error`] and a buffer past the
+    /// [`with_limit`](Self::with_limit) cap as errors.
+    pub fn try_push(&mut self, chunk: &[u8]) -> Result<SseEvents<'_>, SseError> {
+        self.check_backlog(chunk.len())?;
+        self.append(chunk);
+        self.process_buffer();
+        self.check_frame_limit()?;
+        if let Some(max) = self.max_queued_events {
+            if self.overflow_policy == SseOverflowPolicy::Error
+                && self.queue_full()
+                && find_event_terminator(&self.buffer).is_some()
+            {
+                return Err(SseError::QueueOverflow { max });
+            }
+        }
+        Ok(SseEvents { decoder: self })
+    }
+
+    /// Refuse `incoming` more bytes when complete frames are already waiting
+    /// behind a full queue and the buffer would pass `max_buffer_bytes`.
+    fn check_backlog(&self, incoming: usize) -> Result<(), SseError> {
+        let Some(limit) = self.max_buffer_bytes else {
+            return Ok(());
+        };
+        let buffered = self.buffer.len() + incoming;
+        if buffered <= limit
+            || !self.queue_full()
+            || find_event_terminator(&self.buffer).is_none()
+        {
+            return Ok(());
+        }
+        Err(SseError::BufferFull { limit, buffered })
+    }
+
+    /// Discard the buffer when it passed `max_buffer_bytes` without holding a
+    /// complete frame.
+    fn check_frame_limit(&mut self) -> Result<(), SseError> {
+        let Some(limit) = self.max_buffer_bytes else {
+            return Ok(());
+        };
+        let buffered = self.buffer.len();
+        if buffered <= limit || find_event_terminator(&self.buffer).is_some() {
+            return Ok(());
+        }
+        self.buffer.clear();
+        self.current_event = EventBuilder::new();
+        Err(SseError::FrameTooLarge { limit, buffered })
+    }
+
+    /// Buffer `chunk`, stripping a leading BOM even when it is split across
+    /// the first chunks.
+    fn append(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+        if self.bom_checked {
+            return;
+        }
+        if self.buffer.starts_with(UTF8_BOM) {
+            self.buffer.drain(..UTF8_BOM.len());
+            self.bom_checked = true;
+        } else if !UTF8_BOM.starts_with(&self.buffer) {
+            self.bom_checked = true;
+        }
+    }
+
+    fn queue_full(&self) -> bool {
+        self.max_queued_events
+            .is_some_and(|max| self.event_queue.len() >= max)
     }
 
     /// Process the internal buffer to extract complete events
     fn process_buffer(&mut self) {
-        // Process all complete frames (ending with a blank line)
-        while let Some(frame_end) = find_event_terminator(&self.buffer) {
+        // Process complete frames (ending with a blank line) up to the cap
+        while !self.queue_full() {
+            let Some(frame_end) = find_event_terminator(&self.buffer) else {
+                break;
+            };
             // Extract frame including the terminator characters
             let frame = self.buffer.drain(..=frame_end).collect::<Vec<_>>();
 
@@ -114,13 +279,22 @@ impl SseDecoder {
                     let value = rest.trim_start_matches(':').trim_start();
                     (field, value)
                 } else {
+                    if line == "data" {
+                        self.current_event.append_bare_data();
+                        continue;
+                    }
                     (line, "")
                 };
 
                 match field {
                     "data" => self.current_event.append_data(value),
                     "event" => self.current_event.set_event(value),
-                    "id" => self.current_event.set_id(value),
+                    "id" => {
+                        self.current_event.set_id(value);
+                        if !value.is_empty() {
+                            self.last_event_id = Some(value.to_string());
+                        }
+                    }
                     "retry" => {
                         if let Ok(retry) = value.parse::<u64>() {
                             self.current_event.set_retry(retry);
@@ -140,6 +314,24 @@ impl SseDecoder {
         }
     }
 
+    /// Most recent non-empty `id:` field seen in a complete frame
+    ///
+    /// Frames without an id keep the previous value, so this is what a
+    /// reconnecting client sends as `Last-Event-ID`. An id in a frame still
+    /// waiting for its terminator is not reported yet.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Drop a partially received frame before decoding a new connection
+    ///
+    /// Queued events and [`last_event_id`](Self::last_event_id) are kept.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.current_event = EventBuilder::new();
+        self.bom_checked = false;
+    }
+
     /// Check if decoder has buffered data that might be incomplete
     pub fn has_buffered_data(&self) -> bool {
         !self.buffer.is_empty() || self.current_event.has_data()
@@ -151,7 +343,7 @@ impl SseDecoder {
     /// payload without sending the trailing blank line that normally
     /// terminates an SSE event. Appending a synthetic blank line ensures the
     /// final event is emitted instead of being dropped on stream shutdown.
-    pub fn finish(&mut self) -> impl Iterator<Item = SseEvent> + '_ {
+    pub fn finish(&mut self) -> SseEvents<'_> {
         if self.has_buffered_data() {
             // Append a synthetic blank line (`\n\n`) and process it like a
             // normal frame boundary. This handles `\r`, `\n`, and `\r\n`
@@ -160,7 +352,26 @@ impl SseDecoder {
             self.buffer.extend_from_slice(b"\n\n");
             self.process_buffer();
         }
-        self.event_queue.drain(..)
+        SseEvents { decoder: self }
+    }
+}
+
+/// Events decoded from pushed data, returned by [`SseDecoder::push`]
+///
+/// When the queue is capped, further buffered frames are decoded as the
+/// iterator drains the queue. Events not taken stay queued for the next call.
+pub struct SseEvents<'a> {
+    decoder: &'a mut SseDecoder,
+}
+
+impl Iterator for SseEvents<'_> {
+    type Item = SseEvent;
+
+    fn next(&mut self) -> Option<SseEvent> {
+        if self.decoder.event_queue.is_empty() {
+            self.decoder.process_buffer();
+        }
+        self.decoder.event_queue.pop_front()
     }
 }
 
@@ -174,6 +385,7 @@ impl Default for SseDecoder {
 struct EventBuilder {
     event: Option<String>,
     data_parts: Vec<String>,
+    has_data: bool,
     id: Option<String>,
     retry: Option<u64>,
 }
@@ -183,15 +395,27 @@ impl EventBuilder {
         Self {
             event: None,
             data_parts: Vec::new(),
+            has_data: false,
             id: None,
             retry: None,
         }
     }
 
     fn append_data(&mut self, data: &str) {
+        // Empty `data:` lines are used as heartbeats by some providers; they
+        // must not turn an otherwise empty frame into an event. They are
+        // still kept so blank lines inside a multi-line payload survive.
+        self.has_data |= !data.is_empty();
         self.data_parts.push(data.to_string());
     }
 
+    /// A bare `data` line without a colon, which the spec defines as an
+    /// explicit empty data field.
+    fn append_bare_data(&mut self) {
+        self.has_data = true;
+        self.data_parts.push(String::new());
+    }
+
     fn set_event(&mut self, event: &str) {
         self.event = Some(event.to_string());
     }
@@ -205,15 +429,21 @@ impl EventBuilder {
     }
 
     fn has_data(&self) -> bool {
-        !self.data_parts.is_empty()
+        self.has_data
     }
 
     fn build(self) -> Option<SseEvent> {
-        if self.data_parts.is_empty() {
+        if !self.has_data {
             return None;
         }
 
-        let data = self.data_parts.join("\n");
+        // Heartbeats before the payload are not part of it.
+        let start = self
+            .data_parts
+            .iter()
+            .position(|part| !part.is_empty())
+            .unwrap_or(0);
+        let data = self.data_parts[start..].join("\n");
         Some(SseEvent {
             event: self.event,
             data: Bytes::from(data),
@@ -270,6 +500,16 @@ pub trait ProviderChunk {
     /// - `Ok(None)` if the SSE event should be ignored (e.g., heartbeat)
     /// - `Err(e)` if parsing failed
     fn try_from_sse(&mut self, event: &SseEvent) -> Result<Option<Vec<Event>>, SdkError>;
+
+    /// Events to emit when the byte stream ends before a terminal event
+    /// (`Done` or `Error`) was produced.
+    ///
+    /// Returns `None` by default, which reports an unexpected EOF. Parsers
+    /// that already know the response failed (e.g. after a provider error
+    /// event) can return their closing events instead.
+    fn on_eof(&mut self) -> Option<Vec<Event>> {
+        None
+    }
 }
 
 // Optional: Stream support when the feature is enabled
@@ -277,8 +517,23 @@ pub trait ProviderChunk {
 pub mod stream;
 
 #[cfg(feature = "stream")]
-pub use stream::{SseStream, SseStreamExt};
+pub use stream::{ReconnectAttempt, SseStream, SseStreamExt};
 
 // Pipeline module for unified SSE to Event conversion
 pub mod pipeline;
 pub use pipeline::{sse_to_events, PipelineBuilder};
+
+// UI message stream encoding for serving model output to clients
+pub mod ui_message;
+pub use ui_message::{ui_message_chunk, UI_MESSAGE_STREAM_DONE, UI_MESSAGE_STREAM_HEADER};
+
+// Optional: axum responder when the integration feature is enabled
+#[cfg(feature = "integrations-axum")]
+pub mod axum;
+
+#[cfg(feature = "integrations-axum")]
+pub use self::axum::sse_response;
+
+#[cfg(test)]
+#[path = "../../crates/streaming-sse/tests/decoder_tests.rs"]
+mod decoder_tests;
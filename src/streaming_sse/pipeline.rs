@@ -4,7 +4,7 @@
 //! into typed Event streams using provider-specific parsers.
 
 use crate::ai_sdk_core::SdkError;
-use crate::ai_sdk_streaming_sse::{ProviderChunk, SseDecoder};
+use crate::ai_sdk_streaming_sse::{ProviderChunk, SseDecoder, SseOverflowPolicy};
 use crate::ai_sdk_types::Event;
 use bytes::Bytes;
 use futures_core::Stream;
@@ -33,13 +33,31 @@ use std::marker::PhantomData;
 /// let events = sse_to_events::<_, MyProviderChunk, _>(byte_stream);
 /// ```
 pub fn sse_to_events<S, P, E>(bytes: S) -> impl Stream<Item = Result<Event, SdkError>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    P: ProviderChunk + Default + Send + 'static,
+    E: Into<SdkError> + Send + 'static,
+{
+    decode_events::<S, P, E>(bytes, SseDecoder::new(), false)
+}
+
+/// Shared decode loop behind [`sse_to_events`] and [`PipelineBuilder`].
+///
+/// When the byte stream ends, [`SseDecoder::finish`] flushes a final frame
+/// that arrived without its terminating blank line before EOF is reported
+/// through [`ProviderChunk::on_eof`].
+fn decode_events<S, P, E>(
+    bytes: S,
+    decoder: SseDecoder,
+    include_raw: bool,
+) -> impl Stream<Item = Result<Event, SdkError>>
 where
     S: Stream<Item = Result<Bytes, E>> + Send + 'static,
     P: ProviderChunk + Default + Send + 'static,
     E: Into<SdkError> + Send + 'static,
 {
     async_stream::try_stream! {
-        let mut decoder = SseDecoder::new();
+        let mut decoder = decoder;
         let mut parser = P::default();
         futures_util::pin_mut!(bytes);
         // Per-stream transformation state (across all chunks)
@@ -87,11 +105,24 @@ where
             }
         };
 
-        while let Some(chunk_result) = bytes.next().await {
-            let chunk = chunk_result.map_err(|e| e.into())?;
+        let mut ended = false;
+        while !ended {
+            let chunk = match bytes.next().await {
+                Some(chunk_result) => Some(chunk_result.map_err(|e| e.into())?),
+                None => None,
+            };
+            let sse_events = match &chunk {
+                Some(chunk) => decoder.try_push(chunk)?,
+                None => {
+                    ended = true;
+                    decoder.finish()
+                }
+            };
 
-            // Process all complete SSE events from this chunk
-            for sse_event in decoder.push(&chunk) {
+            for sse_event in sse_events {
+                if include_raw {
+                    yield Event::Raw { raw_value: raw_value(&sse_event.data) };
+                }
                 let events = parser.try_from_sse(&sse_event)?.unwrap_or_default();
                 for event in events {
                     let (out, stop) = map_event(event);
@@ -105,29 +136,39 @@ where
             }
         }
 
-        for sse_event in decoder.finish() {
-            let events = parser.try_from_sse(&sse_event)?.unwrap_or_default();
-            for event in events {
-                let (out, stop) = map_event(event);
-                for ev in out {
-                    yield ev;
-                }
-                if stop {
-                    return;
+        match parser.on_eof() {
+            Some(events) => {
+                for event in events {
+                    let (out, stop) = map_event(event);
+                    for ev in out {
+                        yield ev;
+                    }
+                    if stop {
+                        return;
+                    }
                 }
             }
+            None => {
+                yield Event::Error {
+                    message: "Unexpected EOF".into(),
+                };
+            }
         }
-
-        yield Event::Error {
-            message: "Unexpected EOF".into(),
-        };
     }
 }
 
+/// Raw SSE payload as JSON when it parses, otherwise as a string.
+fn raw_value(data: &[u8]) -> serde_json::Value {
+    serde_json::from_slice(data)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(data).to_string()))
+}
+
 /// Builder for configuring SSE to Event pipeline
 pub struct PipelineBuilder<P> {
     provider_name: Option<&'static str>,
     include_raw: bool,
+    max_queued_events: Option<(usize, SseOverflowPolicy)>,
+    max_buffer_bytes: Option<usize>,
     _phantom: PhantomData<P>,
 }
 
@@ -137,6 +178,8 @@ impl<P: ProviderChunk + Default + Send + 'static> PipelineBuilder<P> {
         Self {
             provider_name: None,
             include_raw: false,
+            max_queued_events: None,
+            max_buffer_bytes: None,
             _phantom: PhantomData,
         }
     }
@@ -154,151 +197,37 @@ impl<P: ProviderChunk + Default + Send + 'static> PipelineBuilder<P> {
         self
     }
 
+    /// Cap the decoder's queue of decoded SSE events; see
+    /// [`SseDecoder::with_max_queued_events`]. Default is unbounded.
+    ///
+    /// With [`SseOverflowPolicy::Error`] a chunk holding more frames than
+    /// `max` ends the stream with an error.
+    pub fn max_queued_events(mut self, max: usize, policy: SseOverflowPolicy) -> Self {
+        self.max_queued_events = Some((max, policy));
+        self
+    }
+
+    /// Fail the stream once buffered SSE data passes `max` bytes; see
+    /// [`SseDecoder::with_limit`]. Default is unbounded.
+    pub fn max_buffer_bytes(mut self, max: usize) -> Self {
+        self.max_buffer_bytes = Some(max);
+        self
+    }
+
     /// Build the pipeline for the given byte stream
     pub fn build<S, E>(self, bytes: S) -> impl Stream<Item = Result<Event, SdkError>>
     where
         S: Stream<Item = Result<Bytes, E>> + Send + 'static,
         E: Into<SdkError> + Send + 'static,
     {
-        let include_raw = self.include_raw;
-        match self.provider_name {
-            Some(_provider_name) => {
-                // Inline pipeline with raw support
-                async_stream::try_stream! {
-                    let mut decoder = SseDecoder::new();
-                    let mut parser = P::default();
-                    futures_util::pin_mut!(bytes);
-                    static REASONING_ID: &str = "reasoning:0";
-                    let mut reasoning_started = false;
-
-                    while let Some(chunk_result) = bytes.next().await {
-                        let chunk = chunk_result.map_err(|e| e.into())?;
-
-                        for sse_event in decoder.push(&chunk) {
-                            if include_raw {
-                                let raw_val: serde_json::Value = match serde_json::from_slice(&sse_event.data) {
-                                    Ok(v) => v,
-                                    Err(_) => serde_json::Value::String(String::from_utf8_lossy(&sse_event.data).to_string()),
-                                };
-                                yield Event::Raw { raw_value: raw_val };
-                            }
-
-                            if let Some(events) = parser.try_from_sse(&sse_event)? {
-                                for event in events {
-                                    match event {
-                                        Event::ReasoningStart { .. } => {
-                                            if !reasoning_started {
-                                                reasoning_started = true;
-                                            }
-                                            yield event;
-                                        }
-                                        Event::ReasoningDelta { delta } => {
-                                            if !reasoning_started {
-                                                reasoning_started = true;
-                                                yield Event::ReasoningStart { id: REASONING_ID.to_string() };
-                                            }
-                                            yield Event::ReasoningDelta { delta };
-                                        }
-                                        Event::ReasoningEnd => {
-                                            if reasoning_started {
-                                                reasoning_started = false;
-                                            }
-                                            yield Event::ReasoningEnd;
-                                        }
-                                        event @ Event::Error { .. } => {
-                                            yield event;
-                                            return;
-                                        }
-                                        Event::Done => {
-                                            if reasoning_started {
-                                                yield Event::ReasoningEnd;
-                                            }
-                                            yield Event::Done;
-                                            return;
-                                        }
-                                        other => {
-                                            yield other;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    yield Event::Error {
-                        message: "Unexpected EOF".into(),
-                    };
-                }.boxed()
-            }
-            None => {
-                if !include_raw {
-                    sse_to_events::<S, P, E>(bytes).boxed()
-                } else {
-                    // Inline pipeline without metrics + raw support
-                    async_stream::try_stream! {
-                        let mut decoder = SseDecoder::new();
-                        let mut parser = P::default();
-                        futures_util::pin_mut!(bytes);
-                        static REASONING_ID: &str = "reasoning:0";
-                        let mut reasoning_started = false;
-
-                        while let Some(chunk_result) = bytes.next().await {
-                            let chunk = chunk_result.map_err(|e| e.into())?;
-                            for sse_event in decoder.push(&chunk) {
-                                if include_raw {
-                                    let raw_val: serde_json::Value = match serde_json::from_slice(&sse_event.data) {
-                                        Ok(v) => v,
-                                        Err(_) => serde_json::Value::String(String::from_utf8_lossy(&sse_event.data).to_string()),
-                                    };
-                                    yield Event::Raw { raw_value: raw_val };
-                                }
-                                if let Some(events) = parser.try_from_sse(&sse_event)? {
-                                    for event in events {
-                                        match event {
-                                            Event::ReasoningStart { .. } => {
-                                                if !reasoning_started {
-                                                    reasoning_started = true;
-                                                }
-                                                yield event;
-                                            }
-                                            Event::ReasoningDelta { delta } => {
-                                                if !reasoning_started {
-                                                    reasoning_started = true;
-                                                    yield Event::ReasoningStart { id: REASONING_ID.to_string() };
-                                                }
-                                                yield Event::ReasoningDelta { delta };
-                                            }
-                                            Event::ReasoningEnd => {
-                                                if reasoning_started {
-                                                    reasoning_started = false;
-                                                }
-                                                yield Event::ReasoningEnd;
-                                            }
-                                            event @ Event::Error { .. } => {
-                                                yield event;
-                                                return;
-                                            }
-                                            Event::Done => {
-                                                if reasoning_started {
-                                                    yield Event::ReasoningEnd;
-                                                }
-                                                yield Event::Done;
-                                                return;
-                                            }
-                                            other => {
-                                                yield other;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        yield Event::Error {
-                            message: "Unexpected EOF".into(),
-                        };
-                    }.boxed()
-                }
-            }
+        let mut decoder = match self.max_buffer_bytes {
+            Some(max) => SseDecoder::with_limit(max),
+            None => SseDecoder::new(),
+        };
+        if let Some((max, policy)) = self.max_queued_events {
+            decoder = decoder.with_max_queued_events(max, policy);
         }
+        decode_events::<S, P, E>(bytes, decoder, self.include_raw)
     }
 }
 
@@ -307,3 +236,7 @@ impl<P: ProviderChunk + Default + Send + 'static> Default for PipelineBuilder<P>
         Self::new()
     }
 }
+
+#[cfg(test)]
+#[path = "../../crates/streaming-sse/tests/pipeline_tests.rs"]
+mod tests;
@@ -6,12 +6,31 @@ use futures_core::Stream;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+/// Re-dial callback for [`SseStream::with_reconnect`]
+type Reconnect<S> = Box<dyn FnMut(ReconnectAttempt<'_>) -> Option<S> + Send>;
+
+/// What [`SseStream::with_reconnect`] hands its callback when the byte
+/// stream fails
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectAttempt<'a> {
+    /// The error that ended the byte stream
+    pub error: &'a dyn std::error::Error,
+    /// 1 for the first re-dial, counting up while re-dialed streams fail
+    /// before yielding an event
+    pub attempt: u32,
+    /// See [`SseDecoder::last_event_id`]; send it as `Last-Event-ID`
+    pub last_event_id: Option<&'a str>,
+}
+
 /// Adapter that turns a byte stream into an SSE event stream
 pub struct SseStream<S> {
     inner: S,
     decoder: SseDecoder,
     pending_events: Vec<SseEvent>,
     current_index: usize,
+    reconnect: Option<Reconnect<S>>,
+    /// Re-dials since the last decoded event
+    reconnect_attempts: u32,
 }
 
 impl<S> SseStream<S> {
@@ -22,8 +41,34 @@ impl<S> SseStream<S> {
             decoder: SseDecoder::new(),
             pending_events: Vec::new(),
             current_index: 0,
+            reconnect: None,
+            reconnect_attempts: 0,
+        }
+    }
+
+    /// Create an SSE stream that re-dials when the byte stream fails
+    ///
+    /// On an error from the byte stream, `reconnect` is called with the
+    /// error, the attempt number and the last event id. Returning a new byte
+    /// stream drops the partial frame and resumes decoding from it; returning
+    /// `None` yields the error. The attempt number resets once an event is
+    /// decoded, so the callback can cap retries of a server that keeps
+    /// failing and back off by returning a stream that waits before dialing.
+    /// A stream that ends cleanly is not re-dialed.
+    pub fn with_reconnect<F>(stream: S, reconnect: F) -> Self
+    where
+        F: FnMut(ReconnectAttempt<'_>) -> Option<S> + Send + 'static,
+    {
+        Self {
+            reconnect: Some(Box::new(reconnect)),
+            ..Self::new(stream)
         }
     }
+
+    /// See [`SseDecoder::last_event_id`]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.decoder.last_event_id()
+    }
 }
 
 impl<S, E> Stream for SseStream<S>
@@ -53,6 +98,7 @@ where
 
                 // If we got events, return the first one
                 if !self.pending_events.is_empty() {
+                    self.reconnect_attempts = 0;
                     let event = self.pending_events[0].clone();
                     self.current_index = 1;
                     Poll::Ready(Some(Ok(event)))
@@ -62,19 +108,35 @@ where
                     Poll::Pending
                 }
             }
-            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-            Poll::Ready(None) => {
-                // Stream ended - check if decoder has any buffered data
-                // In a well-formed SSE stream, this shouldn't happen,
-                // but we handle it gracefully
-                if self.decoder.has_buffered_data() {
-                    // Force flush with double newline
-                    self.pending_events = self.decoder.push(b"\n\n").collect();
-                    if !self.pending_events.is_empty() {
-                        let event = self.pending_events[0].clone();
-                        self.current_index = 1;
-                        return Poll::Ready(Some(Ok(event)));
+            Poll::Ready(Some(Err(e))) => {
+                let this = &mut *self;
+                let attempt = this.reconnect_attempts.saturating_add(1);
+                let redialed = this.reconnect.as_mut().and_then(|reconnect| {
+                    reconnect(ReconnectAttempt {
+                        error: &e,
+                        attempt,
+                        last_event_id: this.decoder.last_event_id(),
+                    })
+                });
+                match redialed {
+                    Some(stream) => {
+                        this.reconnect_attempts = attempt;
+                        this.inner = stream;
+                        this.decoder.reset();
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
                     }
+                    None => Poll::Ready(Some(Err(e))),
+                }
+            }
+            Poll::Ready(None) => {
+                // Stream ended - flush a final event that arrived without its
+                // terminating blank line.
+                self.pending_events = self.decoder.finish().collect();
+                if !self.pending_events.is_empty() {
+                    let event = self.pending_events[0].clone();
+                    self.current_index = 1;
+                    return Poll::Ready(Some(Ok(event)));
                 }
                 Poll::Ready(None)
             }
@@ -95,3 +157,7 @@ pub trait SseStreamExt: Stream {
 }
 
 impl<S: Stream> SseStreamExt for S {}
+
+#[cfg(test)]
+#[path = "../../crates/streaming-sse/tests/stream_tests.rs"]
+mod tests;
@@ -0,0 +1,148 @@
+//! Encoding of model stream parts into the AI SDK UI message stream protocol.
+//!
+//! Each [`v2t::StreamPart`] maps to at most one JSON chunk, sent to the client
+//! as an SSE `data:` line. The stream is terminated with `data: [DONE]`.
+//! [`data_stream_frame`](crate::types::datastream::data_stream_frame) renders
+//! the same chunks in the older v1 data stream protocol.
+
+use crate::core::error::SdkError;
+use crate::types::datastream::{chunk, error_text};
+use crate::types::v2 as v2t;
+use bytes::Bytes;
+use serde_json::{json, Value as JsonValue};
+
+/// Response header identifying the UI message stream protocol version.
+pub const UI_MESSAGE_STREAM_HEADER: (&str, &str) = ("x-vercel-ai-ui-message-stream", "v1");
+
+/// Terminal SSE payload sent after the last chunk.
+pub const UI_MESSAGE_STREAM_DONE: &str = "[DONE]";
+
+/// Map a stream part to its UI message stream chunk.
+///
+/// Parts with no client-facing equivalent (signatures, raw chunks, response
+/// metadata, tool input end, unrecognized parts) return `None`.
+pub fn ui_message_chunk(part: &v2t::StreamPart) -> Option<JsonValue> {
+    use v2t::StreamPart as P;
+    let value = match part {
+        P::StreamStart { .. } => chunk("start", []),
+        P::TextStart { id, .. } => chunk("text-start", [("id", json!(id))]),
+        P::TextDelta { id, delta, .. } => {
+            chunk("text-delta", [("id", json!(id)), ("delta", json!(delta))])
+        }
+        P::TextEnd { id, .. } => chunk("text-end", [("id", json!(id))]),
+        P::ReasoningStart { id, .. } => chunk("reasoning-start", [("id", json!(id))]),
+        P::ReasoningDelta { id, delta, .. } => chunk(
+            "reasoning-delta",
+            [("id", json!(id)), ("delta", json!(delta))],
+        ),
+        P::ReasoningEnd { id, .. } => chunk("reasoning-end", [("id", json!(id))]),
+        P::ToolInputStart {
+            id,
+            tool_name,
+            provider_executed,
+            ..
+        } => chunk(
+            "tool-input-start",
+            [
+                ("toolCallId", json!(id)),
+                ("toolName", json!(tool_name)),
+                ("providerExecuted", json!(provider_executed)),
+            ],
+        ),
+        P::ToolInputDelta { id, delta, .. } => chunk(
+            "tool-input-delta",
+            [("toolCallId", json!(id)), ("inputTextDelta", json!(delta))],
+        ),
+        P::ToolCall(call) => chunk(
+            "tool-input-available",
+            [
+                ("toolCallId", json!(call.tool_call_id)),
+                ("toolName", json!(call.tool_name)),
+                (
+                    "input",
+                    serde_json::from_str(&call.input).unwrap_or_else(|_| json!(call.input)),
+                ),
+                ("providerExecuted", json!(call.provider_executed)),
+            ],
+        ),
+        P::ToolApprovalRequest {
+            approval_id,
+            tool_call_id,
+            ..
+        } => chunk(
+            "tool-approval-request",
+            [
+                ("approvalId", json!(approval_id)),
+                ("toolCallId", json!(tool_call_id)),
+            ],
+        ),
+        P::ToolResult {
+            tool_call_id,
+            result,
+            is_error: true,
+            ..
+        } => chunk(
+            "tool-output-error",
+            [
+                ("toolCallId", json!(tool_call_id)),
+                ("errorText", json!(error_text(result))),
+            ],
+        ),
+        P::ToolResult {
+            tool_call_id,
+            result,
+            preliminary,
+            ..
+        } => {
+            let mut value = chunk(
+                "tool-output-available",
+                [
+                    ("toolCallId", json!(tool_call_id)),
+                    ("output", result.clone()),
+                ],
+            );
+            if *preliminary {
+                value["preliminary"] = json!(true);
+            }
+            value
+        }
+        P::File {
+            media_type, data, ..
+        } => chunk(
+            "file",
+            [
+                ("url", json!(format!("data:{media_type};base64,{data}"))),
+                ("mediaType", json!(media_type)),
+            ],
+        ),
+        P::SourceUrl { id, url, title, .. } => {
+            let mut value = chunk("source-url", [("sourceId", json!(id)), ("url", json!(url))]);
+            if let Some(title) = title {
+                value["title"] = json!(title);
+            }
+            value
+        }
+        P::Finish { .. } => chunk("finish", []),
+        P::Error { error } => chunk("error", [("errorText", json!(error_text(error)))]),
+        P::ReasoningSignature { .. }
+        | P::ToolInputEnd { .. }
+        | P::ResponseMetadata { .. }
+        | P::Raw { .. }
+        | P::Unknown { .. } => return None,
+    };
+    Some(value)
+}
+
+/// UI message stream chunk for a stream error.
+pub fn ui_message_error_chunk(error: &SdkError) -> JsonValue {
+    chunk("error", [("errorText", json!(error.to_string()))])
+}
+
+/// Frame a payload as a single SSE `data:` event.
+pub fn sse_data_frame(payload: &str) -> Bytes {
+    Bytes::from(format!("data: {payload}\n\n"))
+}
+
+#[cfg(test)]
+#[path = "../../crates/streaming-sse/tests/ui_message_tests.rs"]
+mod tests;
@@ -1,5 +1,11 @@
-use crate::core::error::{display_body_for_error, TransportError};
-use crate::core::transport::{emit_transport_event, TransportBody, TransportEvent};
+use crate::core::error::{display_body_for_error, html_error_message, TransportError};
+use crate::core::transport::{
+    emit_transport_event, ContentEncoding, StreamCoalesceConfig, TransportBody, TransportConfig,
+    TransportEvent, TransportStream, DEFAULT_REDACTED_HEADERS,
+};
+use bytes::{Bytes, BytesMut};
+use futures_util::StreamExt;
+use std::io::Write;
 use std::time::{Instant, SystemTime};
 
 pub(crate) struct RequestContext {
@@ -10,6 +16,7 @@ pub(crate) struct RequestContext {
     request_headers: Vec<(String, String)>,
     request_body: Option<TransportBody>,
     is_stream: bool,
+    redact_headers: Vec<String>,
 }
 
 impl RequestContext {
@@ -28,12 +35,24 @@ impl RequestContext {
             request_headers,
             request_body,
             is_stream,
+            redact_headers: DEFAULT_REDACTED_HEADERS.map(String::from).to_vec(),
         }
     }
+
+    /// Redact headers named in `names` from the events this context emits,
+    /// replacing the [`DEFAULT_REDACTED_HEADERS`] it starts with.
+    pub(crate) fn with_redacted_headers(mut self, names: &[String]) -> Self {
+        self.redact_headers = names.to_vec();
+        self
+    }
+
+    fn emit(&self, event: TransportEvent) {
+        emit_transport_event(event.redacted(&self.redact_headers));
+    }
 }
 
 pub(crate) fn emit_send_error_event(context: &RequestContext, detail: String) {
-    emit_transport_event(TransportEvent {
+    context.emit(TransportEvent {
         started_at: context.started_at,
         latency: Some(context.start_instant.elapsed()),
         method: context.method.clone(),
@@ -56,7 +75,7 @@ pub(crate) fn emit_response_success_event(
     response_body: Option<TransportBody>,
     response_size: Option<usize>,
 ) {
-    emit_transport_event(TransportEvent {
+    context.emit(TransportEvent {
         started_at: context.started_at,
         latency: Some(context.start_instant.elapsed()),
         method: context.method.clone(),
@@ -79,8 +98,9 @@ pub(crate) fn map_http_status_error(
     response_headers: Vec<(String, String)>,
     body: String,
 ) -> TransportError {
-    let sanitized = display_body_for_error(&body);
-    emit_transport_event(TransportEvent {
+    let sanitized = html_error_message(&response_headers, &body)
+        .unwrap_or_else(|| display_body_for_error(&body));
+    context.emit(TransportEvent {
         started_at: context.started_at,
         latency: Some(context.start_instant.elapsed()),
         method: context.method.clone(),
@@ -103,6 +123,14 @@ pub(crate) fn map_http_status_error(
     }
 }
 
+/// Error for a successful response whose body is not JSON, naming the likely
+/// cause when it is an HTML page.
+pub(crate) fn invalid_json_error(headers: &[(String, String)], body: &str) -> TransportError {
+    TransportError::BodyRead(
+        html_error_message(headers, body).unwrap_or_else(|| "invalid json".into()),
+    )
+}
+
 pub(crate) fn header_pairs(headers: &http::HeaderMap) -> Vec<(String, String)> {
     headers
         .iter()
@@ -115,10 +143,239 @@ pub(crate) fn header_pairs(headers: &http::HeaderMap) -> Vec<(String, String)> {
         .collect()
 }
 
+/// Milliseconds to wait from a `Retry-After` value: delta-seconds, or an
+/// HTTP-date (IMF-fixdate / RFC 1123) measured from now and clamped to zero.
 pub(crate) fn parse_retry_after_ms(value: &str) -> Option<u64> {
-    value
-        .trim()
-        .parse::<u64>()
-        .ok()
-        .map(|seconds| seconds * 1000)
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds.saturating_mul(1000));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let at = SystemTime::from(at);
+    Some(
+        at.duration_since(SystemTime::now())
+            .map(|delay| delay.as_millis() as u64)
+            .unwrap_or(0),
+    )
+}
+
+/// Encoded bytes fed to a decoder between decompressed-size checks, which
+/// bounds how far one check can overshoot the limit.
+const DECODE_INPUT_SLICE: usize = 4 * 1024;
+
+/// Incremental decoder for a response body in an encoding the configured
+/// [`DecompressionMode`](crate::core::transport::DecompressionMode) accepts.
+pub(crate) struct BodyDecoder {
+    encoding: ContentEncoding,
+    inner: Decoder,
+    limit: Option<usize>,
+    decoded: usize,
+}
+
+enum Decoder {
+    Gzip(flate2::write::GzDecoder<Vec<u8>>),
+    Deflate(flate2::write::ZlibDecoder<Vec<u8>>),
+    Brotli(Box<brotli_decompressor::DecompressorWriter<Vec<u8>>>),
+}
+
+impl BodyDecoder {
+    /// Decoder for a response with `headers`, or `None` when its body is
+    /// passed through unchanged. Stacked encodings (`gzip, br`) are never
+    /// decoded. Output is capped at `cfg.max_decompressed_bytes`.
+    pub(crate) fn for_response(
+        headers: &[(String, String)],
+        cfg: &TransportConfig,
+    ) -> Option<Self> {
+        let mode = &cfg.decompression;
+        let (_, value) = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))?;
+        let encoding = ContentEncoding::parse(value)?;
+        if !mode.decodes(encoding) {
+            return None;
+        }
+        let inner = match encoding {
+            ContentEncoding::Gzip => Decoder::Gzip(flate2::write::GzDecoder::new(Vec::new())),
+            ContentEncoding::Deflate => {
+                Decoder::Deflate(flate2::write::ZlibDecoder::new(Vec::new()))
+            }
+            ContentEncoding::Brotli => Decoder::Brotli(Box::new(
+                brotli_decompressor::DecompressorWriter::new(Vec::new(), 4096),
+            )),
+        };
+        Some(Self {
+            encoding,
+            inner,
+            limit: cfg.max_decompressed_bytes,
+            decoded: 0,
+        })
+    }
+
+    /// Headers describing the decoded body: the encoding and the encoded
+    /// length no longer apply.
+    pub(crate) fn decoded_headers(headers: Vec<(String, String)>) -> Vec<(String, String)> {
+        headers
+            .into_iter()
+            .filter(|(name, _)| {
+                !name.eq_ignore_ascii_case("content-encoding")
+                    && !name.eq_ignore_ascii_case("content-length")
+            })
+            .collect()
+    }
+
+    /// Feed the next encoded chunk and take whatever it decoded to.
+    pub(crate) fn decode(&mut self, chunk: &[u8]) -> Result<Bytes, TransportError> {
+        for slice in chunk.chunks(DECODE_INPUT_SLICE) {
+            let written = match &mut self.inner {
+                Decoder::Gzip(decoder) => decoder.write_all(slice),
+                Decoder::Deflate(decoder) => decoder.write_all(slice),
+                Decoder::Brotli(decoder) => decoder.write_all(slice),
+            };
+            written.map_err(|err| self.error(err))?;
+            self.check_limit()?;
+        }
+        let flushed = match &mut self.inner {
+            Decoder::Gzip(decoder) => decoder.flush(),
+            Decoder::Deflate(decoder) => decoder.flush(),
+            Decoder::Brotli(_) => Ok(()),
+        };
+        flushed.map_err(|err| self.error(err))?;
+        self.take_output()
+    }
+
+    /// Signal the end of the body; fails when it was truncated.
+    pub(crate) fn finish(mut self) -> Result<Bytes, TransportError> {
+        let finished = match &mut self.inner {
+            Decoder::Gzip(decoder) => decoder.try_finish(),
+            Decoder::Deflate(decoder) => decoder.try_finish(),
+            Decoder::Brotli(decoder) => decoder.close(),
+        };
+        finished.map_err(|err| self.error(err))?;
+        self.take_output()
+    }
+
+    /// Decode a complete body.
+    pub(crate) fn decode_all(mut self, body: &[u8]) -> Result<Bytes, TransportError> {
+        let mut decoded = BytesMut::from(self.decode(body)?.as_ref());
+        decoded.extend_from_slice(&self.finish()?);
+        Ok(decoded.freeze())
+    }
+
+    /// Take the pending output, failing once the running total passes the
+    /// decompressed-size limit.
+    fn take_output(&mut self) -> Result<Bytes, TransportError> {
+        self.check_limit()?;
+        let output = std::mem::take(self.output());
+        self.decoded += output.len();
+        Ok(Bytes::from(output))
+    }
+
+    fn check_limit(&mut self) -> Result<(), TransportError> {
+        let seen = self.decoded + self.output().len();
+        match self.limit {
+            Some(limit) if seen > limit => Err(TransportError::ResponseTooLarge { limit, seen }),
+            _ => Ok(()),
+        }
+    }
+
+    fn output(&mut self) -> &mut Vec<u8> {
+        match &mut self.inner {
+            Decoder::Gzip(decoder) => decoder.get_mut(),
+            Decoder::Deflate(decoder) => decoder.get_mut(),
+            Decoder::Brotli(decoder) => decoder.get_mut(),
+        }
+    }
+
+    fn error(&self, err: std::io::Error) -> TransportError {
+        TransportError::BodyRead(format!(
+            "failed to decode {} response body: {err}",
+            self.encoding.as_str()
+        ))
+    }
+}
+
+/// Fail once `seen` response bytes pass `limit`.
+pub(crate) fn check_body_limit(limit: Option<usize>, seen: usize) -> Result<(), TransportError> {
+    match limit {
+        Some(limit) if seen > limit => Err(TransportError::BodyTooLarge { limit, seen }),
+        _ => Ok(()),
+    }
+}
+
+/// Buffer tiny body chunks until `min_bytes` are pending or `max_delay` elapses.
+pub(crate) fn coalesce_transport_stream(
+    inner: TransportStream,
+    cfg: StreamCoalesceConfig,
+) -> TransportStream {
+    Box::pin(async_stream::try_stream! {
+        let mut inner = inner;
+        let mut pending = BytesMut::new();
+        let mut deadline: Option<tokio::time::Instant> = None;
+        loop {
+            let next = match deadline {
+                Some(at) => match tokio::time::timeout_at(at, inner.next()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        deadline = None;
+                        yield pending.split().freeze();
+                        continue;
+                    }
+                },
+                None => inner.next().await,
+            };
+            match next {
+                None => break,
+                Some(Err(err)) => {
+                    if !pending.is_empty() {
+                        yield pending.split().freeze();
+                    }
+                    Err(err)?;
+                }
+                Some(Ok(bytes)) => {
+                    if pending.is_empty() && bytes.len() >= cfg.min_bytes {
+                        yield bytes;
+                        continue;
+                    }
+                    pending.extend_from_slice(&bytes);
+                    if pending.len() >= cfg.min_bytes {
+                        deadline = None;
+                        yield pending.split().freeze();
+                    } else if deadline.is_none() {
+                        deadline = Some(tokio::time::Instant::now() + cfg.max_delay);
+                    }
+                }
+            }
+        }
+        if !pending.is_empty() {
+            yield pending.freeze();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_retry_after_ms;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn retry_after_accepts_delta_seconds() {
+        assert_eq!(parse_retry_after_ms("120"), Some(120_000));
+        assert_eq!(parse_retry_after_ms(" 0 "), Some(0));
+        assert_eq!(parse_retry_after_ms("soon"), None);
+    }
+
+    #[test]
+    fn retry_after_accepts_http_dates() {
+        assert_eq!(
+            parse_retry_after_ms("Tue, 15 Nov 1994 08:12:31 GMT"),
+            Some(0)
+        );
+
+        let at = SystemTime::now() + Duration::from_secs(90);
+        let header = chrono::DateTime::<chrono::Utc>::from(at)
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        let delay = parse_retry_after_ms(&header).expect("http date");
+        assert!((85_000..=90_000).contains(&delay), "{delay}");
+    }
 }
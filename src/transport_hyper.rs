@@ -1,34 +1,40 @@
 use crate::core::error::TransportError;
 use crate::core::transport::{
-    emit_transport_event, HttpTransport, JsonStreamWebsocketConnection, MultipartForm,
-    MultipartValue, TransportBody, TransportConfig, TransportEvent, TransportStream,
+    emit_transport_event, CancelToken, DecompressionMode, HttpMethod, HttpTransport,
+    JsonStreamWebsocketConnection, MultipartForm, MultipartValue, TraceContextProvider,
+    TransportBody, TransportConfig, TransportEvent, TransportStream, WebsocketHandshake,
+    WsReconnect,
 };
 use crate::transport_http_common::{
-    emit_response_success_event, emit_send_error_event, header_pairs, map_http_status_error,
-    parse_retry_after_ms, RequestContext,
+    check_body_limit, coalesce_transport_stream, emit_response_success_event,
+    emit_send_error_event, header_pairs, invalid_json_error, map_http_status_error,
+    parse_retry_after_ms, BodyDecoder, RequestContext,
 };
 use crate::transport_websocket_common::{
-    map_websocket_connect_error, map_websocket_stream_error, open_http_proxy_tunnel,
-    resolve_proxy_for_websocket_url, should_skip_websocket_header, websocket_connect_error_headers,
-    websocket_message_to_sse_chunk, WebsocketMessageOutcome,
+    is_reconnectable_websocket_error, map_websocket_connect_error, map_websocket_stream_error,
+    open_http_proxy_tunnel, resolve_proxy_for_websocket_url, should_skip_websocket_header,
+    websocket_connect_error_headers, websocket_message_to_sse_chunk, websocket_reconnect_comment,
+    WebsocketMessageOutcome,
 };
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use futures_util::future::{self, Either};
 use futures_util::{SinkExt, StreamExt};
 use http::header::{CONTENT_TYPE, RETRY_AFTER};
 use http::{Method, Request, Uri};
-use http_body_util::combinators::BoxBody;
-use http_body_util::{BodyExt, Empty, Full};
-use hyper::body::Incoming;
-use hyper_rustls::{ConfigBuilderExt, HttpsConnector};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Empty, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper_rustls::HttpsConnector;
 use hyper_util::client::legacy::connect::proxy::{SocksV4, SocksV5, Tunnel};
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::client::proxy::matcher::{Intercept, Matcher};
 use hyper_util::rt::{TokioExecutor, TokioTimer};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use serde_json::Value;
 use std::borrow::Cow;
-use std::convert::Infallible;
 use std::error::Error as StdError;
 use std::future::Future;
 use std::pin::Pin;
@@ -39,24 +45,52 @@ use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::Mutex;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::Message as WsMessage;
-use tokio_tungstenite::{client_async_tls_with_config, connect_async};
+use tokio_tungstenite::{client_async_tls_with_config, connect_async_tls_with_config, Connector};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tower_service::Service;
 use tracing::debug;
 use uuid::Uuid;
 
 type BoxError = Box<dyn StdError + Send + Sync>;
-type RequestBody = BoxBody<Bytes, Infallible>;
+type RequestBody = UnsyncBoxBody<Bytes, TransportError>;
 type HyperConnector = HttpsConnector<ProxyAwareConnector>;
 type HyperClient = Client<HyperConnector, RequestBody>;
+
+fn full_body(data: Bytes) -> RequestBody {
+    Full::new(data)
+        .map_err(|never| match never {})
+        .boxed_unsync()
+}
+
+fn push_multipart_file_head(
+    body: &mut BytesMut,
+    name: &str,
+    filename: Option<&str>,
+    content_type: Option<&str>,
+) {
+    let mut disposition = format!("Content-Disposition: form-data; name=\"{name}\"");
+    if let Some(filename) = filename {
+        disposition.push_str(&format!("; filename=\"{filename}\""));
+    }
+    body.extend_from_slice(disposition.as_bytes());
+    body.extend_from_slice(b"\r\n");
+    if let Some(content_type) = content_type {
+        body.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+    }
+    body.extend_from_slice(b"\r\n");
+}
 type HyperIo = <HttpConnector as Service<Uri>>::Response;
 type HyperWebsocketStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
 
 #[derive(Clone)]
 pub struct HyperTransport {
     client: HyperClient,
+    tls: Arc<rustls::ClientConfig>,
+    trace_context: Option<Arc<dyn TraceContextProvider>>,
 }
 
+const TRACE_CONTEXT_HEADERS: [&str; 2] = ["traceparent", "tracestate"];
+
 pub use HyperTransport as ReqwestTransport;
 
 #[derive(Clone)]
@@ -72,17 +106,24 @@ struct HyperJsonStreamWebsocketConnection {
 }
 
 impl ProxyAwareConnector {
-    fn new(cfg: &TransportConfig) -> Self {
+    fn new(cfg: &TransportConfig) -> Result<Self, TransportError> {
         let mut http = HttpConnector::new();
         http.enforce_http(false);
         http.set_keepalive(Some(Duration::from_secs(60)));
         http.set_keepalive_interval(Some(Duration::from_secs(30)));
         http.set_connect_timeout(Some(cfg.connect_timeout));
         http.set_nodelay(true);
-        Self {
+        let matcher = match cfg.proxy.as_ref() {
+            Some(proxy) => Matcher::builder()
+                .all(proxy.url_with_credentials()?)
+                .no(proxy.no_proxy.join(","))
+                .build(),
+            None => Matcher::from_env(),
+        };
+        Ok(Self {
             http,
-            matcher: Arc::new(Matcher::from_env()),
-        }
+            matcher: Arc::new(matcher),
+        })
     }
 }
 
@@ -188,25 +229,126 @@ impl HyperTransport {
         });
     }
 
-    fn build_client(cfg: &TransportConfig) -> Result<HyperClient, TransportError> {
+    /// Root certificates for the enabled `tls-*` features; see
+    /// [`TransportConfig::tls_backend`].
+    fn root_cert_store() -> Result<rustls::RootCertStore, TransportError> {
+        #[allow(unused_mut)]
+        let mut roots = rustls::RootCertStore::empty();
+        #[cfg(feature = "tls-rustls")]
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        #[cfg(any(feature = "tls-native", not(feature = "tls-rustls")))]
+        {
+            let native = rustls_native_certs::load_native_certs();
+            if !native.errors.is_empty() {
+                debug!(
+                    "native root certificate loading errors: {:?}",
+                    native.errors
+                );
+            }
+            roots.add_parsable_certificates(native.certs);
+            if roots.is_empty() {
+                return Err(TransportError::Other(format!(
+                    "failed to load native roots: no valid certificates found ({:?})",
+                    native.errors
+                )));
+            }
+        }
+        Ok(roots)
+    }
+
+    /// TLS settings shared by HTTPS and wss:// connections: feature roots
+    /// plus `cfg.root_certs`, and `cfg.client_identity` for mutual TLS.
+    fn tls_config(cfg: &TransportConfig) -> Result<rustls::ClientConfig, TransportError> {
         Self::install_rustls_provider();
-        let tls = rustls::ClientConfig::builder()
-            .with_native_roots()
-            .map_err(|err| TransportError::Other(format!("failed to load native roots: {err}")))?
-            .with_no_client_auth();
-        let connector = HttpsConnector::from((ProxyAwareConnector::new(cfg), tls));
+        let mut roots = Self::root_cert_store()?;
+        for (idx, bytes) in cfg.root_certs.iter().enumerate() {
+            let what = format!("root certificate #{idx}");
+            for cert in parse_certificates(bytes, &what)? {
+                roots
+                    .add(cert)
+                    .map_err(|err| TransportError::Other(format!("invalid {what}: {err}")))?;
+            }
+        }
+        let builder = rustls::ClientConfig::builder().with_root_certificates(roots);
+        let Some(identity) = cfg.client_identity.as_ref() else {
+            return Ok(builder.with_no_client_auth());
+        };
+        let certs = parse_certificates(&identity.cert, "client certificate")?;
+        let key = parse_private_key(&identity.key)?;
+        builder
+            .with_client_auth_cert(certs, key)
+            .map_err(|err| TransportError::Other(format!("invalid client identity: {err}")))
+    }
+
+    fn build_client(
+        cfg: &TransportConfig,
+        tls: rustls::ClientConfig,
+    ) -> Result<HyperClient, TransportError> {
+        let connector = HttpsConnector::from((ProxyAwareConnector::new(cfg)?, tls));
         let mut builder = Client::builder(TokioExecutor::new());
         builder.pool_idle_timeout(Duration::from_secs(90));
         builder.pool_timer(TokioTimer::new());
         Ok(builder.build(connector))
     }
 
+    /// Fails with [`TransportError::Other`] when the proxy, root
+    /// certificates or client identity in `cfg` cannot be used.
     pub fn try_new(cfg: &TransportConfig) -> Result<Self, TransportError> {
+        let tls = Self::tls_config(cfg)?;
         Ok(Self {
-            client: Self::build_client(cfg)?,
+            client: Self::build_client(cfg, tls.clone())?,
+            tls: Arc::new(tls),
+            trace_context: None,
         })
     }
 
+    /// Inject `traceparent`/`tracestate` from `provider` into every outbound
+    /// request. Headers already set by the caller take precedence.
+    pub fn with_trace_context(mut self, provider: impl TraceContextProvider + 'static) -> Self {
+        self.trace_context = Some(Arc::new(provider));
+        self
+    }
+
+    /// Headers actually sent upstream: caller headers plus injected trace
+    /// context, with `accept-encoding` replaced as
+    /// [`TransportConfig::decompression`] says.
+    fn outbound_headers<'a>(
+        &self,
+        headers: &'a [(String, String)],
+        cfg: &TransportConfig,
+    ) -> Cow<'a, [(String, String)]> {
+        let decompression = &cfg.decompression;
+        let accept_encoding = decompression.accept_encoding();
+        let replace_accept_encoding = !matches!(decompression, DecompressionMode::None);
+        if self.trace_context.is_none() && !replace_accept_encoding {
+            return Cow::Borrowed(headers);
+        }
+        let mut merged: Vec<(String, String)> = headers
+            .iter()
+            .filter(|(name, _)| {
+                !(replace_accept_encoding && name.eq_ignore_ascii_case("accept-encoding"))
+            })
+            .cloned()
+            .collect();
+        if let Some(value) = accept_encoding {
+            merged.push(("accept-encoding".to_string(), value));
+        }
+        if let Some(provider) = self.trace_context.as_ref() {
+            for (name, value) in provider.trace_headers() {
+                let is_trace_header = TRACE_CONTEXT_HEADERS
+                    .iter()
+                    .any(|allowed| name.eq_ignore_ascii_case(allowed));
+                let already_set = headers
+                    .iter()
+                    .any(|(existing, _)| existing.eq_ignore_ascii_case(&name));
+                if is_trace_header && !already_set {
+                    merged.push((name.to_ascii_lowercase(), value));
+                }
+            }
+        }
+        Cow::Owned(merged)
+    }
+
     pub fn new(cfg: &TransportConfig) -> Self {
         match Self::try_new(cfg) {
             Ok(transport) => transport,
@@ -268,21 +410,55 @@ impl HyperTransport {
         request: Request<RequestBody>,
         cfg: &TransportConfig,
     ) -> Result<hyper::Response<Incoming>, TransportError> {
-        let response = if let Some(request_timeout) = cfg.request_timeout {
-            tokio::time::timeout(request_timeout, self.client.request(request))
-                .await
-                .map_err(|_| TransportError::ConnectTimeout(cfg.connect_timeout))?
-        } else {
-            self.client.request(request).await
-        };
-        response.map_err(|err| map_hyper_request_error(err, cfg))
+        let send = std::pin::pin!(async {
+            let response = if let Some(request_timeout) = cfg.request_timeout {
+                tokio::time::timeout(request_timeout, self.client.request(request))
+                    .await
+                    .map_err(|_| TransportError::ConnectTimeout(cfg.connect_timeout))?
+            } else {
+                self.client.request(request).await
+            };
+            response.map_err(|err| map_hyper_request_error(err, cfg))
+        });
+        let cancelled = std::pin::pin!(wait_cancelled(cfg.cancel.as_ref()));
+        match future::select(cancelled, send).await {
+            Either::Left(_) => Err(TransportError::Cancelled),
+            Either::Right((response, _)) => response,
+        }
     }
 
-    async fn collect_body_bytes(body: Incoming) -> Result<Bytes, TransportError> {
-        body.collect()
-            .await
-            .map(|collected| collected.to_bytes())
-            .map_err(|err| TransportError::BodyRead(format_error_chain(&err)))
+    async fn collect_body_bytes(
+        mut body: Incoming,
+        limit: Option<usize>,
+    ) -> Result<Bytes, TransportError> {
+        let mut collected = BytesMut::new();
+        while let Some(frame) = body.frame().await {
+            let frame = frame.map_err(|err| TransportError::BodyRead(format_error_chain(&err)))?;
+            if let Ok(data) = frame.into_data() {
+                check_body_limit(limit, collected.len() + data.len())?;
+                collected.extend_from_slice(&data);
+            }
+        }
+        Ok(collected.freeze())
+    }
+
+    /// Collect a response body, decoding it as
+    /// [`TransportConfig::decompression`] allows.
+    /// Returns the headers that describe the returned bytes.
+    async fn collect_response(
+        response: hyper::Response<Incoming>,
+        cfg: &TransportConfig,
+    ) -> Result<(Bytes, Vec<(String, String)>), TransportError> {
+        let headers = header_pairs(response.headers());
+        let body = Self::collect_body_bytes(response.into_body(), cfg.max_response_bytes).await?;
+        match BodyDecoder::for_response(&headers, cfg) {
+            Some(decoder) => {
+                let decoded = decoder.decode_all(&body)?;
+                check_body_limit(cfg.max_response_bytes, decoded.len())?;
+                Ok((decoded, BodyDecoder::decoded_headers(headers)))
+            }
+            None => Ok((body, headers)),
+        }
     }
 
     async fn connect_websocket_stream(
@@ -291,9 +467,9 @@ impl HyperTransport {
         cfg: &TransportConfig,
     ) -> Result<(HyperWebsocketStream, http::Response<Option<Vec<u8>>>), TransportError> {
         let request_url = request.uri().to_string();
-        if let Some(proxy) = resolve_proxy_for_websocket_url(&request_url)? {
+        if let Some(proxy) = resolve_proxy_for_websocket_url(&request_url, cfg.proxy.as_ref())? {
             let tunnel = open_http_proxy_tunnel(request.uri(), &proxy, cfg).await?;
-            return client_async_tls_with_config(request, tunnel, None, None)
+            return client_async_tls_with_config(request, tunnel, None, Some(self.ws_connector()))
                 .await
                 .map_err(|err| {
                     let (mapped, _, _) = map_websocket_connect_error(err);
@@ -301,10 +477,16 @@ impl HyperTransport {
                 });
         }
 
-        connect_async(request).await.map_err(|err| {
-            let (mapped, _, _) = map_websocket_connect_error(err);
-            mapped
-        })
+        connect_async_tls_with_config(request, None, false, Some(self.ws_connector()))
+            .await
+            .map_err(|err| {
+                let (mapped, _, _) = map_websocket_connect_error(err);
+                mapped
+            })
+    }
+
+    fn ws_connector(&self) -> Connector {
+        Connector::Rustls(Arc::clone(&self.tls))
     }
 
     async fn open_json_stream_websocket(
@@ -315,13 +497,14 @@ impl HyperTransport {
     ) -> Result<HyperJsonStreamWebsocketConnection, TransportError> {
         let started_at = SystemTime::now();
         let start_instant = Instant::now();
+        let headers = self.outbound_headers(headers, cfg);
         let request_headers = headers.to_vec();
         let request_body = None;
 
         let mut request = url.into_client_request().map_err(|err| {
             TransportError::Other(format!("invalid websocket url '{url}': {err}"))
         })?;
-        for (name, value) in headers {
+        for (name, value) in headers.iter() {
             if should_skip_websocket_header(name) {
                 continue;
             }
@@ -342,57 +525,66 @@ impl HyperTransport {
         let (socket, response) = match connect_result {
             Err(_) => {
                 let err = TransportError::ConnectTimeout(cfg.connect_timeout);
-                emit_transport_event(TransportEvent {
-                    started_at,
-                    latency: Some(start_instant.elapsed()),
-                    method: "GET".to_string(),
-                    url: url.to_string(),
-                    status: None,
-                    request_headers,
-                    response_headers: Vec::new(),
-                    request_body,
-                    response_body: None,
-                    response_size: None,
-                    error: Some(err.to_string()),
-                    is_stream: true,
-                });
+                emit_transport_event(
+                    TransportEvent {
+                        started_at,
+                        latency: Some(start_instant.elapsed()),
+                        method: "GET".to_string(),
+                        url: url.to_string(),
+                        status: None,
+                        request_headers,
+                        response_headers: Vec::new(),
+                        request_body,
+                        response_body: None,
+                        response_size: None,
+                        error: Some(err.to_string()),
+                        is_stream: true,
+                    }
+                    .redacted(&cfg.redact_headers),
+                );
                 return Err(err);
             }
             Ok(Err(err)) => {
-                emit_transport_event(TransportEvent {
-                    started_at,
-                    latency: Some(start_instant.elapsed()),
-                    method: "GET".to_string(),
-                    url: url.to_string(),
-                    status: err.status(),
-                    request_headers,
-                    response_headers: websocket_connect_error_headers(&err),
-                    request_body,
-                    response_body: None,
-                    response_size: None,
-                    error: Some(err.to_string()),
-                    is_stream: true,
-                });
+                emit_transport_event(
+                    TransportEvent {
+                        started_at,
+                        latency: Some(start_instant.elapsed()),
+                        method: "GET".to_string(),
+                        url: url.to_string(),
+                        status: err.status(),
+                        request_headers,
+                        response_headers: websocket_connect_error_headers(&err),
+                        request_body,
+                        response_body: None,
+                        response_size: None,
+                        error: Some(err.to_string()),
+                        is_stream: true,
+                    }
+                    .redacted(&cfg.redact_headers),
+                );
                 return Err(err);
             }
             Ok(Ok(ok)) => ok,
         };
 
         let response_headers = header_pairs(response.headers());
-        emit_transport_event(TransportEvent {
-            started_at,
-            latency: Some(start_instant.elapsed()),
-            method: "GET".to_string(),
-            url: url.to_string(),
-            status: Some(response.status().as_u16()),
-            request_headers,
-            response_headers: response_headers.clone(),
-            request_body,
-            response_body: None,
-            response_size: None,
-            error: None,
-            is_stream: true,
-        });
+        emit_transport_event(
+            TransportEvent {
+                started_at,
+                latency: Some(start_instant.elapsed()),
+                method: "GET".to_string(),
+                url: url.to_string(),
+                status: Some(response.status().as_u16()),
+                request_headers,
+                response_headers: response_headers.clone(),
+                request_body,
+                response_body: None,
+                response_size: None,
+                error: None,
+                is_stream: true,
+            }
+            .redacted(&cfg.redact_headers),
+        );
 
         Ok(HyperJsonStreamWebsocketConnection {
             socket: Arc::new(Mutex::new(socket)),
@@ -411,12 +603,101 @@ impl HyperTransport {
         let connection = self.open_json_stream_websocket(url, headers, cfg).await?;
         let response_headers = connection.response_headers();
         let stream = connection.send_json_stream(cleaned_body, cfg).await?;
+        let stream = match cfg.ws_reconnect {
+            Some(policy) => {
+                self.reconnecting_websocket_stream(stream, url, headers, cleaned_body, cfg, policy)
+            }
+            None => stream,
+        };
         Ok((stream, response_headers))
     }
 
-    fn build_multipart_body(form: &MultipartForm) -> Result<(Bytes, String), TransportError> {
+    /// Wrap `stream` so a drop before the first chunk re-dials `url` and
+    /// re-sends `body` under `policy`, announcing each reconnect with an SSE
+    /// comment frame. Once a chunk has been yielded a re-sent request would
+    /// start a new response and replay it, so later drops fail the stream.
+    fn reconnecting_websocket_stream(
+        &self,
+        mut stream: TransportStream,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+        policy: WsReconnect,
+    ) -> TransportStream {
+        let transport = self.clone();
+        let url = url.to_string();
+        let headers = headers.to_vec();
+        let body = body.clone();
+        let cfg = cfg.clone();
+        Box::pin(async_stream::stream! {
+            let mut attempt = 0;
+            let mut yielded = false;
+            while let Some(item) = stream.next().await {
+                let mut err = match item {
+                    Ok(chunk) => {
+                        yielded = true;
+                        yield Ok(chunk);
+                        continue;
+                    }
+                    Err(err) => err,
+                };
+                let reconnected = loop {
+                    if yielded
+                        || attempt >= policy.max_attempts
+                        || !is_reconnectable_websocket_error(&err)
+                    {
+                        break None;
+                    }
+                    attempt += 1;
+                    let sleep = std::pin::pin!(tokio::time::sleep(policy.delay(attempt)));
+                    let cancelled = std::pin::pin!(wait_cancelled(cfg.cancel.as_ref()));
+                    if let Either::Left(_) = future::select(cancelled, sleep).await {
+                        err = TransportError::Cancelled;
+                        break None;
+                    }
+                    debug!(
+                        target: "ai_sdk::transport::hyper",
+                        %url,
+                        attempt,
+                        error = %err,
+                        "reconnecting websocket stream"
+                    );
+                    let dialed = match transport
+                        .open_json_stream_websocket(&url, &headers, &cfg)
+                        .await
+                    {
+                        Ok(connection) => connection.send_json_stream(&body, &cfg).await,
+                        Err(dial_err) => Err(dial_err),
+                    };
+                    match dialed {
+                        Ok(next) => break Some(next),
+                        Err(dial_err) => err = dial_err,
+                    }
+                };
+                match reconnected {
+                    Some(next) => {
+                        stream = next;
+                        yield Ok(websocket_reconnect_comment(attempt));
+                    }
+                    None => {
+                        yield Err(err);
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Encode `form`, returning the body, its content type and, when a part
+    /// is streamed, the total length to send as `Content-Length`.
+    fn build_multipart_body(
+        form: &MultipartForm,
+    ) -> Result<(RequestBody, String, Option<u64>), TransportError> {
         let boundary = format!("----ai-sdk-rs-{}", Uuid::new_v4().simple());
-        let mut body = Vec::new();
+        let mut body = BytesMut::new();
+        let mut segments: Vec<TransportStream> = Vec::new();
+        let mut streamed_len = 0u64;
 
         for field in &form.fields {
             body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
@@ -435,29 +716,59 @@ impl HyperTransport {
                     filename,
                     content_type,
                 } => {
-                    let mut disposition =
-                        format!("Content-Disposition: form-data; name=\"{}\"", field.name);
-                    if let Some(filename) = filename {
-                        disposition.push_str(&format!("; filename=\"{filename}\""));
-                    }
-                    body.extend_from_slice(disposition.as_bytes());
-                    body.extend_from_slice(b"\r\n");
-                    if let Some(content_type) = content_type {
-                        body.extend_from_slice(
-                            format!("Content-Type: {content_type}\r\n").as_bytes(),
-                        );
-                    }
-                    body.extend_from_slice(b"\r\n");
+                    push_multipart_file_head(
+                        &mut body,
+                        &field.name,
+                        filename.as_deref(),
+                        content_type.as_deref(),
+                    );
                     body.extend_from_slice(data);
                     body.extend_from_slice(b"\r\n");
                 }
+                MultipartValue::Stream {
+                    stream,
+                    len,
+                    filename,
+                    content_type,
+                } => {
+                    let stream = stream.take().ok_or_else(|| {
+                        TransportError::Other(format!(
+                            "multipart stream for field '{}' was already consumed",
+                            field.name
+                        ))
+                    })?;
+                    push_multipart_file_head(
+                        &mut body,
+                        &field.name,
+                        filename.as_deref(),
+                        content_type.as_deref(),
+                    );
+                    streamed_len += body.len() as u64 + len;
+                    segments.push(Box::pin(futures_util::stream::once(future::ready(Ok(
+                        body.split().freeze(),
+                    )))));
+                    segments.push(stream);
+                    body.extend_from_slice(b"\r\n");
+                }
             }
         }
 
         body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+        let content_type = format!("multipart/form-data; boundary={boundary}");
+        if segments.is_empty() {
+            return Ok((full_body(body.freeze()), content_type, None));
+        }
+        let total_len = streamed_len + body.len() as u64;
+        segments.push(Box::pin(futures_util::stream::once(future::ready(Ok(
+            body.freeze(),
+        )))));
+        let frames = futures_util::stream::iter(segments)
+            .flatten()
+            .map(|chunk| chunk.map(Frame::data));
         Ok((
-            Bytes::from(body),
-            format!("multipart/form-data; boundary={boundary}"),
+            BodyExt::boxed_unsync(StreamBody::new(frames)),
+            content_type,
+            Some(total_len),
         ))
     }
 }
@@ -502,9 +813,23 @@ impl JsonStreamWebsocketConnection for HyperJsonStreamWebsocketConnection {
         tokio::spawn(async move {
             let mut saw_terminal_event = false;
             loop {
+                // Stop reading as soon as the consumer drops the stream rather
+                // than holding the socket until the next frame or idle timeout.
                 let next_item = {
                     let mut guard = socket.lock().await;
-                    tokio::time::timeout(idle, guard.next()).await
+                    let read = std::pin::pin!(tokio::time::timeout(idle, guard.next()));
+                    let dropped = std::pin::pin!(tx.closed());
+                    match future::select(dropped, read).await {
+                        Either::Left(_) => None,
+                        Either::Right((item, _)) => Some(item),
+                    }
+                };
+                let Some(next_item) = next_item else {
+                    // The in-flight response is abandoned, so the connection
+                    // cannot be reused for another request.
+                    closed.store(true, Ordering::SeqCst);
+                    let _ = socket.lock().await.close(None).await;
+                    break;
                 };
 
                 let outcome = match next_item {
@@ -582,6 +907,19 @@ impl HttpTransport for HyperTransport {
         body: &Value,
         cfg: &TransportConfig,
     ) -> Result<Self::StreamResponse, TransportError> {
+        self.request_json_stream(HttpMethod::Post, url, headers, body, cfg)
+            .await
+    }
+
+    async fn request_json_stream(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: &Value,
+        cfg: &TransportConfig,
+    ) -> Result<Self::StreamResponse, TransportError> {
+        let method = hyper_method(method);
         let cleaned_body = Self::json_request_body(body, cfg);
 
         if Self::is_websocket_url(url) {
@@ -595,15 +933,16 @@ impl HttpTransport for HyperTransport {
             TransportError::Other(format!("failed to encode request body: {err}"))
         })?;
         let (request, context) = Self::build_request(
-            Method::POST,
+            method,
             url,
-            headers,
-            Full::new(Bytes::from(body_bytes)).boxed(),
+            &self.outbound_headers(headers, cfg),
+            full_body(Bytes::from(body_bytes)),
             Some("application/json"),
             true,
             request_body,
             true,
         )?;
+        let context = context.with_redacted_headers(&cfg.redact_headers);
 
         let response = match self.send_request(request, cfg).await {
             Ok(response) => response,
@@ -622,10 +961,8 @@ impl HttpTransport for HyperTransport {
                 .get(RETRY_AFTER)
                 .and_then(|value| value.to_str().ok())
                 .and_then(parse_retry_after_ms);
-            let response_headers = header_pairs(response.headers());
-            let body_text =
-                String::from_utf8_lossy(&Self::collect_body_bytes(response.into_body()).await?)
-                    .to_string();
+            let (body_bytes, response_headers) = Self::collect_response(response, cfg).await?;
+            let body_text = String::from_utf8_lossy(&body_bytes).to_string();
             return Err(map_http_status_error(
                 &context,
                 status.as_u16(),
@@ -635,7 +972,11 @@ impl HttpTransport for HyperTransport {
             ));
         }
 
-        let response_headers = header_pairs(response.headers());
+        let mut response_headers = header_pairs(response.headers());
+        let mut decoder = BodyDecoder::for_response(&response_headers, cfg);
+        if decoder.is_some() {
+            response_headers = BodyDecoder::decoded_headers(response_headers);
+        }
         emit_response_success_event(
             &context,
             status.as_u16(),
@@ -646,18 +987,49 @@ impl HttpTransport for HyperTransport {
 
         let mut body = response.into_body().into_data_stream();
         let idle = cfg.idle_read_timeout;
+        let mut wait = cfg.first_byte_timeout.unwrap_or(idle);
+        let cancel = cfg.cancel.clone();
+        let limit = cfg.max_response_bytes;
+        let mut yielded = 0;
         let stream = async_stream::try_stream! {
             loop {
-                let next = tokio::time::timeout(idle, body.next()).await;
+                let read = std::pin::pin!(tokio::time::timeout(wait, body.next()));
+                let cancelled = std::pin::pin!(wait_cancelled(cancel.as_ref()));
+                let next = match future::select(cancelled, read).await {
+                    Either::Left(_) => Err(TransportError::Cancelled)?,
+                    Either::Right((next, _)) => next,
+                };
                 match next {
-                    Err(_) => Err(TransportError::IdleReadTimeout(idle))?,
+                    Err(_) => Err(TransportError::IdleReadTimeout(wait))?,
                     Ok(None) => break,
                     Ok(Some(Err(err))) => Err(TransportError::BodyRead(format_error_chain(&err)))?,
-                    Ok(Some(Ok(bytes))) => yield bytes,
+                    Ok(Some(Ok(bytes))) => {
+                        wait = idle;
+                        let chunk = match decoder.as_mut() {
+                            Some(decoder) => decoder.decode(&bytes)?,
+                            None => bytes,
+                        };
+                        if !chunk.is_empty() {
+                            yielded += chunk.len();
+                            check_body_limit(limit, yielded)?;
+                            yield chunk
+                        }
+                    }
                 }
             }
+            if let Some(decoder) = decoder.take() {
+                let tail = decoder.finish()?;
+                if !tail.is_empty() {
+                    check_body_limit(limit, yielded + tail.len())?;
+                    yield tail
+                }
+            }
+        };
+        let stream: TransportStream = match cfg.stream_coalesce {
+            Some(coalesce) => coalesce_transport_stream(Box::pin(stream), coalesce),
+            None => Box::pin(stream),
         };
-        Ok((Box::pin(stream), response_headers))
+        Ok((stream, response_headers))
     }
 
     async fn connect_json_stream_websocket(
@@ -671,6 +1043,27 @@ impl HttpTransport for HyperTransport {
         ))
     }
 
+    async fn check_websocket(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        cfg: &TransportConfig,
+    ) -> Result<WebsocketHandshake, TransportError> {
+        let connection = self.open_json_stream_websocket(url, headers, cfg).await?;
+        let _ = connection.socket.lock().await.close(None).await;
+        connection.closed.store(true, Ordering::SeqCst);
+        let subprotocol = connection
+            .response_headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("sec-websocket-protocol"))
+            .map(|(_, value)| value.clone());
+        Ok(WebsocketHandshake {
+            status: http::StatusCode::SWITCHING_PROTOCOLS.as_u16(),
+            subprotocol,
+            response_headers: connection.response_headers,
+        })
+    }
+
     async fn post_json(
         &self,
         url: &str,
@@ -678,21 +1071,118 @@ impl HttpTransport for HyperTransport {
         body: &Value,
         cfg: &TransportConfig,
     ) -> Result<(Value, Vec<(String, String)>), TransportError> {
-        let cleaned_body = Self::json_request_body(body, cfg);
-        let request_body = Some(TransportBody::Json(cleaned_body.as_ref().clone()));
-        let body_bytes = serde_json::to_vec(cleaned_body.as_ref()).map_err(|err| {
-            TransportError::Other(format!("failed to encode request body: {err}"))
-        })?;
+        self.request_json(HttpMethod::Post, url, headers, Some(body), cfg)
+            .await
+    }
+
+    async fn request_json(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&Value>,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let (request, context) = match body {
+            Some(body) => {
+                let cleaned_body = Self::json_request_body(body, cfg);
+                let request_body = Some(TransportBody::Json(cleaned_body.as_ref().clone()));
+                let body_bytes = serde_json::to_vec(cleaned_body.as_ref()).map_err(|err| {
+                    TransportError::Other(format!("failed to encode request body: {err}"))
+                })?;
+                Self::build_request(
+                    hyper_method(method),
+                    url,
+                    &self.outbound_headers(headers, cfg),
+                    full_body(Bytes::from(body_bytes)),
+                    Some("application/json"),
+                    true,
+                    request_body,
+                    false,
+                )?
+            }
+            None => Self::build_request(
+                hyper_method(method),
+                url,
+                &self.outbound_headers(headers, cfg),
+                full_body(Bytes::new()),
+                None,
+                false,
+                None,
+                false,
+            )?,
+        };
+        let context = context.with_redacted_headers(&cfg.redact_headers);
+
+        let response = match self.send_request(request, cfg).await {
+            Ok(response) => response,
+            Err(err) => {
+                let detail = err.to_string();
+                debug!(target: "ai_sdk::transport::hyper", %detail, "hyper request failed");
+                emit_send_error_event(&context, detail);
+                return Err(err);
+            }
+        };
+
+        let status = response.status();
+        let (body_bytes, response_headers) = Self::collect_response(response, cfg).await?;
+
+        if !status.is_success() {
+            let retry_after_ms = response_headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(RETRY_AFTER.as_str()))
+                .and_then(|(_, value)| parse_retry_after_ms(value));
+            let body_text = String::from_utf8_lossy(&body_bytes).to_string();
+            return Err(map_http_status_error(
+                &context,
+                status.as_u16(),
+                retry_after_ms,
+                response_headers,
+                body_text,
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&body_bytes).to_string();
+        let json: Value = if text.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text).map_err(|_| invalid_json_error(&response_headers, &text))?
+        };
+        emit_response_success_event(
+            &context,
+            status.as_u16(),
+            response_headers.clone(),
+            Some(TransportBody::Json(json.clone())),
+            Some(text.len()),
+        );
+        Ok((json, response_headers))
+    }
+
+    async fn post_multipart(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        form: &MultipartForm,
+        cfg: &TransportConfig,
+    ) -> Result<(Value, Vec<(String, String)>), TransportError> {
+        let (body, content_type, content_length) = Self::build_multipart_body(form)?;
+        let mut outbound_headers = self.outbound_headers(headers, cfg);
+        if let Some(content_length) = content_length {
+            outbound_headers
+                .to_mut()
+                .push(("content-length".into(), content_length.to_string()));
+        }
         let (request, context) = Self::build_request(
             Method::POST,
             url,
-            headers,
-            Full::new(Bytes::from(body_bytes)).boxed(),
-            Some("application/json"),
+            &outbound_headers,
+            body,
+            Some(&content_type),
             true,
-            request_body,
+            None,
             false,
         )?;
+        let context = context.with_redacted_headers(&cfg.redact_headers);
 
         let response = match self.send_request(request, cfg).await {
             Ok(response) => response,
@@ -705,8 +1195,7 @@ impl HttpTransport for HyperTransport {
         };
 
         let status = response.status();
-        let response_headers = header_pairs(response.headers());
-        let body_bytes = Self::collect_body_bytes(response.into_body()).await?;
+        let (body_bytes, response_headers) = Self::collect_response(response, cfg).await?;
 
         if !status.is_success() {
             let retry_after_ms = response_headers
@@ -725,7 +1214,7 @@ impl HttpTransport for HyperTransport {
 
         let text = String::from_utf8_lossy(&body_bytes).to_string();
         let json: Value = serde_json::from_str(&text)
-            .map_err(|_| TransportError::BodyRead("invalid json".into()))?;
+            .map_err(|_| invalid_json_error(&response_headers, &text))?;
         emit_response_success_event(
             &context,
             status.as_u16(),
@@ -736,24 +1225,33 @@ impl HttpTransport for HyperTransport {
         Ok((json, response_headers))
     }
 
-    async fn post_multipart(
+    async fn post_bytes(
         &self,
         url: &str,
         headers: &[(String, String)],
-        form: &MultipartForm,
+        body: Bytes,
         cfg: &TransportConfig,
     ) -> Result<(Value, Vec<(String, String)>), TransportError> {
-        let (body_bytes, content_type) = Self::build_multipart_body(form)?;
+        let content_type = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| "application/octet-stream".into());
+        let mut outbound_headers = self.outbound_headers(headers, cfg);
+        outbound_headers
+            .to_mut()
+            .push(("content-length".into(), body.len().to_string()));
         let (request, context) = Self::build_request(
             Method::POST,
             url,
-            headers,
-            Full::new(body_bytes).boxed(),
+            &outbound_headers,
+            full_body(body),
             Some(&content_type),
             true,
             None,
             false,
         )?;
+        let context = context.with_redacted_headers(&cfg.redact_headers);
 
         let response = match self.send_request(request, cfg).await {
             Ok(response) => response,
@@ -766,8 +1264,7 @@ impl HttpTransport for HyperTransport {
         };
 
         let status = response.status();
-        let response_headers = header_pairs(response.headers());
-        let body_bytes = Self::collect_body_bytes(response.into_body()).await?;
+        let (body_bytes, response_headers) = Self::collect_response(response, cfg).await?;
 
         if !status.is_success() {
             let retry_after_ms = response_headers
@@ -786,7 +1283,7 @@ impl HttpTransport for HyperTransport {
 
         let text = String::from_utf8_lossy(&body_bytes).to_string();
         let json: Value = serde_json::from_str(&text)
-            .map_err(|_| TransportError::BodyRead("invalid json".into()))?;
+            .map_err(|_| invalid_json_error(&response_headers, &text))?;
         emit_response_success_event(
             &context,
             status.as_u16(),
@@ -806,13 +1303,16 @@ impl HttpTransport for HyperTransport {
         let (request, context) = Self::build_request(
             Method::GET,
             url,
-            headers,
-            Empty::<Bytes>::new().boxed(),
+            &self.outbound_headers(headers, cfg),
+            Empty::<Bytes>::new()
+                .map_err(|never| match never {})
+                .boxed_unsync(),
             None,
             false,
             None,
             false,
         )?;
+        let context = context.with_redacted_headers(&cfg.redact_headers);
 
         let response = match self.send_request(request, cfg).await {
             Ok(response) => response,
@@ -825,8 +1325,7 @@ impl HttpTransport for HyperTransport {
         };
 
         let status = response.status();
-        let response_headers = header_pairs(response.headers());
-        let body_bytes = Self::collect_body_bytes(response.into_body()).await?;
+        let (body_bytes, response_headers) = Self::collect_response(response, cfg).await?;
 
         if !status.is_success() {
             let retry_after_ms = response_headers
@@ -854,6 +1353,57 @@ impl HttpTransport for HyperTransport {
     }
 }
 
+fn is_pem(bytes: &[u8]) -> bool {
+    bytes.windows(11).any(|window| window == b"-----BEGIN ")
+}
+
+/// Certificates from PEM (all `CERTIFICATE` blocks) or a single DER blob.
+fn parse_certificates(
+    bytes: &[u8],
+    what: &str,
+) -> Result<Vec<CertificateDer<'static>>, TransportError> {
+    if !is_pem(bytes) {
+        return Ok(vec![CertificateDer::from(bytes.to_vec())]);
+    }
+    let certs = CertificateDer::pem_slice_iter(bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| TransportError::Other(format!("invalid {what} PEM: {err}")))?;
+    if certs.is_empty() {
+        return Err(TransportError::Other(format!(
+            "invalid {what} PEM: no CERTIFICATE block found"
+        )));
+    }
+    Ok(certs)
+}
+
+fn parse_private_key(bytes: &[u8]) -> Result<PrivateKeyDer<'static>, TransportError> {
+    if is_pem(bytes) {
+        PrivateKeyDer::from_pem_slice(bytes)
+            .map_err(|err| TransportError::Other(format!("invalid client identity key PEM: {err}")))
+    } else {
+        PrivateKeyDer::try_from(bytes.to_vec())
+            .map_err(|err| TransportError::Other(format!("invalid client identity key: {err}")))
+    }
+}
+
+/// Resolves when `cancel` is triggered; never without a token.
+async fn wait_cancelled(cancel: Option<&CancelToken>) {
+    match cancel {
+        Some(token) => token.cancelled().await,
+        None => future::pending().await,
+    }
+}
+
+fn hyper_method(method: HttpMethod) -> Method {
+    match method {
+        HttpMethod::Get => Method::GET,
+        HttpMethod::Post => Method::POST,
+        HttpMethod::Put => Method::PUT,
+        HttpMethod::Patch => Method::PATCH,
+        HttpMethod::Delete => Method::DELETE,
+    }
+}
+
 fn format_error_chain(err: &(dyn StdError + 'static)) -> String {
     let mut out = err.to_string();
     let mut current = err.source();
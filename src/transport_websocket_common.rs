@@ -1,5 +1,5 @@
 use crate::core::error::TransportError;
-use crate::core::transport::TransportConfig;
+use crate::core::transport::{ProxyConfig, TransportConfig};
 use crate::transport_http_common::{header_pairs, parse_retry_after_ms};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
@@ -8,6 +8,7 @@ use serde_json::Value;
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use tokio_tungstenite::tungstenite::{Error as WsError, Message as WsMessage};
 use url::Url;
 
@@ -33,7 +34,9 @@ pub(crate) fn should_skip_websocket_header(name: &str) -> bool {
     {
         return true;
     }
-    name.to_ascii_lowercase().starts_with("sec-websocket-")
+    // The subprotocol offer is caller-controlled; the rest is handshake state.
+    let name = name.to_ascii_lowercase();
+    name.starts_with("sec-websocket-") && name != "sec-websocket-protocol"
 }
 
 pub(crate) fn map_websocket_connect_error(
@@ -138,6 +141,13 @@ pub(crate) fn websocket_message_to_sse_chunk(
             chunk: None,
             terminal: false,
         }),
+        WsMessage::Close(Some(frame)) if is_fatal_close_code(frame.code) => {
+            Err(TransportError::Other(format!(
+                "websocket closed with code {}: {}",
+                u16::from(frame.code),
+                frame.reason
+            )))
+        }
         WsMessage::Close(_) => Err(TransportError::StreamClosed),
         _ => Ok(WebsocketMessageOutcome {
             chunk: None,
@@ -146,6 +156,37 @@ pub(crate) fn websocket_message_to_sse_chunk(
     }
 }
 
+/// Close codes reporting a request the server will reject again.
+fn is_fatal_close_code(code: CloseCode) -> bool {
+    matches!(
+        code,
+        CloseCode::Protocol
+            | CloseCode::Unsupported
+            | CloseCode::Invalid
+            | CloseCode::Policy
+            | CloseCode::Size
+            | CloseCode::Extension
+    )
+}
+
+/// Drops and dial failures a [`WsReconnect`](crate::core::transport::WsReconnect)
+/// policy retries: non-fatal closes, read and connect timeouts, and network
+/// errors.
+pub(crate) fn is_reconnectable_websocket_error(err: &TransportError) -> bool {
+    matches!(
+        err,
+        TransportError::StreamClosed
+            | TransportError::IdleReadTimeout(_)
+            | TransportError::ConnectTimeout(_)
+            | TransportError::Network(_)
+    )
+}
+
+/// SSE comment frame announcing a reconnect to downstream parsers.
+pub(crate) fn websocket_reconnect_comment(attempt: u32) -> Bytes {
+    Bytes::from(format!(": reconnected attempt={attempt}\n\n"))
+}
+
 fn looks_like_sse_payload(text: &str) -> bool {
     text.lines().any(|line| {
         line.starts_with("data:")
@@ -204,19 +245,39 @@ fn sse_data_frame(payload: &str) -> String {
     out
 }
 
+/// Proxy for a websocket url: the configured proxy when set, otherwise the
+/// proxy environment variables.
 pub(crate) fn resolve_proxy_for_websocket_url(
     url: &str,
+    configured: Option<&ProxyConfig>,
 ) -> Result<Option<ResolvedProxy>, TransportError> {
     let parsed = Url::parse(url)
         .map_err(|err| TransportError::Other(format!("invalid websocket url '{url}': {err}")))?;
     let host = parsed
         .host_str()
         .ok_or_else(|| TransportError::Other(format!("websocket url '{url}' is missing host")))?;
-    if is_no_proxy_host(host) {
-        return Ok(None);
-    }
-    let Some(proxy_value) = websocket_proxy_env_value(parsed.scheme()) else {
-        return Ok(None);
+    let proxy_value = match configured {
+        Some(proxy) => {
+            if proxy
+                .no_proxy
+                .iter()
+                .map(|entry| entry.trim())
+                .filter(|entry| !entry.is_empty())
+                .any(|entry| no_proxy_entry_matches(host, entry))
+            {
+                return Ok(None);
+            }
+            proxy.url_with_credentials()?
+        }
+        None => {
+            if is_no_proxy_host(host) {
+                return Ok(None);
+            }
+            let Some(proxy_value) = websocket_proxy_env_value(parsed.scheme()) else {
+                return Ok(None);
+            };
+            proxy_value
+        }
     };
     let proxy = Url::parse(&proxy_value).map_err(|err| {
         TransportError::Other(format!("invalid proxy url '{proxy_value}': {err}"))
@@ -444,6 +505,28 @@ mod tests {
         assert!(matches!(err, TransportError::StreamClosed));
     }
 
+    #[test]
+    fn websocket_fatal_close_code_is_not_reconnectable() {
+        use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+
+        let close = |code| {
+            websocket_message_to_sse_chunk(WsMessage::Close(Some(CloseFrame {
+                code,
+                reason: "bye".into(),
+            })))
+            .expect_err("close frame should terminate the stream with an error")
+        };
+        let err = close(CloseCode::Away);
+        assert!(matches!(err, TransportError::StreamClosed));
+        assert!(is_reconnectable_websocket_error(&err));
+        let err = close(CloseCode::Policy);
+        assert_eq!(
+            err.to_string(),
+            "other: websocket closed with code 1008: bye"
+        );
+        assert!(!is_reconnectable_websocket_error(&err));
+    }
+
     #[test]
     fn websocket_header_filter_skips_upgrade_headers() {
         assert!(should_skip_websocket_header("connection"));
@@ -451,6 +534,7 @@ mod tests {
         assert!(should_skip_websocket_header("sec-websocket-key"));
         assert!(should_skip_websocket_header("content-type"));
         assert!(!should_skip_websocket_header("authorization"));
+        assert!(!should_skip_websocket_header("Sec-WebSocket-Protocol"));
     }
 
     #[test]
@@ -461,7 +545,7 @@ mod tests {
         std::env::remove_var("ALL_PROXY");
         std::env::remove_var("NO_PROXY");
         let proxy =
-            resolve_proxy_for_websocket_url("wss://chatgpt.com/backend-api/codex/responses")
+            resolve_proxy_for_websocket_url("wss://chatgpt.com/backend-api/codex/responses", None)
                 .expect("proxy resolution")
                 .expect("proxy configured");
         assert_eq!(proxy.host, "127.0.0.1");
@@ -481,11 +565,42 @@ mod tests {
         std::env::set_var("HTTPS_PROXY", "http://127.0.0.1:8080");
         std::env::set_var("NO_PROXY", "chatgpt.com,.example.invalid");
         let proxy =
-            resolve_proxy_for_websocket_url("wss://chatgpt.com/backend-api/codex/responses")
+            resolve_proxy_for_websocket_url("wss://chatgpt.com/backend-api/codex/responses", None)
                 .expect("proxy resolution");
         assert!(proxy.is_none());
     }
 
+    #[test]
+    fn websocket_proxy_resolution_prefers_configured_proxy() {
+        let _guard = env_test_lock();
+        std::env::set_var("HTTPS_PROXY", "http://127.0.0.1:8080");
+        std::env::remove_var("NO_PROXY");
+        let configured = ProxyConfig::new("http://proxy.internal:3128")
+            .with_basic_auth("alice", "s3cret")
+            .with_no_proxy([".corp.example"]);
+        let proxy = resolve_proxy_for_websocket_url(
+            "wss://chatgpt.com/backend-api/codex/responses",
+            Some(&configured),
+        )
+        .expect("proxy resolution")
+        .expect("proxy configured");
+        assert_eq!(proxy.host, "proxy.internal");
+        assert_eq!(proxy.port, 3128);
+        assert_eq!(
+            proxy.authorization,
+            Some(format!(
+                "Basic {}",
+                BASE64_STANDARD.encode("alice:s3cret".as_bytes())
+            ))
+        );
+
+        let bypassed =
+            resolve_proxy_for_websocket_url("wss://llm.corp.example/v1", Some(&configured))
+                .expect("proxy resolution");
+        assert!(bypassed.is_none());
+        assert!(!format!("{configured:?}").contains("s3cret"));
+    }
+
     #[test]
     fn builds_http_proxy_connect_request_with_basic_auth() {
         let request = build_http_proxy_connect_request(
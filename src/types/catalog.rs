@@ -2,6 +2,7 @@
 //!
 //! This module contains types for defining AI providers and their available models.
 
+use crate::types::v2::{ToolLimits, Usage};
 use serde::{de::Deserializer, Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -157,6 +158,15 @@ pub struct ModelCost {
     pub cache_read: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cache_write: Option<f64>,
+    /// Audio input rate; audio tokens fall back to `input` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_input: Option<f64>,
+    /// Image input rate; image tokens fall back to `input` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_input: Option<f64>,
+    /// Audio output rate; audio tokens fall back to `output` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_output: Option<f64>,
 }
 
 /// Single pricing entry from the new appendix API format.
@@ -186,11 +196,44 @@ impl ModelCost {
                 "tokens_output" => cost.output = Some(entry.price),
                 "cache_read" => cost.cache_read = Some(entry.price),
                 "cache_write" => cost.cache_write = Some(entry.price),
+                "audio_input" => cost.audio_input = Some(entry.price),
+                "image_input" => cost.image_input = Some(entry.price),
+                "audio_output" => cost.audio_output = Some(entry.price),
                 _ => {}
             }
         }
         cost
     }
+
+    /// Estimated cost of `usage`, with rates per million tokens.
+    ///
+    /// Cached, audio and image tokens are carved out of the input count and
+    /// billed at their own rates (falling back to the input rate), and audio
+    /// output tokens likewise out of the output count. Returns `None` when
+    /// neither an input nor an output rate is known.
+    pub fn estimate(&self, usage: &Usage) -> Option<f64> {
+        if self.input.is_none() && self.output.is_none() {
+            return None;
+        }
+        let input_rate = self.input.unwrap_or(0.0);
+        let output_rate = self.output.unwrap_or(0.0);
+        let cached = usage.cached_input_tokens.unwrap_or(0);
+        let audio_in = usage.audio_input_tokens.unwrap_or(0);
+        let image_in = usage.image_input_tokens.unwrap_or(0);
+        let audio_out = usage.audio_output_tokens.unwrap_or(0);
+        let text_in = usage
+            .input_tokens
+            .unwrap_or(0)
+            .saturating_sub(cached + audio_in + image_in);
+        let text_out = usage.output_tokens.unwrap_or(0).saturating_sub(audio_out);
+        let total = text_in as f64 * input_rate
+            + cached as f64 * self.cache_read.unwrap_or(input_rate)
+            + audio_in as f64 * self.audio_input.unwrap_or(input_rate)
+            + image_in as f64 * self.image_input.unwrap_or(input_rate)
+            + text_out as f64 * output_rate
+            + audio_out as f64 * self.audio_output.unwrap_or(output_rate);
+        Some(total / 1_000_000.0)
+    }
 }
 
 fn deserialize_modalities_field<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -245,6 +288,16 @@ pub struct ProviderDefinition {
     /// Idle timeout (ms) to wait for streaming activity before treating the connection as lost.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stream_idle_timeout_ms: Option<u64>,
+    /// TCP connect timeout (ms) override for this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_ms: Option<u64>,
+    /// Overall request timeout (ms) for this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_ms: Option<u64>,
+    /// Maximum wait (ms) for the first streamed body chunk, e.g. generous
+    /// values for reasoning models that think before emitting output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_byte_timeout_ms: Option<u64>,
     /// Authentication type (e.g., "bearer", "api-key")
     pub auth_type: String,
     /// Available models for this provider
@@ -254,6 +307,24 @@ pub struct ProviderDefinition {
     /// Some providers (like GitHub Models) expect just the model name without prefixes
     #[serde(default = "default_preserve_model_prefix")]
     pub preserve_model_prefix: bool,
+    /// Top-level request body keys renamed before sending, e.g.
+    /// `max_tokens` -> `max_new_tokens`. Applied by the OpenAI-compatible
+    /// chat and completion providers.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub field_aliases: HashMap<String, String>,
+    /// Tool count and schema size limits enforced before sending, for
+    /// servers that reject large tool sets. Applied by the OpenAI-compatible
+    /// chat provider; unset dimensions are not checked.
+    #[serde(default, skip_serializing_if = "is_default_tool_limits")]
+    pub tool_limits: ToolLimits,
+    /// Models tried in order when the requested one fails with a retryable
+    /// error. Applied by the gateway provider.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_models: Vec<String>,
+}
+
+fn is_default_tool_limits(limits: &ToolLimits) -> bool {
+    *limits == ToolLimits::default()
 }
 
 fn default_preserve_model_prefix() -> bool {
@@ -0,0 +1,234 @@
+//! AI SDK UI data stream protocol (v1) for [`Event`]s.
+//!
+//! Each frame is a one-character type code, a colon, a JSON value and a
+//! newline (`0:"Hello"\n`), which is what `useChat` from `@ai-sdk/react`
+//! reads when the response carries the [`DATA_STREAM_HEADER`].
+//!
+//! Frames are rendered from the same typed chunks (`{"type": "text-delta",
+//! ...}`) that the UI message stream encoder in
+//! `streaming_sse::ui_message` sends, so both protocols share one mapping:
+//! [`data_stream_frame`] turns any such chunk into its v1 frame.
+//!
+//! [`to_data_stream_frame`] encodes one event on its own. A completed tool
+//! call (`9:`) and the finish frames (`e:`/`d:`) need what earlier events
+//! carried, so a whole stream should go through a [`DataStreamEncoder`].
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::types::json::parse_json_loose;
+use crate::types::{Event, TokenUsage};
+
+/// Response header marking a body as a v1 data stream.
+pub const DATA_STREAM_HEADER: (&str, &str) = ("x-vercel-ai-data-stream", "v1");
+
+/// Typed UI message stream chunk: `{"type": kind, ...fields}`.
+pub(crate) fn chunk(
+    kind: &str,
+    fields: impl IntoIterator<Item = (&'static str, JsonValue)>,
+) -> JsonValue {
+    let mut obj = Map::new();
+    obj.insert("type".into(), json!(kind));
+    for (key, value) in fields {
+        obj.insert(key.into(), value);
+    }
+    JsonValue::Object(obj)
+}
+
+/// Client-facing text for an error value: the string itself, its `message`
+/// field, or the JSON as a last resort.
+pub(crate) fn error_text(error: &JsonValue) -> String {
+    match error {
+        JsonValue::String(text) => text.clone(),
+        other => other
+            .get("message")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string)
+            .unwrap_or_else(|| other.to_string()),
+    }
+}
+
+/// Frame for a typed UI message stream chunk, or an empty string when the v1
+/// protocol has no part for it. Finish chunks without a `finishReason`
+/// report `stop`.
+pub fn data_stream_frame(chunk: &JsonValue) -> String {
+    let field = |key: &str| chunk.get(key).cloned().unwrap_or(JsonValue::Null);
+    match chunk.get("type").and_then(JsonValue::as_str).unwrap_or("") {
+        "text-delta" => frame('0', &field("delta")),
+        "reasoning-delta" => frame('g', &field("delta")),
+        "tool-input-start" => frame(
+            'b',
+            &json!({ "toolCallId": field("toolCallId"), "toolName": field("toolName") }),
+        ),
+        "tool-input-delta" => frame(
+            'c',
+            &json!({
+                "toolCallId": field("toolCallId"),
+                "argsTextDelta": field("inputTextDelta"),
+            }),
+        ),
+        "tool-input-available" => frame(
+            '9',
+            &json!({
+                "toolCallId": field("toolCallId"),
+                "toolName": field("toolName"),
+                "args": field("input"),
+            }),
+        ),
+        "tool-output-available" => frame(
+            'a',
+            &json!({ "toolCallId": field("toolCallId"), "result": field("output") }),
+        ),
+        "error" => frame('3', &field("errorText")),
+        "finish-step" => {
+            let mut step = finish_fields(chunk);
+            step["isContinued"] = json!(false);
+            frame('e', &step)
+        }
+        "finish" => frame('d', &finish_fields(chunk)),
+        kind => match kind.strip_prefix("data-") {
+            Some(key) => frame('2', &json!([{ key: field("data") }])),
+            None => String::new(),
+        },
+    }
+}
+
+/// Frame for `event` alone, or an empty string when it has no stateless
+/// frame (`ToolCallEnd`, `Usage` and events the protocol has no part for).
+/// `Done` finishes with reason `stop` and no usage.
+pub fn to_data_stream_frame(event: &Event) -> String {
+    event_chunk(event)
+        .map(|chunk| data_stream_frame(&chunk))
+        .unwrap_or_default()
+}
+
+/// Typed chunk for the events that map to one without earlier context.
+fn event_chunk(event: &Event) -> Option<JsonValue> {
+    let value = match event {
+        Event::TextDelta { delta } => chunk("text-delta", [("delta", json!(delta))]),
+        Event::ReasoningDelta { delta } => chunk("reasoning-delta", [("delta", json!(delta))]),
+        Event::ToolCallStart { id, name } => chunk(
+            "tool-input-start",
+            [("toolCallId", json!(id)), ("toolName", json!(name))],
+        ),
+        Event::ToolCallDelta { id, args_json } => chunk(
+            "tool-input-delta",
+            [
+                ("toolCallId", json!(id)),
+                ("inputTextDelta", json!(args_json)),
+            ],
+        ),
+        Event::Data { key, value } => chunk(&format!("data-{key}"), [("data", value.clone())]),
+        Event::Error { message } => chunk("error", [("errorText", json!(message))]),
+        Event::Done => chunk("finish", [("finishReason", json!("stop"))]),
+        Event::ReasoningStart { .. }
+        | Event::ReasoningEnd
+        | Event::Usage { .. }
+        | Event::ToolCallEnd { .. }
+        | Event::Retrying { .. }
+        | Event::Raw { .. }
+        | Event::Unknown { .. } => return None,
+    };
+    Some(value)
+}
+
+/// Encodes a whole event stream, tracking what the stateful frames need:
+/// tool call names and arguments for `9:`, and the last usage and whether
+/// an error or tool call was seen for the finish frames.
+#[derive(Debug, Default)]
+pub struct DataStreamEncoder {
+    tool_calls: HashMap<String, (String, String)>,
+    has_tool_calls: bool,
+    has_error: bool,
+    usage: Option<TokenUsage>,
+}
+
+impl DataStreamEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Frames for `event`, possibly none. `Done` ends the message with a
+    /// finish-step (`e:`) and a finish-message (`d:`) frame whose reason is
+    /// `error` after an `Error` event, `tool-calls` when a tool call
+    /// completed and `stop` otherwise.
+    pub fn encode(&mut self, event: &Event) -> String {
+        match event {
+            Event::ToolCallStart { id, name } => {
+                self.tool_calls
+                    .insert(id.clone(), (name.clone(), String::new()));
+            }
+            Event::ToolCallDelta { id, args_json } => {
+                if let Some((_, args)) = self.tool_calls.get_mut(id) {
+                    args.push_str(args_json);
+                }
+            }
+            Event::ToolCallEnd { id } => {
+                let Some((name, args)) = self.tool_calls.remove(id) else {
+                    return String::new();
+                };
+                self.has_tool_calls = true;
+                let args = parse_json_loose(&args).unwrap_or_else(|| json!({}));
+                return data_stream_frame(&chunk(
+                    "tool-input-available",
+                    [
+                        ("toolCallId", json!(id)),
+                        ("toolName", json!(name)),
+                        ("input", args),
+                    ],
+                ));
+            }
+            Event::Error { .. } => self.has_error = true,
+            Event::Usage { usage } => {
+                self.usage = Some(usage.clone());
+                return String::new();
+            }
+            Event::Done => return self.finish_frames(),
+            _ => {}
+        }
+        to_data_stream_frame(event)
+    }
+
+    fn finish_frames(&self) -> String {
+        let finish_reason = if self.has_error {
+            "error"
+        } else if self.has_tool_calls {
+            "tool-calls"
+        } else {
+            "stop"
+        };
+        let mut fields = vec![("finishReason", json!(finish_reason))];
+        if let Some(usage) = &self.usage {
+            fields.push((
+                "usage",
+                json!({
+                    "promptTokens": usage.input_tokens,
+                    "completionTokens": usage.output_tokens,
+                }),
+            ));
+        }
+        data_stream_frame(&chunk("finish-step", fields.clone()))
+            + &data_stream_frame(&chunk("finish", fields))
+    }
+}
+
+/// `finishReason` (default `stop`) and, when present, `usage` of a finish
+/// chunk.
+fn finish_fields(chunk: &JsonValue) -> JsonValue {
+    let mut fields = json!({
+        "finishReason": chunk.get("finishReason").cloned().unwrap_or_else(|| json!("stop")),
+    });
+    if let Some(usage) = chunk.get("usage") {
+        fields["usage"] = usage.clone();
+    }
+    fields
+}
+
+fn frame(code: char, value: &JsonValue) -> String {
+    format!("{code}:{value}\n")
+}
+
+#[cfg(test)]
+#[path = "../../crates/sdk-types/tests/datastream_tests.rs"]
+mod tests;
@@ -19,6 +19,24 @@ pub struct EmbedOptions {
         rename = "providerOptions"
     )]
     pub provider_options: ProviderOptions,
+    /// Embed each distinct value once and copy its vector to every position
+    /// where it occurs. Usage then reflects the unique values only.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::ai_sdk_types::v2::bool_is_false"
+    )]
+    pub deduplicate: bool,
+    /// Requested output dimensionality, for models that can shorten their
+    /// vectors. Takes precedence over a `dimensions` provider option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    /// Scale each returned embedding to unit L2 length. Zero vectors are
+    /// returned unchanged.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::ai_sdk_types::v2::bool_is_false"
+    )]
+    pub normalize: bool,
 }
 
 impl EmbedOptions {
@@ -66,6 +66,25 @@ pub struct ImageUsage {
     pub total_tokens: Option<u64>,
 }
 
+/// How generated images are returned by the provider.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResponseFormat {
+    /// Hosted URLs, downloaded into [`ImageData::Bytes`].
+    Url,
+    /// Inline base64, returned as [`ImageData::Base64`].
+    B64Json,
+}
+
+impl ImageResponseFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Url => "url",
+            Self::B64Json => "b64_json",
+        }
+    }
+}
+
 fn default_image_count() -> u32 {
     1
 }
@@ -86,6 +105,13 @@ pub struct ImageOptions {
     pub aspect_ratio: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub seed: Option<u64>,
+    /// Provider response format; providers pick one when `None`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "responseFormat"
+    )]
+    pub response_format: Option<ImageResponseFormat>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub files: Vec<ImageFile>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -98,6 +124,22 @@ pub struct ImageOptions {
         rename = "providerOptions"
     )]
     pub provider_options: ProviderOptions,
+    /// Split calls for more than this many images into concurrent requests
+    /// of at most this many each, for endpoints that cap `n`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "maxImagesPerCall"
+    )]
+    pub max_images_per_call: Option<u32>,
+    /// Upper bound on concurrent requests when a call is split; providers
+    /// pick a default when `None`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "maxParallelCalls"
+    )]
+    pub max_parallel_calls: Option<u32>,
 }
 
 impl Default for ImageOptions {
@@ -108,10 +150,13 @@ impl Default for ImageOptions {
             size: None,
             aspect_ratio: None,
             seed: None,
+            response_format: None,
             files: Vec::new(),
             mask: None,
             headers: HashMap::new(),
             provider_options: ProviderOptions::new(),
+            max_images_per_call: None,
+            max_parallel_calls: None,
         }
     }
 }
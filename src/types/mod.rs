@@ -3,6 +3,8 @@
 //! This crate provides the shared types used across ai-sdk-rs for
 //! provider-normalized content, events, usage, embeddings, images, and v2 calls.
 
+pub mod cancel;
+pub mod datastream;
 pub mod embedding;
 pub mod image;
 pub mod json;
@@ -153,6 +155,8 @@ pub enum ContentPart {
     },
     /// Image content
     Image { data: Vec<u8>, mime_type: String },
+    /// Audio content, e.g. speech input (`audio/wav`, `audio/mpeg`)
+    Audio { data: Vec<u8>, mime_type: String },
     /// Error message
     Error { error: String },
 }
@@ -184,13 +188,94 @@ impl ContentPart {
             other => other,
         }
     }
+
+    /// The v2 user prompt part carrying this content, for `Text`, `Image`
+    /// and `Audio`. Media becomes a file part with its MIME type, which
+    /// provider converters map to their image or audio blocks.
+    pub fn to_user_part(&self) -> Option<v2::UserPart> {
+        match self {
+            ContentPart::Text { text } => Some(v2::UserPart::Text {
+                text: text.clone(),
+                provider_options: None,
+            }),
+            ContentPart::Image { data, mime_type } | ContentPart::Audio { data, mime_type } => {
+                Some(v2::UserPart::File {
+                    filename: None,
+                    data: v2::DataContent::Bytes {
+                        bytes: data.clone(),
+                    },
+                    media_type: mime_type.clone(),
+                    provider_options: None,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ContentPart, ToolResultArtifact, ToolResultCompletionEffect};
+    use super::{ContentPart, Event, ToolResultArtifact, ToolResultCompletionEffect};
     use serde_json::json;
 
+    #[test]
+    fn audio_content_part_round_trips() {
+        let part = ContentPart::Audio {
+            data: vec![82, 73, 70, 70],
+            mime_type: "audio/wav".to_string(),
+        };
+        let value = serde_json::to_value(&part).expect("serialize");
+        assert_eq!(
+            value,
+            json!({"type": "audio", "data": [82, 73, 70, 70], "mime_type": "audio/wav"})
+        );
+
+        let back: ContentPart = serde_json::from_value(value).expect("deserialize");
+        assert!(matches!(
+            back,
+            ContentPart::Audio { ref data, ref mime_type }
+                if data == &[82, 73, 70, 70] && mime_type == "audio/wav"
+        ));
+    }
+
+    #[test]
+    fn audio_content_part_becomes_an_input_audio_block() {
+        let part = ContentPart::Audio {
+            data: vec![0, 1, 2, 3],
+            mime_type: "audio/wav".to_string(),
+        };
+        let prompt = vec![crate::types::v2::PromptMessage::User {
+            content: vec![part.to_user_part().expect("user part")],
+            provider_options: None,
+        }];
+
+        assert_eq!(
+            crate::providers::openai_compatible::chat::convert::convert_to_openai_chat_messages(
+                "openai", &prompt,
+            ),
+            vec![json!({
+                "role": "user",
+                "content": [{"type": "input_audio", "input_audio": {"data": "AAECAw==", "format": "wav"}}]
+            })]
+        );
+    }
+
+    #[test]
+    fn unknown_event_tag_is_kept_raw() {
+        let raw = json!({"event": "plan_update", "step": 2});
+        let event: Event = serde_json::from_value(raw.clone()).expect("future event");
+
+        match &event {
+            Event::Unknown { raw: kept } => assert_eq!(kept, &raw),
+            other => panic!("expected unknown event, got {other:?}"),
+        }
+        assert_eq!(serde_json::to_value(&event).expect("serialize"), raw);
+
+        let known: Event = serde_json::from_value(json!({"event": "text_delta", "delta": "hi"}))
+            .expect("text delta");
+        assert!(matches!(known, Event::TextDelta { ref delta } if delta == "hi"));
+    }
+
     #[test]
     fn tool_result_round_trips_nodecode_metadata_fields() {
         let part = ContentPart::ToolResult {
@@ -309,6 +394,13 @@ pub enum Event {
         raw_value: serde_json::Value,
     },
     Done,
+    /// An event this SDK version does not recognize, kept as its JSON
+    /// object (see [`v2::StreamPart::Unknown`]).
+    #[serde(untagged)]
+    Unknown {
+        #[serde(flatten)]
+        raw: serde_json::Value,
+    },
 }
 
 /// Token usage information for a message.
@@ -334,6 +426,49 @@ impl TokenUsage {
             cache_write_tokens: None,
         }
     }
+
+    /// Adds `other` into `self`, e.g. to total usage over the steps of an
+    /// agent loop. See the [`Add`](std::ops::Add) impl for the rules.
+    pub fn accumulate(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.total_tokens = self.input_tokens + self.output_tokens;
+        self.cache_read_tokens = add_cache_tokens(self.cache_read_tokens, other.cache_read_tokens);
+        self.cache_write_tokens =
+            add_cache_tokens(self.cache_write_tokens, other.cache_write_tokens);
+    }
+}
+
+/// Sums cache counts, treating a missing side as zero; stays `None` only
+/// when neither side reported one.
+fn add_cache_tokens(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    }
+}
+
+/// Field-wise sum. `total_tokens` is recomputed as input + output rather than
+/// summed, and a cache count is `None` only when both sides are `None`.
+impl std::ops::Add for TokenUsage {
+    type Output = TokenUsage;
+
+    fn add(mut self, other: TokenUsage) -> TokenUsage {
+        self.accumulate(&other);
+        self
+    }
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: TokenUsage) {
+        self.accumulate(&other);
+    }
+}
+
+impl std::ops::AddAssign<&TokenUsage> for TokenUsage {
+    fn add_assign(&mut self, other: &TokenUsage) {
+        self.accumulate(other);
+    }
 }
 
 // Catalog types module
@@ -1,14 +1,36 @@
+use crate::ai_sdk_types::v2::Usage;
 use crate::ai_sdk_types::TokenUsage;
 use serde_json::Value;
 
+/// Read a token count from a usage field.
+///
+/// Some gateways report counts as floats or numeric strings; floats are
+/// rounded. Negative, non-finite and non-numeric values yield `None`.
+pub fn token_count(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().and_then(float_count)),
+        Value::String(s) => {
+            let s = s.trim();
+            s.parse::<u64>()
+                .ok()
+                .or_else(|| s.parse::<f64>().ok().and_then(float_count))
+        }
+        _ => None,
+    }
+}
+
+fn float_count(value: f64) -> Option<u64> {
+    (value.is_finite() && value >= 0.0).then(|| value.round() as u64)
+}
+
 fn cache_creation_tokens(value: &Value) -> u64 {
     let ephemeral_5m = value
         .get("ephemeral_5m_input_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0);
     let ephemeral_1h = value
         .get("ephemeral_1h_input_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0);
     ephemeral_5m + ephemeral_1h
 }
@@ -20,25 +42,25 @@ pub fn from_openai(u: &Value) -> Option<TokenUsage> {
     let input = obj
         .get("input_tokens")
         .or_else(|| obj.get("prompt_tokens"))
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0) as usize;
     let output = obj
         .get("output_tokens")
         .or_else(|| obj.get("completion_tokens"))
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0) as usize;
     let total = obj
         .get("total_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize)
         .unwrap_or(input + output);
     let cache_read_tokens = obj
         .get("cache_read_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize);
     let cache_write_tokens = obj
         .get("cache_write_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .map(|v| v as usize)
         .or_else(|| {
             obj.get("cache_creation")
@@ -58,25 +80,23 @@ pub fn from_openai(u: &Value) -> Option<TokenUsage> {
 /// Normalize Anthropic usage payloads into a TokenUsage-like JSON object.
 /// Returns a flat normalized JSON with keys used across the SDK.
 pub fn normalize_anthropic(u: &Value) -> Value {
-    let get_u64 = |k: &str| u.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+    let get_u64 = |k: &str| u.get(k).and_then(token_count).unwrap_or(0);
     let input = get_u64("input_tokens");
     let output = get_u64("output_tokens");
     let cache_read = u
         .get("cache_read_input_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(0);
 
     // Prefer flat "cache_creation_input_tokens" if present; otherwise sum nested ephemeral counts.
-    let cache_write_flat = u
-        .get("cache_creation_input_tokens")
-        .and_then(|v| v.as_u64());
+    let cache_write_flat = u.get("cache_creation_input_tokens").and_then(token_count);
     let cache_write_nested = u.get("cache_creation").map(cache_creation_tokens);
     let cache_write = cache_write_flat.or(cache_write_nested).unwrap_or(0);
 
     // Total may be absent on Anthropic; compute if needed.
     let total = u
         .get("total_tokens")
-        .and_then(|v| v.as_u64())
+        .and_then(token_count)
         .unwrap_or(input + output);
 
     serde_json::json!({
@@ -92,3 +112,59 @@ pub fn normalize_anthropic(u: &Value) -> Value {
 pub fn from_anthropic(u: &Value) -> TokenUsage {
     from_openai(&normalize_anthropic(u)).unwrap_or_default()
 }
+
+/// Copy audio and image token counts from OpenAI usage details
+/// (`input_tokens_details` / `prompt_tokens_details` and
+/// `output_tokens_details` / `completion_tokens_details`) into `usage`.
+pub fn apply_openai_modality_details(u: &Value, usage: &mut Usage) {
+    let input_details = u
+        .get("input_tokens_details")
+        .or_else(|| u.get("prompt_tokens_details"));
+    let output_details = u
+        .get("output_tokens_details")
+        .or_else(|| u.get("completion_tokens_details"));
+    let detail =
+        |details: Option<&Value>, key: &str| details.and_then(|d| d.get(key)).and_then(token_count);
+    if let Some(audio) = detail(input_details, "audio_tokens") {
+        usage.audio_input_tokens = Some(audio);
+    }
+    if let Some(image) = detail(input_details, "image_tokens") {
+        usage.image_input_tokens = Some(image);
+    }
+    if let Some(audio) = detail(output_details, "audio_tokens") {
+        usage.audio_output_tokens = Some(audio);
+    }
+}
+
+/// Copy audio and image token counts from Gemini `usageMetadata`
+/// (`promptTokensDetails` / `candidatesTokensDetails` modality lists) into
+/// `usage`.
+pub fn apply_gemini_modality_details(usage_metadata: &Value, usage: &mut Usage) {
+    let modality = |key: &str, wanted: &str| {
+        let entries = usage_metadata.get(key)?.as_array()?;
+        let counts: Vec<u64> = entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .get("modality")
+                    .and_then(Value::as_str)
+                    .is_some_and(|m| m.eq_ignore_ascii_case(wanted))
+            })
+            .filter_map(|entry| entry.get("tokenCount").and_then(token_count))
+            .collect();
+        (!counts.is_empty()).then(|| counts.iter().sum())
+    };
+    if let Some(audio) = modality("promptTokensDetails", "AUDIO") {
+        usage.audio_input_tokens = Some(audio);
+    }
+    if let Some(image) = modality("promptTokensDetails", "IMAGE") {
+        usage.image_input_tokens = Some(image);
+    }
+    if let Some(audio) = modality("candidatesTokensDetails", "AUDIO") {
+        usage.audio_output_tokens = Some(audio);
+    }
+}
+
+#[cfg(test)]
+#[path = "../../crates/sdk-types/tests/usage_tests.rs"]
+mod tests;
@@ -1,6 +1,7 @@
 //! LanguageModel V2 parity types inspired by Vercel AI SDK.
 //! These types are provider-agnostic and designed for interop with adapters.
 
+use crate::types::cancel::CancelToken;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
@@ -38,6 +39,17 @@ pub enum PromptMessage {
         )]
         provider_options: Option<ProviderOptions>,
     },
+    /// Developer-role instructions. Sent as `developer` by OpenAI Responses
+    /// and chat; providers without the role send it as a system message.
+    Developer {
+        content: String,
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            rename = "providerOptions"
+        )]
+        provider_options: Option<ProviderOptions>,
+    },
     User {
         content: Vec<UserPart>,
         #[serde(
@@ -67,6 +79,32 @@ pub enum PromptMessage {
     },
 }
 
+impl PromptMessage {
+    /// System message with text `content`.
+    pub fn system(content: impl Into<String>) -> Self {
+        PromptMessage::System {
+            content: content.into(),
+            provider_options: None,
+        }
+    }
+
+    /// Developer message with text `content`.
+    pub fn developer(content: impl Into<String>) -> Self {
+        PromptMessage::Developer {
+            content: content.into(),
+            provider_options: None,
+        }
+    }
+
+    /// Whether this is a system or developer message.
+    pub fn is_instruction(&self) -> bool {
+        matches!(
+            self,
+            PromptMessage::System { .. } | PromptMessage::Developer { .. }
+        )
+    }
+}
+
 pub type Prompt = Vec<PromptMessage>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -283,6 +321,132 @@ pub struct CallOptions {
     pub headers: HashMap<String, String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub provider_options: ProviderOptions,
+    /// Upper bound on prompt messages sent to the provider; see
+    /// [`PromptOverflow`] for what happens when it is exceeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_prompt_messages: Option<usize>,
+    #[serde(default, skip_serializing_if = "PromptOverflow::is_error")]
+    pub prompt_overflow: PromptOverflow,
+    /// Where system content is placed in the provider request; `None` keeps
+    /// the provider's default placement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_mode: Option<SystemPromptMode>,
+    /// Capture raw provider output for debugging and replay: generate calls
+    /// fill `response_body` and streams expose the raw frames through
+    /// `StreamResponse::raw_response`.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub capture_raw_response: bool,
+    /// Byte budget for captured raw stream frames; frames past the budget are
+    /// dropped and the capture is marked truncated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_raw_response_bytes: Option<usize>,
+    /// End a stream as soon as the first complete client-executed tool call
+    /// is emitted, cancelling the provider request. Ignored (with a warning)
+    /// by non-streaming calls.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub stop_on_first_tool_call: bool,
+    /// Tool count and schema size limits checked before the request is sent.
+    /// Set fields override the provider's known limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_limits: Option<ToolLimits>,
+    /// Reject calls that exceed a tool limit instead of warning.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub strict_tool_limits: bool,
+    /// Estimate usage from the prompt and generated text when the provider
+    /// reports none, flagging it as `provider_metadata["usage"]["estimated"]`.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub estimate_usage_if_missing: bool,
+    /// What to do with tools passed to a model known to lack tool calling.
+    /// Honored by the OpenAI, Anthropic, Bedrock and Google providers, which
+    /// know their models' tool support; OpenAI-compatible servers and the
+    /// gateway always receive the tools.
+    #[serde(default, skip_serializing_if = "UnsupportedToolsPolicy::is_error")]
+    pub on_unsupported_tools: UnsupportedToolsPolicy,
+    /// Emit each streamed source once: a `SourceUrl` part repeating the url,
+    /// title and provider metadata of an earlier one is dropped.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub dedupe_sources: bool,
+    /// Cancels the call: an in-flight request is aborted and a stream ends
+    /// with an "aborted" error part. Not serialized.
+    #[serde(skip)]
+    pub abort: Option<CancelToken>,
+}
+
+/// Upper bounds on the tools sent with a request. `None` leaves a dimension
+/// unchecked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ToolLimits {
+    /// Maximum number of tool definitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tools: Option<usize>,
+    /// Maximum size in bytes of the serialized tool definitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_schema_bytes: Option<usize>,
+}
+
+impl ToolLimits {
+    /// Fill dimensions left unset in `self` from `fallback`.
+    pub fn or(self, fallback: ToolLimits) -> ToolLimits {
+        ToolLimits {
+            max_tools: self.max_tools.or(fallback.max_tools),
+            max_schema_bytes: self.max_schema_bytes.or(fallback.max_schema_bytes),
+        }
+    }
+}
+
+/// Placement of system prompt content in provider requests.
+///
+/// Providers that cannot honor a mode emit an unsupported-setting warning and
+/// fall back to their default placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SystemPromptMode {
+    /// Dedicated system message (or the provider's native system field).
+    System,
+    /// Developer-role instructions (OpenAI Responses).
+    Developer,
+    /// Prepend system content to the first user message, for models that
+    /// ignore the system role.
+    FoldIntoUser,
+}
+
+/// Behavior when a prompt exceeds [`CallOptions::max_prompt_messages`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PromptOverflow {
+    /// Reject the call with an invalid-argument error.
+    #[default]
+    Error,
+    /// Drop the oldest non-system messages and emit a warning. System
+    /// messages are always kept.
+    SlidingWindow,
+}
+
+impl PromptOverflow {
+    fn is_error(&self) -> bool {
+        matches!(self, PromptOverflow::Error)
+    }
+}
+
+/// Handling of tools passed to a model without native tool calling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnsupportedToolsPolicy {
+    /// Reject the call with an invalid-argument error.
+    #[default]
+    Error,
+    /// Send the request without tools and emit a warning.
+    DropWithWarning,
+    /// Describe the function tools in the system prompt and ask the model to
+    /// answer with a JSON tool call; provider tools are dropped with a
+    /// warning.
+    InlineAsText,
+}
+
+impl UnsupportedToolsPolicy {
+    fn is_error(&self) -> bool {
+        matches!(self, UnsupportedToolsPolicy::Error)
+    }
 }
 
 impl CallOptions {
@@ -300,6 +464,121 @@ impl CallOptions {
         self.max_output_tokens = Some(n);
         self
     }
+    pub fn with_abort(mut self, token: CancelToken) -> Self {
+        self.abort = Some(token);
+        self
+    }
+
+    /// Whether providers should surface raw chunks, either because the caller
+    /// asked for them or because the raw response is being captured.
+    pub fn emit_raw_chunks(&self) -> bool {
+        self.include_raw_chunks || self.capture_raw_response
+    }
+
+    /// Canonical JSON form of these options for cache and dedup keys.
+    ///
+    /// Object keys are sorted recursively, floats are rounded to
+    /// [`CANONICAL_FLOAT_DECIMALS`] places and tools are ordered by name, so
+    /// semantically identical requests compare equal. Prompt order is kept.
+    /// `headers` are excluded since they carry per-request transport data
+    /// (request ids, tracing) rather than model inputs; `abort` is never
+    /// serialized, so a cancel token does not change the key either.
+    pub fn canonical_json(&self) -> JsonValue {
+        let mut value = serde_json::to_value(self)
+            .expect("CallOptions has string map keys and always serializes to JSON");
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("headers");
+            if let Some(JsonValue::Array(tools)) = obj.get_mut("tools") {
+                tools.sort_by_cached_key(|tool| {
+                    let name = tool
+                        .get("name")
+                        .and_then(JsonValue::as_str)
+                        .unwrap_or_default()
+                        .to_string();
+                    (name, canonicalize_json(tool.clone()).to_string())
+                });
+            }
+        }
+        canonicalize_json(value)
+    }
+
+    /// Stable 64-bit FNV-1a hash of [`CallOptions::canonical_json`].
+    ///
+    /// Unlike `std`'s `DefaultHasher`, the result does not change between
+    /// Rust releases, so it is safe to persist.
+    pub fn canonical_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.canonical_json()
+            .to_string()
+            .bytes()
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Attach a privacy-preserving end-user identifier for provider abuse
+    /// monitoring, sent as OpenAI's `safety_identifier` (under the `openai`
+    /// provider options scope).
+    ///
+    /// The value comes from [`hash_end_user_id`], so the provider sees a
+    /// consistent identifier without the raw user id. It is only useful for
+    /// abuse correlation if it is stable per user: keep `salt` fixed for the
+    /// lifetime of the app and always pass the same raw id for a user.
+    pub fn hashed_end_user(mut self, raw_user_id: &str, salt: &[u8]) -> Self {
+        self.provider_options
+            .entry("openai".into())
+            .or_default()
+            .insert(
+                "safetyIdentifier".into(),
+                JsonValue::String(hash_end_user_id(raw_user_id, salt)),
+            );
+        self
+    }
+}
+
+/// Stable, non-reversible end-user identifier: lowercase hex HMAC-SHA256 of
+/// `raw_user_id` keyed by a per-app `salt` (64 characters).
+///
+/// The same inputs always produce the same identifier; without the salt the
+/// raw id cannot be recovered or brute-forced from the output.
+pub fn hash_end_user_id(raw_user_id: &str, salt: &[u8]) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, salt);
+    ring::hmac::sign(&key, raw_user_id.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Decimal places floats are rounded to in [`CallOptions::canonical_json`].
+pub const CANONICAL_FLOAT_DECIMALS: i32 = 6;
+
+fn canonicalize_json(value: JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(obj) => {
+            let mut entries: Vec<(String, JsonValue)> = obj.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            JsonValue::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize_json(value)))
+                    .collect(),
+            )
+        }
+        JsonValue::Array(items) => {
+            JsonValue::Array(items.into_iter().map(canonicalize_json).collect())
+        }
+        JsonValue::Number(n) if n.is_f64() => {
+            let scale = 10f64.powi(CANONICAL_FLOAT_DECIMALS);
+            n.as_f64()
+                .map(|f| (f * scale).round() / scale)
+                .and_then(serde_json::Number::from_f64)
+                .map(JsonValue::Number)
+                .unwrap_or(JsonValue::Number(n))
+        }
+        other => other,
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -427,6 +706,15 @@ pub struct Usage {
     pub reasoning_tokens: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cached_input_tokens: Option<u64>,
+    /// Audio tokens within `input_tokens`, for models that bill them separately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_input_tokens: Option<u64>,
+    /// Image tokens within `input_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_input_tokens: Option<u64>,
+    /// Audio tokens within `output_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_output_tokens: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -575,9 +863,13 @@ pub enum StreamPart {
         provider_metadata: Option<ProviderMetadata>,
     },
     // Files and sources
+    /// Base64 file data. Progressive previews of a file still being generated
+    /// carry `partial: true` (and their `index`) in the provider metadata.
     File {
         media_type: String,
         data: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provider_metadata: Option<ProviderMetadata>,
     },
     SourceUrl {
         id: String,
@@ -608,4 +900,21 @@ pub enum StreamPart {
     Error {
         error: JsonValue,
     },
+    /// A part this SDK version does not recognize, kept as its JSON object so
+    /// streams persisted by newer versions still replay. Parts whose `type`
+    /// is known but whose fields do not match also land here. Serializes
+    /// back to `raw` unchanged.
+    #[serde(untagged)]
+    Unknown {
+        #[serde(flatten)]
+        raw: JsonValue,
+    },
 }
+
+#[cfg(test)]
+#[path = "../../crates/sdk-types/tests/call_options_tests.rs"]
+mod call_options_tests;
+
+#[cfg(test)]
+#[path = "../../crates/sdk-types/tests/stream_part_tests.rs"]
+mod stream_part_tests;
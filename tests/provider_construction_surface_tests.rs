@@ -1,11 +1,15 @@
-use ai_sdk_rs::core::transport::TransportConfig;
+use ai_sdk_rs::core::transport::{
+    set_transport_observer, TransportBody, TransportConfig, TransportEvent, TransportObserver,
+};
 use ai_sdk_rs::core::{EmbeddingModel, LanguageModel};
-use ai_sdk_rs::provider::{registry, Credentials};
+use ai_sdk_rs::provider::{catalog_model_capabilities, registry, Credentials};
 use ai_sdk_rs::providers::anthropic::AnthropicMessagesLanguageModel;
 use ai_sdk_rs::providers::openai::OpenAIResponsesLanguageModel;
 use ai_sdk_rs::providers::openai_compatible::OpenAICompatibleEmbeddingModel;
 use ai_sdk_rs::types::catalog::{ModelInfo, ProviderDefinition, SdkType};
+use ai_sdk_rs::types::v2::{CallOptions, PromptMessage, UserPart};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 fn empty_models() -> HashMap<String, ModelInfo> {
     HashMap::new()
@@ -67,9 +71,15 @@ fn registry_construction_remains_available_for_dynamic_openai_compatible_definit
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: empty_models(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     };
 
     let model = (registration.build)(
@@ -82,3 +92,147 @@ fn registry_construction_remains_available_for_dynamic_openai_compatible_definit
     assert_eq!(model.provider_name(), "openai-compatible");
     assert_eq!(model.model_id(), "gpt-4o-mini");
 }
+
+#[test]
+fn capabilities_combine_provider_knowledge_with_the_catalog() {
+    let gpt_4o = OpenAIResponsesLanguageModel::builder("gpt-4o")
+        .with_api_key("test-key")
+        .build()
+        .expect("build openai model");
+    let o3 = OpenAIResponsesLanguageModel::builder("o3")
+        .with_api_key("test-key")
+        .build()
+        .expect("build openai model");
+    assert!(!gpt_4o.capabilities().reasoning);
+    assert!(o3.capabilities().reasoning);
+
+    let claude = AnthropicMessagesLanguageModel::builder("claude-sonnet-4-5-20250929")
+        .with_api_key("test-key")
+        .build()
+        .expect("build anthropic model");
+    assert!(claude.capabilities().reasoning && claude.capabilities().vision);
+
+    let registration = registry::iter()
+        .find(|entry| entry.id.eq_ignore_ascii_case("openai-compatible"))
+        .expect("openai-compatible registration");
+    let info: ModelInfo = serde_json::from_value(serde_json::json!({
+        "id": "qwen3-vl",
+        "display_name": "Qwen3 VL",
+        "capabilities": {"tool_call": true, "reasoning": true},
+        "modalities": {"input": ["text", "image"], "output": ["text"]},
+        "limits": {"context_output": 32768}
+    }))
+    .expect("model info");
+    let definition = ProviderDefinition {
+        name: "openai-compatible".into(),
+        display_name: "Compat".into(),
+        sdk_type: SdkType::OpenAICompatible,
+        base_url: "https://api.compat.example/v1".into(),
+        env: None,
+        npm: None,
+        doc: None,
+        endpoint_path: "/chat/completions".into(),
+        headers: HashMap::new(),
+        query_params: HashMap::new(),
+        stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
+        auth_type: "api-key".into(),
+        models: HashMap::from([("qwen3-vl".to_string(), info)]),
+        preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
+    };
+    let model = (registration.build)(
+        &definition,
+        "qwen3-vl",
+        &Credentials::ApiKey("test-key".into()),
+    )
+    .expect("build openai-compatible model");
+
+    let caps = catalog_model_capabilities(&definition, model.as_ref());
+    assert!(caps.vision && caps.tools && caps.reasoning && caps.streaming);
+    assert!(!caps.audio_in && !caps.audio_out);
+    assert_eq!(caps.max_output_tokens, Some(32_768));
+    assert!(!model.capabilities().vision);
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    events: std::sync::Mutex<Vec<TransportEvent>>,
+}
+
+impl TransportObserver for RecordingObserver {
+    fn on_event(&self, event: TransportEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[tokio::test]
+async fn registry_applies_definition_field_aliases_to_the_wire_body() {
+    let observer = Arc::new(RecordingObserver::default());
+    assert!(set_transport_observer(observer.clone()));
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+    let port = listener.local_addr().expect("listener addr").port();
+    drop(listener);
+    let registration = registry::iter()
+        .find(|entry| entry.id.eq_ignore_ascii_case("openai-compatible"))
+        .expect("openai-compatible registration");
+    let definition = ProviderDefinition {
+        name: "openai-compatible".into(),
+        display_name: "Compat".into(),
+        sdk_type: SdkType::OpenAICompatible,
+        base_url: format!("http://127.0.0.1:{port}/v1"),
+        env: None,
+        npm: None,
+        doc: None,
+        endpoint_path: "/chat/completions".into(),
+        headers: HashMap::new(),
+        query_params: HashMap::new(),
+        stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
+        auth_type: "api-key".into(),
+        models: empty_models(),
+        preserve_model_prefix: true,
+        field_aliases: HashMap::from([("max_tokens".into(), "max_new_tokens".into())]),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
+    };
+    let model = (registration.build)(
+        &definition,
+        "qwen3",
+        &Credentials::ApiKey("test-key".into()),
+    )
+    .expect("build openai-compatible model");
+
+    let _ = model
+        .do_stream(
+            CallOptions::new(vec![PromptMessage::User {
+                content: vec![UserPart::Text {
+                    text: "Hello".into(),
+                    provider_options: None,
+                }],
+                provider_options: None,
+            }])
+            .with_max_output_tokens(64),
+        )
+        .await;
+
+    let body = observer
+        .events
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|event| match &event.request_body {
+            Some(TransportBody::Json(body)) => Some(body.clone()),
+            _ => None,
+        })
+        .expect("recorded request body");
+    assert_eq!(body["max_new_tokens"], serde_json::json!(64));
+    assert!(body.get("max_tokens").is_none());
+}
@@ -27,9 +27,15 @@ fn openai_definition() -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: HashMap::new(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
@@ -46,9 +52,15 @@ fn anthropic_definition() -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: HashMap::new(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
@@ -65,9 +77,15 @@ fn openai_compatible_definition(name: &str) -> ProviderDefinition {
         headers: HashMap::new(),
         query_params: HashMap::new(),
         stream_idle_timeout_ms: None,
+        connect_timeout_ms: None,
+        request_timeout_ms: None,
+        first_byte_timeout_ms: None,
         auth_type: "api-key".into(),
         models: HashMap::new(),
         preserve_model_prefix: true,
+        field_aliases: HashMap::new(),
+        tool_limits: Default::default(),
+        fallback_models: Vec::new(),
     }
 }
 
@@ -120,6 +138,7 @@ fn anthropic_messages_new_is_the_current_typed_construction_surface() {
                 vec![r"^https?://.*$".to_string()],
             )]),
             default_options: None,
+            default_max_tokens: None,
         },
     );
 